@@ -0,0 +1,14 @@
+// Generates the gRPC service from proto/go_engine.proto when the grpc feature is enabled,
+// compiling the proto with protox so that no protoc installation is needed
+fn main() {
+    #[cfg(feature = "grpc")]
+    {
+        let descriptors = protox::compile(["proto/go_engine.proto"], ["proto"])
+            .expect("proto/go_engine.proto should compile");
+        tonic_build::configure()
+            .build_client(false)
+            .compile_fds(descriptors)
+            .expect("gRPC service should generate");
+    }
+    println!("cargo:rerun-if-changed=proto/go_engine.proto");
+}
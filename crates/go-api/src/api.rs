@@ -0,0 +1,549 @@
+use super::*;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::response::Response;
+use axum::extract::{Path, State};
+use axum::http::header::RETRY_AFTER;
+use axum::http::{HeaderName, HeaderValue, Method};
+use axum::{routing::get, routing::post, Json, Router};
+use std::io::{self, Write};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::sync::mpsc;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use serde::Deserialize;
+use utoipa::{OpenApi, ToSchema};
+
+// Address the API listens on unless given with --host or GO_ENGINE_API_HOST
+const DEFAULT_HOST: &str = "0.0.0.0";
+
+// Port the API listens on unless given with --port or GO_ENGINE_API_PORT
+const DEFAULT_PORT: u16 = 80;
+
+// Environment variables giving the address and port the API listens on, overridden by the options
+const HOST_VAR: &str = "GO_ENGINE_API_HOST";
+const PORT_VAR: &str = "GO_ENGINE_API_PORT";
+
+// Environment variables giving the PEM certificate chain and private key the API serves HTTPS
+// with, overridden by the options
+const TLS_CERT_VAR: &str = "GO_ENGINE_API_TLS_CERT";
+const TLS_KEY_VAR: &str = "GO_ENGINE_API_TLS_KEY";
+
+// Environment variable giving the SQLite database games are saved to, overridden by the option
+const DATABASE_VAR: &str = "GO_ENGINE_API_DB";
+
+// Environment variables giving the comma separated origins browsers may call the API from, and
+// the methods and headers they may use, overridden by the options
+const CORS_ORIGINS_VAR: &str = "GO_ENGINE_API_CORS_ORIGINS";
+const CORS_METHODS_VAR: &str = "GO_ENGINE_API_CORS_METHODS";
+const CORS_HEADERS_VAR: &str = "GO_ENGINE_API_CORS_HEADERS";
+
+// Methods and headers browsers may use from the allowed origins unless given
+const DEFAULT_CORS_METHODS: &str = "GET,POST,PATCH,DELETE";
+const DEFAULT_CORS_HEADERS: &str = "content-type,x-api-key";
+
+// Settings of the API, parsed from the environment and the command line
+pub(crate) struct ApiSettings {
+    pub(crate) host: String,
+    pub(crate) port: u16,
+    // the API serves HTTPS when given both, which requires the tls feature
+    pub(crate) tls_cert: Option<PathBuf>,
+    pub(crate) tls_key: Option<PathBuf>,
+    // games are saved to this database and resumed from it when given, else held in memory only
+    pub(crate) database: Option<PathBuf>,
+    // browsers may call the API from these origins, where "*" is any, with these methods and
+    // headers, and from none when no origins are given
+    pub(crate) cors_origins: Vec<String>,
+    pub(crate) cors_methods: Vec<String>,
+    pub(crate) cors_headers: Vec<String>,
+}
+
+impl ApiSettings {
+    // Parses API settings from the arguments following the api run mode and the environment
+    // Accepts "--host ADDRESS" and "--port N", where the address defaults to 0.0.0.0 and the port
+    // to 80 unless GO_ENGINE_API_HOST or GO_ENGINE_API_PORT give them, and "--tls-cert FILE" with
+    // "--tls-key FILE" to serve HTTPS, also given by GO_ENGINE_API_TLS_CERT and GO_ENGINE_API_TLS_KEY,
+    // "--db FILE" to save games to, also given by GO_ENGINE_API_DB, and "--cors-origins",
+    // "--cors-methods", and "--cors-headers" with comma separated lists, also given by
+    // GO_ENGINE_API_CORS_ORIGINS, GO_ENGINE_API_CORS_METHODS, and GO_ENGINE_API_CORS_HEADERS
+    pub(crate) fn from_args(args: &[String]) -> Result<ApiSettings, String> {
+        ApiSettings::parse(args, |name| std::env::var(name).ok())
+    }
+
+    // Parses API settings from the given arguments and environment variables, as from_args does
+    pub(crate) fn parse(args: &[String], var: impl Fn(&str) -> Option<String>) -> Result<ApiSettings, String> {
+        let port = |value: &str, source: &str| {
+            value.parse().map_err(|_| format!("Invalid value given to {source}: {value}"))
+        };
+        let list = |value: &str| {
+            value.split(',').map(str::trim).filter(|item| !item.is_empty()).map(String::from).collect()
+        };
+        let mut settings = ApiSettings {
+            host: var(HOST_VAR).unwrap_or_else(|| DEFAULT_HOST.to_string()),
+            port: match var(PORT_VAR) {
+                Some(value) => port(&value, PORT_VAR)?,
+                None => DEFAULT_PORT,
+            },
+            tls_cert: var(TLS_CERT_VAR).map(PathBuf::from),
+            tls_key: var(TLS_KEY_VAR).map(PathBuf::from),
+            database: var(DATABASE_VAR).map(PathBuf::from),
+            cors_origins: list(&var(CORS_ORIGINS_VAR).unwrap_or_default()),
+            cors_methods: list(&var(CORS_METHODS_VAR).unwrap_or_else(|| DEFAULT_CORS_METHODS.to_string())),
+            cors_headers: list(&var(CORS_HEADERS_VAR).unwrap_or_else(|| DEFAULT_CORS_HEADERS.to_string())),
+        };
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--host" => {
+                    settings.host = args.next().ok_or("No value given to api option --host")?.clone();
+                }
+                "--port" => {
+                    let value = args.next().ok_or("No value given to api option --port")?;
+                    settings.port = port(value, "api option --port")?;
+                }
+                "--tls-cert" => {
+                    settings.tls_cert = Some(args.next().ok_or("No value given to api option --tls-cert")?.into());
+                }
+                "--tls-key" => {
+                    settings.tls_key = Some(args.next().ok_or("No value given to api option --tls-key")?.into());
+                }
+                "--db" => {
+                    settings.database = Some(args.next().ok_or("No value given to api option --db")?.into());
+                }
+                "--cors-origins" => {
+                    settings.cors_origins = list(args.next().ok_or("No value given to api option --cors-origins")?);
+                }
+                "--cors-methods" => {
+                    settings.cors_methods = list(args.next().ok_or("No value given to api option --cors-methods")?);
+                }
+                "--cors-headers" => {
+                    settings.cors_headers = list(args.next().ok_or("No value given to api option --cors-headers")?);
+                }
+                _ => return Err(format!("Unknown api option given: {arg}")),
+            }
+        }
+        settings.cors_layer()?;
+
+        match (&settings.tls_cert, &settings.tls_key) {
+            (Some(_), None) => Err("No private key given for the TLS certificate, give it with --tls-key".to_string()),
+            (None, Some(_)) => Err("No certificate given for the TLS private key, give it with --tls-cert".to_string()),
+            (Some(_), Some(_)) if !cfg!(feature = "tls") => {
+                Err("Serving the API over TLS requires the \"tls\" feature".to_string())
+            }
+            _ => Ok(settings),
+        }
+    }
+}
+
+impl ApiSettings {
+    // The CORS middleware letting browsers call the API from the allowed origins, with the allowed
+    // methods and headers, or None if no origins are allowed
+    // Returns an error naming an origin, method, or header that is not valid in HTTP
+    pub(crate) fn cors_layer(&self) -> Result<Option<CorsLayer>, String> {
+        if self.cors_origins.is_empty() {
+            return Ok(None);
+        }
+        let invalid = |kind: &str, value: &str| format!("Invalid CORS {kind} given: {value}");
+
+        let origins = match self.cors_origins.iter().any(|origin| origin == "*") {
+            true => AllowOrigin::any(),
+            false => AllowOrigin::list(
+                self.cors_origins
+                    .iter()
+                    .map(|origin| HeaderValue::from_str(origin).map_err(|_| invalid("origin", origin)))
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+        };
+        let methods = self
+            .cors_methods
+            .iter()
+            .map(|method| Method::from_str(&method.to_uppercase()).map_err(|_| invalid("method", method)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let headers = self
+            .cors_headers
+            .iter()
+            .map(|header| HeaderName::from_str(header).map_err(|_| invalid("header", header)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // rate limited clients are told when to retry
+        Ok(Some(
+            CorsLayer::new()
+                .allow_origin(origins)
+                .allow_methods(methods)
+                .allow_headers(headers)
+                .expose_headers([RETRY_AFTER]),
+        ))
+    }
+}
+
+// The current state of the app, holding every game in progress, the rate limits of its clients,
+// and its metrics
+#[derive(Clone, Default)]
+pub(crate) struct AppState {
+    pub(crate) games: Arc<GameManager>,
+    pub(crate) limiter: Arc<RateLimiter>,
+    pub(crate) metrics: Arc<Metrics>,
+}
+
+// A position to run a GTP command in, where the rules, komi, handicap, and time limits are each
+// left as GTP starts them unless given, and each move is a color and vertex (ex. "B D4")
+#[derive (Deserialize, ToSchema)]
+pub(crate) struct CommandInput {
+    board_size: u16,
+    ruleset: Option<String>,
+    komi: Option<f64>,
+    // stones placed on the star points as fixed_handicap places them
+    handicap: Option<u16>,
+    // stones placed on the given vertices as set_free_handicap places them
+    handicap_stones: Option<Vec<String>>,
+    time_settings: Option<TimeInput>,
+    move_list: Vec<String>,
+    next_command: String,
+}
+
+impl CommandInput {
+    // The GTP commands setting up the position of this input, in the order they are run, each with
+    // the setting it applies
+    // The rules are set before the komi, since set_rules gives the komi usual for them
+    fn setup(&self) -> Vec<(String, Vec<String>)> {
+        let command = |name: &str, value: String| vec![name.to_string(), value];
+        let mut setup = vec![("board size".to_string(), command("boardsize", self.board_size.to_string()))];
+        let settings = [
+            self.ruleset.as_ref().map(|ruleset| ("ruleset", command("set_rules", ruleset.clone()))),
+            self.komi.map(|komi| ("komi", command("komi", komi.to_string()))),
+            self.handicap.map(|stones| ("handicap", command("fixed_handicap", stones.to_string()))),
+            self.handicap_stones.as_ref().map(|stones| {
+                let vertices = stones.iter().cloned();
+                ("handicap stones", ["set_free_handicap".to_string()].into_iter().chain(vertices).collect())
+            }),
+            self.time_settings.as_ref().map(|time| ("time settings", time.command())),
+        ];
+        setup.extend(settings.into_iter().flatten().map(|(setting, command)| (setting.to_string(), command)));
+        for mov in &self.move_list {
+            let arguments = mov.split_whitespace().map(str::to_string);
+            setup.push((format!("move {mov}"), ["play".to_string()].into_iter().chain(arguments).collect()));
+        }
+        setup
+    }
+
+    // Runs next_command in the given new game, set up as this input gives, answering with its GTP
+    // response, or with the setting the game refused and why
+    pub(crate) fn run(self, mut gtp: GTP) -> String {
+        if BoardSize::from_u16(self.board_size).is_none() {
+            return format!("Invalid board size {} given", self.board_size);
+        }
+
+        for (setting, command) in self.setup() {
+            let arguments: Vec<&str> = command.iter().map(String::as_str).collect();
+            if let Some(Err(error)) = gtp.execute(&arguments) {
+                return format!("Invalid {setting} given: {error}");
+            }
+        }
+        gtp.accept_command(self.next_command)
+    }
+}
+
+// Limits the searches of the given GTP session to the tokens of the given client's bucket, like
+// the expensive endpoints, noting in the returned cell the seconds until a token refills whenever
+// a search is refused
+pub(crate) fn limit_searches(gtp: &mut GTP, limiter: &Arc<RateLimiter>, client: String) -> Arc<Mutex<Option<u64>>> {
+    let refused = Arc::new(Mutex::new(None));
+    let (limiter, noted) = (Arc::clone(limiter), Arc::clone(&refused));
+    gtp.set_search_limit(move || {
+        limiter.limit(&client).map_err(|seconds| {
+            *noted.lock().unwrap() = Some(seconds);
+            ApiError::rate_limited(seconds).into_message()
+        })
+    });
+    refused
+}
+
+// POST /: runs next_command in a new game set up as the input gives
+// A search the client is out of requests for is answered with 429 Too Many Requests
+#[utoipa::path(
+    post,
+    path = "/",
+    tag = "gtp",
+    request_body = CommandInput,
+    responses(
+        (status = 200, description = "The GTP response to next_command", body = String),
+        (status = 429, description = "next_command searches and the client is out of requests", body = ErrorBody),
+    ),
+)]
+async fn read_json(
+    State(state): State<AppState>,
+    Client(client): Client,
+    Json(payload): Json<CommandInput>,
+) -> Result<String, Response> {
+    let mut gtp = GTP::new();
+    let refused = limit_searches(&mut gtp, &state.limiter, client);
+    let response = payload.run(gtp);
+    if let Some(seconds) = refused.lock().unwrap().take() {
+        return Err(too_many_requests(seconds));
+    }
+    Ok(response)
+}
+
+// Starts a new game for a GTP session, returning the session identifier to post its commands under
+#[utoipa::path(
+    post,
+    path = "/command",
+    tag = "gtp",
+    responses((status = 200, description = "The session identifier", body = String)),
+)]
+async fn new_session(State(state): State<AppState>) -> String {
+    state.games.create().await
+}
+
+// Given the session identifier in the path and a GTP command, returns the output of the GTP
+// command in the game of that id, which is started on its first command
+// quit ends the session, and the next command under its identifier starts a new one
+// A search the client is out of requests for is answered with 429 Too Many Requests
+#[utoipa::path(
+    post,
+    path = "/command/{session}",
+    tag = "gtp",
+    params(("session" = String, Path, description = "The session identifier")),
+    request_body(content = String, description = "The GTP command", content_type = "text/plain"),
+    responses(
+        (status = 200, description = "The GTP response", body = String),
+        (status = 429, description = "The command searches and the client is out of requests", body = ErrorBody),
+    ),
+)]
+async fn read_command(
+    State(state): State<AppState>,
+    Client(client): Client,
+    Path(session): Path<String>,
+    command: String,
+) -> Result<String, Response> {
+    let mut game = state.games.get_or_create(&session).await;
+    // the command name follows the optional command id
+    let genmove = command.split_whitespace().find(|word| word.parse::<u32>().is_err()) == Some("genmove");
+    let refused = limit_searches(&mut game.gtp, &state.limiter, client);
+    let start = Instant::now();
+    // commands such as genmove search for a while, so they run off the async threads
+    let response = tokio::task::spawn_blocking(move || {
+        let response = game.gtp.accept_command(command);
+        // the game's other clients are limited by their own requests
+        game.gtp.remove_search_limit();
+        response
+    })
+    .await
+    .unwrap_or_else(|error| format!("? {error}"));
+    if let Some(seconds) = refused.lock().unwrap().take() {
+        return Err(too_many_requests(seconds));
+    }
+    if genmove && response.starts_with('=') {
+        state.metrics.observe_genmove(start.elapsed());
+    }
+
+    if response == "quit" {
+        state.games.remove(&session).await;
+        return Ok(String::from("= "));
+    }
+
+    Ok(response)
+}
+
+// The output of a GTP session over a WebSocket, which sends each response once it is written
+struct WebSocketOutput {
+    buffer: Vec<u8>,
+    responses: mpsc::UnboundedSender<String>,
+}
+
+impl Write for WebSocketOutput {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(bytes);
+        Ok(bytes.len())
+    }
+
+    // Sends the response written so far, failing once the WebSocket has closed
+    fn flush(&mut self) -> io::Result<()> {
+        let response = String::from_utf8_lossy(&self.buffer).into_owned();
+        self.buffer.clear();
+        self.responses
+            .send(response)
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "WebSocket closed"))
+    }
+}
+
+// Upgrades a request to a WebSocket carrying a GTP session with a new game
+#[utoipa::path(
+    get,
+    path = "/gtp",
+    tag = "gtp",
+    responses(
+        (
+            status = 101,
+            description = "A WebSocket taking a GTP command per line of a text message, answering each in a \
+                           text message of its own",
+        ),
+    )
+)]
+async fn gtp_socket(State(state): State<AppState>, Client(client): Client, upgrade: WebSocketUpgrade) -> Response {
+    upgrade.on_upgrade(move |socket| serve_gtp_socket(state, client, socket))
+}
+
+// Runs a GTP session over the given WebSocket, taking each line of a text message as a command
+// and answering each in a text message of its own, until the client quits or disconnects
+// The session listens on a blocking thread, so stop reaches genmove while it searches, and each
+// search takes a request from the given client's bucket, answering with an error once it is empty
+async fn serve_gtp_socket(state: AppState, client: String, mut socket: WebSocket) {
+    let (commands, input) = std::sync::mpsc::channel::<String>();
+    let (responses, mut output) = mpsc::unbounded_channel();
+    let mut gtp = GTP::new();
+    limit_searches(&mut gtp, &state.limiter, client);
+    let session = tokio::task::spawn_blocking(move || {
+        let mut output = WebSocketOutput { buffer: Vec::new(), responses };
+        gtp.listen(input.into_iter().map(Ok), &mut output)
+    });
+
+    loop {
+        tokio::select! {
+            response = output.recv() => match response {
+                Some(response) => {
+                    if socket.send(Message::Text(response.into())).await.is_err() {
+                        break;
+                    }
+                }
+                None => break, // the session quit
+            },
+            message = socket.recv() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    text.lines().for_each(|line| {
+                        let _ = commands.send(line.to_string()); // the session may have quit
+                    });
+                }
+                Some(Ok(_)) => {}
+                _ => break, // the client disconnected
+            },
+        }
+    }
+
+    // quitting stops any search, and closing the input ends the session if it quit already
+    let _ = commands.send("quit".to_string());
+    drop(commands);
+    if let Ok(Err(error)) = session.await {
+        tracing::warn!("GTP WebSocket session failed: {error}");
+    }
+    let _ = socket.send(Message::Close(None)).await; // the client may have disconnected already
+}
+
+// The OpenAPI description of the GTP routes, and the document of every route of the API
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Go Engine", description = "Plays and analyzes games of Go over HTTP and WebSockets"),
+    paths(read_json, new_session, read_command, gtp_socket, api_docs),
+    tags(
+        (name = "gtp", description = "Go Text Protocol sessions"),
+        (name = "docs", description = "This document"),
+    )
+)]
+struct ApiDoc;
+
+// The OpenAPI document of every route of the API
+pub(crate) fn api_document() -> utoipa::openapi::OpenApi {
+    let mut document = ApiDoc::openapi();
+    document.merge(RestApi::openapi());
+    document.merge(PlayApi::openapi());
+    document.merge(MetricsApi::openapi());
+    document.merge(SpectateApi::openapi());
+    #[cfg(feature = "image")]
+    document.merge(PngApi::openapi());
+    document
+}
+
+// GET /docs: the OpenAPI document of the API, for clients to generate typed bindings from
+#[utoipa::path(
+    get,
+    path = "/docs",
+    tag = "docs",
+    responses((status = 200, description = "The OpenAPI document", content_type = "application/json")),
+)]
+async fn api_docs() -> Json<utoipa::openapi::OpenApi> {
+    Json(api_document())
+}
+
+// Removes the games left idle for IDLE_TIMEOUT, and the rate limits of clients that have
+// regained every request, every IDLE_SWEEP_INTERVAL
+async fn sweep_idle_games(state: AppState) {
+    let mut interval = tokio::time::interval(IDLE_SWEEP_INTERVAL);
+    loop {
+        interval.tick().await;
+        let expired = state.games.expire_idle(IDLE_TIMEOUT).await;
+        if expired > 0 {
+            tracing::info!("Removed {expired} idle games, {} remain", state.games.len().await);
+        }
+        state.limiter.expire_full();
+    }
+}
+
+// Serves the given app over HTTPS on the given address and port, with the given PEM certificate
+// chain and private key
+#[cfg(feature = "tls")]
+async fn serve_tls(
+    app: axum::extract::connect_info::IntoMakeServiceWithConnectInfo<Router, SocketAddr>,
+    (host, port): (&str, u16),
+    cert: &std::path::Path,
+    key: &std::path::Path,
+) -> std::io::Result<()> {
+    // ring is the only provider built, so installing it fails only if it was installed already
+    let _ = rustls::crypto::ring::default_provider().install_default();
+    let config = axum_server::tls_rustls::RustlsConfig::from_pem_file(cert, key).await?;
+    let address = tokio::net::lookup_host((host, port))
+        .await?
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::AddrNotAvailable, format!("No address found for {host}")))?;
+
+    tracing::info!("API listening on {address} over TLS");
+    axum_server::bind_rustls(address, config).serve(app).await
+}
+
+// Begins a new listener for HTTP requests on the address and port given by the settings, serving
+// HTTPS when they give a certificate and private key
+#[tokio::main]
+pub async fn start_api(settings: ApiSettings) -> std::io::Result<()> {
+    let mut state = AppState::default();
+    if let Some(path) = &settings.database {
+        let store = GameStore::open(path).map_err(io::Error::other)?;
+        state.games = Arc::new(GameManager::with_store(store).map_err(io::Error::other)?);
+        tracing::info!("Saving games to {}", path.display());
+    }
+    tokio::spawn(sweep_idle_games(state.clone()));
+    let app = Router::new()
+        .route("/", post(read_json))
+        .route("/command", post(new_session))
+        .route("/command/{session}", post(read_command))
+        .route("/gtp", get(gtp_socket))
+        .route("/play", get(play_socket))
+        .route("/docs", get(api_docs))
+        .route("/healthz", get(health))
+        .route("/metrics", get(get_metrics))
+        .merge(rest_routes())
+        .merge(spectate_routes());
+    #[cfg(feature = "image")]
+    let app = app.merge(png_routes());
+    let app = app
+        .layer(axum::middleware::from_fn_with_state(state.clone(), count_requests))
+        .with_state(state);
+
+    // clients are rate limited by their address unless they give an API key
+    let app = match settings.cors_layer().map_err(io::Error::other)? {
+        Some(cors) => app.layer(cors),
+        None => app,
+    };
+    let app = app.into_make_service_with_connect_info::<SocketAddr>();
+    let address = (settings.host.as_str(), settings.port);
+    match (&settings.tls_cert, &settings.tls_key) {
+        #[cfg(feature = "tls")]
+        (Some(cert), Some(key)) => serve_tls(app, address, cert, key).await,
+        _ => {
+            let listener = tokio::net::TcpListener::bind(address).await?;
+            tracing::info!("API listening on {}", listener.local_addr()?);
+            axum::serve(listener, app).await
+        }
+    }
+}
\ No newline at end of file
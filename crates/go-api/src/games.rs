@@ -0,0 +1,327 @@
+use super::*;
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, Mutex, OwnedMutexGuard};
+/******************************************************\
+|****************      CONSTANTS       ****************|
+\******************************************************/
+
+// Games left untouched for this long are removed by the idle sweep
+pub(crate) const IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+// How often the API sweeps for idle games
+pub(crate) const IDLE_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+// Updates of a game a spectator may fall behind by before it skips to the latest
+const SPECTATOR_BUFFER: usize = 64;
+
+/****************************************************\
+|****************    GLOBAL TYPES    ****************|
+\****************************************************/
+
+// The games the API holds, each its own board and engine state by its id, locked separately so
+// that clients of different games never wait on each other
+// With a GameStore, every change to a game is saved, and games not held are loaded from it
+#[derive(Default)]
+pub(crate) struct GameManager {
+    games: Mutex<HashMap<String, Arc<Mutex<Game>>>>,
+    next_id: AtomicU64,
+    store: Option<Arc<GameStore>>,
+    settings: RwLock<EngineSettings>,
+    // counts the changes to the settings, so that each game takes them on when next used
+    settings_version: AtomicU64,
+}
+
+// The settings of the engine serving the games of the API, changed at runtime: the knobs every
+// game searches with, and the ruleset of the games started after the change
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub(crate) struct EngineSettings {
+    pub(crate) params: SearchParams,
+    pub(crate) ruleset: Ruleset,
+}
+
+// A game held by the GameManager, with the time it was last used
+pub(crate) struct Game {
+    pub(crate) gtp: GTP,
+    last_used: Instant,
+    // the game as it was last saved, if it was
+    saved: Option<GameSnapshot>,
+    // set once the game is removed, so that it is never saved again
+    removed: bool,
+    // the version of the EngineSettings the game searches with
+    settings_version: u64,
+    // sends the game to its spectators after every change
+    spectators: broadcast::Sender<GameView>,
+    // the record of the game as its spectators last saw it
+    spectated: String,
+}
+
+// A game locked for use, which saves it to the GameManager's store, if any, once unlocked
+pub(crate) struct GameGuard {
+    id: String,
+    game: OwnedMutexGuard<Game>,
+    store: Option<Arc<GameStore>>,
+}
+
+/*****************************************************\
+|****************        SETUP        ****************|
+\*****************************************************/
+
+impl Game {
+    // Creates a new game, on a new GTP session
+    fn new() -> Game {
+        Game::on(GTP::new(), None)
+    }
+
+    // Resumes the game of the given snapshot, as it was saved
+    fn restored(snapshot: GameSnapshot) -> Result<Game, String> {
+        Ok(Game::on(snapshot.restore()?, Some(snapshot)))
+    }
+
+    // Holds the game of the given GTP session, saved as the given snapshot if it was
+    fn on(gtp: GTP, saved: Option<GameSnapshot>) -> Game {
+        Game {
+            gtp,
+            last_used: Instant::now(),
+            saved,
+            removed: false,
+            settings_version: 0,
+            spectators: broadcast::channel(SPECTATOR_BUFFER).0,
+            spectated: String::new(),
+        }
+    }
+}
+
+impl GameManager {
+    // Creates a GameManager saving its games to the given store, and resuming those saved in it,
+    // whose ids are never given to new games
+    pub(crate) fn with_store(store: GameStore) -> Result<GameManager, String> {
+        let next_id = AtomicU64::new(store.max_id()?);
+        Ok(GameManager { next_id, store: Some(Arc::new(store)), ..GameManager::default() })
+    }
+}
+
+impl Default for EngineSettings {
+    // The settings a GTP session starts with
+    fn default() -> EngineSettings {
+        EngineSettings { params: SearchParams::default(), ruleset: Ruleset::Chinese }
+    }
+}
+
+/********************************************************\
+|****************     PUBLIC METHODS     ****************|
+\********************************************************/
+
+impl GameManager {
+    // Creates a new game under a new unique id, returning the id
+    pub(crate) async fn create(&self) -> String {
+        let mut games = self.games.lock().await;
+        // ids given by clients may already hold the next number
+        let id = loop {
+            let id = (self.next_id.fetch_add(1, Ordering::Relaxed) + 1).to_string();
+            if !games.contains_key(&id) && !self.is_saved(&id) {
+                break id;
+            }
+        };
+        games.insert(id.clone(), Arc::new(Mutex::new(self.new_game())));
+        id
+    }
+
+    // Locks the game of the given id for use, resuming it from the store if it is not held, or
+    // None if there is no such game
+    pub(crate) async fn get(&self, id: &str) -> Option<GameGuard> {
+        let game = {
+            let mut games = self.games.lock().await;
+            match games.get(id) {
+                Some(game) => Arc::clone(game),
+                None => {
+                    let game = Arc::new(Mutex::new(self.resume(id)?));
+                    games.insert(id.to_string(), Arc::clone(&game));
+                    game
+                }
+            }
+        };
+        Some(self.used(id, game).await)
+    }
+
+    // Locks the game of the given id for use, resuming it from the store or creating it first if
+    // there is no such game
+    pub(crate) async fn get_or_create(&self, id: &str) -> GameGuard {
+        let game = Arc::clone(
+            self.games
+                .lock()
+                .await
+                .entry(id.to_string())
+                .or_insert_with(|| Arc::new(Mutex::new(self.resume(id).unwrap_or_else(|| self.new_game())))),
+        );
+        self.used(id, game).await
+    }
+
+    // Removes the game of the given id, from the store too, returning whether there was one
+    // Waits for the game to be unlocked, so that no use of it saves it again
+    pub(crate) async fn remove(&self, id: &str) -> bool {
+        let held = self.games.lock().await.remove(id);
+        if let Some(game) = &held {
+            game.lock().await.removed = true;
+        }
+
+        let saved = self.is_saved(id);
+        if let Some(store) = self.store.as_ref().filter(|_| saved) {
+            if let Err(error) = store.delete(id) {
+                tracing::warn!("{error}");
+            }
+        }
+        held.is_some() || saved
+    }
+
+    // Removes every game not used for the given time, returning how many were removed
+    // Games in use or watched by spectators are never idle, whenever they were last used, and
+    // games removed stay saved in the store, to be resumed when next used
+    pub(crate) async fn expire_idle(&self, timeout: Duration) -> usize {
+        let mut games = self.games.lock().await;
+        let before = games.len();
+        games.retain(|_, game| match game.try_lock() {
+            Ok(game) => game.last_used.elapsed() < timeout || game.spectators.receiver_count() > 0,
+            Err(_) => true,
+        });
+        before - games.len()
+    }
+
+    // Watches the game of the given id, giving it as it is and receiving it after every change
+    // until it is removed, or None if there is no such game
+    pub(crate) async fn spectate(&self, id: &str) -> Option<(GameView, broadcast::Receiver<GameView>)> {
+        let mut game = self.get(id).await?;
+        game.spectated = game.gtp.record().to_string();
+        Some((game_view(id, &game), game.spectators.subscribe()))
+    }
+
+    // The number of games held
+    pub(crate) async fn len(&self) -> usize {
+        self.games.lock().await.len()
+    }
+
+    // The settings of the engine serving the games
+    pub(crate) fn settings(&self) -> EngineSettings {
+        *self.settings.read().unwrap()
+    }
+
+    // Changes the settings of the engine serving the games, which every game searches with from
+    // its next use, where only the games started from now on are played under its ruleset
+    pub(crate) fn set_settings(&self, settings: EngineSettings) {
+        *self.settings.write().unwrap() = settings;
+        self.settings_version.fetch_add(1, Ordering::Relaxed);
+        tracing::info!(?settings, "Changed engine settings");
+    }
+}
+
+impl Deref for GameGuard {
+    type Target = Game;
+
+    fn deref(&self) -> &Game {
+        &self.game
+    }
+}
+
+impl DerefMut for GameGuard {
+    fn deref_mut(&mut self) -> &mut Game {
+        &mut self.game
+    }
+}
+
+impl Drop for GameGuard {
+    // Sends the game to its spectators, and saves it, if it changed since they last saw it and
+    // since it was last saved
+    fn drop(&mut self) {
+        self.broadcast();
+        self.save();
+    }
+}
+
+/****************************************************\
+|****************       HELPER       ****************|
+\****************************************************/
+
+impl GameManager {
+    // Locks the given game of the given id, marking it as used now, and giving it the search
+    // params of the current settings if they changed since it was last used
+    async fn used(&self, id: &str, game: Arc<Mutex<Game>>) -> GameGuard {
+        let mut game = game.lock_owned().await;
+        game.last_used = Instant::now();
+        let version = self.settings_version.load(Ordering::Relaxed);
+        if game.settings_version != version {
+            game.gtp.set_params(self.settings().params);
+            game.settings_version = version;
+        }
+        GameGuard { id: id.to_string(), game, store: self.store.clone() }
+    }
+
+    // Starts a new game under the ruleset of the current settings, with the komi usual for it
+    // unless the ruleset is the one a GTP session starts with
+    fn new_game(&self) -> Game {
+        let mut game = Game::new();
+        let ruleset = self.settings().ruleset;
+        if ruleset != EngineSettings::default().ruleset {
+            let _ = game.gtp.execute(&["set_rules", &ruleset.to_string()]); // every Ruleset reads back from its name
+        }
+        game
+    }
+
+    // The game of the given id as it was saved in the store, if it was
+    fn resume(&self, id: &str) -> Option<Game> {
+        let snapshot = match self.store.as_ref()?.load(id) {
+            Ok(snapshot) => snapshot?,
+            Err(error) => {
+                tracing::warn!("{error}");
+                return None;
+            }
+        };
+        match Game::restored(snapshot) {
+            Ok(game) => {
+                tracing::info!(id, "Resumed saved game");
+                Some(game)
+            }
+            Err(error) => {
+                tracing::warn!(id, "Could not resume saved game: {error}");
+                None
+            }
+        }
+    }
+
+    // Whether the game of the given id is saved in the store
+    fn is_saved(&self, id: &str) -> bool {
+        self.store.as_ref().is_some_and(|store| store.load(id).is_ok_and(|snapshot| snapshot.is_some()))
+    }
+}
+
+impl GameGuard {
+    // Sends the game to its spectators, if it has any, when its record changed since they last
+    // saw it
+    fn broadcast(&mut self) {
+        if self.game.spectators.receiver_count() == 0 {
+            return;
+        }
+        let record = self.game.gtp.record().to_string();
+        if record != self.game.spectated {
+            let _ = self.game.spectators.send(game_view(&self.id, &self.game)); // spectators may have left
+            self.game.spectated = record;
+        }
+    }
+
+    // Saves the game to the store, if there is one, when it changed since it was last saved
+    fn save(&mut self) {
+        let Some(store) = self.store.as_ref().filter(|_| !self.game.removed) else {
+            return;
+        };
+        let snapshot = GameSnapshot::of(&self.game.gtp);
+        if self.game.saved.as_ref() == Some(&snapshot) {
+            return;
+        }
+        match store.save(&self.id, &snapshot) {
+            Ok(()) => self.game.saved = Some(snapshot),
+            Err(error) => tracing::warn!("{error}"),
+        }
+    }
+}
@@ -0,0 +1,262 @@
+// tonic's Status is the error of every gRPC call, however large
+#![allow(clippy::result_large_err)]
+
+use super::*;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status};
+
+// Types and service trait generated from proto/go_engine.proto by build.rs
+mod proto {
+    tonic::include_proto!("go_engine");
+}
+use proto::go_engine_server::{GoEngine, GoEngineServer};
+use proto::*;
+/******************************************************\
+|****************      CONSTANTS       ****************|
+\******************************************************/
+
+// Port the gRPC service listens on unless given with --port
+const DEFAULT_PORT: u16 = 50051;
+
+// MCTS iterations added between analysis updates, and updates streamed, when a request gives 0
+const DEFAULT_ITERATIONS_PER_UPDATE: u32 = 100;
+const DEFAULT_UPDATES: u32 = 5;
+
+/****************************************************\
+|****************    GLOBAL TYPES    ****************|
+\****************************************************/
+
+// Settings of the gRPC service, parsed from the command line
+pub(crate) struct GrpcSettings {
+    port: u16,
+}
+
+/*****************************************************\
+|****************    PRIVATE TYPES    ****************|
+\*****************************************************/
+
+// The gRPC service, holding every game in progress by its id
+#[derive(Default)]
+struct GoEngineService {
+    games: Arc<Mutex<HashMap<u64, Engine>>>,
+    next_id: AtomicU64,
+}
+
+/*****************************************************\
+|****************        SETUP        ****************|
+\*****************************************************/
+
+impl GrpcSettings {
+    // Parses gRPC settings from the arguments following the grpc run mode
+    // Accepts "--port N", where the port defaults to 50051
+    pub(crate) fn from_args(args: &[String]) -> Result<GrpcSettings, String> {
+        let mut settings = GrpcSettings { port: DEFAULT_PORT };
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--port" => {
+                    let value = args.next().ok_or("No value given to grpc option --port")?;
+                    settings.port = value
+                        .parse()
+                        .map_err(|_| format!("Invalid value given to grpc option --port: {value}"))?;
+                }
+                _ => return Err(format!("Unknown grpc option given: {arg}")),
+            }
+        }
+
+        Ok(settings)
+    }
+}
+
+/****************************************************\
+|****************       HELPER       ****************|
+\****************************************************/
+
+impl GoEngineService {
+    // Runs the given function on the game with the given id
+    fn with_game<T>(&self, id: u64, function: impl FnOnce(&mut Engine) -> Result<T, String>) -> Result<T, Status> {
+        let mut games = self.games.lock().unwrap();
+        let game = games
+            .get_mut(&id)
+            .ok_or_else(|| Status::not_found(format!("No game with id {id}")))?;
+        function(game).map_err(Status::invalid_argument)
+    }
+}
+
+// Describes the given game with the given id
+fn game_state(id: u64, game: &Engine) -> GameState {
+    let vertices = |color| game.stones(color).iter().map(Position::to_string).collect();
+    GameState {
+        id,
+        size: game.size().to_u16() as u32,
+        komi: game.komi(),
+        to_play: color_name(game.to_play()).to_string(),
+        black_stones: vertices(Color::Black),
+        white_stones: vertices(Color::White),
+        sgf: game.to_sgf(),
+    }
+}
+
+// The Color of the given name in a request, ex. "b" or "white"
+fn parse_color(name: &str) -> Result<Color, String> {
+    Color::from_name(name).ok_or(format!("Invalid color: {name}"))
+}
+
+// The name of the given Color in a reply, "b" or "w"
+fn color_name(color: Color) -> &'static str {
+    match color {
+        Color::Black => "b",
+        Color::White => "w",
+    }
+}
+
+/*****************************************************\
+|****************       SERVICE       ****************|
+\*****************************************************/
+
+#[tonic::async_trait]
+impl GoEngine for GoEngineService {
+    async fn new_game(&self, request: Request<NewGameRequest>) -> Result<Response<GameState>, Status> {
+        let request = request.into_inner();
+        let mut game = if request.sgf.is_empty() {
+            let size = u16::try_from(request.size).ok().and_then(BoardSize::from_u16);
+            size.map(|size| Engine::new(size, request.komi)).ok_or(format!("Unsupported board size: {}", request.size))
+        } else {
+            Engine::from_sgf(&request.sgf)
+        }
+        .map_err(Status::invalid_argument)?;
+        if request.playouts > 0 {
+            game.set_playouts(request.playouts.min(u16::MAX as u32) as u16);
+        }
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let state = game_state(id, &game);
+        self.games.lock().unwrap().insert(id, game);
+        tracing::info!(id, "Started gRPC game");
+        Ok(Response::new(state))
+    }
+
+    async fn get_game(&self, request: Request<GameId>) -> Result<Response<GameState>, Status> {
+        let id = request.into_inner().id;
+        self.with_game(id, |game| Ok(game_state(id, game))).map(Response::new)
+    }
+
+    async fn end_game(&self, request: Request<GameId>) -> Result<Response<Empty>, Status> {
+        let id = request.into_inner().id;
+        match self.games.lock().unwrap().remove(&id) {
+            Some(_) => Ok(Response::new(Empty {})),
+            None => Err(Status::not_found(format!("No game with id {id}"))),
+        }
+    }
+
+    async fn play(&self, request: Request<PlayRequest>) -> Result<Response<GameState>, Status> {
+        let request = request.into_inner();
+        self.with_game(request.id, |game| {
+            let color = parse_color(&request.color)?;
+            match Position::parse_vertex(&request.vertex, game.size()) {
+                Ok(Some(pos)) => game.play(color, pos)?,
+                Ok(None) => game.pass(color),
+                Err(_) => return Err(format!("Invalid vertex: {}", request.vertex)),
+            }
+            Ok(game_state(request.id, game))
+        })
+        .map(Response::new)
+    }
+
+    async fn gen_move(&self, request: Request<GenMoveRequest>) -> Result<Response<GenMoveReply>, Status> {
+        let request = request.into_inner();
+        let games = Arc::clone(&self.games);
+
+        // the search blocks, so it runs off the async workers
+        tokio::task::spawn_blocking(move || {
+            let mut games = games.lock().unwrap();
+            let game = games
+                .get_mut(&request.id)
+                .ok_or_else(|| Status::not_found(format!("No game with id {}", request.id)))?;
+            let color = parse_color(&request.color).map_err(Status::invalid_argument)?;
+            Ok(Response::new(GenMoveReply {
+                vertex: move_to_vertex(&game.genmove(color)),
+                state: Some(game_state(request.id, game)),
+            }))
+        })
+        .await
+        .map_err(|error| Status::internal(error.to_string()))?
+    }
+
+    async fn score(&self, request: Request<ScoreRequest>) -> Result<Response<ScoreReply>, Status> {
+        let request = request.into_inner();
+        self.with_game(request.id, |game| {
+            let ruleset = Ruleset::from_name(&request.rules).ok_or(format!("Unknown rules: {}", request.rules))?;
+            Ok(ScoreReply {
+                score: game.score(ruleset.scoring()),
+                result: game.result(ruleset.scoring()),
+            })
+        })
+        .map(Response::new)
+    }
+
+    type AnalyzeStream = ReceiverStream<Result<AnalysisUpdate, Status>>;
+
+    async fn analyze(&self, request: Request<AnalyzeRequest>) -> Result<Response<Self::AnalyzeStream>, Status> {
+        let request = request.into_inner();
+        let (position, color) = self.with_game(request.id, |game| {
+            let (board, color) = game.position();
+            Ok((board.deepcopy(), color))
+        })?;
+        let per_update = match request.iterations_per_update {
+            0 => DEFAULT_ITERATIONS_PER_UPDATE,
+            iterations => iterations,
+        };
+        let updates = match request.updates {
+            0 => DEFAULT_UPDATES,
+            updates => updates,
+        };
+
+        // each update searches afresh with more iterations, stopping early once the client hangs up
+        let (sender, receiver) = mpsc::channel(1);
+        tokio::task::spawn_blocking(move || {
+            for update in 1..=updates {
+                let iterations = (per_update * update).min(u16::MAX as u32);
+                let candidates = analyze_candidates(&position, color, iterations as u16)
+                    .into_iter()
+                    .map(|analysis| Candidate {
+                        vertex: move_to_vertex(&analysis.best_move),
+                        winrate: analysis.winrate,
+                        score: analysis.score,
+                        visits: analysis.visits as u32,
+                    })
+                    .collect();
+
+                let update = AnalysisUpdate { iterations, candidates };
+                if sender.blocking_send(Ok(update)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Response::new(ReceiverStream::new(receiver)))
+    }
+}
+
+/********************************************************\
+|****************     PUBLIC METHODS     ****************|
+\********************************************************/
+
+// Serves the gRPC service on the port given by the settings until the process is stopped
+#[tokio::main]
+pub(crate) async fn run_grpc(settings: GrpcSettings) -> std::io::Result<()> {
+    let address = SocketAddr::from(([0, 0, 0, 0], settings.port));
+    tracing::info!("gRPC service listening on {address}");
+
+    tonic::transport::Server::builder()
+        .add_service(GoEngineServer::new(GoEngineService::default()))
+        .serve(address)
+        .await
+        .map_err(std::io::Error::other)
+}
@@ -0,0 +1,78 @@
+#![doc(hidden)]
+#[cfg(feature = "api")]
+mod api;
+#[cfg(feature = "api")]
+mod games;
+#[cfg(feature = "api")]
+mod rest;
+#[cfg(feature = "api")]
+mod play;
+#[cfg(feature = "api")]
+mod ratelimit;
+#[cfg(feature = "api")]
+mod metrics;
+#[cfg(feature = "api")]
+mod store;
+#[cfg(feature = "api")]
+mod spectate;
+#[cfg(feature = "api")]
+mod svg;
+#[cfg(all(feature = "api", feature = "image"))]
+mod png;
+#[cfg(feature = "grpc")]
+mod grpc;
+mod tests;
+
+use engine::*;
+#[cfg(feature = "api")]
+use go_gtp::*;
+#[cfg(feature = "api")]
+use api::*;
+#[cfg(feature = "api")]
+use games::*;
+#[cfg(feature = "api")]
+use rest::*;
+#[cfg(feature = "api")]
+use play::*;
+#[cfg(feature = "api")]
+use ratelimit::*;
+#[cfg(feature = "api")]
+use metrics::*;
+#[cfg(feature = "api")]
+use store::*;
+#[cfg(feature = "api")]
+use spectate::*;
+#[cfg(feature = "api")]
+use svg::*;
+#[cfg(all(feature = "api", feature = "image"))]
+use png::*;
+#[cfg(feature = "grpc")]
+use grpc::*;
+/****************************************************\
+|****************        MAIN        ****************|
+\****************************************************/
+
+// Library function of the API run modes, controls the runmode of the program as start_go_agent does for the others
+// Returns an AgentError for a missing or unknown mode, invalid arguments, a run mode whose feature was not built,
+// or a server that failed
+pub fn start_api_agent(args: Vec<String>) -> Result<(), AgentError> {
+    init_logging();
+    let mode = args.get(1).ok_or(AgentError::MissingMode)?;
+    let mode_args = &args[2..];
+
+    if mode.eq_ignore_ascii_case("api") {
+        #[cfg(feature = "api")]
+        start_api(ApiSettings::from_args(mode_args).map_err(AgentError::InvalidArguments)?)?;
+        #[cfg(not(feature = "api"))]
+        return Err(AgentError::MissingFeature("api", "api"));
+    } else if mode.eq_ignore_ascii_case("grpc") {
+        #[cfg(feature = "grpc")]
+        run_grpc(GrpcSettings::from_args(mode_args).map_err(AgentError::InvalidArguments)?)?;
+        #[cfg(not(feature = "grpc"))]
+        return Err(AgentError::MissingFeature("grpc", "grpc"));
+    } else {
+        return Err(AgentError::UnknownMode(mode.clone()));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use super::*;
+use axum::extract::{MatchedPath, Request, State};
+use axum::http::header::CONTENT_TYPE;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::time::Duration;
+use utoipa::OpenApi;
+/******************************************************\
+|****************      CONSTANTS       ****************|
+\******************************************************/
+
+// Upper bounds, in seconds, of the buckets of the genmove latency histogram
+const GENMOVE_BUCKETS: [f64; 10] = [0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0];
+
+// Content type of the Prometheus text exposition format
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/****************************************************\
+|****************    GLOBAL TYPES    ****************|
+\****************************************************/
+
+// The measurements of the API exposed to Prometheus by /metrics
+#[derive(Default)]
+pub(crate) struct Metrics {
+    // requests answered by their method, route, and status
+    requests: Mutex<BTreeMap<(String, String, u16), u64>>,
+    genmoves: Mutex<Histogram>,
+}
+
+/*****************************************************\
+|****************    PRIVATE TYPES    ****************|
+\*****************************************************/
+
+// The durations observed, counted in the GENMOVE_BUCKETS they fall in, where the last count holds
+// those past every bucket
+#[derive(Default)]
+struct Histogram {
+    counts: [u64; GENMOVE_BUCKETS.len() + 1],
+    sum: f64,
+}
+
+/****************************************************\
+|****************       HELPER       ****************|
+\****************************************************/
+
+impl Histogram {
+    // Counts the given duration
+    fn observe(&mut self, duration: Duration) {
+        let seconds = duration.as_secs_f64();
+        let bucket = GENMOVE_BUCKETS.iter().position(|&bound| seconds <= bound).unwrap_or(GENMOVE_BUCKETS.len());
+        self.counts[bucket] += 1;
+        self.sum += seconds;
+    }
+
+    // Writes the histogram under the given name in the Prometheus text format, its buckets
+    // counting every duration at most their bound
+    fn render(&self, output: &mut String, name: &str) {
+        let mut cumulative = 0;
+        for (bound, count) in GENMOVE_BUCKETS.iter().zip(self.counts) {
+            cumulative += count;
+            let _ = writeln!(output, "{name}_bucket{{le=\"{bound}\"}} {cumulative}");
+        }
+        let total: u64 = self.counts.iter().sum();
+        let _ = writeln!(output, "{name}_bucket{{le=\"+Inf\"}} {total}");
+        let _ = writeln!(output, "{name}_sum {}", self.sum);
+        let _ = writeln!(output, "{name}_count {total}");
+    }
+}
+
+// Writes the help and type lines of the metric of the given name
+fn describe(output: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(output, "# HELP {name} {help}");
+    let _ = writeln!(output, "# TYPE {name} {kind}");
+}
+
+/********************************************************\
+|****************     PUBLIC METHODS     ****************|
+\********************************************************/
+
+impl Metrics {
+    // Counts a request answered with the given method, route, and status
+    pub(crate) fn record_request(&self, method: &str, route: &str, status: u16) {
+        let mut requests = self.requests.lock().unwrap();
+        *requests.entry((method.to_string(), route.to_string(), status)).or_default() += 1;
+    }
+
+    // Counts a move generated over the API in the given time
+    pub(crate) fn observe_genmove(&self, duration: Duration) {
+        self.genmoves.lock().unwrap().observe(duration);
+    }
+
+    // The metrics in the Prometheus text format, with the given number of games held and the
+    // searches of every move the engine has generated
+    pub(crate) fn render(&self, games: usize) -> String {
+        let mut output = String::new();
+
+        let name = "go_engine_http_requests_total";
+        describe(&mut output, name, "counter", "HTTP requests answered, by method, route, and status");
+        for ((method, route, status), count) in self.requests.lock().unwrap().iter() {
+            let _ = writeln!(output, "{name}{{method=\"{method}\",route=\"{route}\",status=\"{status}\"}} {count}");
+        }
+
+        describe(&mut output, "go_engine_active_games", "gauge", "Games held by the API");
+        let _ = writeln!(output, "go_engine_active_games {games}");
+
+        let totals = search_totals();
+        let name = "go_engine_search_iterations_total";
+        describe(&mut output, name, "counter", "MCTS iterations searched for generated moves");
+        let _ = writeln!(output, "{name} {}", totals.iterations);
+        let name = "go_engine_search_seconds_total";
+        describe(&mut output, name, "counter", "Time spent searching for generated moves");
+        let _ = writeln!(output, "{name} {}", totals.time.as_secs_f64());
+        let name = "go_engine_search_nodes_per_second";
+        describe(&mut output, name, "gauge", "MCTS iterations per second of the last search");
+        let _ = writeln!(output, "{name} {}", totals.last_rate);
+
+        let name = "go_engine_genmove_duration_seconds";
+        describe(&mut output, name, "histogram", "Time taken to generate moves requested over the API");
+        self.genmoves.lock().unwrap().render(&mut output, name);
+
+        output
+    }
+}
+
+/*****************************************************\
+|****************      ENDPOINTS      ****************|
+\*****************************************************/
+
+// Counts every request by its method, route, and the status it is answered with, where requests
+// matching no route are counted together so that unknown paths cannot grow the metrics
+pub(crate) async fn count_requests(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or("unmatched".to_string(), |path| path.as_str().to_string());
+    let response = next.run(request).await;
+    state.metrics.record_request(&method, &route, response.status().as_u16());
+    response
+}
+
+// GET /healthz: answers ok while the API is serving
+#[utoipa::path(
+    get,
+    path = "/healthz",
+    tag = "monitoring",
+    responses((status = 200, description = "The API is serving", body = String, content_type = "text/plain")),
+)]
+pub(crate) async fn health() -> &'static str {
+    "ok"
+}
+
+// GET /metrics: the metrics of the API in the Prometheus text format
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "monitoring",
+    responses(
+        (
+            status = 200,
+            description = "Request counts, active games, search speed, and genmove latency",
+            body = String,
+            content_type = "text/plain",
+        ),
+    )
+)]
+pub(crate) async fn get_metrics(State(state): State<AppState>) -> Response {
+    let games = state.games.len().await;
+    ([(CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)], state.metrics.render(games)).into_response()
+}
+
+// The OpenAPI description of the monitoring routes
+#[derive(OpenApi)]
+#[openapi(
+    paths(health, get_metrics),
+    tags((name = "monitoring", description = "Health checks and Prometheus metrics"))
+)]
+pub(crate) struct MetricsApi;
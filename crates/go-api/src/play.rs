@@ -0,0 +1,289 @@
+use super::*;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::State;
+use axum::response::Response;
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+use utoipa::{OpenApi, ToSchema};
+/****************************************************\
+|****************    GLOBAL TYPES    ****************|
+\****************************************************/
+
+// A message from a player to the play protocol, each a JSON object naming its type
+#[derive(Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum ClientMessage {
+    // Joins the game of the given id, or starts a new one of the given size, komi, and time
+    // limits, where the engine answers the moves of the other color when given its own
+    Join {
+        game: Option<String>,
+        size: Option<u16>,
+        komi: Option<f64>,
+        time: Option<TimeInput>,
+        engine: Option<String>,
+    },
+    // Plays the given move, which may be a pass or resignation
+    Play { color: String, vertex: String },
+    // Asks the engine to play a move for the given color
+    Genmove { color: String },
+}
+
+// A message from the play protocol to a player, each a JSON object naming its type
+#[derive(Serialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum ServerMessage {
+    // The game the player joined
+    Joined { state: GameView },
+    // The game after the player's move
+    Moved { state: GameView },
+    // The move the engine played, and the game after it
+    EngineMove { vertex: String, state: GameView },
+    // The time each player has left, sent after every move of a game with time limits
+    Clock { black: TimeLeftState, white: TimeLeftState },
+    // Why a message was refused
+    Error { error: String },
+}
+
+/*****************************************************\
+|****************    PRIVATE TYPES    ****************|
+\*****************************************************/
+
+// The time limits of a new game, as time_settings takes them: seconds of main time, then
+// overtime periods of byo-yomi seconds for every byo-yomi stones moves
+#[derive(Deserialize, ToSchema)]
+pub(crate) struct TimeInput {
+    main_time: u64,
+    #[serde(default)]
+    byo_yomi_time: u64,
+    #[serde(default)]
+    byo_yomi_stones: u32,
+}
+
+// The time a player has left: seconds of main time while stones is 0, else of the current
+// overtime period, with the moves left to play in it or the periods left
+#[derive(Serialize, ToSchema)]
+pub(crate) struct TimeLeftState {
+    seconds: f64,
+    stones: u32,
+}
+
+// A player's connection to the play protocol, once joined to a game
+struct PlaySession {
+    id: String,
+    // the key the player is rate limited by, whose bucket each of the engine's moves takes from
+    client: String,
+    // the Color the engine answers the player's moves for, if any
+    engine: Option<Color>,
+    // when the player's turn started, to take their thinking time off their clock
+    turn_started: Instant,
+}
+
+/****************************************************\
+|****************       HELPER       ****************|
+\****************************************************/
+
+impl TimeInput {
+    // The time_settings command setting these time limits
+    pub(crate) fn command(&self) -> Vec<String> {
+        let values = [self.main_time, self.byo_yomi_time, self.byo_yomi_stones as u64];
+        ["time_settings".to_string()].into_iter().chain(values.map(|value| value.to_string())).collect()
+    }
+}
+
+impl PlaySession {
+    // Answers the given message from the player of the given client key, sending every reply to
+    // the given WebSocket
+    // Returns the message of the error the player is sent if the message is refused
+    async fn handle(
+        session: &mut Option<PlaySession>,
+        state: &AppState,
+        client: &str,
+        socket: &mut WebSocket,
+        message: ClientMessage,
+    ) -> Result<(), String> {
+        match message {
+            ClientMessage::Join { game, size, komi, time, engine } => {
+                let engine = match engine {
+                    Some(color) => Some(Color::from_name(&color).ok_or(format!("Invalid engine color: {color}"))?),
+                    None => None,
+                };
+                let id = match game {
+                    Some(id) => id,
+                    None => Self::new_game(state, size, komi, time).await?,
+                };
+                let game = find_game(state, &id).await.map_err(ApiError::into_message)?;
+                send(socket, ServerMessage::Joined { state: game_view(&id, &game) }).await;
+                drop(game);
+
+                let client = client.to_string();
+                let joined = session.insert(PlaySession { id, client, engine, turn_started: Instant::now() });
+                joined.answer(state, socket).await
+            }
+            ClientMessage::Play { color, vertex } => {
+                let joined = session.as_mut().ok_or("Join a game before playing")?;
+                let mut game = find_game(state, &joined.id).await.map_err(ApiError::into_message)?;
+                execute(&mut game, &["play", &color, &vertex]).map_err(ApiError::into_message)?;
+                if let Some(color) = Color::from_name(&color) {
+                    game.gtp.spend_time(color, joined.turn_started.elapsed());
+                }
+                send(socket, ServerMessage::Moved { state: game_view(&joined.id, &game) }).await;
+                send_clock(socket, &game).await;
+                drop(game);
+
+                joined.answer(state, socket).await
+            }
+            ClientMessage::Genmove { color } => {
+                let joined = session.as_mut().ok_or("Join a game before playing")?;
+                let game = find_game(state, &joined.id).await.map_err(ApiError::into_message)?;
+                joined.engine_move(state, socket, game, color).await
+            }
+        }
+    }
+
+    // Starts a new game of the given size, komi, and time limits, returning its id
+    async fn new_game(
+        state: &AppState,
+        size: Option<u16>,
+        komi: Option<f64>,
+        time: Option<TimeInput>,
+    ) -> Result<String, String> {
+        let id = state.games.create().await;
+        let mut game = find_game(state, &id).await.map_err(ApiError::into_message)?;
+        let settings = [
+            size.map(|size| vec!["boardsize".to_string(), size.to_string()]),
+            komi.map(|komi| vec!["komi".to_string(), komi.to_string()]),
+            time.map(|time| time.command()),
+        ];
+        for command in settings.into_iter().flatten() {
+            let arguments: Vec<&str> = command.iter().map(String::as_str).collect();
+            if let Err(error) = execute(&mut game, &arguments) {
+                drop(game);
+                state.games.remove(&id).await;
+                return Err(error.into_message());
+            }
+        }
+
+        tracing::info!(id, "Started game over the play protocol");
+        Ok(id)
+    }
+
+    // Plays the engine's move if it is the engine's turn in a game that is not over, then starts
+    // the player's turn
+    async fn answer(&mut self, state: &AppState, socket: &mut WebSocket) -> Result<(), String> {
+        let game = find_game(state, &self.id).await.map_err(ApiError::into_message)?;
+        match self.engine {
+            Some(engine) if game.gtp.side_to_move() == engine && game.gtp.record().result.is_none() => {
+                self.engine_move(state, socket, game, color_name(engine).to_string()).await
+            }
+            _ => {
+                self.turn_started = Instant::now();
+                Ok(())
+            }
+        }
+    }
+
+    // Plays the engine's move for the given color in the given game, sending it to the player,
+    // then starts the player's turn
+    // The search takes a request from the player's bucket, like the expensive endpoints
+    async fn engine_move(
+        &mut self,
+        state: &AppState,
+        socket: &mut WebSocket,
+        mut game: GameGuard,
+        color: String,
+    ) -> Result<(), String> {
+        state.limiter.limit(&self.client).map_err(|seconds| ApiError::rate_limited(seconds).into_message())?;
+
+        // the search blocks, so it runs off the async workers
+        let start = Instant::now();
+        let (game, vertex) = tokio::task::spawn_blocking(move || {
+            let vertex = execute(&mut game, &["genmove", &color]);
+            (game, vertex)
+        })
+        .await
+        .map_err(|error| error.to_string())?;
+
+        let vertex = vertex.map_err(ApiError::into_message)?;
+        state.metrics.observe_genmove(start.elapsed());
+        send(socket, ServerMessage::EngineMove { vertex, state: game_view(&self.id, &game) }).await;
+        send_clock(socket, &game).await;
+        self.turn_started = Instant::now();
+        Ok(())
+    }
+}
+
+// Sends the given message to the player, who may have disconnected already
+async fn send(socket: &mut WebSocket, message: ServerMessage) {
+    if let Ok(text) = serde_json::to_string(&message) {
+        let _ = socket.send(Message::Text(text.into())).await;
+    }
+}
+
+// Sends the time each player has left in the given game, if it has time limits
+async fn send_clock(socket: &mut WebSocket, game: &Game) {
+    let left = |color| {
+        game.gtp.clock().remaining(color).map(|(time, stones)| TimeLeftState { seconds: time.as_secs_f64(), stones })
+    };
+    if let (Some(black), Some(white)) = (left(Color::Black), left(Color::White)) {
+        send(socket, ServerMessage::Clock { black, white }).await;
+    }
+}
+
+/*****************************************************\
+|****************      ENDPOINTS      ****************|
+\*****************************************************/
+
+// GET /play: upgrades a request to a WebSocket speaking the play protocol
+#[utoipa::path(
+    get,
+    path = "/play",
+    tag = "play",
+    responses(
+        (
+            status = 101,
+            description = "A WebSocket taking ClientMessage objects and answering in ServerMessage objects, \
+                           each a JSON text message",
+        ),
+    )
+)]
+pub(crate) async fn play_socket(
+    State(state): State<AppState>,
+    Client(client): Client,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    upgrade.on_upgrade(move |socket| serve_play_socket(state, client, socket))
+}
+
+// Answers the messages of the player of the given client key over the given WebSocket until they
+// disconnect, where each message refused is answered with an error
+async fn serve_play_socket(state: AppState, client: String, mut socket: WebSocket) {
+    let mut session: Option<PlaySession> = None;
+    while let Some(Ok(message)) = socket.recv().await {
+        let text = match message {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+        let result = match serde_json::from_str::<ClientMessage>(&text) {
+            Ok(message) => PlaySession::handle(&mut session, &state, &client, &mut socket, message).await,
+            Err(error) => Err(format!("Invalid message: {error}")),
+        };
+        if let Err(error) = result {
+            send(&mut socket, ServerMessage::Error { error }).await;
+        }
+    }
+}
+
+/********************************************************\
+|****************     PUBLIC METHODS     ****************|
+\********************************************************/
+
+// The OpenAPI description of the play protocol, whose messages are given as schemas since its
+// WebSocket carries them
+#[derive(OpenApi)]
+#[openapi(
+    paths(play_socket),
+    components(schemas(ClientMessage, ServerMessage)),
+    tags((name = "play", description = "Games played in JSON messages over a WebSocket"))
+)]
+pub(crate) struct PlayApi;
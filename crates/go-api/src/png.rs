@@ -0,0 +1,45 @@
+use super::*;
+use axum::extract::State;
+use axum::http::header::{CACHE_CONTROL, CONTENT_TYPE};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use utoipa::OpenApi;
+/*****************************************************\
+|****************      ENDPOINTS      ****************|
+\*****************************************************/
+
+// GET /games/{id}/board.png: draws the position of the game as a PNG image, marking the last move,
+// which is never cached so that thumbnails show the game as it is on every refresh
+#[utoipa::path(
+    get,
+    path = "/games/{id}/board.png",
+    tag = "games",
+    params(("id" = String, Path, description = "The id of the game")),
+    responses(
+        (status = 200, description = "The position of the game", body = Vec<u8>, content_type = "image/png"),
+        (status = 404, description = "No game has the id", body = ErrorBody),
+    )
+)]
+async fn get_board_png(
+    State(state): State<AppState>,
+    axum::extract::Path(id): axum::extract::Path<String>,
+) -> Result<Response, ApiError> {
+    let game = find_game(&state, &id).await?;
+    let png = board_png(game.gtp.board(), last_intersection(&game)).map_err(ApiError::internal)?;
+    Ok(([(CONTENT_TYPE, "image/png"), (CACHE_CONTROL, "no-store")], png).into_response())
+}
+
+/********************************************************\
+|****************     PUBLIC METHODS     ****************|
+\********************************************************/
+
+// The OpenAPI description of the PNG image route
+#[derive(OpenApi)]
+#[openapi(paths(get_board_png))]
+pub(crate) struct PngApi;
+
+// The route of the PNG images of games
+pub(crate) fn png_routes() -> Router<AppState> {
+    Router::new().route("/games/{id}/board.png", get(get_board_png))
+}
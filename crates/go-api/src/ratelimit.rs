@@ -0,0 +1,162 @@
+use super::*;
+use axum::extract::{ConnectInfo, FromRequestParts};
+use axum::http::header::RETRY_AFTER;
+use axum::http::request::Parts;
+use axum::response::{IntoResponse, Response};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+/******************************************************\
+|****************      CONSTANTS       ****************|
+\******************************************************/
+
+// Requests to the expensive endpoints a client may make in a burst before it is limited
+const RATE_LIMIT_BURST: f64 = 5.0;
+
+// Requests to the expensive endpoints a client regains each second, one every 6 seconds
+const RATE_LIMIT_REFILL: f64 = 1.0 / 6.0;
+
+// Header a client may name its API key in, to be limited by the key rather than its address
+const API_KEY_HEADER: &str = "x-api-key";
+
+/****************************************************\
+|****************    GLOBAL TYPES    ****************|
+\****************************************************/
+
+// The token buckets of the clients of the expensive endpoints, each holding up to the burst of
+// requests and refilled continuously, so that one client cannot hold the engine from the others
+pub(crate) struct RateLimiter {
+    buckets: Mutex<HashMap<String, TokenBucket>>,
+    burst: f64,
+    refill: f64,
+}
+
+// Extracted by the handlers of the expensive endpoints, taking a token from the client's bucket,
+// or answering 429 Too Many Requests with the seconds until one refills if it is empty
+pub(crate) struct RateLimit;
+
+// The key a request's client is limited by, extracted by the handlers that only limit some of
+// their requests, such as GTP sessions limiting genmove
+pub(crate) struct Client(pub(crate) String);
+
+/*****************************************************\
+|****************    PRIVATE TYPES    ****************|
+\*****************************************************/
+
+// The requests a client has left, as of the last time it was updated
+struct TokenBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/*****************************************************\
+|****************        SETUP        ****************|
+\*****************************************************/
+
+impl RateLimiter {
+    // Creates a limiter letting each client make the given burst of requests, regaining the
+    // given number every second
+    pub(crate) fn new(burst: f64, refill: f64) -> RateLimiter {
+        RateLimiter { buckets: Mutex::new(HashMap::new()), burst, refill }
+    }
+}
+
+impl Default for RateLimiter {
+    fn default() -> RateLimiter {
+        RateLimiter::new(RATE_LIMIT_BURST, RATE_LIMIT_REFILL)
+    }
+}
+
+/****************************************************\
+|****************       HELPER       ****************|
+\****************************************************/
+
+impl TokenBucket {
+    // Refills the bucket for the time since it was last updated, up to the given burst
+    fn refill(&mut self, burst: f64, refill: f64, now: Instant) {
+        let elapsed = now.duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * refill).min(burst);
+        self.updated = now;
+    }
+}
+
+// The response to a client out of requests until the given seconds pass: 429 Too Many Requests,
+// with the seconds to retry after
+pub(crate) fn too_many_requests(seconds: u64) -> Response {
+    ([(RETRY_AFTER, seconds.to_string())], ApiError::rate_limited(seconds)).into_response()
+}
+
+// The key a request's client is limited by: its API key if it gives one, else its address
+fn client_key(parts: &Parts) -> String {
+    if let Some(key) = parts.headers.get(API_KEY_HEADER).and_then(|key| key.to_str().ok()) {
+        return format!("key:{key}");
+    }
+    match parts.extensions.get::<ConnectInfo<SocketAddr>>() {
+        Some(ConnectInfo(address)) => format!("ip:{}", address.ip()),
+        None => String::from("ip:unknown"),
+    }
+}
+
+/********************************************************\
+|****************     PUBLIC METHODS     ****************|
+\********************************************************/
+
+impl RateLimiter {
+    // Takes a token from the bucket of the given client, or gives the time until one refills
+    pub(crate) fn acquire(&self, client: &str) -> Result<(), Duration> {
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets
+            .entry(client.to_string())
+            .or_insert(TokenBucket { tokens: self.burst, updated: now });
+        bucket.refill(self.burst, self.refill, now);
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / self.refill))
+        }
+    }
+
+    // Takes a token from the bucket of the given client like acquire, or gives the whole seconds
+    // until one refills, logging that the client was limited
+    pub(crate) fn limit(&self, client: &str) -> Result<(), u64> {
+        self.acquire(client).map_err(|wait| {
+            let seconds = wait.as_secs_f64().ceil() as u64;
+            tracing::info!(client, "Rate limited for {seconds}s");
+            seconds
+        })
+    }
+
+    // Removes the buckets that have refilled, which hold nothing a new bucket would not, returning
+    // how many were removed
+    pub(crate) fn expire_full(&self) -> usize {
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap();
+        let before = buckets.len();
+        buckets.retain(|_, bucket| {
+            bucket.refill(self.burst, self.refill, now);
+            bucket.tokens < self.burst
+        });
+        before - buckets.len()
+    }
+}
+
+impl FromRequestParts<AppState> for RateLimit {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<RateLimit, Response> {
+        state.limiter.limit(&client_key(parts)).map(|_| RateLimit).map_err(too_many_requests)
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for Client {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Client, Infallible> {
+        Ok(Client(client_key(parts)))
+    }
+}
@@ -0,0 +1,809 @@
+use super::*;
+use axum::extract::{Path, Query, State};
+use axum::http::header::{CACHE_CONTROL, CONTENT_TYPE};
+use axum::http::StatusCode;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
+use utoipa::{OpenApi, ToSchema};
+/******************************************************\
+|****************      CONSTANTS       ****************|
+\******************************************************/
+
+// Visits given to each position of a game reviewed by /analyze/sgf unless asked for more or fewer
+const REVIEW_VISITS: u16 = 30;
+
+// Levels of moves below the root a search tree is described to unless asked for more, and the
+// most it may be asked for, past which transpositions could repeat the tree without end
+const TREE_DEPTH: usize = 1;
+const MAX_TREE_DEPTH: usize = 10;
+
+// Least time between the progress events a streamed genmove sends while it searches
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+/****************************************************\
+|****************    GLOBAL TYPES    ****************|
+\****************************************************/
+
+// A game as the REST API describes it
+#[derive(Serialize, Clone, ToSchema)]
+pub(crate) struct GameView {
+    id: String,
+    size: u16,
+    komi: f64,
+    to_play: &'static str,
+    black_stones: Vec<String>,
+    white_stones: Vec<String>,
+    moves: Vec<PlayedMove>,
+    result: Option<String>,
+    sgf: String,
+}
+
+// The position of a game as the REST API describes it, with each point of the grid "b", "w",
+// or null for an empty point, in rows from the top
+#[derive(Serialize, ToSchema)]
+pub(crate) struct BoardState {
+    size: u16,
+    grid: Vec<Vec<Option<&'static str>>>,
+    captures: Captures,
+    ko: Option<String>,
+    move_number: usize,
+    to_play: &'static str,
+    last_move: Option<PlayedMove>,
+}
+
+// An error of the REST API, answered with its status and a JSON body holding the message
+pub(crate) struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+// The JSON body of an ApiError
+#[derive(Serialize, ToSchema)]
+pub(crate) struct ErrorBody {
+    error: String,
+}
+
+/*****************************************************\
+|****************    PRIVATE TYPES    ****************|
+\*****************************************************/
+
+// A move of a game as the REST API describes it, where the vertex may be "pass" or "resign"
+#[derive(Serialize, Clone, ToSchema)]
+struct PlayedMove {
+    color: &'static str,
+    vertex: String,
+}
+
+// The stones each Color has captured
+#[derive(Serialize, ToSchema)]
+struct Captures {
+    black: u16,
+    white: u16,
+}
+
+// The settings of a new game, each left as GTP starts it unless given
+#[derive(Deserialize, Default, ToSchema)]
+#[serde(default)]
+struct NewGameInput {
+    size: Option<u16>,
+    komi: Option<f64>,
+}
+
+// A move to play, in Go Text Protocol notation
+#[derive(Deserialize, ToSchema)]
+struct MoveInput {
+    color: String,
+    vertex: String,
+}
+
+// Whether a move may be played next, and if not, the reason it may not: "out_of_turn",
+// "off_board", "occupied", "ko", or "suicide"
+#[derive(Serialize, ToSchema)]
+struct MoveCheck {
+    legal: bool,
+    reason: Option<String>,
+}
+
+// The color to generate a move for, in Go Text Protocol notation
+#[derive(Deserialize, ToSchema)]
+struct GenmoveInput {
+    color: String,
+}
+
+// The move generated for a game, and the game after it
+#[derive(Serialize, ToSchema)]
+struct GenmoveReply {
+    vertex: String,
+    state: GameView,
+}
+
+// The progress of a search, as a streamed genmove sends it
+#[derive(Serialize, ToSchema)]
+struct ProgressState {
+    iterations: u32,
+    best_move: String,
+    visits: u16,
+    winrate: f64,
+    pv: Vec<String>,
+}
+
+// The options of a dump of a search tree
+#[derive(Deserialize)]
+struct TreeQuery {
+    depth: Option<usize>,
+}
+
+// The search tree of a game's position, with the moves searched for the color to play
+#[derive(Serialize, ToSchema)]
+struct TreeState {
+    to_play: &'static str,
+    playouts: u16,
+    children: Vec<TreeNodeState>,
+}
+
+// A move of a search tree, with the winrate it gives the color playing it, and the moves searched
+// after it, most visited first
+#[derive(Serialize, ToSchema)]
+#[schema(no_recursion)]
+struct TreeNodeState {
+    vertex: String,
+    visits: u16,
+    winrate: f64,
+    children: Vec<TreeNodeState>,
+}
+
+// The options of a review of an SGF game
+#[derive(Deserialize)]
+struct ReviewQuery {
+    visits: Option<u16>,
+}
+
+// The review of an SGF game: the engine's evaluation of every move, and the game annotated with it
+#[derive(Serialize, ToSchema)]
+struct ReviewState {
+    size: u16,
+    komi: f64,
+    moves: Vec<MoveReviewState>,
+    sgf: String,
+}
+
+// The engine's evaluation of a single move of a reviewed game, where winrate_loss is what the move
+// lost for the color playing it, and black_winrate and score, positive when Black leads, follow it
+#[derive(Serialize, ToSchema)]
+struct MoveReviewState {
+    move_number: usize,
+    color: &'static str,
+    played: String,
+    suggested: String,
+    winrate_loss: f64,
+    black_winrate: f64,
+    score: f64,
+    alternatives: Vec<AlternativeState>,
+}
+
+// A move the engine preferred to the one played, with the winrate it gives the color to move
+#[derive(Serialize, ToSchema)]
+struct AlternativeState {
+    vertex: String,
+    winrate: f64,
+    visits: u16,
+}
+
+// The settings of the engine serving the games, where a max_time of 0 leaves searches unlimited
+#[derive(Serialize, ToSchema)]
+struct EngineSettingsState {
+    playouts: u16,
+    max_time: f64,
+    resign_threshold: f64,
+    ruleset: String,
+}
+
+// The settings of the engine to change, each left as it is unless given
+#[derive(Deserialize, Default, ToSchema)]
+#[serde(default)]
+struct EngineSettingsInput {
+    playouts: Option<u64>,
+    max_time: Option<f64>,
+    resign_threshold: Option<f64>,
+    ruleset: Option<String>,
+}
+
+/****************************************************\
+|****************       HELPER       ****************|
+\****************************************************/
+
+impl ApiError {
+    // The error for a request the game refused, with the reason it gave
+    pub(crate) fn bad_request(message: impl Into<String>) -> ApiError {
+        ApiError { status: StatusCode::BAD_REQUEST, message: message.into() }
+    }
+
+    // The message of this error, for protocols without statuses
+    pub(crate) fn into_message(self) -> String {
+        self.message
+    }
+
+    // The error for a request that failed on the server, with the error it failed with
+    pub(crate) fn internal(error: impl std::fmt::Display) -> ApiError {
+        ApiError { status: StatusCode::INTERNAL_SERVER_ERROR, message: error.to_string() }
+    }
+
+    // The error for a game id that holds no game
+    pub(crate) fn not_found(id: &str) -> ApiError {
+        ApiError { status: StatusCode::NOT_FOUND, message: format!("No game with id {id}") }
+    }
+
+    // The error for a client out of requests to the expensive endpoints until the given seconds pass
+    pub(crate) fn rate_limited(seconds: u64) -> ApiError {
+        ApiError {
+            status: StatusCode::TOO_MANY_REQUESTS,
+            message: format!("Too many requests, retry in {seconds} seconds"),
+        }
+    }
+}
+
+impl PlayedMove {
+    // Describes the move of the given node of a game record
+    fn from_node(node: &SgfNode) -> PlayedMove {
+        PlayedMove { color: color_name(node.color), vertex: move_to_vertex(&node.mov) }
+    }
+}
+
+impl ProgressState {
+    // Describes the given progress of a search
+    fn from_progress(progress: &SearchProgress) -> ProgressState {
+        ProgressState {
+            iterations: progress.iterations,
+            best_move: move_to_vertex(&progress.best_move),
+            visits: progress.visits,
+            winrate: progress.winrate,
+            pv: progress.pv.iter().map(move_to_vertex).collect(),
+        }
+    }
+}
+
+impl MoveReviewState {
+    // Describes the given review of a move
+    fn from_review(review: &MoveReview) -> MoveReviewState {
+        MoveReviewState {
+            move_number: review.move_number,
+            color: color_name(review.color),
+            played: move_to_vertex(&review.played),
+            suggested: move_to_vertex(&review.suggested),
+            winrate_loss: review.winrate_loss,
+            black_winrate: review.black_winrate,
+            score: review.score,
+            alternatives: review
+                .alternatives
+                .iter()
+                .map(|alternative| AlternativeState {
+                    vertex: move_to_vertex(&alternative.best_move),
+                    winrate: alternative.winrate,
+                    visits: alternative.visits,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TreeNodeState {
+    // Describes the given move of a search tree, and the moves searched after it
+    fn from_node(node: &SearchNode) -> TreeNodeState {
+        TreeNodeState {
+            vertex: move_to_vertex(&node.mov),
+            visits: node.visits,
+            winrate: node.winrate,
+            children: node.children.iter().map(TreeNodeState::from_node).collect(),
+        }
+    }
+}
+
+impl EngineSettingsState {
+    // Describes the given settings of the engine
+    fn from_settings(settings: &EngineSettings) -> EngineSettingsState {
+        let params = &settings.params;
+        EngineSettingsState {
+            playouts: params.iterations,
+            max_time: params.max_time.map_or(0.0, |time| time.as_secs_f64()),
+            resign_threshold: params.resignation_threshold,
+            ruleset: settings.ruleset.to_string(),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(ErrorBody { error: self.message })).into_response()
+    }
+}
+
+// The Go Text Protocol notation of the given Color
+pub(crate) fn color_name(color: Color) -> &'static str {
+    match color {
+        Color::Black => "b",
+        Color::White => "w",
+    }
+}
+
+// Describes the given game with the given id
+pub(crate) fn game_view(id: &str, game: &Game) -> GameView {
+    let (board, record) = (game.gtp.board(), game.gtp.record());
+    let vertices = |color| board.stones(color).iter().map(|&intsc| Position::from(intsc).to_string()).collect();
+    GameView {
+        id: id.to_string(),
+        size: board.size.to_u16(),
+        komi: board.komi,
+        to_play: color_name(game.gtp.side_to_move()),
+        black_stones: vertices(Color::Black),
+        white_stones: vertices(Color::White),
+        moves: record
+            .nodes
+            .iter()
+            .map(PlayedMove::from_node)
+            .collect(),
+        result: record.result.clone(),
+        sgf: record.to_string(),
+    }
+}
+
+// Describes the position of the given game
+pub(crate) fn board_state(game: &Game) -> BoardState {
+    let (board, record) = (game.gtp.board(), game.gtp.record());
+    let point = |state: engine::State| match state {
+        engine::State::Occupied(color) => Some(color_name(color)),
+        _ => None,
+    };
+    BoardState {
+        size: board.size.to_u16(),
+        grid: board.rows().into_iter().map(|row| row.into_iter().map(point).collect()).collect(),
+        captures: Captures { black: board.black_captures, white: board.white_captures },
+        ko: board.ko().map(|intsc| Position::from(intsc).to_string()),
+        move_number: record.nodes.len(),
+        to_play: color_name(game.gtp.side_to_move()),
+        last_move: record.nodes.last().map(PlayedMove::from_node),
+    }
+}
+
+// Runs the given GTP command on the given game, giving its result, or its error as a bad request
+pub(crate) fn execute(game: &mut Game, arguments: &[&str]) -> Result<String, ApiError> {
+    game.gtp
+        .execute(arguments)
+        .unwrap_or_else(|| Err("The game has closed".to_string()))
+        .map_err(ApiError::bad_request)
+}
+
+// The intersection of the last move of the given game, unless it was a pass or no move was played
+pub(crate) fn last_intersection(game: &Game) -> Option<Intersection> {
+    match game.gtp.record().nodes.last().map(|node| node.mov) {
+        Some(Move::MOVE(intsc, _)) => Some(intsc),
+        _ => None,
+    }
+}
+
+// Locks the game with the given id, or answers that there is no such game
+pub(crate) async fn find_game(state: &AppState, id: &str) -> Result<GameGuard, ApiError> {
+    state.games.get(id).await.ok_or_else(|| ApiError::not_found(id))
+}
+
+/*****************************************************\
+|****************      ENDPOINTS      ****************|
+\*****************************************************/
+
+// POST /games: starts a new game, of the size and komi given if any
+#[utoipa::path(
+    post,
+    path = "/games",
+    tag = "games",
+    request_body(
+        content = Option<NewGameInput>,
+        description = "The size and komi of the game, each left as GTP starts it unless given",
+    ),
+    responses(
+        (status = 201, description = "The game started", body = GameView),
+        (status = 400, description = "The game refused the request", body = ErrorBody),
+    )
+)]
+async fn create_game(
+    State(state): State<AppState>,
+    input: Option<Json<NewGameInput>>,
+) -> Result<(StatusCode, Json<GameView>), ApiError> {
+    let input = input.map(|Json(input)| input).unwrap_or_default();
+    let id = state.games.create().await;
+    let mut game = find_game(&state, &id).await?;
+    let settings = [
+        input.size.map(|size| ("boardsize", size.to_string())),
+        input.komi.map(|komi| ("komi", komi.to_string())),
+    ];
+    for (command, value) in settings.into_iter().flatten() {
+        if let Err(error) = execute(&mut game, &[command, &value]) {
+            drop(game);
+            state.games.remove(&id).await;
+            return Err(error);
+        }
+    }
+
+    tracing::info!(id, "Started REST game");
+    Ok((StatusCode::CREATED, Json(game_view(&id, &game))))
+}
+
+// GET /games/{id}: describes the game
+#[utoipa::path(
+    get,
+    path = "/games/{id}",
+    tag = "games",
+    params(("id" = String, Path, description = "The id of the game")),
+    responses(
+        (status = 200, description = "The game", body = GameView),
+        (status = 404, description = "No game has the id", body = ErrorBody),
+    )
+)]
+async fn get_game(State(state): State<AppState>, Path(id): Path<String>) -> Result<Json<GameView>, ApiError> {
+    let game = find_game(&state, &id).await?;
+    Ok(Json(game_view(&id, &game)))
+}
+
+// GET /games/{id}/board: describes the position of the game
+#[utoipa::path(
+    get,
+    path = "/games/{id}/board",
+    tag = "games",
+    params(("id" = String, Path, description = "The id of the game")),
+    responses(
+        (status = 200, description = "The position of the game", body = BoardState),
+        (status = 404, description = "No game has the id", body = ErrorBody),
+    )
+)]
+async fn get_board(State(state): State<AppState>, Path(id): Path<String>) -> Result<Json<BoardState>, ApiError> {
+    let game = find_game(&state, &id).await?;
+    Ok(Json(board_state(&game)))
+}
+
+// GET /games/{id}/board.svg: draws the position of the game as an SVG image, marking the last
+// move, which is never cached so that overlays show the game as it is on every refresh
+#[utoipa::path(
+    get,
+    path = "/games/{id}/board.svg",
+    tag = "games",
+    params(("id" = String, Path, description = "The id of the game")),
+    responses(
+        (status = 200, description = "The position of the game", body = String, content_type = "image/svg+xml"),
+        (status = 404, description = "No game has the id", body = ErrorBody),
+    )
+)]
+async fn get_board_svg(State(state): State<AppState>, Path(id): Path<String>) -> Result<Response, ApiError> {
+    let game = find_game(&state, &id).await?;
+    let svg = board_svg(game.gtp.board(), last_intersection(&game));
+    Ok(([(CONTENT_TYPE, "image/svg+xml"), (CACHE_CONTROL, "no-store")], svg).into_response())
+}
+
+// DELETE /games/{id}: ends the game, removing it
+#[utoipa::path(
+    delete,
+    path = "/games/{id}",
+    tag = "games",
+    params(("id" = String, Path, description = "The id of the game")),
+    responses(
+        (status = 204, description = "The game was removed"),
+        (status = 404, description = "No game has the id", body = ErrorBody),
+    )
+)]
+async fn delete_game(State(state): State<AppState>, Path(id): Path<String>) -> Result<StatusCode, ApiError> {
+    match state.games.remove(&id).await {
+        true => Ok(StatusCode::NO_CONTENT),
+        false => Err(ApiError::not_found(&id)),
+    }
+}
+
+// POST /games/{id}/moves: plays the given move, which may be a pass or resignation
+#[utoipa::path(
+    post,
+    path = "/games/{id}/moves",
+    tag = "games",
+    params(("id" = String, Path, description = "The id of the game")),
+    request_body = MoveInput,
+    responses(
+        (status = 200, description = "The game after the move", body = GameView),
+        (status = 400, description = "The game refused the request", body = ErrorBody),
+        (status = 404, description = "No game has the id", body = ErrorBody),
+    )
+)]
+async fn play_move(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(input): Json<MoveInput>,
+) -> Result<Json<GameView>, ApiError> {
+    let mut game = find_game(&state, &id).await?;
+    execute(&mut game, &["play", &input.color, &input.vertex])?;
+    Ok(Json(game_view(&id, &game)))
+}
+
+// POST /games/{id}/check: tells whether the given move may be played next by the side to move,
+// and if not why, without playing it
+#[utoipa::path(
+    post,
+    path = "/games/{id}/check",
+    tag = "games",
+    params(("id" = String, Path, description = "The id of the game")),
+    request_body = MoveInput,
+    responses(
+        (status = 200, description = "Whether the move is legal, and if not why", body = MoveCheck),
+        (status = 400, description = "The color or vertex could not be read", body = ErrorBody),
+        (status = 404, description = "No game has the id", body = ErrorBody),
+    )
+)]
+async fn check_move(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(input): Json<MoveInput>,
+) -> Result<Json<MoveCheck>, ApiError> {
+    let color = Color::from_name(&input.color)
+        .ok_or_else(|| ApiError::bad_request(format!("Invalid color: {}", input.color)))?;
+    let mov = vertex_to_move(&input.vertex, color)
+        .ok_or_else(|| ApiError::bad_request(format!("Invalid vertex: {}", input.vertex)))?;
+    let game = find_game(&state, &id).await?;
+    let reason = game.gtp.check_move(mov, color).err();
+    Ok(Json(MoveCheck { legal: reason.is_none(), reason: reason.map(|reason| reason.to_string()) }))
+}
+
+// POST /games/{id}/genmove: generates and plays the engine's move for the given color
+#[utoipa::path(
+    post,
+    path = "/games/{id}/genmove",
+    tag = "games",
+    params(("id" = String, Path, description = "The id of the game")),
+    request_body = GenmoveInput,
+    responses(
+        (status = 200, description = "The move generated, and the game after it", body = GenmoveReply),
+        (status = 400, description = "The game refused the request", body = ErrorBody),
+        (status = 404, description = "No game has the id", body = ErrorBody),
+        (status = 429, description = "Out of requests until the Retry-After seconds pass", body = ErrorBody),
+    )
+)]
+async fn generate_move(
+    _: RateLimit,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(input): Json<GenmoveInput>,
+) -> Result<Json<GenmoveReply>, ApiError> {
+    let mut game = find_game(&state, &id).await?;
+    // the search blocks, so it runs off the async workers
+    tokio::task::spawn_blocking(move || {
+        let start = Instant::now();
+        let vertex = execute(&mut game, &["genmove", &input.color])?;
+        state.metrics.observe_genmove(start.elapsed());
+        Ok(Json(GenmoveReply { vertex, state: game_view(&id, &game) }))
+    })
+    .await
+    .map_err(ApiError::internal)?
+}
+
+// POST /games/{id}/genmove/stream: generates and plays the engine's move like genmove, streaming
+// the progress of its search as "progress" events, then the move and the game after it as a
+// "move" event, or why no move was generated as an "error" event
+// The search stops early with the best move found so far if the client disconnects
+#[utoipa::path(
+    post,
+    path = "/games/{id}/genmove/stream",
+    tag = "games",
+    params(("id" = String, Path, description = "The id of the game")),
+    request_body = GenmoveInput,
+    responses(
+        (
+            status = 200,
+            description = "Server-sent \"progress\" events of ProgressState, then a \"move\" event of GenmoveReply \
+                           or an \"error\" event of ErrorBody",
+            content_type = "text/event-stream",
+            body = String,
+        ),
+        (status = 404, description = "No game has the id", body = ErrorBody),
+        (status = 429, description = "Out of requests until the Retry-After seconds pass", body = ErrorBody),
+    )
+)]
+async fn stream_genmove(
+    _: RateLimit,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(input): Json<GenmoveInput>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, ApiError> {
+    let mut game = find_game(&state, &id).await?;
+    let (sender, receiver) = mpsc::channel(16);
+    tokio::task::spawn_blocking(move || {
+        let stop = Arc::new(AtomicBool::new(false));
+        let mut report = |progress: SearchProgress| {
+            let event = Event::default().event("progress").json_data(ProgressState::from_progress(&progress));
+            if sender.blocking_send(event).is_err() {
+                stop.store(true, Ordering::Relaxed);
+            }
+        };
+        let progress = ProgressReport { interval: PROGRESS_INTERVAL, report: &mut report };
+        let start = Instant::now();
+        let event = match game.gtp.genmove_reporting(&input.color, Some(Arc::clone(&stop)), progress) {
+            Ok(vertex) => {
+                state.metrics.observe_genmove(start.elapsed());
+                Event::default().event("move").json_data(GenmoveReply { vertex, state: game_view(&id, &game) })
+            }
+            Err(error) => Event::default().event("error").json_data(ErrorBody { error }),
+        };
+        let _ = sender.blocking_send(event); // the client may have disconnected
+    });
+
+    Ok(Sse::new(ReceiverStream::new(receiver)).keep_alive(KeepAlive::default()))
+}
+
+// GET /games/{id}/tree: searches the position of the game for the color to play with the engine's
+// playouts, describing the moves of the search tree down to the depth given, if any
+#[utoipa::path(
+    get,
+    path = "/games/{id}/tree",
+    tag = "analysis",
+    params(
+        ("id" = String, Path, description = "The id of the game"),
+        ("depth" = Option<usize>, Query, description = "Levels of moves below the root, 1 by default and 10 at most"),
+    ),
+    responses(
+        (status = 200, description = "The search tree of the position", body = TreeState),
+        (status = 400, description = "The depth is past the most allowed", body = ErrorBody),
+        (status = 404, description = "No game has the id", body = ErrorBody),
+        (status = 429, description = "Out of requests until the Retry-After seconds pass", body = ErrorBody),
+    )
+)]
+async fn get_tree(
+    _: RateLimit,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<TreeQuery>,
+) -> Result<Json<TreeState>, ApiError> {
+    let depth = query.depth.unwrap_or(TREE_DEPTH);
+    if depth > MAX_TREE_DEPTH {
+        return Err(ApiError::bad_request(format!("The depth may be at most {MAX_TREE_DEPTH}")));
+    }
+    let game = find_game(&state, &id).await?;
+    // the search blocks, so it runs off the async workers
+    tokio::task::spawn_blocking(move || {
+        let (color, params) = (game.gtp.side_to_move(), game.gtp.params());
+        let nodes = search_tree(game.gtp.board(), color, &params, depth);
+        Json(TreeState {
+            to_play: color_name(color),
+            playouts: params.iterations,
+            children: nodes.iter().map(TreeNodeState::from_node).collect(),
+        })
+    })
+    .await
+    .map_err(ApiError::internal)
+}
+
+// POST /analyze/sgf: reviews the SGF game given as the body, searching every position of its main
+// line with the visits given, if any
+#[utoipa::path(
+    post,
+    path = "/analyze/sgf",
+    tag = "analysis",
+    params(("visits" = Option<u16>, Query, description = "Visits given to each position, 30 by default")),
+    request_body(content = String, description = "The SGF game to review", content_type = "text/plain"),
+    responses(
+        (status = 200, description = "The review of every move", body = ReviewState),
+        (status = 400, description = "The SGF game could not be read or reviewed", body = ErrorBody),
+        (status = 429, description = "Out of requests until the Retry-After seconds pass", body = ErrorBody),
+    )
+)]
+async fn review_sgf(
+    _: RateLimit,
+    Query(query): Query<ReviewQuery>,
+    sgf: String,
+) -> Result<Json<ReviewState>, ApiError> {
+    let visits = query.visits.unwrap_or(REVIEW_VISITS);
+    // the searches block, so they run off the async workers
+    tokio::task::spawn_blocking(move || {
+        let mut record = SgfRecord::parse(&sgf).map_err(ApiError::bad_request)?;
+        let reviews = review_game(&mut record, visits).map_err(ApiError::bad_request)?;
+        Ok(Json(ReviewState {
+            size: record.size.to_u16(),
+            komi: record.komi,
+            moves: reviews.iter().map(MoveReviewState::from_review).collect(),
+            sgf: record.to_string(),
+        }))
+    })
+    .await
+    .map_err(ApiError::internal)?
+}
+
+// GET /engine/settings: describes the settings of the engine serving the games
+#[utoipa::path(
+    get,
+    path = "/engine/settings",
+    tag = "engine",
+    responses((status = 200, description = "The settings of the engine", body = EngineSettingsState))
+)]
+async fn get_engine_settings(State(state): State<AppState>) -> Json<EngineSettingsState> {
+    Json(EngineSettingsState::from_settings(&state.games.settings()))
+}
+
+// PATCH /engine/settings: changes the given settings of the engine, which every game searches
+// with from its next move, where the ruleset applies to the games started after the change
+#[utoipa::path(
+    patch,
+    path = "/engine/settings",
+    tag = "engine",
+    request_body = EngineSettingsInput,
+    responses(
+        (status = 200, description = "The settings of the engine after the change", body = EngineSettingsState),
+        (status = 400, description = "A setting was given an invalid value", body = ErrorBody),
+    )
+)]
+async fn patch_engine_settings(
+    State(state): State<AppState>,
+    Json(input): Json<EngineSettingsInput>,
+) -> Result<Json<EngineSettingsState>, ApiError> {
+    let mut settings = state.games.settings();
+    let params = [
+        input.playouts.map(|playouts| ("playouts", playouts.to_string())),
+        input.max_time.map(|seconds| ("max_time", seconds.to_string())),
+        input.resign_threshold.map(|threshold| ("resign_threshold", threshold.to_string())),
+    ];
+    for (name, value) in params.into_iter().flatten() {
+        settings.params.set(name, &value).map_err(ApiError::bad_request)?;
+    }
+    if let Some(ruleset) = input.ruleset {
+        settings.ruleset = Ruleset::from_name(&ruleset)
+            .ok_or_else(|| ApiError::bad_request(format!("Unsupported rules: {ruleset}")))?;
+    }
+
+    state.games.set_settings(settings);
+    Ok(Json(EngineSettingsState::from_settings(&settings)))
+}
+
+/********************************************************\
+|****************     PUBLIC METHODS     ****************|
+\********************************************************/
+
+// The OpenAPI description of the REST game API
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        create_game,
+        get_game,
+        delete_game,
+        get_board,
+        get_board_svg,
+        play_move,
+        check_move,
+        generate_move,
+        stream_genmove,
+        review_sgf,
+        get_tree,
+        get_engine_settings,
+        patch_engine_settings,
+    ),
+    components(schemas(ProgressState, ErrorBody)),
+    tags(
+        (name = "games", description = "Games played in JSON"),
+        (name = "analysis", description = "Reviews of SGF games and search trees of positions"),
+        (name = "engine", description = "Settings of the engine serving the games"),
+    )
+)]
+pub(crate) struct RestApi;
+
+// The routes of the REST game API
+pub(crate) fn rest_routes() -> Router<AppState> {
+    Router::new()
+        .route("/games", post(create_game))
+        .route("/games/{id}", get(get_game).delete(delete_game))
+        .route("/games/{id}/board", get(get_board))
+        .route("/games/{id}/board.svg", get(get_board_svg))
+        .route("/games/{id}/moves", post(play_move))
+        .route("/games/{id}/check", post(check_move))
+        .route("/games/{id}/genmove", post(generate_move))
+        .route("/games/{id}/genmove/stream", post(stream_genmove))
+        .route("/games/{id}/tree", get(get_tree))
+        .route("/analyze/sgf", post(review_sgf))
+        .route("/engine/settings", get(get_engine_settings).patch(patch_engine_settings))
+}
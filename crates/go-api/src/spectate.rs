@@ -0,0 +1,114 @@
+use super::*;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Path, State};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::Response;
+use axum::routing::get;
+use axum::Router;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{Stream, StreamExt};
+use utoipa::OpenApi;
+/****************************************************\
+|****************       HELPER       ****************|
+\****************************************************/
+
+// Sends the given game to the spectator, then the game after every change until it is removed or
+// the spectator disconnects
+async fn serve_spectator(mut socket: WebSocket, game: GameView, mut updates: broadcast::Receiver<GameView>) {
+    let mut game = Some(game);
+    loop {
+        if let Some(game) = game.take() {
+            let Ok(text) = serde_json::to_string(&game) else { continue };
+            if socket.send(Message::Text(text.into())).await.is_err() {
+                return;
+            }
+        }
+
+        tokio::select! {
+            update = updates.recv() => match update {
+                Ok(update) => game = Some(update),
+                Err(RecvError::Lagged(_)) => continue, // the next update holds every move missed
+                Err(RecvError::Closed) => break,
+            },
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => continue, // spectators only watch
+            },
+        }
+    }
+    let _ = socket.send(Message::Close(None)).await; // the client may have disconnected already
+}
+
+/*****************************************************\
+|****************      ENDPOINTS      ****************|
+\*****************************************************/
+
+// GET /games/{id}/spectate: streams the game as "state" events, first as it is, then after every
+// move or other change until it is removed
+#[utoipa::path(
+    get,
+    path = "/games/{id}/spectate",
+    tag = "spectate",
+    params(("id" = String, Path, description = "The id of the game")),
+    responses(
+        (
+            status = 200,
+            description = "Server-sent \"state\" events of GameView",
+            content_type = "text/event-stream",
+            body = String,
+        ),
+        (status = 404, description = "No game has the id", body = ErrorBody),
+    )
+)]
+async fn spectate_events(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, ApiError> {
+    let (game, updates) = state.games.spectate(&id).await.ok_or_else(|| ApiError::not_found(&id))?;
+    // a spectator that falls behind skips to the latest game, which holds every move missed
+    let games = tokio_stream::once(game).chain(BroadcastStream::new(updates).filter_map(Result::ok));
+    let events = games.map(|game| Event::default().event("state").json_data(game));
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+// GET /games/{id}/spectate/ws: upgrades a request to a WebSocket sending the game as a JSON text
+// message, first as it is, then after every move or other change until it is removed
+#[utoipa::path(
+    get,
+    path = "/games/{id}/spectate/ws",
+    tag = "spectate",
+    params(("id" = String, Path, description = "The id of the game")),
+    responses(
+        (status = 101, description = "A WebSocket sending GameView objects, each a JSON text message"),
+        (status = 404, description = "No game has the id", body = ErrorBody),
+    )
+)]
+async fn spectate_socket(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    upgrade: WebSocketUpgrade,
+) -> Result<Response, ApiError> {
+    let (game, updates) = state.games.spectate(&id).await.ok_or_else(|| ApiError::not_found(&id))?;
+    Ok(upgrade.on_upgrade(move |socket| serve_spectator(socket, game, updates)))
+}
+
+/********************************************************\
+|****************     PUBLIC METHODS     ****************|
+\********************************************************/
+
+// The OpenAPI description of the spectator routes
+#[derive(OpenApi)]
+#[openapi(
+    paths(spectate_events, spectate_socket),
+    tags((name = "spectate", description = "Games watched live as they are played"))
+)]
+pub(crate) struct SpectateApi;
+
+// The routes of the spectators of games
+pub(crate) fn spectate_routes() -> Router<AppState> {
+    Router::new()
+        .route("/games/{id}/spectate", get(spectate_events))
+        .route("/games/{id}/spectate/ws", get(spectate_socket))
+}
@@ -0,0 +1,153 @@
+use super::*;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+use std::sync::Mutex;
+/****************************************************\
+|****************    GLOBAL TYPES    ****************|
+\****************************************************/
+
+// The games of the API saved in an SQLite database, so that they outlive the server
+pub(crate) struct GameStore {
+    connection: Mutex<Connection>,
+}
+
+// A game as it is saved: its record, holding its moves and result, and its clock
+#[derive(Clone, PartialEq, Debug)]
+pub(crate) struct GameSnapshot {
+    pub(crate) sgf: String,
+    // the arguments of kgs-time_settings giving the time limits of the game, if it has any
+    pub(crate) time_settings: Option<String>,
+    // the seconds and stones each Color has left, as time_left takes them
+    pub(crate) black_time: Option<(u64, u32)>,
+    pub(crate) white_time: Option<(u64, u32)>,
+}
+
+/*****************************************************\
+|****************        SETUP        ****************|
+\*****************************************************/
+
+impl GameStore {
+    // Opens the database at the given path, creating it and its table of games if needed
+    pub(crate) fn open(path: &Path) -> Result<GameStore, String> {
+        let connection = Connection::open(path)
+            .map_err(|error| format!("Could not open game database {}: {error}", path.display()))?;
+        connection
+            .execute_batch(
+                "CREATE TABLE IF NOT EXISTS games (
+                    id TEXT PRIMARY KEY,
+                    sgf TEXT NOT NULL,
+                    time_settings TEXT,
+                    black_seconds INTEGER,
+                    black_stones INTEGER,
+                    white_seconds INTEGER,
+                    white_stones INTEGER
+                )",
+            )
+            .map_err(|error| format!("Could not create the games of {}: {error}", path.display()))?;
+        Ok(GameStore { connection: Mutex::new(connection) })
+    }
+}
+
+impl GameSnapshot {
+    // Takes a snapshot of the game of the given GTP session
+    pub(crate) fn of(gtp: &GTP) -> GameSnapshot {
+        let time_left = |color| gtp.clock().remaining(color).map(|(time, stones)| (time.as_secs(), stones));
+        GameSnapshot {
+            sgf: gtp.record().to_string(),
+            time_settings: gtp.clock().settings().map(|settings| settings.to_kgs()),
+            black_time: time_left(Color::Black),
+            white_time: time_left(Color::White),
+        }
+    }
+
+    // Starts a GTP session on the game of this snapshot, replaying its moves and setting its clock
+    pub(crate) fn restore(&self) -> Result<GTP, String> {
+        let mut gtp = GTP::new();
+        gtp.load_record(SgfRecord::parse(&self.sgf)?)?;
+
+        // loading the record restarts the clock, so the time left is set after it
+        let mut commands = Vec::new();
+        if let Some(settings) = &self.time_settings {
+            commands.push(format!("kgs-time_settings {settings}"));
+        }
+        for (color, time) in [("b", self.black_time), ("w", self.white_time)] {
+            if let Some((seconds, stones)) = time {
+                commands.push(format!("time_left {color} {seconds} {stones}"));
+            }
+        }
+        for command in commands {
+            let arguments: Vec<&str> = command.split_whitespace().collect();
+            gtp.execute(&arguments).unwrap_or_else(|| Err("The game has closed".to_string()))?;
+        }
+        Ok(gtp)
+    }
+}
+
+/********************************************************\
+|****************     PUBLIC METHODS     ****************|
+\********************************************************/
+
+impl GameStore {
+    // Saves the given snapshot of the game of the given id, replacing any saved before
+    pub(crate) fn save(&self, id: &str, snapshot: &GameSnapshot) -> Result<(), String> {
+        let (black_seconds, black_stones) = snapshot.black_time.unzip();
+        let (white_seconds, white_stones) = snapshot.white_time.unzip();
+        self.connection
+            .lock()
+            .unwrap()
+            .execute(
+                "INSERT OR REPLACE INTO games VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    id,
+                    snapshot.sgf,
+                    snapshot.time_settings,
+                    black_seconds,
+                    black_stones,
+                    white_seconds,
+                    white_stones,
+                ],
+            )
+            .map(|_| ())
+            .map_err(|error| format!("Could not save game {id}: {error}"))
+    }
+
+    // The snapshot of the game of the given id, or None if no such game is saved
+    pub(crate) fn load(&self, id: &str) -> Result<Option<GameSnapshot>, String> {
+        let time = |seconds: Option<u64>, stones: Option<u32>| seconds.zip(stones);
+        self.connection
+            .lock()
+            .unwrap()
+            .query_row("SELECT * FROM games WHERE id = ?1", [id], |row| {
+                Ok(GameSnapshot {
+                    sgf: row.get(1)?,
+                    time_settings: row.get(2)?,
+                    black_time: time(row.get(3)?, row.get(4)?),
+                    white_time: time(row.get(5)?, row.get(6)?),
+                })
+            })
+            .optional()
+            .map_err(|error| format!("Could not load game {id}: {error}"))
+    }
+
+    // Removes the game of the given id, if it is saved
+    pub(crate) fn delete(&self, id: &str) -> Result<(), String> {
+        self.connection
+            .lock()
+            .unwrap()
+            .execute("DELETE FROM games WHERE id = ?1", [id])
+            .map(|_| ())
+            .map_err(|error| format!("Could not delete game {id}: {error}"))
+    }
+
+    // The highest numeric id of the games saved, or 0 if there are none
+    pub(crate) fn max_id(&self) -> Result<u64, String> {
+        let connection = self.connection.lock().unwrap();
+        let mut statement = connection
+            .prepare("SELECT id FROM games")
+            .map_err(|error| format!("Could not read the saved games: {error}"))?;
+        let ids = statement
+            .query_map([], |row| row.get::<_, String>(0))
+            .map_err(|error| format!("Could not read the saved games: {error}"))?;
+        Ok(ids.filter_map(|id| id.ok()?.parse::<u64>().ok()).max().unwrap_or(0))
+    }
+}
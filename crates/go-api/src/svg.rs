@@ -0,0 +1,126 @@
+use super::*;
+use std::fmt::Write;
+/******************************************************\
+|****************      CONSTANTS       ****************|
+\******************************************************/
+
+// Pixels between neighboring lines of the grid
+const CELL: u16 = 40;
+
+// Pixels between the outermost lines of the grid and the edge of the image, holding the coordinates
+const MARGIN: u16 = 40;
+
+// Colors of the board, its lines, and its stones
+const BOARD_COLOR: &str = "#dcb35c";
+const LINE_COLOR: &str = "#000000";
+const BLACK_STONE_COLOR: &str = "#000000";
+const WHITE_STONE_COLOR: &str = "#ffffff";
+
+/****************************************************\
+|****************       HELPER       ****************|
+\****************************************************/
+
+// The pixel offset of the line at the given index of the grid, from the left or top of the image
+fn offset(index: u16) -> u16 {
+    MARGIN + index * CELL
+}
+
+// The fill color of a stone of the given Color
+fn stone_color(color: Color) -> &'static str {
+    match color {
+        Color::Black => BLACK_STONE_COLOR,
+        Color::White => WHITE_STONE_COLOR,
+    }
+}
+
+// The star points of a board of the given size, where 9x9 boards have only the corners and tengen
+fn star_points(size: BoardSize) -> Vec<Intersection> {
+    let stones = if size == BoardSize::Nine { 5 } else { 9 };
+    size.handicap_points(stones).unwrap_or_default().into_iter().map(Intersection::from).collect()
+}
+
+// The column and row of the given Intersection on a board of the given size, counted from the top
+// left of the grid
+fn grid_point(intsc: &Intersection, size: u16) -> (u16, u16) {
+    (intsc.column().to_u16(), size - intsc.row())
+}
+
+/********************************************************\
+|****************     PUBLIC METHODS     ****************|
+\********************************************************/
+
+// Draws the given Board as an SVG image, with its coordinates, star points, and stones, marking
+// the stone of the given last move, if any
+pub(crate) fn board_svg(board: &Board, last_move: Option<Intersection>) -> String {
+    let size = board.size.to_u16();
+    let (start, end, width) = (offset(0), offset(size - 1), offset(size - 1) + MARGIN);
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{width}\" \
+         viewBox=\"0 0 {width} {width}\">"
+    );
+    let _ = writeln!(svg, "<rect width=\"{width}\" height=\"{width}\" fill=\"{BOARD_COLOR}\"/>");
+
+    // the grid, with the column letters above and below it and the row numbers on either side
+    let _ = writeln!(svg, "<g stroke=\"{LINE_COLOR}\" stroke-width=\"1\">");
+    for index in 0..size {
+        let line = offset(index);
+        let _ = writeln!(svg, "<line x1=\"{line}\" y1=\"{start}\" x2=\"{line}\" y2=\"{end}\"/>");
+        let _ = writeln!(svg, "<line x1=\"{start}\" y1=\"{line}\" x2=\"{end}\" y2=\"{line}\"/>");
+    }
+    let _ = writeln!(svg, "</g>");
+    let _ = writeln!(
+        svg,
+        "<g font-family=\"sans-serif\" font-size=\"16\" text-anchor=\"middle\" fill=\"{LINE_COLOR}\">"
+    );
+    for index in 0..size {
+        let line = offset(index);
+        let column = ColumnIdentifier::from_u16(index).unwrap(); // boards are never wider than 19
+        let row = size - index;
+        for y in [MARGIN / 2 + 6, end + MARGIN / 2 + 6] {
+            let _ = writeln!(svg, "<text x=\"{line}\" y=\"{y}\">{column}</text>");
+        }
+        for x in [MARGIN / 2, end + MARGIN / 2] {
+            let _ = writeln!(svg, "<text x=\"{x}\" y=\"{}\">{row}</text>", line + 6);
+        }
+    }
+    let _ = writeln!(svg, "</g>");
+
+    for point in star_points(board.size) {
+        let (column, row) = grid_point(&point, size);
+        let (x, y) = (offset(column), offset(row));
+        let _ = writeln!(svg, "<circle cx=\"{x}\" cy=\"{y}\" r=\"4\" fill=\"{LINE_COLOR}\"/>");
+    }
+
+    let rows = board.rows();
+    let radius = CELL / 2 - 1;
+    for (row, states) in rows.iter().enumerate() {
+        for (column, state) in states.iter().enumerate() {
+            if let engine::State::Occupied(color) = state {
+                let (x, y) = (offset(column as u16), offset(row as u16));
+                let fill = stone_color(*color);
+                let _ = writeln!(
+                    svg,
+                    "<circle cx=\"{x}\" cy=\"{y}\" r=\"{radius}\" fill=\"{fill}\" stroke=\"{LINE_COLOR}\"/>"
+                );
+            }
+        }
+    }
+
+    // the last move is marked by a ring of the other color on its stone, if it is still there
+    if let Some((column, row)) = last_move.map(|intsc| grid_point(&intsc, size)) {
+        if let engine::State::Occupied(color) = rows[row as usize][column as usize] {
+            let ring = stone_color(color.opposite_color());
+            let (x, y) = (offset(column), offset(row));
+            let _ = writeln!(
+                svg,
+                "<circle cx=\"{x}\" cy=\"{y}\" r=\"{}\" fill=\"none\" stroke=\"{ring}\" stroke-width=\"2\"/>",
+                CELL / 4
+            );
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
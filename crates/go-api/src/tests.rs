@@ -0,0 +1,344 @@
+#[cfg(test)]
+use super::*;
+/*****************************************************\
+|****************         API         ****************|
+\*****************************************************/
+
+#[cfg(feature = "api")]
+#[test]
+fn test_board_svg() {
+    use ColumnIdentifier::*;
+    let mut board = Board::new(BoardSize::Nine);
+    board.play(Move::MOVE(Intersection::new(C, 3), Color::Black));
+    board.play(Move::MOVE(Intersection::new(G, 7), Color::White));
+    let svg = board_svg(&board, Some(Intersection::new(G, 7)));
+
+    // a 9x9 grid, 40 pixels apart inside a 40 pixel margin, with its coordinates and 5 star points
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"400\" height=\"400\""));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    assert_eq!(svg.matches("<line ").count(), 18);
+    assert_eq!(svg.matches(">J</text>").count(), 2);
+    assert_eq!(svg.matches(r#"r="4""#).count(), 5);
+
+    // C3 and G7 are drawn in their colors, and G7 is marked with a black ring as the last move
+    assert!(svg.contains(r##"<circle cx="120" cy="280" r="19" fill="#000000""##));
+    assert!(svg.contains(r##"<circle cx="280" cy="120" r="19" fill="#ffffff""##));
+    assert!(svg.contains(r##"<circle cx="280" cy="120" r="10" fill="none" stroke="#000000""##));
+    assert!(!board_svg(&board, None).contains(r#"fill="none""#));
+}
+
+#[cfg(feature = "api")]
+#[tokio::test]
+async fn test_game_manager() {
+    use std::time::Duration;
+    let games = GameManager::default();
+    let (first, second) = (games.create().await, games.create().await);
+    assert_ne!(first, second);
+
+    // each game keeps its own board
+    games.get_or_create(&first).await.gtp.accept_command("play B D4".to_string());
+    let board = games.get_or_create(&second).await.gtp.accept_command("showboard".to_string());
+    assert!(!board.contains('X'));
+
+    // a game in use is never idle, however long it was held
+    let in_use = games.get_or_create(&first).await;
+    assert_eq!(games.expire_idle(Duration::ZERO).await, 1);
+    drop(in_use);
+    assert_eq!(games.len().await, 1);
+    assert!(games.remove(&first).await);
+    assert!(!games.remove(&first).await);
+
+    // ids given by clients are skipped when creating games
+    games.get_or_create("3").await;
+    assert_eq!(games.create().await, "4");
+}
+
+#[cfg(feature = "api")]
+#[tokio::test]
+async fn test_game_store() {
+    use std::time::Duration;
+    let path = std::env::temp_dir().join(format!("go_engine_games_{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let games = GameManager::with_store(GameStore::open(&path).unwrap()).unwrap();
+    let id = games.create().await;
+    {
+        let mut game = games.get(&id).await.unwrap();
+        for command in ["boardsize 9", "kgs-time_settings byoyomi 600 30 5", "play b E5", "play w C3"] {
+            game.gtp.accept_command(command.to_string());
+        }
+        game.gtp.spend_time(Color::Black, Duration::from_secs(100));
+    }
+
+    // a new server resumes the game, its moves and clock as they were, and gives new games new ids
+    let resumed = GameManager::with_store(GameStore::open(&path).unwrap()).unwrap();
+    let game = resumed.get(&id).await.unwrap();
+    assert_eq!(game.gtp.board().size, BoardSize::Nine);
+    assert_eq!(game.gtp.record().nodes.len(), 2);
+    assert_eq!(game.gtp.side_to_move(), Color::Black);
+    assert_eq!(game.gtp.clock().remaining(Color::Black), Some((Duration::from_secs(500), 0)));
+    assert_eq!(game.gtp.clock().settings().map(|settings| settings.to_kgs()).as_deref(), Some("byoyomi 600 30 5"));
+    drop(game);
+    assert_ne!(resumed.create().await, id);
+
+    // idle games stay saved, and removed games do not
+    assert!(resumed.expire_idle(Duration::ZERO).await > 0);
+    assert!(resumed.get(&id).await.is_some());
+    assert!(resumed.remove(&id).await);
+    assert!(resumed.get(&id).await.is_none());
+    let reopened = GameManager::with_store(GameStore::open(&path).unwrap()).unwrap();
+    assert!(reopened.get(&id).await.is_none());
+    let _ = std::fs::remove_file(&path);
+}
+
+#[cfg(feature = "api")]
+#[tokio::test]
+async fn test_engine_settings() {
+    use std::time::Duration;
+    let games = GameManager::default();
+    let rules = |game: &mut GameGuard| game.gtp.execute(&["get_rules"]).unwrap().unwrap();
+    let before = games.create().await;
+    assert_eq!(games.settings(), EngineSettings::default());
+
+    let params = SearchParams { iterations: 200, max_time: Some(Duration::from_secs(2)), ..SearchParams::default() };
+    games.set_settings(EngineSettings { params, ruleset: Ruleset::Japanese });
+    assert_eq!(games.settings().params, params);
+
+    // games in progress search with the new params, but keep their rules
+    let mut game = games.get(&before).await.unwrap();
+    assert_eq!(game.gtp.params(), params);
+    assert!(rules(&mut game).contains("\"rules\":\"chinese\""));
+    drop(game);
+
+    // games started after the change are played under the new rules
+    let after = games.create().await;
+    let mut game = games.get(&after).await.unwrap();
+    assert_eq!(game.gtp.params(), params);
+    assert!(rules(&mut game).contains("\"rules\":\"japanese\""));
+    assert_eq!(game.gtp.board().komi, Ruleset::Japanese.default_komi());
+}
+
+#[cfg(feature = "api")]
+#[tokio::test]
+async fn test_spectators() {
+    use std::time::Duration;
+    use tokio::sync::broadcast::error::{RecvError, TryRecvError};
+    let games = GameManager::default();
+    let id = games.create().await;
+    assert!(games.spectate("missing").await.is_none());
+
+    let json = |game: GameView| serde_json::to_value(game).unwrap();
+    let (game, mut updates) = games.spectate(&id).await.unwrap();
+    assert_eq!(json(game)["moves"], serde_json::json!([]));
+    let (_, mut late) = games.spectate(&id).await.unwrap();
+
+    // every spectator receives each move, and nothing for uses that change nothing
+    games.get(&id).await.unwrap().gtp.accept_command("play b D4".to_string());
+    drop(games.get(&id).await.unwrap());
+    for updates in [&mut updates, &mut late] {
+        let game = json(updates.try_recv().unwrap());
+        assert_eq!(game["moves"], serde_json::json!([{ "color": "b", "vertex": "D4" }]));
+        assert_eq!(game["black_stones"], serde_json::json!(["D4"]));
+        assert!(matches!(updates.try_recv(), Err(TryRecvError::Empty)));
+    }
+
+    // watched games are never idle, and removing a game ends its spectators' updates
+    assert_eq!(games.expire_idle(Duration::ZERO).await, 0);
+    drop(late);
+    assert!(games.remove(&id).await);
+    assert!(matches!(updates.recv().await, Err(RecvError::Closed)));
+}
+
+#[cfg(feature = "api")]
+#[test]
+fn test_play_protocol_messages() {
+    // messages name their type, and the settings of a new game may be left out
+    let join = r#"{"type": "join", "size": 9, "engine": "w"}"#;
+    match serde_json::from_str::<ClientMessage>(join).unwrap() {
+        ClientMessage::Join { game, size, komi, time, engine } => {
+            assert_eq!((game, size, komi, time.is_none()), (None, Some(9), None, true));
+            assert_eq!(engine.as_deref(), Some("w"));
+        }
+        _ => panic!("join parsed as another message"),
+    }
+    assert!(serde_json::from_str::<ClientMessage>(r#"{"type": "play", "color": "b"}"#).is_err());
+
+    let error = ServerMessage::Error { error: "invalid move".to_string() };
+    assert_eq!(serde_json::to_string(&error).unwrap(), r#"{"type":"error","error":"invalid move"}"#);
+}
+
+#[cfg(feature = "api")]
+#[test]
+fn test_api_document() {
+    let document = serde_json::to_value(api_document()).unwrap();
+
+    // every route is described, REST and WebSocket alike
+    let paths = document["paths"].as_object().unwrap();
+    for path in ["/", "/command", "/command/{session}", "/gtp", "/play", "/docs", "/games", "/games/{id}"] {
+        assert!(paths.contains_key(path), "{path} is not described");
+    }
+    for path in ["/games/{id}/board", "/games/{id}/moves", "/games/{id}/genmove", "/games/{id}/genmove/stream"] {
+        assert!(paths.contains_key(path), "{path} is not described");
+    }
+    assert!(paths.contains_key("/analyze/sgf"));
+    assert!(paths["/games/{id}"].get("get").is_some() && paths["/games/{id}"].get("delete").is_some());
+
+    // bodies refer to schemas the document holds, including the play protocol's messages
+    let schemas = document["components"]["schemas"].as_object().unwrap();
+    for schema in ["GameView", "BoardState", "MoveInput", "ReviewState", "ClientMessage", "ServerMessage"] {
+        assert!(schemas.contains_key(schema), "{schema} is not described");
+    }
+}
+
+#[cfg(feature = "api")]
+#[test]
+fn test_command_input() {
+    let run = |json: &str| serde_json::from_str::<CommandInput>(json).unwrap().run(GTP::new());
+
+    // the settings are applied before the moves, and the rules before the komi they would reset
+    let rules = run(
+        r#"{"board_size": 9, "ruleset": "japanese", "komi": 0.5, "handicap": 2, "time_settings": {"main_time": 600},
+            "move_list": ["W E5"], "next_command": "get_rules"}"#,
+    );
+    assert!(rules.starts_with(r#"= {"rules":"japanese""#));
+    assert!(rules.contains(r#""komi":0.5"#));
+    let placed = r#"{"board_size": 9, "handicap_stones": ["C3", "G7"], "move_list": [], "next_command": "play b C3"}"#;
+    assert!(run(placed).starts_with('?'));
+    assert_eq!(run(r#"{"board_size": 9, "move_list": [], "next_command": "name"}"#), "= TBD");
+
+    // settings the game refuses are answered with why, before running the command
+    let ruleset = run(r#"{"board_size": 9, "ruleset": "go", "move_list": [], "next_command": "name"}"#);
+    assert_eq!(ruleset, "Invalid ruleset given: Unsupported rules given to set_rules: go");
+    let mov = run(r#"{"board_size": 9, "move_list": ["B D4", "W D4"], "next_command": "name"}"#);
+    assert_eq!(mov, "Invalid move W D4 given: invalid move");
+    let size = run(r#"{"board_size": 25, "move_list": [], "next_command": "name"}"#);
+    assert_eq!(size, "Invalid board size 25 given");
+}
+
+#[cfg(feature = "api")]
+#[test]
+fn test_api_settings() {
+    let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<String>>();
+    let no_vars = |_: &str| None;
+
+    let settings = ApiSettings::parse(&[], no_vars).unwrap();
+    assert_eq!((settings.host.as_str(), settings.port), ("0.0.0.0", 80));
+
+    // the environment gives the address and port, and the options override it
+    let vars = |name: &str| match name {
+        "GO_ENGINE_API_HOST" => Some("127.0.0.1".to_string()),
+        "GO_ENGINE_API_PORT" => Some("8080".to_string()),
+        _ => None,
+    };
+    let settings = ApiSettings::parse(&[], vars).unwrap();
+    assert_eq!((settings.host.as_str(), settings.port), ("127.0.0.1", 8080));
+    let settings = ApiSettings::parse(&args(&["--port", "3000", "--host", "::"]), vars).unwrap();
+    assert_eq!((settings.host.as_str(), settings.port), ("::", 3000));
+
+    assert!(ApiSettings::parse(&args(&["--port", "http"]), no_vars).is_err());
+    assert!(ApiSettings::parse(&args(&["--host"]), no_vars).is_err());
+    assert!(ApiSettings::parse(&args(&["--tls"]), no_vars).is_err());
+    assert!(ApiSettings::parse(&[], |name: &str| (name == "GO_ENGINE_API_PORT").then(|| "-1".to_string())).is_err());
+
+    // games are held in memory only unless given a database
+    assert!(settings.database.is_none());
+    let settings = ApiSettings::parse(&args(&["--db", "games.db"]), no_vars).unwrap();
+    assert_eq!(settings.database.as_deref(), Some(std::path::Path::new("games.db")));
+
+    // browsers are only let in from the origins given, with valid methods and headers
+    assert!(settings.cors_origins.is_empty() && settings.cors_layer().unwrap().is_none());
+    let settings = ApiSettings::parse(&args(&["--cors-origins", "https://a.example, https://b.example"]), no_vars);
+    let settings = settings.unwrap();
+    assert_eq!(settings.cors_origins, ["https://a.example", "https://b.example"]);
+    assert_eq!(settings.cors_methods, ["GET", "POST", "PATCH", "DELETE"]);
+    assert!(settings.cors_layer().unwrap().is_some());
+    let origins = |name: &str| (name == "GO_ENGINE_API_CORS_ORIGINS").then(|| "*".to_string());
+    let settings = ApiSettings::parse(&args(&["--cors-headers", "authorization"]), origins).unwrap();
+    assert_eq!(settings.cors_origins, ["*"]);
+    assert_eq!(settings.cors_headers, ["authorization"]);
+    assert!(ApiSettings::parse(&args(&["--cors-methods", "GET,NOT A METHOD"]), origins).is_err());
+    assert!(ApiSettings::parse(&args(&["--cors-headers", "bad header"]), origins).is_err());
+
+    // HTTPS takes both a certificate and a private key, and the tls feature to serve them
+    assert!(settings.tls_cert.is_none() && settings.tls_key.is_none());
+    assert!(ApiSettings::parse(&args(&["--tls-cert", "cert.pem"]), no_vars).is_err());
+    let key = |name: &str| (name == "GO_ENGINE_API_TLS_KEY").then(|| "key.pem".to_string());
+    let settings = ApiSettings::parse(&args(&["--tls-cert", "cert.pem"]), key);
+    if cfg!(feature = "tls") {
+        let settings = settings.unwrap();
+        assert_eq!(settings.tls_cert.as_deref(), Some(std::path::Path::new("cert.pem")));
+        assert_eq!(settings.tls_key.as_deref(), Some(std::path::Path::new("key.pem")));
+    } else {
+        assert!(settings.is_err_and(|error| error.contains("\"tls\" feature")));
+    }
+}
+
+#[cfg(feature = "api")]
+#[test]
+fn test_metrics() {
+    use std::time::Duration;
+    let metrics = Metrics::default();
+    metrics.record_request("POST", "/games", 201);
+    metrics.record_request("POST", "/games", 201);
+    metrics.record_request("GET", "/games/{id}", 404);
+    metrics.observe_genmove(Duration::from_millis(300));
+    metrics.observe_genmove(Duration::from_secs(90));
+
+    let output = metrics.render(3);
+    let lines: Vec<&str> = output.lines().collect();
+    assert!(lines.contains(&r#"go_engine_http_requests_total{method="POST",route="/games",status="201"} 2"#));
+    assert!(lines.contains(&r#"go_engine_http_requests_total{method="GET",route="/games/{id}",status="404"} 1"#));
+    assert!(lines.contains(&"go_engine_active_games 3"));
+    assert!(lines.contains(&"# TYPE go_engine_search_nodes_per_second gauge"));
+
+    // histogram buckets count every duration at most their bound, and +Inf every duration
+    assert!(lines.contains(&r#"go_engine_genmove_duration_seconds_bucket{le="0.25"} 0"#));
+    assert!(lines.contains(&r#"go_engine_genmove_duration_seconds_bucket{le="0.5"} 1"#));
+    assert!(lines.contains(&r#"go_engine_genmove_duration_seconds_bucket{le="60"} 1"#));
+    assert!(lines.contains(&r#"go_engine_genmove_duration_seconds_bucket{le="+Inf"} 2"#));
+    assert!(lines.contains(&"go_engine_genmove_duration_seconds_count 2"));
+}
+
+#[cfg(feature = "api")]
+#[test]
+fn test_rate_limiter() {
+    use std::time::Duration;
+    let limiter = RateLimiter::new(2.0, 0.5);
+
+    // a client may make its burst of requests, then waits for a token to refill
+    assert!(limiter.acquire("ip:1").is_ok() && limiter.acquire("ip:1").is_ok());
+    let wait = limiter.acquire("ip:1").unwrap_err();
+    assert!(wait > Duration::from_secs(1) && wait <= Duration::from_secs(2));
+
+    // other clients have buckets of their own, and only full buckets expire
+    assert!(limiter.acquire("key:other").is_ok());
+    assert_eq!(limiter.expire_full(), 0);
+    let refilled = RateLimiter::new(1.0, 1000.0);
+    refilled.acquire("ip:1").unwrap();
+    std::thread::sleep(Duration::from_millis(10));
+    assert_eq!(refilled.expire_full(), 1);
+}
+
+#[cfg(feature = "api")]
+#[test]
+fn test_gtp_search_limit() {
+    use std::sync::Arc;
+    let limiter = Arc::new(RateLimiter::new(1.0, 0.001));
+    let mut gtp = GTP::new();
+    let refused = limit_searches(&mut gtp, &limiter, "ip:1".to_string());
+
+    // only the commands that search take a request, and one refused leaves the game as it is
+    assert_eq!(gtp.accept_command("boardsize 9".to_string()), "= ");
+    assert!(gtp.accept_command("genmove b".to_string()).starts_with('='));
+    let before = gtp.accept_command("printsgf".to_string());
+    assert!(gtp.accept_command("2 reg_genmove w".to_string()).starts_with("?2 Too many requests, retry in"));
+    assert_eq!(gtp.accept_command("printsgf".to_string()), before);
+    assert!(refused.lock().unwrap().take().is_some_and(|seconds| seconds > 0));
+
+    // a client's requests are shared by its sessions, and the commands of a JSON input
+    let mut gtp = GTP::new();
+    let refused = limit_searches(&mut gtp, &limiter, "ip:1".to_string());
+    let input = r#"{"board_size": 9, "move_list": [], "next_command": "genmove b"}"#;
+    assert!(serde_json::from_str::<CommandInput>(input).unwrap().run(gtp).starts_with('?'));
+    assert!(refused.lock().unwrap().is_some());
+}
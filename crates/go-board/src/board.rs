@@ -0,0 +1,857 @@
+//! The game board of Go.
+//!
+//! Supports 9x9, 13x13, and 19x19 board sizes.
+
+use std::fmt;
+use std::fmt::Formatter;
+use crate::groups;
+use crate::tree::GameTree;
+use crate::update::{update, Message, Position, RuleViolation};
+
+/// The colors of stones on a Go Board.
+#[derive(Copy, Clone, Debug, PartialOrd, PartialEq)]
+pub enum Color {
+    /// Black stones.
+    Black,
+    /// White stones.
+    White,
+}
+
+/// The states of intersections on a Go Board
+#[derive(Copy, Clone, Debug, PartialOrd, PartialEq)]
+pub enum State {
+    /// An empty intersection
+    Empty,
+    /// An intersection occupied by a stone of a given [`Color`]
+    Occupied(Color),
+    #[doc(hidden)]
+    /// A sentinel value that borders the board for ease of computation.
+    Offboard,
+}
+
+/// The column letters of Go notation, which skip `I`.
+pub const COLUMN_LETTERS: &str = "ABCDEFGHJKLMNOPQRST";
+
+// Each intersection of a board is stored in a single byte, relying on the niche of `Color` for
+// the layout of `Occupied`, so full board scans walk a densely packed `Vec<State>`
+const _: () = assert!(std::mem::size_of::<State>() == 1);
+
+/// The supported sizes of Go Boards.
+///
+/// Currently, supports the standard 9x9, 13x13, and 19x19 sizes.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BoardSize {
+    /// Board size of 9x9.
+    Nine,
+    /// Board size of 13x13.
+    Thirteen,
+    /// Board size of 19x19.
+    Nineteen,
+}
+
+/// The rules deciding which earlier positions a move may not recreate.
+///
+/// ```
+/// use go_board::{update, Board, Color, KoRule, Message, Position};
+///
+/// // Black and White each take a ko, then after a pass each take the other ko back
+/// let double_ko = |ko_rule| {
+///     let mut board = Board::new();
+///     update(&mut board, Message::SetKoRule(ko_rule)).unwrap();
+///     for (row, col) in [(2, 1), (1, 2), (3, 2), (6, 2), (5, 3), (7, 3), (6, 4)] {
+///         update(&mut board, Message::PlaceStone(Color::Black, Position::new(row, col))).unwrap();
+///     }
+///     for (row, col) in [(2, 2), (1, 3), (3, 3), (2, 4), (6, 1), (5, 2), (7, 2)] {
+///         update(&mut board, Message::PlaceStone(Color::White, Position::new(row, col))).unwrap();
+///     }
+///     update(&mut board, Message::Play(Color::Black, Position::new(2, 3))).unwrap();
+///     update(&mut board, Message::Play(Color::White, Position::new(6, 3))).unwrap();
+///     update(&mut board, Message::Pass).unwrap();
+///     update(&mut board, Message::Play(Color::White, Position::new(2, 2))).unwrap();
+///     update(&mut board, Message::Play(Color::Black, Position::new(6, 2)))
+/// };
+///
+/// assert!(double_ko(KoRule::Simple).is_ok());
+/// assert!(double_ko(KoRule::PositionalSuperko).is_err());
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum KoRule {
+    /// Only immediately retaking a single stone ko is forbidden, as in Japanese rules.
+    #[default]
+    Simple,
+    /// Recreating any earlier whole board position is forbidden, as in Chinese rules.
+    PositionalSuperko,
+}
+
+/// The rulesets a game of Go can be played under, deciding how it is scored and which moves are
+/// legal.
+///
+/// ```
+/// use go_board::{update, Board, Color, Message, Position, Ruleset, ScoringRules};
+///
+/// // White fills the last liberty of its own two stones in the corner
+/// let suicide = |ruleset| {
+///     let mut board = Board::new();
+///     update(&mut board, Message::SetRuleset(ruleset)).unwrap();
+///     update(&mut board, Message::Play(Color::Black, Position::new(2, 0))).unwrap();
+///     update(&mut board, Message::Play(Color::White, Position::new(1, 0))).unwrap();
+///     update(&mut board, Message::Play(Color::Black, Position::new(1, 1))).unwrap();
+///     update(&mut board, Message::Pass).unwrap();
+///     update(&mut board, Message::Play(Color::Black, Position::new(0, 1))).unwrap();
+///     update(&mut board, Message::Play(Color::White, Position::new(0, 0)))
+/// };
+///
+/// assert!(suicide(Ruleset::Chinese).is_err());
+/// assert!(suicide(Ruleset::NewZealand).is_ok());
+/// assert_eq!(Ruleset::Japanese.scoring(), ScoringRules::Territory);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum Ruleset {
+    /// Area scoring and positional superko.
+    #[default]
+    Chinese,
+    /// Territory scoring and simple ko.
+    Japanese,
+    /// Area scoring and positional superko, as played by the American Go Association.
+    Aga,
+    /// Area scoring, positional superko, and suicide.
+    NewZealand,
+    /// Area scoring, positional superko, and suicide, as a formal ruleset for computer Go.
+    TrompTaylor,
+}
+
+/// The rules used to score a game.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ScoringRules {
+    /// Area scoring, as in Chinese rules: stones and surrounded empty points both count.
+    Area,
+    /// Territory scoring, as in Japanese rules: surrounded empty points and captures count.
+    Territory,
+}
+
+/// How a finished game of Go was won.
+///
+/// ```
+/// use go_board::{update, Board, Color, GameResult, Message, Position};
+///
+/// let mut board = Board::new();
+/// update(&mut board, Message::Play(Color::Black, Position::new(3, 3))).unwrap();
+/// update(&mut board, Message::Pass).unwrap();
+/// let Ok(Message::Played(effects)) = update(&mut board, Message::Pass) else { panic!() };
+/// assert!(matches!(effects.result, Some(GameResult::Score(Color::Black, _))));
+/// assert_eq!(board.result().unwrap().to_string(), "B+354.5");
+/// assert!(update(&mut board, Message::Play(Color::Black, Position::new(4, 4))).is_err());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GameResult {
+    /// Both players passed, and the given [`Color`] won on points under the board's [`Ruleset`]
+    /// by the given margin, komi included.
+    Score(Color, f64),
+    /// Both players passed with equal scores.
+    Draw,
+    /// The given [`Color`] won after the other resigned.
+    Resignation(Color),
+}
+
+/// Represents a Go Board
+#[derive(Clone)]
+pub struct Board {
+    pub(crate) size: BoardSize,
+    pub(crate) board: Vec<State>,
+    pub(crate) ko: Option<usize>,
+    pub(crate) black_captures: u16,
+    pub(crate) white_captures: u16,
+    pub(crate) player_turn: Color,
+    pub(crate) move_number: u16,
+    pub(crate) moves: Vec<(Color, Option<Position>)>,
+    pub(crate) setup: Vec<(Color, Position)>,
+    pub(crate) ruleset: Ruleset,
+    pub(crate) ko_rule: KoRule,
+    pub(crate) hash: u64,
+    pub(crate) history: Vec<u64>,
+    pub(crate) tree: GameTree,
+    pub(crate) komi: f64,
+    pub(crate) resigned: Option<Color>,
+}
+
+/// A group of connected stones of one [`Color`] on a [`Board`], as given by [`Board::groups`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Group {
+    /// The [`Color`] of the stones of the group.
+    pub color: Color,
+    /// The [`Position`]s of the stones of the group.
+    pub stones: Vec<Position>,
+    /// The [`Position`]s of the empty intersections next to the group.
+    pub liberties: Vec<Position>,
+}
+
+/// The position of a [`Board`] at one point of a game, restored to revert an illegal move or to
+/// move between the nodes of its [`GameTree`].
+#[derive(Clone)]
+pub(crate) struct Snapshot {
+    stones: Vec<State>,
+    ko: Option<usize>,
+    black_captures: u16,
+    white_captures: u16,
+    hash: u64,
+    player_turn: Color,
+    move_number: u16,
+}
+
+impl BoardSize {
+    /// Converts a numeric board size into its [`BoardSize`], or [`None`] if it is unsupported.
+    pub fn from_u16(size: u16) -> Option<BoardSize> {
+        match size {
+            9 => Some(BoardSize::Nine),
+            13 => Some(BoardSize::Thirteen),
+            19 => Some(BoardSize::Nineteen),
+            _ => None,
+        }
+    }
+
+    /// Converts a [`BoardSize`] to its numeric representation.
+    pub fn to_u16(&self) -> u16 {
+        match self {
+            BoardSize::Nine => 9,
+            BoardSize::Thirteen => 13,
+            BoardSize::Nineteen => 19,
+        }
+    }
+
+    /// The star points the given number of handicap stones are placed on, in the order of the Go
+    /// Text Protocol: opposite corners first, then the other corners, the sides, and the center
+    /// for an odd number of stones.
+    ///
+    /// Outputs [`None`] unless there are between 2 and 9 stones.
+    pub fn handicap_points(&self, stones: u16) -> Option<Vec<Position>> {
+        if !(2..=9).contains(&stones) {
+            return None;
+        }
+
+        let size = self.to_u16();
+        let edge = if *self == BoardSize::Nine { 2 } else { 3 };
+        let (low, middle, high) = (edge, size / 2, size - 1 - edge);
+        let mut points = vec![(low, low), (high, high), (low, high), (high, low)];
+        if stones >= 6 {
+            points.extend([(low, middle), (high, middle)]);
+        }
+        if stones >= 8 {
+            points.extend([(middle, low), (middle, high)]);
+        }
+        points.truncate(stones as usize - stones as usize % 2);
+        if stones % 2 == 1 {
+            points.push((middle, middle));
+        }
+
+        Some(points.into_iter().map(|(col, row)| Position::new(row, col)).collect())
+    }
+}
+
+impl Color {
+    /// Returns the opposite [`Color`] of the current.
+    pub fn opposite_color(&self) -> Self {
+        match self {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        }
+    }
+
+    /// The [`Color`] of the given name, `b` or `black` and `w` or `white` in any case.
+    pub fn from_name(name: &str) -> Option<Color> {
+        match name.to_lowercase().as_str() {
+            "b" | "black" => Some(Color::Black),
+            "w" | "white" => Some(Color::White),
+            _ => None,
+        }
+    }
+}
+
+impl Board {
+    /// Constructs a new empty [`Board`]. Default size is [`19x19`](BoardSize::Nineteen)
+    ///
+    /// Use [`Board::builder`] to start from another size, komi, or position.
+    pub fn new() -> Self {
+        let board = init_board(&BoardSize::Nineteen);
+        let start = Snapshot {
+            stones: board.clone(),
+            ko: None,
+            black_captures: 0,
+            white_captures: 0,
+            hash: 0,
+            player_turn: Color::Black,
+            move_number: 0,
+        };
+
+        Board {
+            size: BoardSize::Nineteen,
+            board,
+            ko: None,
+            black_captures: 0,
+            white_captures: 0,
+            player_turn: Color::Black,
+            move_number: 0,
+            moves: vec![],
+            setup: vec![],
+            ruleset: Ruleset::Chinese,
+            ko_rule: Ruleset::Chinese.ko_rule(),
+            hash: 0,
+            history: vec![],
+            tree: GameTree::new(start),
+            komi: 6.5,
+            resigned: None,
+        }
+    }
+
+    /// The moves leading to the current position of this [`Board`] since it was last cleared, in
+    /// order, each a [`Position`] played or a pass.
+    pub fn moves(&self) -> &[(Color, Option<Position>)] {
+        &self.moves
+    }
+
+    /// The stones placed on this [`Board`] outside of play since it was last cleared, ex.
+    /// handicap stones, including those placed during the current line of its [`GameTree`].
+    pub fn setup(&self) -> &[(Color, Position)] {
+        &self.setup
+    }
+
+    /// The [`GameTree`] of the moves played on this [`Board`] since it was last cleared or had a
+    /// stone placed outside of play before its first move.
+    pub fn tree(&self) -> &GameTree {
+        &self.tree
+    }
+
+    /// The komi given to White when scoring this [`Board`], 6.5 unless set otherwise.
+    pub fn komi(&self) -> f64 {
+        self.komi
+    }
+
+    /// The result of the game on this [`Board`] if it is over, after a resignation or two
+    /// passes in a row.
+    pub fn result(&self) -> Option<GameResult> {
+        if let Some(color) = self.resigned {
+            return Some(GameResult::Resignation(color.opposite_color()));
+        }
+
+        let passes = self.moves.iter().rev().take_while(|(_, mov)| mov.is_none()).count();
+        if passes < 2 {
+            return None;
+        }
+        let score = self.score();
+        Some(if score > 0.0 {
+            GameResult::Score(Color::Black, score)
+        } else if score < 0.0 {
+            GameResult::Score(Color::White, -score)
+        } else {
+            GameResult::Draw
+        })
+    }
+
+    /// Scores this [`Board`] under its [`Ruleset`], with every stone left on the board alive.
+    ///
+    /// The score includes komi, and is positive when Black leads.
+    pub fn score(&self) -> f64 {
+        match self.ruleset.scoring() {
+            ScoringRules::Area => self.area_score(),
+            ScoringRules::Territory => self.territory_score(),
+        }
+    }
+
+    /// Scores this [`Board`] by area, counting the stones of each [`Color`] and the empty
+    /// intersections surrounded only by them, with every stone left on the board alive.
+    ///
+    /// The score includes komi, and is positive when Black leads.
+    pub fn area_score(&self) -> f64 {
+        let ((black_stones, black_territory), (white_stones, white_territory)) = self.count_areas();
+        (black_stones + black_territory) as f64 - (white_stones + white_territory) as f64 - self.komi
+    }
+
+    /// Scores this [`Board`] by territory, counting the empty intersections surrounded only by
+    /// each [`Color`] and the stones it captured, with every stone left on the board alive.
+    ///
+    /// The score includes komi, and is positive when Black leads.
+    pub fn territory_score(&self) -> f64 {
+        let ((_, black_territory), (_, white_territory)) = self.count_areas();
+        let black = black_territory + self.black_captures as u32;
+        let white = white_territory + self.white_captures as u32;
+        black as f64 - white as f64 - self.komi
+    }
+
+    /// Counts the stones of Black and White, each paired with the empty intersections surrounded
+    /// only by their stones.
+    fn count_areas(&self) -> ((u32, u32), (u32, u32)) {
+        let mut seen = vec![false; self.board.len()];
+        let (mut black, mut white) = ((0u32, 0u32), (0u32, 0u32));
+
+        for (start, state) in self.board.iter().enumerate() {
+            match state {
+                State::Occupied(Color::Black) => black.0 += 1,
+                State::Occupied(Color::White) => white.0 += 1,
+                State::Empty if !seen[start] => {
+                    // flood the empty region from here, noting the colors bordering it
+                    let (mut region, mut borders_black, mut borders_white) = (0u32, false, false);
+                    let mut worklist = vec![start];
+                    seen[start] = true;
+                    while let Some(index) = worklist.pop() {
+                        region += 1;
+                        for neighbor in groups::neighbors(index, &self.board, &self.size) {
+                            match self.board[neighbor] {
+                                State::Empty if !seen[neighbor] => {
+                                    seen[neighbor] = true;
+                                    worklist.push(neighbor);
+                                }
+                                State::Occupied(Color::Black) => borders_black = true,
+                                State::Occupied(Color::White) => borders_white = true,
+                                _ => {}
+                            }
+                        }
+                    }
+                    match (borders_black, borders_white) {
+                        (true, false) => black.1 += region,
+                        (false, true) => white.1 += region,
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        (black, white)
+    }
+
+    /// The [`Ruleset`] the game on this [`Board`] is played and scored under, Chinese unless set
+    /// otherwise.
+    pub fn ruleset(&self) -> Ruleset {
+        self.ruleset
+    }
+
+    /// The [`KoRule`] moves on this [`Board`] are played under.
+    pub fn ko_rule(&self) -> KoRule {
+        self.ko_rule
+    }
+
+    /// The Zobrist hash of the stones on this [`Board`], the same for any two boards of the same
+    /// size holding the same stones.
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// The [`BoardSize`] of this [`Board`].
+    pub fn size(&self) -> BoardSize {
+        self.size
+    }
+
+    /// The [`Color`] of the player whose turn it is on this [`Board`].
+    pub fn player_turn(&self) -> Color {
+        self.player_turn
+    }
+
+    /// The number of stones the player of the given [`Color`] has captured on this [`Board`].
+    pub fn captures(&self, color: Color) -> u16 {
+        match color {
+            Color::Black => self.black_captures,
+            Color::White => self.white_captures,
+        }
+    }
+
+    /// The [`Position`] the player to move may not play at this turn because it would retake a
+    /// ko, if any.
+    pub fn ko(&self) -> Option<Position> {
+        Some(Position::from_board_index(self.ko?, &self.size))
+    }
+
+    /// The [`Board`] after a stone of [`Color`] is played at the given [`Position`], leaving this
+    /// [`Board`] unchanged, for trying out candidate moves.
+    ///
+    /// Outputs the [`RuleViolation`] the move is rejected for on failure, as [`update`] would.
+    ///
+    /// The returned [`Board`] keeps the moves played so far, but its [`GameTree`] starts from this
+    /// position rather than copying every earlier one, so [`Message::Undo`] goes back no further
+    /// than the tried move.
+    ///
+    /// ```
+    /// use go_board::{Board, Color, Position, RuleViolation, State};
+    ///
+    /// let board = Board::new();
+    /// let after = board.try_play(Color::Black, Position::new(3, 3)).unwrap();
+    /// assert_eq!(after.state(Position::new(3, 3)), Some(State::Occupied(Color::Black)));
+    /// assert_eq!(board.state(Position::new(3, 3)), Some(State::Empty));
+    /// let out_of_turn = board.try_play(Color::White, Position::new(3, 3));
+    /// assert_eq!(out_of_turn.err(), Some(RuleViolation::OutOfTurn));
+    /// ```
+    pub fn try_play(&self, color: Color, pos: Position) -> Result<Board, RuleViolation> {
+        let mut board = Board {
+            size: self.size,
+            board: self.board.clone(),
+            ko: self.ko,
+            black_captures: self.black_captures,
+            white_captures: self.white_captures,
+            player_turn: self.player_turn,
+            move_number: self.move_number,
+            moves: self.moves.clone(),
+            setup: self.setup.clone(),
+            ruleset: self.ruleset,
+            ko_rule: self.ko_rule,
+            hash: self.hash,
+            history: self.history.clone(),
+            tree: GameTree::new(Snapshot::of(self)),
+            komi: self.komi,
+            resigned: self.resigned,
+        };
+        update(&mut board, Message::Play(color, pos))?;
+        Ok(board)
+    }
+
+    /// The [`State`] of the intersection at the given [`Position`], if it is on this [`Board`].
+    pub fn state(&self, pos: Position) -> Option<State> {
+        let size = self.size.to_u16();
+        if pos.row() >= size || pos.col() >= size {
+            return None;
+        }
+        pos.to_board_index(&self.size).map(|index| self.board[index])
+    }
+
+    /// The [`Position`] and [`State`] of every intersection on this [`Board`], row by row from the
+    /// bottom left corner, leaving out the off-board border around it.
+    ///
+    /// ```
+    /// use go_board::{Board, BoardSize, Position, State};
+    ///
+    /// let board = Board::builder().size(BoardSize::Nine).build().unwrap();
+    /// assert_eq!(board.intersections().count(), 81);
+    /// assert_eq!(board.intersections().next(), Some((Position::new(0, 0), State::Empty)));
+    /// ```
+    pub fn intersections(&self) -> impl Iterator<Item = (Position, State)> + '_ {
+        let size = self.size.to_u16();
+        (0..size)
+            .flat_map(move |row| (0..size).map(move |col| Position::new(row, col)))
+            .map(|pos| {
+                let index = pos.to_board_index(&self.size).expect("the position is on the board");
+                (pos, self.board[index])
+            })
+    }
+
+    /// The [`Position`]s of the stones of the given [`Color`] on this [`Board`], in the order of
+    /// [`Board::intersections`].
+    pub fn stones(&self, color: Color) -> impl Iterator<Item = Position> + '_ {
+        self.intersections()
+            .filter_map(move |(pos, state)| (state == State::Occupied(color)).then_some(pos))
+    }
+
+    /// Every [`Group`] of connected stones on this [`Board`], each given once, in the order of
+    /// [`Board::intersections`] of their first stone.
+    ///
+    /// ```
+    /// use go_board::{Board, Color, Position};
+    ///
+    /// let stones = [(0, 0), (0, 1), (5, 5)].map(|(row, col)| Position::new(row, col));
+    /// let board = Board::builder().stones(stones.map(|pos| (Color::Black, pos))).build().unwrap();
+    /// let sizes: Vec<usize> = board.groups().map(|group| group.stones.len()).collect();
+    /// assert_eq!(sizes, vec![2, 1]);
+    /// assert_eq!(board.groups().next().unwrap().liberties.len(), 3);
+    /// ```
+    pub fn groups(&self) -> impl Iterator<Item = Group> + '_ {
+        let mut seen = vec![false; self.board.len()];
+        let positions = |indexes: &[usize]| {
+            indexes.iter().map(|index| Position::from_board_index(*index, &self.size)).collect()
+        };
+        self.intersections().filter_map(move |(pos, state)| {
+            let State::Occupied(color) = state else {
+                return None;
+            };
+            let index = pos.to_board_index(&self.size).expect("the position is on the board");
+            if seen[index] {
+                return None;
+            }
+            let group = groups::find_group(index, &color, &self.board, &self.size);
+            group.stones.iter().for_each(|stone| seen[*stone] = true);
+            let (stones, liberties) = (positions(&group.stones), positions(&group.liberties));
+            Some(Group { color, stones, liberties })
+        })
+    }
+
+    /// Sets the [`State`] of the intersection at the given index, keeping the hash in step.
+    pub(crate) fn set_state(&mut self, index: usize, state: State) {
+        self.hash ^= zobrist_key(index, self.board[index]) ^ zobrist_key(index, state);
+        self.board[index] = state;
+    }
+
+    /// Removes the groups of the opponent of [`Color`] left without liberties by the stone played
+    /// at the given index, setting the ko point.
+    ///
+    /// Outputs the indexes of the captured stones.
+    pub(crate) fn attempt_captures(&mut self, played_index: usize, color: &Color) -> Vec<usize> {
+        let mut potential_kos: Vec<usize> = vec![]; // todo: probably better way to deal with ko
+        let mut captured = vec![];
+        
+        for start_index in groups::neighbors(played_index, &self.board, &self.size) {
+            let group = groups::find_group(
+                start_index,
+                &color.opposite_color(),
+                &self.board,
+                &self.size,
+            );
+
+            if group.liberties.is_empty() {
+                if self.capture_causes_ko(&group) {
+                    potential_kos.push(group.stones[0]); // guaranteed to be a group of size 1
+                }
+                
+                group.stones.iter().for_each(|index| self.set_state(*index, State::Empty));
+                match color {
+                    Color::White => self.white_captures += group.stones.len() as u16,
+                    Color::Black => self.black_captures += group.stones.len() as u16,
+                }
+                captured.extend(group.stones);
+            }
+        }
+
+        self.ko = if potential_kos.len() == 1 {
+            Some(potential_kos[0])
+        } else {
+            None
+        };
+        captured
+    }
+
+    fn capture_causes_ko(&mut self, captured_group: &groups::Group) -> bool {
+        if captured_group.stones.len() == 1 {
+            groups::neighbors(captured_group.stones[0], &self.board, &self.size)
+                .iter()
+                .map(|index| self.board[*index])
+                .all(|state| state == State::Occupied(captured_group.color.opposite_color()) || state == State::Offboard)
+        } else {
+            false
+        }
+    }
+}
+
+impl Snapshot {
+    /// Records the current position of the [`Board`].
+    pub(crate) fn of(board: &Board) -> Self {
+        Snapshot {
+            stones: board.board.clone(),
+            ko: board.ko,
+            black_captures: board.black_captures,
+            white_captures: board.white_captures,
+            hash: board.hash,
+            player_turn: board.player_turn,
+            move_number: board.move_number,
+        }
+    }
+
+    /// The Zobrist hash of the recorded position.
+    pub(crate) fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// Returns the [`Board`] to the recorded position.
+    pub(crate) fn restore(self, board: &mut Board) {
+        board.board = self.stones;
+        board.ko = self.ko;
+        board.black_captures = self.black_captures;
+        board.white_captures = self.white_captures;
+        board.hash = self.hash;
+        board.player_turn = self.player_turn;
+        board.move_number = self.move_number;
+    }
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Writes the stones of the board as `X` for Black and `O` for White, with the columns labeled by
+/// letter (skipping `I`) and the rows by number.
+///
+/// ```
+/// use go_board::{update, Board, BoardSize, Color, Message, Position};
+///
+/// let mut board = Board::new();
+/// update(&mut board, Message::SetSize(BoardSize::Nine)).unwrap();
+/// update(&mut board, Message::Play(Color::Black, Position::new(2, 2))).unwrap();
+/// update(&mut board, Message::Play(Color::White, Position::new(2, 6))).unwrap();
+///
+/// let lines: Vec<String> = board.to_string().lines().map(String::from).collect();
+/// assert_eq!(lines[0], "   A B C D E F G H J");
+/// assert_eq!(lines[7], " 3 . . X . . . O . .  3");
+/// assert_eq!(lines.len(), 11);
+/// ```
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let size = self.size.to_u16();
+        let letters: String =
+            COLUMN_LETTERS.chars().take(size as usize).flat_map(|c| [' ', c]).collect();
+
+        writeln!(f, "  {letters}")?;
+        for row in (0..size).rev() {
+            write!(f, "{:>2}", row + 1)?;
+            for col in 0..size {
+                match self.state(Position::new(row, col)) {
+                    Some(State::Occupied(color)) => write!(f, " {color}")?,
+                    _ => f.write_str(" .")?,
+                }
+            }
+            writeln!(f, " {:>2}", row + 1)?;
+        }
+        write!(f, "  {letters}")
+    }
+}
+
+impl Ruleset {
+    /// The [`Ruleset`] of the given rules name in any case, where Korean rules play as Japanese
+    /// rules, and `area` and `territory` as Chinese and Japanese rules.
+    ///
+    /// Reads the names [`Ruleset`]s are displayed with, ex. `new_zealand`.
+    pub fn from_name(name: &str) -> Option<Ruleset> {
+        match name.to_lowercase().as_str() {
+            "chinese" | "area" => Some(Ruleset::Chinese),
+            "japanese" | "korean" | "territory" => Some(Ruleset::Japanese),
+            "aga" => Some(Ruleset::Aga),
+            "new-zealand" | "new_zealand" | "nz" => Some(Ruleset::NewZealand),
+            "tromp-taylor" | "tromp_taylor" => Some(Ruleset::TrompTaylor),
+            _ => None,
+        }
+    }
+
+    /// The [`ScoringRules`] games under this [`Ruleset`] are scored with.
+    pub fn scoring(&self) -> ScoringRules {
+        match self {
+            Ruleset::Japanese => ScoringRules::Territory,
+            _ => ScoringRules::Area,
+        }
+    }
+
+    /// The [`KoRule`] moves under this [`Ruleset`] are played with.
+    ///
+    /// AGA and New Zealand rules forbid recreating a position with the same player to move,
+    /// which is enforced as positional superko.
+    pub fn ko_rule(&self) -> KoRule {
+        match self {
+            Ruleset::Japanese => KoRule::Simple,
+            _ => KoRule::PositionalSuperko,
+        }
+    }
+
+    /// Whether a move under this [`Ruleset`] may leave its own group without liberties,
+    /// capturing it.
+    ///
+    /// Suicide of a single stone never changes the position, so is illegal under every ruleset.
+    pub fn allows_suicide(&self) -> bool {
+        matches!(self, Ruleset::NewZealand | Ruleset::TrompTaylor)
+    }
+
+    /// The komi games under this [`Ruleset`] are usually played with.
+    pub fn default_komi(&self) -> f64 {
+        match self {
+            Ruleset::Japanese => 6.5,
+            Ruleset::NewZealand => 7.0,
+            _ => 7.5,
+        }
+    }
+}
+
+impl KoRule {
+    /// The [`KoRule`] of the given name in any case, `simple`, or `positional` or `superko`.
+    pub fn from_name(name: &str) -> Option<KoRule> {
+        match name.to_lowercase().as_str() {
+            "simple" => Some(KoRule::Simple),
+            "positional" | "superko" => Some(KoRule::PositionalSuperko),
+            _ => None,
+        }
+    }
+}
+
+impl GameResult {
+    /// The [`Color`] that won, or [`None`] for a draw.
+    pub fn winner(&self) -> Option<Color> {
+        match self {
+            GameResult::Score(color, _) | GameResult::Resignation(color) => Some(*color),
+            GameResult::Draw => None,
+        }
+    }
+}
+
+impl fmt::Display for GameResult {
+    /// Writes the result as in SGF, ex. `B+3.5`, `W+R`, or `0` for a draw.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let winner = |color: &Color| match color {
+            Color::Black => "B",
+            Color::White => "W",
+        };
+        match self {
+            GameResult::Score(color, margin) => write!(f, "{}+{margin}", winner(color)),
+            GameResult::Draw => f.write_str("0"),
+            GameResult::Resignation(color) => write!(f, "{}+R", winner(color)),
+        }
+    }
+}
+
+impl fmt::Display for Ruleset {
+    /// Writes the name of the rules, as [`Ruleset::from_name`] reads it, ex. `new_zealand`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Ruleset::Chinese => "chinese",
+            Ruleset::Japanese => "japanese",
+            Ruleset::Aga => "aga",
+            Ruleset::NewZealand => "new_zealand",
+            Ruleset::TrompTaylor => "tromp-taylor",
+        })
+    }
+}
+
+impl fmt::Display for KoRule {
+    /// Writes the name of the ko rule, as [`KoRule::from_name`] reads it.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            KoRule::Simple => "simple",
+            KoRule::PositionalSuperko => "positional",
+        })
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Color::Black => write!(f, "X"),
+            Color::White => write!(f, "O"),
+        }
+    }
+}
+
+/// The Zobrist key of the [`State`] at the given index, where intersections without a stone have
+/// no key so that the hash of a [`Board`] is the XOR of the keys of its stones.
+///
+/// Keys are the splitmix64 mix of the index and [`Color`], the same on every run.
+pub(crate) fn zobrist_key(index: usize, state: State) -> u64 {
+    let color_bit = match state {
+        State::Occupied(Color::Black) => 0,
+        State::Occupied(Color::White) => 1,
+        State::Empty | State::Offboard => return 0,
+    };
+
+    let mut key = ((index as u64) << 1 | color_bit).wrapping_add(0x9E37_79B9_7F4A_7C15);
+    key = (key ^ (key >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    key = (key ^ (key >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    key ^ (key >> 31)
+}
+
+#[doc(hidden)]
+/// Initializes an empty board vector of the given [`BoardSize`].
+pub(crate) fn init_board(size: &BoardSize) -> Vec<State> {
+    let mut board: Vec<State> = vec![];
+    let row_len = size.to_u16() + 2;
+
+    for i in 0..row_len * row_len {
+        if i / row_len == 0 || i / row_len == row_len - 1 {
+            board.push(State::Offboard)
+        } else if i % row_len == row_len - 1 || i % row_len == 0 {
+            board.push(State::Offboard);
+        } else {
+            board.push(State::Empty);
+        }
+    }
+
+    board
+}
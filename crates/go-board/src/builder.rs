@@ -0,0 +1,154 @@
+//! A builder of boards holding arbitrary starting positions.
+
+use crate::board::{Board, BoardSize, Color, Ruleset, Snapshot};
+use crate::tree::GameTree;
+use crate::update::{update, Message, Position, RuleViolation};
+
+/// Builds a [`Board`] holding a starting position, validating it as a whole.
+///
+/// Started by [`Board::builder`], which begins from an empty 19x19 board with 6.5 komi under
+/// [`Ruleset::Chinese`], the same as [`Board::new`].
+///
+/// ```
+/// use go_board::{Board, BoardSize, Color, Position, State};
+///
+/// let board = Board::builder()
+///     .size(BoardSize::Nine)
+///     .komi(0.5)
+///     .handicap(4)
+///     .stones([(Color::White, Position::new(4, 4))])
+///     .build()
+///     .unwrap();
+/// assert_eq!(board.state(Position::new(2, 2)), Some(State::Occupied(Color::Black)));
+/// assert_eq!(board.state(Position::new(4, 4)), Some(State::Occupied(Color::White)));
+/// assert_eq!(board.player_turn(), Color::White);
+/// ```
+#[derive(Clone, Debug)]
+pub struct BoardBuilder {
+    size: BoardSize,
+    komi: f64,
+    ruleset: Ruleset,
+    handicap: u16,
+    stones: Vec<(Color, Position)>,
+    to_play: Option<Color>,
+}
+
+/// The reasons a [`BoardBuilder`] could not build a [`Board`] from its starting position.
+///
+/// ```
+/// use go_board::{Board, BoardSize, BuildError, Color, Position, RuleViolation};
+///
+/// assert_eq!(Board::builder().handicap(12).build().err(), Some(BuildError::Handicap(12)));
+///
+/// let off_board = Position::new(9, 0);
+/// let builder = Board::builder().size(BoardSize::Nine).stones([(Color::Black, off_board)]);
+/// let error = BuildError::Stone(off_board, RuleViolation::OutOfBounds);
+/// assert_eq!(builder.build().err(), Some(error));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum BuildError {
+    /// The number of handicap stones was neither none nor between 2 and 9.
+    #[error("Unsupported number of handicap stones: {0}")]
+    Handicap(u16),
+    /// The stone at the given [`Position`] could not be placed, off the board or on an occupied
+    /// intersection.
+    #[error("Cannot place a stone at {0}: {1}")]
+    Stone(Position, RuleViolation),
+    /// The group of stones holding the given [`Position`] has no liberties.
+    #[error("Cannot start from a group of stones without liberties at {0}")]
+    NoLiberties(Position),
+}
+
+impl Board {
+    /// Starts a [`BoardBuilder`] for a [`Board`] holding a starting position.
+    pub fn builder() -> BoardBuilder {
+        BoardBuilder {
+            size: BoardSize::Nineteen,
+            komi: 6.5,
+            ruleset: Ruleset::Chinese,
+            handicap: 0,
+            stones: vec![],
+            to_play: None,
+        }
+    }
+}
+
+impl BoardBuilder {
+    /// Sets the [`BoardSize`] of the [`Board`].
+    pub fn size(mut self, size: BoardSize) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Sets the komi given to White when the game is scored.
+    pub fn komi(mut self, komi: f64) -> Self {
+        self.komi = komi;
+        self
+    }
+
+    /// Sets the [`Ruleset`] the game is played and scored under, along with its
+    /// [`KoRule`](crate::KoRule).
+    pub fn ruleset(mut self, ruleset: Ruleset) -> Self {
+        self.ruleset = ruleset;
+        self
+    }
+
+    /// Sets the number of handicap stones Black places on the star points given by
+    /// [`BoardSize::handicap_points`], either none or between 2 and 9, which gives White the
+    /// first move.
+    pub fn handicap(mut self, stones: u16) -> Self {
+        self.handicap = stones;
+        self
+    }
+
+    /// Adds the given stones of each [`Color`] at each [`Position`] to the starting position.
+    pub fn stones(mut self, stones: impl IntoIterator<Item = (Color, Position)>) -> Self {
+        self.stones.extend(stones);
+        self
+    }
+
+    /// Sets the [`Color`] to play first, in place of Black, or White after handicap stones.
+    pub fn to_play(mut self, color: Color) -> Self {
+        self.to_play = Some(color);
+        self
+    }
+
+    /// Constructs the [`Board`] of the starting position, with its stones placed outside of play.
+    ///
+    /// Outputs the [`BuildError`] making the position invalid on failure: an unsupported number
+    /// of handicap stones, a stone off the board or on an occupied intersection, or a group of
+    /// stones without liberties.
+    pub fn build(self) -> Result<Board, BuildError> {
+        let mut board = Board::new();
+        let settings = [
+            Message::SetSize(self.size),
+            Message::SetRuleset(self.ruleset),
+            Message::SetKomi(self.komi),
+        ];
+        for msg in settings {
+            update(&mut board, msg).expect("settings are always accepted");
+        }
+
+        let handicap = match self.handicap {
+            0 => vec![],
+            stones => self
+                .size
+                .handicap_points(stones)
+                .ok_or(BuildError::Handicap(stones))?,
+        };
+        let stones = handicap.into_iter().map(|pos| (Color::Black, pos)).chain(self.stones);
+        for (color, pos) in stones {
+            update(&mut board, Message::PlaceStone(color, pos))
+                .map_err(|violation| BuildError::Stone(pos, violation))?;
+        }
+
+        if let Some(group) = board.groups().find(|group| group.liberties.is_empty()) {
+            return Err(BuildError::NoLiberties(group.stones[0]));
+        }
+
+        let first = if self.handicap > 0 { Color::White } else { Color::Black };
+        board.player_turn = self.to_play.unwrap_or(first);
+        board.tree = GameTree::new(Snapshot::of(&board));
+        Ok(board)
+    }
+}
@@ -0,0 +1,66 @@
+//! Computations regarding groups of stones.
+
+use crate::board::{BoardSize, Color, State};
+use smallvec::SmallVec;
+
+/// Words of the set of position indexes seen by [`find_group`], enough for a padded 19x19 board.
+const SEEN_WORDS: usize = 7;
+
+/// A group of [`Color`] stones on a Go Board.
+pub(crate) struct Group {
+    /// The list of position indexes of stones in the group.
+    pub(crate) stones: SmallVec<[usize; 16]>,
+    /// The list of position indexes of liberties in the group.
+    pub(crate) liberties: SmallVec<[usize; 16]>,
+    /// The color of stones in the group.
+    pub(crate) color: Color,
+}
+
+/// Calculates the neighboring position indexes of the given index if the given index is valid.
+pub(crate) fn neighbors(index: usize, board: &[State], size: &BoardSize) -> SmallVec<[usize; 4]> {
+    let row_len = size.to_u16() as usize + 2;
+    match board[index] {
+        State::Offboard => SmallVec::new(),
+        _ => SmallVec::from_buf([index + 1, index - 1, index + row_len, index - row_len]),
+    }
+}
+
+/// Finds the group of stones on the board of [`Color`] connected to the stone at the given index.
+///
+/// The search allocates nothing for groups of up to 16 stones and 16 liberties.
+pub(crate) fn find_group(
+    start_index: usize,
+    color: &Color,
+    board: &[State],
+    size: &BoardSize,
+) -> Group {
+    let mut seen = [0u64; SEEN_WORDS];
+    let mut worklist: SmallVec<[usize; 32]> = SmallVec::new();
+    let mut group = Group {
+        stones: SmallVec::new(),
+        liberties: SmallVec::new(),
+        color: *color,
+    };
+
+    worklist.push(start_index);
+    seen[start_index / 64] |= 1 << (start_index % 64);
+
+    while let Some(cur_index) = worklist.pop() {
+        match board[cur_index] {
+            State::Empty => group.liberties.push(cur_index),
+            State::Occupied(c) if c == *color => {
+                group.stones.push(cur_index);
+                for neighbor in neighbors(cur_index, board, size) {
+                    let (word, bit) = (neighbor / 64, 1 << (neighbor % 64));
+                    if seen[word] & bit == 0 {
+                        seen[word] |= bit;
+                        worklist.push(neighbor);
+                    }
+                }
+            }
+            State::Occupied(_) | State::Offboard => {}
+        }
+    }
+
+    group
+}
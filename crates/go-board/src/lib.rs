@@ -0,0 +1,33 @@
+//! The board and rules of the game of Go.
+//!
+//! This crate is the model of the `go_engine` library, with [`board`] holding a game and [`update`]
+//! applying [`Message`]s to it, and [`sgf`] writing the games played on a [`Board`] with the
+//! variations of its [`tree`]. It has no engine or servers of its own, for other projects to play
+//! and record games of Go on.
+//!
+//! # Example
+//!
+//! ```
+//! use go_board::{update, Board, BoardSize, Color, Message, Position};
+//!
+//! let mut board = Board::builder().size(BoardSize::Nine).komi(7.5).build().unwrap();
+//! update(&mut board, Message::Play(Color::Black, Position::new(3, 3))).unwrap();
+//! ```
+
+#![warn(missing_docs)]
+
+pub mod board;
+pub mod builder;
+pub mod sgf;
+#[cfg(any(test, feature = "proptest"))]
+pub mod testing;
+pub mod tree;
+pub mod update;
+
+mod groups;
+#[cfg(test)]
+mod tests;
+
+pub use board::{Board, BoardSize, Color, GameResult, Group, KoRule, Ruleset, ScoringRules, State};
+pub use builder::{BoardBuilder, BuildError};
+pub use update::{update, Message, MoveEffects, ParsePositionError, Position, RuleViolation};
@@ -0,0 +1,501 @@
+//! Game records in the Smart Game Format (SGF).
+//!
+//! A [`Board`] records the moves played on it through [`update`](crate::update) in its
+//! [`GameTree`], which [`to_sgf`] writes with every variation along with the [`GameInfo`] the
+//! board does not hold, such as komi and the result. [`parse_sgf`] reads a record back as a
+//! [`GameRecord`], keeping its variations.
+
+use crate::board::{Board, BoardSize, Color};
+use crate::tree::{GameNode, GameTree, NodeId};
+use crate::update::{place_setup_stone, update, Message, Position};
+use std::fmt::Write;
+use std::io;
+use std::iter::Peekable;
+use std::path::Path;
+use std::str::Chars;
+
+/// Information about a game that is recorded alongside its moves.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GameInfo {
+    /// The komi given to White.
+    pub komi: f64,
+    /// The number of handicap stones given to Black.
+    pub handicap: u16,
+    /// The name of the Black player, if known.
+    pub black_player: Option<String>,
+    /// The name of the White player, if known.
+    pub white_player: Option<String>,
+    /// The result of the game, ex. `B+3.5` or `W+R`, if it has finished. [`to_sgf`] writes the
+    /// result of the board when this is [`None`].
+    pub result: Option<String>,
+}
+
+/// A node of a game tree read from an SGF record.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SgfNode {
+    /// The move of this node, a [`Position`] played or a pass, if it has one.
+    pub mov: Option<(Color, Option<Position>)>,
+    /// The stones this node places outside of play, ex. handicap stones.
+    pub setup: Vec<(Color, Position)>,
+    /// The comment on this node, if any.
+    pub comment: Option<String>,
+    /// The nodes following this one: the first continues the main line, and the others are
+    /// variations of it.
+    pub children: Vec<SgfNode>,
+}
+
+/// A game read from an SGF record.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GameRecord {
+    /// The size of the board the game is played on.
+    pub size: BoardSize,
+    /// The information about the game in its root node.
+    pub info: GameInfo,
+    /// The root node of the game tree, holding any setup stones before the first move.
+    pub root: SgfNode,
+}
+
+impl GameRecord {
+    /// The nodes of the main line of the game, from the root through the first child of every node.
+    pub fn main_line(&self) -> Vec<&SgfNode> {
+        let mut line = vec![&self.root];
+        while let Some(next) = line[line.len() - 1].children.first() {
+            line.push(next);
+        }
+        line
+    }
+
+    /// Replays the game on a new [`Board`], with every variation in its
+    /// [`GameTree`](crate::tree::GameTree), ending at the last position of the main line.
+    ///
+    /// Moves are played by the [`Color`] the record gives them, even out of turn. Outputs a
+    /// [`String`] detailing the first move or stone that could not be played on failure.
+    pub fn board(&self) -> Result<Board, String> {
+        let mut board = Board::builder()
+            .size(self.size)
+            .build()
+            .expect("an empty board is always valid");
+        replay(&mut board, &self.root, 0)?;
+
+        let main_line_end = board.tree().main_line().pop().expect("the main line holds the root");
+        update(&mut board, Message::GoTo(main_line_end)).map_err(|error| error.to_string())?;
+        Ok(board)
+    }
+}
+
+/// Replays the given node of a game tree and every node following it on the [`Board`], taking
+/// back each variation after replaying it.
+fn replay(board: &mut Board, node: &SgfNode, depth: usize) -> Result<(), String> {
+    for (color, pos) in &node.setup {
+        // below the root, setup stones belong to their variation, so are taken back with it
+        let placed = match depth {
+            0 => update(board, Message::PlaceStone(*color, *pos)),
+            _ => place_setup_stone(board, *color, *pos),
+        };
+        placed.map_err(|error| format!("Invalid setup stone in node {depth}: {error}"))?;
+    }
+    if let Some((color, mov)) = node.mov {
+        board.player_turn = color;
+        let msg = match mov {
+            Some(pos) => Message::Play(color, pos),
+            None => Message::Pass,
+        };
+        update(board, msg).map_err(|error| format!("Illegal move in node {depth}: {error}"))?;
+    }
+    if node.comment.is_some() {
+        update(board, Message::Comment(node.comment.clone())).map_err(|error| error.to_string())?;
+    }
+
+    let current = board.tree().current();
+    for child in &node.children {
+        replay(board, child, depth + 1)?;
+        update(board, Message::GoTo(current)).map_err(|error| error.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Writes the game played on the given [`Board`] as an SGF record, with the given [`GameInfo`].
+///
+/// Stones placed outside of play, such as handicap stones, are written as setup stones of the
+/// root node, followed by every move of the board's [`GameTree`] with its variations and
+/// comments. The captures of each player in the current position are noted in the game comment,
+/// as SGF has no property for them.
+///
+/// # Example
+///
+/// ```
+/// use go_board::sgf::{to_sgf, GameInfo};
+/// use go_board::{update, Board, Color, Message, Position};
+///
+/// let mut board = Board::new();
+/// update(&mut board, Message::Play(Color::Black, Position::new(15, 3))).unwrap();
+/// update(&mut board, Message::Pass).unwrap();
+///
+/// let info = GameInfo { komi: 6.5, ..GameInfo::default() };
+/// assert!(to_sgf(&board, &info).ends_with("KM[6.5]GC[Captures: Black 0, White 0];B[dd];W[])"));
+/// ```
+pub fn to_sgf(board: &Board, info: &GameInfo) -> String {
+    let size = board.size.to_u16();
+    let mut sgf = format!(
+        "(;GM[1]FF[4]CA[UTF-8]AP[Go_Engine:{}]SZ[{size}]KM[{}]",
+        env!("CARGO_PKG_VERSION"),
+        info.komi
+    );
+
+    // writing to a String cannot fail
+    if info.handicap > 0 {
+        let _ = write!(sgf, "HA[{}]", info.handicap);
+    }
+    let result = info.result.clone().or_else(|| board.result().map(|result| result.to_string()));
+    for (identifier, value) in [
+        ("PB", &info.black_player),
+        ("PW", &info.white_player),
+        ("RE", &result),
+    ] {
+        if let Some(value) = value {
+            let _ = write!(sgf, "{identifier}[{}]", escape_value(value));
+        }
+    }
+    let _ = write!(
+        sgf,
+        "GC[Captures: Black {}, White {}]",
+        board.black_captures, board.white_captures
+    );
+
+    // the moves and setup stones of the current line not in the tree lead up to its root
+    let tree = board.tree();
+    let node = |id: NodeId| tree.node(id).expect("nodes on a path are in the tree");
+    let path = tree.path(tree.current()).expect("the current node is in the tree");
+    let moves_in_tree = path.iter().filter(|id| node(**id).mov().is_some()).count();
+    let setup_in_tree: usize = path.iter().map(|id| node(*id).setup().len()).sum();
+
+    let root_setup = &board.setup[..board.setup.len() - setup_in_tree];
+    write_setup(&mut sgf, root_setup, size);
+    if !root_setup.is_empty() {
+        let first_to_play = board.moves.first().map_or(board.player_turn, |(color, _)| *color);
+        let _ = write!(sgf, "PL[{}]", color_identifier(first_to_play));
+    }
+
+    let root = node(tree.root());
+    if let Some(comment) = root.comment() {
+        let _ = write!(sgf, "C[{}]", escape_value(comment));
+    }
+
+    for (color, mov) in &board.moves[..board.moves.len() - moves_in_tree] {
+        write_move(&mut sgf, *color, *mov, size);
+    }
+    write_variations(&mut sgf, tree, tree.root(), size);
+
+    sgf.push(')');
+    sgf
+}
+
+/// Writes the nodes following the given node of the [`GameTree`] as SGF, each variation in
+/// parentheses after the main line continues past it.
+fn write_variations(sgf: &mut String, tree: &GameTree, id: NodeId, size: u16) {
+    let node = |id: NodeId| tree.node(id).expect("children are in the tree");
+    let mut id = id;
+    loop {
+        match node(id).children() {
+            [] => return,
+            [only] => {
+                write_node(sgf, node(*only), size);
+                id = *only;
+            }
+            variations => {
+                for child in variations {
+                    sgf.push('(');
+                    write_node(sgf, node(*child), size);
+                    write_variations(sgf, tree, *child, size);
+                    sgf.push(')');
+                }
+                return;
+            }
+        }
+    }
+}
+
+/// Writes the move or setup stones, and comment of the given [`GameNode`] as an SGF node.
+fn write_node(sgf: &mut String, node: &GameNode, size: u16) {
+    match node.mov() {
+        Some((color, mov)) => write_move(sgf, color, mov, size),
+        None => {
+            sgf.push(';');
+            write_setup(sgf, node.setup(), size);
+        }
+    }
+    if let Some(comment) = node.comment() {
+        let _ = write!(sgf, "C[{}]", escape_value(comment));
+    }
+}
+
+/// Writes the given setup stones as the `AB` and `AW` properties of an SGF node.
+fn write_setup(sgf: &mut String, stones: &[(Color, Position)], size: u16) {
+    for color in [Color::Black, Color::White] {
+        let mut stones = stones.iter().filter(|(stone, _)| *stone == color).peekable();
+        if stones.peek().is_some() {
+            sgf.push_str(match color {
+                Color::Black => "AB",
+                Color::White => "AW",
+            });
+            for (_, pos) in stones {
+                let _ = write!(sgf, "[{}]", to_coordinate(pos, size));
+            }
+        }
+    }
+}
+
+/// Writes a move of the given [`Color`], a [`Position`] played or a pass, as an SGF node.
+fn write_move(sgf: &mut String, color: Color, mov: Option<Position>, size: u16) {
+    let coordinate = mov.map_or(String::new(), |pos| to_coordinate(&pos, size));
+    let _ = write!(sgf, ";{}[{coordinate}]", color_identifier(color));
+}
+
+/// Writes the game played on the given [`Board`] as an SGF record to the file at the given
+/// path, as done by [`to_sgf`].
+pub fn write_sgf_file(path: impl AsRef<Path>, board: &Board, info: &GameInfo) -> io::Result<()> {
+    std::fs::write(path, to_sgf(board, info))
+}
+
+/// Parses the first game of the given SGF text into a [`GameRecord`], with every variation.
+///
+/// Moves, setup stones (`AB`, `AW`, including compressed rectangles such as `aa:cc`),
+/// comments, and the game information of [`GameInfo`] are read, and other properties are
+/// ignored. Outputs a [`String`] detailing why the text could not be parsed on failure.
+///
+/// # Example
+///
+/// ```
+/// use go_board::sgf::parse_sgf;
+///
+/// let record = parse_sgf("(;SZ[9]KM[7]AB[cc][gg];W[ee]C[center](;B[ec])(;B[ce]))").unwrap();
+/// assert_eq!(record.info.komi, 7.0);
+/// assert_eq!(record.root.setup.len(), 2);
+/// assert_eq!(record.main_line().len(), 3);
+/// assert_eq!(record.root.children[0].comment.as_deref(), Some("center"));
+/// assert_eq!(record.root.children[0].children.len(), 2);
+/// ```
+pub fn parse_sgf(sgf: &str) -> Result<GameRecord, String> {
+    let mut parser = Parser {
+        chars: sgf.chars().peekable(),
+    };
+    parser.skip_whitespace();
+    if parser.chars.peek() != Some(&'(') {
+        return Err("SGF does not begin with a game tree".to_string());
+    }
+    let tree = parser.game_tree()?;
+
+    let mut record = GameRecord {
+        size: BoardSize::Nineteen,
+        info: GameInfo::default(),
+        root: SgfNode::default(),
+    };
+    for (identifier, values) in &tree.properties {
+        let value = values[0].as_str();
+        match identifier.as_str() {
+            "SZ" => {
+                record.size = match value {
+                    "9" => BoardSize::Nine,
+                    "13" => BoardSize::Thirteen,
+                    "19" => BoardSize::Nineteen,
+                    _ => return Err(format!("Unsupported SGF board size: {value}")),
+                }
+            }
+            "KM" => record.info.komi = value.parse().map_err(|_| format!("Invalid SGF komi: {value}"))?,
+            "HA" => record.info.handicap = value.parse().map_err(|_| format!("Invalid SGF handicap: {value}"))?,
+            "PB" => record.info.black_player = Some(value.to_string()),
+            "PW" => record.info.white_player = Some(value.to_string()),
+            "RE" => record.info.result = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    record.root = tree.into_node(record.size.to_u16())?;
+    Ok(record)
+}
+
+/// The properties of a node as read from SGF text, each an identifier and its values.
+type Properties = Vec<(String, Vec<String>)>;
+
+/// A game tree as read from SGF text, before its properties are interpreted.
+struct RawTree {
+    properties: Properties,
+    children: Vec<RawTree>,
+}
+
+impl RawTree {
+    /// Interprets the properties of every node of this tree on a board of the given size.
+    fn into_node(self, size: u16) -> Result<SgfNode, String> {
+        let mut node = SgfNode::default();
+        for (identifier, values) in self.properties {
+            match identifier.as_str() {
+                "B" | "W" => {
+                    let color = if identifier == "B" { Color::Black } else { Color::White };
+                    node.mov = Some((color, from_coordinate(&values[0], size)?));
+                }
+                "AB" | "AW" => {
+                    let color = if identifier == "AB" { Color::Black } else { Color::White };
+                    for value in &values {
+                        for pos in point_list(value, size)? {
+                            node.setup.push((color, pos));
+                        }
+                    }
+                }
+                "C" => node.comment = Some(values[0].clone()),
+                _ => {} // unsupported properties are ignored
+            }
+        }
+
+        node.children = self
+            .children
+            .into_iter()
+            .map(|child| child.into_node(size))
+            .collect::<Result<_, _>>()?;
+        Ok(node)
+    }
+}
+
+/// Reads the structure of SGF text one character at a time.
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl Parser<'_> {
+    /// Skips any whitespace before the next character.
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    /// Reads a game tree, `(` followed by a sequence of nodes and any variations then `)`,
+    /// as a chain of [`RawTree`]s, one per node of the sequence.
+    fn game_tree(&mut self) -> Result<RawTree, String> {
+        self.chars.next(); // the opening '(' was peeked by the caller
+        let mut sequence: Vec<Properties> = vec![];
+        let mut variations: Vec<RawTree> = vec![];
+
+        loop {
+            self.skip_whitespace();
+            match self.chars.peek() {
+                Some(';') if variations.is_empty() => {
+                    self.chars.next();
+                    sequence.push(self.properties()?);
+                }
+                Some('(') => variations.push(self.game_tree()?),
+                Some(')') => {
+                    self.chars.next();
+                    break;
+                }
+                Some(c) => return Err(format!("Unexpected character in SGF: {c}")),
+                None => return Err("SGF game tree is not closed".to_string()),
+            }
+        }
+
+        // each node of the sequence has the next as its only child, and the last the variations
+        let mut children = variations;
+        for properties in sequence.into_iter().rev() {
+            children = vec![RawTree { properties, children }];
+        }
+        children.pop().ok_or("SGF game tree has no nodes".to_string())
+    }
+
+    /// Reads the properties of a node, up to the next node or game tree.
+    fn properties(&mut self) -> Result<Properties, String> {
+        let mut properties: Properties = vec![];
+        loop {
+            self.skip_whitespace();
+            let mut identifier = String::new();
+            while let Some(c) = self.chars.next_if(|c| c.is_ascii_alphabetic()) {
+                if c.is_ascii_uppercase() {
+                    identifier.push(c); // lowercase is ignored in old SGF identifiers
+                }
+            }
+
+            self.skip_whitespace();
+            if self.chars.peek() != Some(&'[') {
+                if identifier.is_empty() {
+                    return Ok(properties);
+                }
+                return Err(format!("SGF property {identifier} has no value"));
+            }
+            if identifier.is_empty() {
+                return Err("SGF property value without an identifier".to_string());
+            }
+
+            let mut values: Vec<String> = vec![];
+            while self.chars.next_if_eq(&'[').is_some() {
+                values.push(self.value()?);
+                self.skip_whitespace();
+            }
+            properties.push((identifier, values));
+        }
+    }
+
+    /// Reads a property value after its `[`, up to and including the closing `]`.
+    fn value(&mut self) -> Result<String, String> {
+        let mut value = String::new();
+        loop {
+            match self.chars.next() {
+                Some('\\') => value.extend(self.chars.next()),
+                Some(']') => return Ok(value),
+                Some(c) => value.push(c),
+                None => return Err("Unterminated SGF property value".to_string()),
+            }
+        }
+    }
+}
+
+/// Converts the given SGF coordinate on a board of the given size to its [`Position`], or
+/// [`None`] for a pass, written as an empty coordinate or `tt`.
+fn from_coordinate(coordinate: &str, size: u16) -> Result<Option<Position>, String> {
+    if coordinate.is_empty() || (coordinate == "tt" && size <= 19) {
+        return Ok(None);
+    }
+
+    let bytes = coordinate.as_bytes();
+    if bytes.len() != 2 || !bytes.iter().all(|byte| byte.is_ascii_lowercase()) {
+        return Err(format!("Invalid SGF coordinate: {coordinate}"));
+    }
+    let (col, row_from_top) = ((bytes[0] - b'a') as u16, (bytes[1] - b'a') as u16);
+    if col >= size || row_from_top >= size {
+        return Err(format!("SGF coordinate out of bounds: {coordinate}"));
+    }
+
+    Ok(Some(Position::new(size - 1 - row_from_top, col)))
+}
+
+/// Converts the given SGF point, or compressed rectangle of points such as `aa:cc`, on a board
+/// of the given size to its [`Position`]s.
+fn point_list(value: &str, size: u16) -> Result<Vec<Position>, String> {
+    let invalid = || format!("Invalid SGF point: {value}");
+    let (first, last) = value.split_once(':').unwrap_or((value, value));
+    let first = from_coordinate(first, size)?.ok_or_else(invalid)?;
+    let last = from_coordinate(last, size)?.ok_or_else(invalid)?;
+
+    let rows = first.row.min(last.row)..=first.row.max(last.row);
+    let cols = first.col.min(last.col)..=first.col.max(last.col);
+    Ok(rows
+        .flat_map(|row| cols.clone().map(move |col| Position::new(row, col)))
+        .collect())
+}
+
+/// The SGF identifier of moves and setup stones of the given [`Color`].
+fn color_identifier(color: Color) -> &'static str {
+    match color {
+        Color::Black => "B",
+        Color::White => "W",
+    }
+}
+
+/// Converts the given [`Position`] to its SGF coordinate on a board of the given size, which
+/// counts rows from the top.
+fn to_coordinate(pos: &Position, size: u16) -> String {
+    let column = (b'a' + pos.col as u8) as char;
+    let row = (b'a' + (size - 1 - pos.row) as u8) as char;
+    format!("{column}{row}")
+}
+
+/// Escapes the characters of the given text that are special inside an SGF property value.
+fn escape_value(text: &str) -> String {
+    text.replace('\\', "\\\\").replace(']', "\\]")
+}
@@ -0,0 +1,176 @@
+//! Property-based testing support for the rules of Go.
+//!
+//! Enabled with the `proptest` feature. Provides [`proptest`] strategies and [`Arbitrary`]
+//! implementations for the types of this crate, and invariant checks that any position reached
+//! by legal play must satisfy, so the rules can be fuzzed against themselves.
+//!
+//! ```
+//! use go_board::testing::{check_liberties, Game};
+//! use proptest::prelude::*;
+//!
+//! proptest!(|(game: Game)| {
+//!     prop_assert!(check_liberties(&game.board()).is_ok());
+//! });
+//! ```
+
+use crate::board::{zobrist_key, Board, BoardSize, Color, State};
+use crate::groups;
+use crate::update::{update, Message, Position};
+use proptest::prelude::*;
+use proptest::{collection, option};
+
+/// The largest number of moves attempted by the [`Arbitrary`] implementation of [`Game`].
+pub const DEFAULT_MAX_MOVES: usize = 120;
+
+/// A game of legal moves, alternating turns starting with [`Color::Black`].
+#[derive(Clone, Debug)]
+pub struct Game {
+    /// The size of the board the game is played on.
+    pub size: BoardSize,
+    /// The moves of the game in order, each a [`Position`] played or a pass.
+    pub moves: Vec<(Color, Option<Position>)>,
+}
+
+impl Game {
+    /// Replays this game on a new [`Board`].
+    pub fn board(&self) -> Board {
+        let mut board = empty_board(self.size);
+        for (color, mov) in &self.moves {
+            let msg = match mov {
+                Some(pos) => Message::Play(*color, *pos),
+                None => Message::Pass,
+            };
+            update(&mut board, msg).expect("games only contain legal moves");
+        }
+
+        board
+    }
+
+    /// This game with the [`Color`] of every move swapped.
+    pub fn swap_colors(&self) -> Game {
+        Game {
+            size: self.size,
+            moves: self
+                .moves
+                .iter()
+                .map(|(color, mov)| (color.opposite_color(), *mov))
+                .collect(),
+        }
+    }
+}
+
+/// Creates a new empty [`Board`] of [`BoardSize`].
+fn empty_board(size: BoardSize) -> Board {
+    Board::builder().size(size).build().expect("an empty board is always valid")
+}
+
+/// A strategy generating every [`Color`].
+pub fn color() -> impl Strategy<Value = Color> {
+    prop_oneof![Just(Color::Black), Just(Color::White)]
+}
+
+/// A strategy generating every [`BoardSize`].
+pub fn board_size() -> impl Strategy<Value = BoardSize> {
+    prop_oneof![
+        Just(BoardSize::Nine),
+        Just(BoardSize::Thirteen),
+        Just(BoardSize::Nineteen),
+    ]
+}
+
+/// A strategy generating every [`Position`] on a board of [`BoardSize`].
+pub fn position(size: BoardSize) -> impl Strategy<Value = Position> {
+    let numeric_size = size.to_u16();
+    (0..numeric_size, 0..numeric_size).prop_map(|(row, col)| Position::new(row, col))
+}
+
+/// A strategy generating [`Game`]s of up to the given number of moves.
+///
+/// Moves are drawn at random, with occasional passes, and any that are illegal when their turn
+/// comes are skipped.
+pub fn game(max_moves: usize) -> impl Strategy<Value = Game> {
+    board_size().prop_flat_map(move |size| {
+        collection::vec(option::weighted(0.95, position(size)), 0..=max_moves).prop_map(
+            move |candidates| {
+                let mut board = empty_board(size);
+                let mut moves = vec![];
+                for mov in candidates {
+                    let color = board.player_turn();
+                    let msg = match mov {
+                        Some(pos) => Message::Play(color, pos),
+                        None => Message::Pass,
+                    };
+                    if update(&mut board, msg).is_ok() {
+                        moves.push((color, mov));
+                    }
+                }
+
+                Game { size, moves }
+            },
+        )
+    })
+}
+
+impl Arbitrary for Color {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Color>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        color().boxed()
+    }
+}
+
+impl Arbitrary for BoardSize {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<BoardSize>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        board_size().boxed()
+    }
+}
+
+impl Arbitrary for Game {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Game>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        game(DEFAULT_MAX_MOVES).boxed()
+    }
+}
+
+/// Checks that every group of stones on the [`Board`] has at least one liberty.
+///
+/// Outputs a [`String`] naming the first group without liberties on failure.
+pub fn check_liberties(board: &Board) -> Result<(), String> {
+    for (index, state) in board.board.iter().enumerate() {
+        if let State::Occupied(color) = state {
+            if groups::find_group(index, color, &board.board, &board.size)
+                .liberties
+                .is_empty()
+            {
+                return Err(format!("Group at index {index} has no liberties"));
+            }
+        }
+    }
+    if let Some(group) = board.groups().find(|group| group.liberties.is_empty()) {
+        return Err(format!("Board::groups gives a group without liberties: {group:?}"));
+    }
+
+    Ok(())
+}
+
+/// Checks that the hash the [`Board`] keeps in step with its moves matches the hash of its stones.
+///
+/// Outputs a [`String`] with both hashes on failure.
+pub fn check_position_hash(board: &Board) -> Result<(), String> {
+    let expected = board
+        .board
+        .iter()
+        .enumerate()
+        .fold(0, |hash, (index, state)| hash ^ zobrist_key(index, *state));
+    if board.hash() != expected {
+        return Err(format!("Board hash {} is not the hash of its stones {expected}", board.hash()));
+    }
+
+    Ok(())
+}
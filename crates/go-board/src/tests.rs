@@ -0,0 +1,224 @@
+//! Property-based tests of the rules of Go, using the strategies of [`crate::testing`].
+
+use crate::testing::*;
+use proptest::prelude::*;
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn played_groups_keep_liberties(game: Game) {
+        prop_assert_eq!(check_liberties(&game.board()), Ok(()));
+    }
+
+    #[test]
+    fn position_hash_follows_stones(game: Game) {
+        prop_assert_eq!(check_position_hash(&game.board()), Ok(()));
+    }
+
+    #[test]
+    fn move_effects_report_captures(game: Game) {
+        use crate::board::{Board, Color, State};
+        use crate::update::{update, Message};
+
+        let mut board = Board::builder().size(game.size).build().unwrap();
+        let captures = |board: &Board| board.captures(Color::Black) + board.captures(Color::White);
+        for (color, mov) in &game.moves {
+            let before = captures(&board);
+            let msg = match mov {
+                Some(pos) => Message::Play(*color, *pos),
+                None => Message::Pass,
+            };
+            let Ok(Message::Played(effects)) = update(&mut board, msg) else {
+                return Err(TestCaseError::fail("a legal move was not reported as played"));
+            };
+            prop_assert_eq!(effects.captured.len() as u16, captures(&board) - before);
+            prop_assert!(effects.captured.iter().all(|pos| board.state(*pos) == Some(State::Empty)));
+            prop_assert_eq!(effects.ko, board.ko());
+            prop_assert_eq!(effects.result, board.result());
+        }
+    }
+
+    #[test]
+    fn try_play_matches_update(
+        (game, pos) in any::<Game>().prop_flat_map(|game| {
+            let size = game.size;
+            (Just(game), position(size))
+        })
+    ) {
+        use crate::board::Board;
+        use crate::update::{update, Message};
+
+        let board = game.board();
+        let color = board.player_turn();
+        let summary = |board: &Board| (board.to_string(), board.hash(), board.moves().to_vec());
+        let mut updated = board.clone();
+        let expected = update(&mut updated, Message::Play(color, pos)).map(|_| summary(&updated));
+        let hash = board.hash();
+        prop_assert_eq!(board.try_play(color, pos).map(|after| summary(&after)), expected);
+        prop_assert_eq!(board.hash(), hash);
+    }
+
+    #[test]
+    fn vertices_round_trip(
+        (size, pos) in board_size().prop_flat_map(|size| (Just(size), position(size)))
+    ) {
+        use crate::update::Position;
+
+        let vertex = pos.to_string();
+        prop_assert_eq!(Position::parse_vertex(&vertex, size), Ok(Some(pos)));
+        prop_assert_eq!(Position::parse_vertex(&vertex.to_lowercase(), size), Ok(Some(pos)));
+    }
+
+    #[test]
+    fn off_board_positions_are_rejected(size in board_size(), row in 0u16..19, col in 0u16..19) {
+        use crate::board::{Board, Color};
+        use crate::update::{update, Message, Position, RuleViolation};
+
+        let edge = size.to_u16();
+        prop_assume!(row >= edge || col >= edge);
+        let pos = Position::new(row, col);
+        let mut board = Board::builder().size(size).build().unwrap();
+        let played = update(&mut board, Message::Play(Color::Black, pos));
+        prop_assert_eq!(played.err(), Some(RuleViolation::OutOfBounds));
+        let placed = update(&mut board, Message::PlaceStone(Color::Black, pos));
+        prop_assert_eq!(placed.err(), Some(RuleViolation::OutOfBounds));
+        prop_assert_eq!(board.hash(), 0);
+    }
+
+    #[test]
+    fn builder_rebuilds_positions(game: Game) {
+        use crate::board::{Board, State};
+
+        let board = game.board();
+        let stones = board.intersections().filter_map(|(pos, state)| match state {
+            State::Occupied(color) => Some((color, pos)),
+            _ => None,
+        });
+        let built = Board::builder()
+            .size(game.size)
+            .stones(stones)
+            .to_play(board.player_turn())
+            .build()
+            .unwrap();
+        prop_assert_eq!(built.hash(), board.hash());
+        prop_assert_eq!(built.player_turn(), board.player_turn());
+    }
+
+    #[test]
+    fn builder_places_handicap_stones(size in board_size(), stones in 2u16..=9) {
+        use crate::board::{Board, Color};
+
+        let board = Board::builder().size(size).handicap(stones).build().unwrap();
+        prop_assert_eq!(board.setup().len(), stones as usize);
+        prop_assert!(board.setup().iter().all(|(color, _)| *color == Color::Black));
+        prop_assert_eq!(board.player_turn(), Color::White);
+    }
+
+    #[test]
+    fn sgf_records_every_move(game: Game) {
+        let sgf = crate::sgf::to_sgf(&game.board(), &crate::sgf::GameInfo::default());
+        prop_assert_eq!(sgf.matches(";B[").count() + sgf.matches(";W[").count(), game.moves.len());
+    }
+
+    #[test]
+    fn sgf_round_trips(game: Game) {
+        let board = game.board();
+        let sgf = crate::sgf::to_sgf(&board, &crate::sgf::GameInfo::default());
+        let replayed = crate::sgf::parse_sgf(&sgf).and_then(|record| record.board());
+        prop_assert_eq!(replayed.map(|replayed| replayed.to_string()), Ok(board.to_string()));
+    }
+
+    #[test]
+    fn sgf_round_trips_variations(game: Game) {
+        use crate::sgf::{parse_sgf, to_sgf, GameInfo};
+        use crate::update::{update, Message};
+
+        // a pass from halfway through the game branches off the main line
+        let mut board = game.board();
+        for _ in 0..game.moves.len() / 2 {
+            update(&mut board, Message::Undo).unwrap();
+        }
+        update(&mut board, Message::Pass).unwrap();
+        let main_line_end = board.tree().main_line().pop().unwrap();
+        update(&mut board, Message::GoTo(main_line_end)).unwrap();
+
+        let sgf = to_sgf(&board, &GameInfo::default());
+        let replayed = parse_sgf(&sgf).and_then(|record| record.board());
+        prop_assert_eq!(replayed.map(|replayed| to_sgf(&replayed, &GameInfo::default())), Ok(sgf));
+    }
+}
+
+#[test]
+fn off_board_positions_do_not_wrap() {
+    use crate::board::{Board, BoardSize, Color};
+    use crate::update::{update, Message, Position, RuleViolation};
+
+    // past the right edge, which once wrapped to the next row, and past the top right corner,
+    // which once indexed past the end of the board
+    for pos in [Position::new(1, 11), Position::new(18, 18)] {
+        let mut board = Board::builder().size(BoardSize::Nine).build().unwrap();
+        let played = update(&mut board, Message::Play(Color::Black, pos));
+        assert_eq!(played.err(), Some(RuleViolation::OutOfBounds));
+        assert_eq!(board.stones(Color::Black).count(), 0);
+    }
+    assert_eq!(Position::try_new(0, 80), None);
+}
+
+#[test]
+fn builder_rejects_invalid_positions() {
+    use crate::board::{Board, Color};
+    use crate::builder::BuildError;
+    use crate::update::{Position, RuleViolation};
+
+    let corner = Position::new(0, 0);
+    let stacked = Board::builder().stones([(Color::Black, corner), (Color::White, corner)]);
+    assert_eq!(stacked.build().err(), Some(BuildError::Stone(corner, RuleViolation::Occupied)));
+
+    // a White stone in the corner surrounded by Black
+    let captured = [(Color::White, corner)]
+        .into_iter()
+        .chain([Position::new(1, 0), Position::new(0, 1)].map(|pos| (Color::Black, pos)));
+    let built = Board::builder().stones(captured).build();
+    assert_eq!(built.err(), Some(BuildError::NoLiberties(corner)));
+}
+
+#[test]
+fn vertices_have_a_single_spelling() {
+    use crate::update::{ParsePositionError, Position};
+
+    for vertex in ["A+1", "A01", "Q016", "A-1", "A 1", "A1 ", "A0", "A20", "A", ""] {
+        let parsed = vertex.parse::<Position>();
+        assert_eq!(parsed, Err(ParsePositionError::Invalid(vertex.to_string())), "{vertex}");
+    }
+}
+
+#[test]
+fn setup_stones_stay_in_their_variation() {
+    use crate::board::{Color, State};
+    use crate::sgf::{parse_sgf, to_sgf, GameInfo};
+    use crate::update::{update, Message, Position};
+
+    let sgf = "(;SZ[9](;B[ee];AW[cc];W[dd])(;B[ce]))";
+    let mut board = parse_sgf(sgf).and_then(|record| record.board()).unwrap();
+    let setup = Position::new(6, 2);
+    assert_eq!(board.state(setup), Some(State::Occupied(Color::White)));
+    assert_eq!(board.setup(), [(Color::White, setup)]);
+
+    // taking back the move after the setup stones, then the stones themselves
+    update(&mut board, Message::Undo).unwrap();
+    assert_eq!(board.state(setup), Some(State::Occupied(Color::White)));
+    update(&mut board, Message::Undo).unwrap();
+    assert_eq!(board.state(setup), Some(State::Empty));
+    assert!(board.setup().is_empty());
+
+    // the other variation never had the stones
+    let tree = board.tree();
+    let sibling = tree.node(tree.root()).unwrap().children()[1];
+    update(&mut board, Message::GoTo(sibling)).unwrap();
+    assert_eq!(board.state(setup), Some(State::Empty));
+
+    update(&mut board, Message::ReturnToMainLine).unwrap();
+    let written = to_sgf(&board, &GameInfo::default());
+    assert!(written.ends_with("(;B[ee];AW[cc];W[dd])(;B[ce]))"), "{written}");
+}
@@ -0,0 +1,218 @@
+//! The game tree of the moves played on a Go Board, with variations.
+//!
+//! Every move or pass played through [`update`](crate::update) adds a node to the [`GameTree`]
+//! of its [`Board`](crate::Board). Taking moves back with [`Message::Undo`](crate::Message::Undo)
+//! and playing different ones starts a variation, which [`Message::GoTo`](crate::Message::GoTo)
+//! and [`Message::ReturnToMainLine`](crate::Message::ReturnToMainLine) move between. Stones
+//! placed with [`Message::PlaceStone`](crate::Message::PlaceStone) after the game has started
+//! add a node of their own, which is taken back the same way.
+//!
+//! ```
+//! use go_board::{update, Board, Color, Message, Position};
+//!
+//! let mut board = Board::new();
+//! update(&mut board, Message::Play(Color::Black, Position::new(3, 3))).unwrap();
+//! update(&mut board, Message::Play(Color::White, Position::new(15, 15))).unwrap();
+//! update(&mut board, Message::Undo).unwrap();
+//! update(&mut board, Message::Play(Color::White, Position::new(15, 3))).unwrap();
+//!
+//! let tree = board.tree();
+//! let first_move = tree.node(tree.root()).unwrap().children()[0];
+//! assert_eq!(tree.node(first_move).unwrap().children().len(), 2);
+//!
+//! update(&mut board, Message::ReturnToMainLine).unwrap();
+//! update(&mut board, Message::Redo).unwrap();
+//! assert_eq!(board.moves()[1], (Color::White, Some(Position::new(15, 15))));
+//! ```
+
+use crate::board::{Color, Snapshot};
+use crate::update::Position;
+
+/// Identifies a node of a [`GameTree`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+/// A node of a [`GameTree`], the position after a move or after setup stones are placed, or, at
+/// the root, before the first move.
+#[derive(Clone)]
+pub struct GameNode {
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+    active_child: usize,
+    mov: Option<(Color, Option<Position>)>,
+    setup: Vec<(Color, Position)>,
+    comment: Option<String>,
+    pub(crate) position: Snapshot,
+}
+
+/// The moves played on a [`Board`](crate::Board) since it was last cleared, as a tree of
+/// positions whose first children continue the main line and whose others are variations.
+#[derive(Clone)]
+pub struct GameTree {
+    nodes: Vec<GameNode>,
+    current: NodeId,
+}
+
+impl GameNode {
+    /// The move leading to this node, a [`Position`] played or a pass, or [`None`] at the root
+    /// and at nodes of setup stones.
+    pub fn mov(&self) -> Option<(Color, Option<Position>)> {
+        self.mov
+    }
+
+    /// The stones placed outside of play leading to this node, empty unless it has no move and
+    /// is not the root.
+    pub fn setup(&self) -> &[(Color, Position)] {
+        &self.setup
+    }
+
+    /// The comment on this node, if any.
+    pub fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+
+    /// The node this one follows, or [`None`] at the root.
+    pub fn parent(&self) -> Option<NodeId> {
+        self.parent
+    }
+
+    /// The nodes following this one: the first continues the main line, and the others are
+    /// variations of it.
+    pub fn children(&self) -> &[NodeId] {
+        &self.children
+    }
+}
+
+impl GameTree {
+    /// Constructs a new [`GameTree`] whose root is the given position.
+    pub(crate) fn new(position: Snapshot) -> Self {
+        GameTree {
+            nodes: vec![GameNode {
+                parent: None,
+                children: vec![],
+                active_child: 0,
+                mov: None,
+                setup: vec![],
+                comment: None,
+                position,
+            }],
+            current: NodeId(0),
+        }
+    }
+
+    /// The root of this [`GameTree`], the position before its first move.
+    pub fn root(&self) -> NodeId {
+        NodeId(0)
+    }
+
+    /// The node of the current position of the [`Board`](crate::Board).
+    pub fn current(&self) -> NodeId {
+        self.current
+    }
+
+    /// The [`GameNode`] identified by the given [`NodeId`], if it is a node of this tree.
+    pub fn node(&self, id: NodeId) -> Option<&GameNode> {
+        self.nodes.get(id.0)
+    }
+
+    /// The nodes from the root to the given node, or [`None`] if it is not a node of this tree.
+    pub fn path(&self, id: NodeId) -> Option<Vec<NodeId>> {
+        let mut path = vec![id];
+        while let Some(parent) = self.node(path[path.len() - 1])?.parent {
+            path.push(parent);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// The nodes of the main line, from the root through the first child of every node.
+    pub fn main_line(&self) -> Vec<NodeId> {
+        let mut line = vec![self.root()];
+        while let Some(next) = self.nodes[line[line.len() - 1].0].children.first() {
+            line.push(*next);
+        }
+        line
+    }
+
+    /// The child of the current node last moved to, which [`Message::Redo`](crate::Message::Redo)
+    /// returns to.
+    pub(crate) fn active_child(&self) -> Option<NodeId> {
+        let node = &self.nodes[self.current.0];
+        node.children.get(node.active_child).copied()
+    }
+
+    /// Moves to the child of the current node with the given move, adding it with the given
+    /// position after the move if the move has not been played from here before.
+    pub(crate) fn play(&mut self, mov: (Color, Option<Position>), position: Snapshot) -> NodeId {
+        let current = self.current;
+        let existing = self.nodes[current.0]
+            .children
+            .iter()
+            .find(|child| self.nodes[child.0].mov == Some(mov))
+            .copied();
+
+        let id = existing.unwrap_or_else(|| {
+            let id = NodeId(self.nodes.len());
+            self.nodes.push(GameNode {
+                parent: Some(current),
+                children: vec![],
+                active_child: 0,
+                mov: Some(mov),
+                setup: vec![],
+                comment: None,
+                position,
+            });
+            self.nodes[current.0].children.push(id);
+            id
+        });
+        self.set_current(id);
+        id
+    }
+
+    /// Adds the given setup stone to the current node if it is a node of setup stones without
+    /// children, or else to a new child of it, with the given position after the stone is placed.
+    pub(crate) fn place(&mut self, stone: (Color, Position), position: Snapshot) -> NodeId {
+        let current = &mut self.nodes[self.current.0];
+        if current.parent.is_some() && current.mov.is_none() && current.children.is_empty() {
+            current.setup.push(stone);
+            current.position = position;
+            return self.current;
+        }
+
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(GameNode {
+            parent: Some(self.current),
+            children: vec![],
+            active_child: 0,
+            mov: None,
+            setup: vec![stone],
+            comment: None,
+            position,
+        });
+        self.nodes[self.current.0].children.push(id);
+        self.set_current(id);
+        id
+    }
+
+    /// Makes the given node of this tree current, remembering the way to it for
+    /// [`Message::Redo`](crate::Message::Redo).
+    pub(crate) fn set_current(&mut self, id: NodeId) {
+        let mut child = id;
+        while let Some(parent) = self.nodes[child.0].parent {
+            let index = self.nodes[parent.0].children.iter().position(|c| *c == child);
+            self.nodes[parent.0].active_child = index.expect("children are listed by their parent");
+            child = parent;
+        }
+        self.current = id;
+    }
+
+    /// Makes [`Message::Redo`](crate::Message::Redo) continue the main line from the current node.
+    pub(crate) fn follow_main_line(&mut self) {
+        self.nodes[self.current.0].active_child = 0;
+    }
+
+    /// Sets the comment on the current node, removing it if given [`None`].
+    pub(crate) fn set_comment(&mut self, comment: Option<String>) {
+        self.nodes[self.current.0].comment = comment;
+    }
+}
@@ -0,0 +1,502 @@
+//! Logic that updates the Go board depending on the received command.
+
+use crate::board::{init_board, Board, BoardSize, Color, GameResult, KoRule, Ruleset, Snapshot, State};
+use crate::board::COLUMN_LETTERS;
+use crate::groups;
+use crate::tree::{GameTree, NodeId};
+use std::fmt;
+use std::str::FromStr;
+
+/// Requests that can be sent to [`update`] which alter the state of the program.
+pub enum Message {
+    /// An empty message.
+    None,
+    /// Plays a stone of [`Color`] at the given [`Position`] if it follows the rules of Go.
+    Play(Color, Position),
+    /// Places a stone of [`Color`] at the given [`Position`] without considering turn or capture logic.
+    ///
+    /// Before the first move, the stone is part of the starting position of the [`GameTree`].
+    /// After it, the stone is placed in a node of the tree, which [`Message::Undo`] takes back.
+    PlaceStone(Color, Position),
+    /// Passes the current player's turn. The game ends when both players pass in a row.
+    Pass,
+    /// Resigns the game for the current player.
+    Resign,
+    /// Output by [`update`] after a [`Message::Play`], [`Message::Pass`], or [`Message::Resign`],
+    /// with the [`MoveEffects`] it had on the board.
+    Played(MoveEffects),
+    /// Clears the current board state.
+    Clear,
+    /// Sets the size of the board to [`BoardSize`].
+    SetSize(BoardSize),
+    /// Sets the [`Ruleset`] later moves are played and the game is scored under, along with its
+    /// [`KoRule`].
+    SetRuleset(Ruleset),
+    /// Sets the [`KoRule`] later moves are played under, in place of the one of the [`Ruleset`].
+    SetKoRule(KoRule),
+    /// Sets the komi given to White when the game is scored.
+    SetKomi(f64),
+    /// Takes back a resignation, or else the last move or pass, restoring the position before it.
+    Undo,
+    /// Replays the move or pass last taken back by [`Message::Undo`] from the current position.
+    Redo,
+    /// Moves to the position of the given node of the board's [`GameTree`].
+    GoTo(NodeId),
+    /// Moves back along the current variation to the position where it leaves the main line,
+    /// from which [`Message::Redo`] continues the main line.
+    ReturnToMainLine,
+    /// Sets the comment on the current position of the board's [`GameTree`], or removes it if
+    /// given [`None`].
+    Comment(Option<String>),
+}
+
+/// What a move, pass, or resignation changed on the [`Board`], for front ends to animate and
+/// servers to relay without comparing positions.
+///
+/// ```
+/// use go_board::{update, Board, Color, Message, Position};
+///
+/// let corner = [(Color::White, Position::new(0, 0)), (Color::Black, Position::new(0, 1))];
+/// let mut board = Board::builder().stones(corner).build().unwrap();
+/// let played = update(&mut board, Message::Play(Color::Black, Position::new(1, 0)));
+/// let Ok(Message::Played(effects)) = played else { panic!("the move is legal") };
+/// assert_eq!(effects.captured, vec![Position::new(0, 0)]);
+/// assert_eq!(effects.result, None);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MoveEffects {
+    /// The stones removed from the board: those of the opponent captured by the move, or the
+    /// group of the player itself when the [`Ruleset`] allows suicide.
+    pub captured: Vec<Position>,
+    /// The [`Position`] the opponent may not play at next because it would retake a ko, if any.
+    pub ko: Option<Position>,
+    /// The [`GameResult`] if this ended the game, after two passes in a row or a resignation.
+    pub result: Option<GameResult>,
+}
+
+/// The reasons [`update`] rejects a [`Message`], leaving the [`Board`] unchanged.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum RuleViolation {
+    /// A stone was placed at an intersection already holding one.
+    #[error("Cannot place stone at occupied intersection")]
+    Occupied,
+    /// A stone was played at the intersection where it would retake a ko.
+    #[error("Placing a stone at this intersection violates the rule of ko")]
+    Ko,
+    /// A stone was played that would repeat an earlier position under
+    /// [`KoRule::PositionalSuperko`].
+    #[error("Placing a stone at this intersection repeats an earlier position")]
+    Superko,
+    /// A stone was played that would leave its own group without liberties, which the
+    /// [`Ruleset`] does not allow.
+    #[error("Placing a stone at this intersection is suicidal")]
+    Suicide,
+    /// A stone was played by the player whose turn it is not.
+    #[error("Playing this move violates the turn order")]
+    OutOfTurn,
+    /// A stone was placed at a [`Position`] off the board.
+    #[error("Intersection is out of bounds for current boardsize")]
+    OutOfBounds,
+    /// A move, pass, or resignation was made after the game ended.
+    #[error("The game is over")]
+    GameOver,
+    /// [`Message::Undo`] was sent at the start of the [`GameTree`].
+    #[error("There is no move to undo")]
+    NothingToUndo,
+    /// [`Message::Redo`] was sent with no move to replay from the current position.
+    #[error("There is no move to redo")]
+    NothingToRedo,
+    /// [`Message::GoTo`] was sent a node that is not in the [`GameTree`].
+    #[error("There is no such node in the game tree")]
+    NoSuchNode,
+}
+
+/// Updates the given [`Board`] according to the [`Message`] received.
+///
+/// Outputs the next requested [`Message`] on a successful operation, or the [`RuleViolation`]
+/// the operation was rejected for.
+///
+/// ```
+/// use go_board::{update, Board, Color, Message, Position, RuleViolation};
+///
+/// let mut board = Board::new();
+/// update(&mut board, Message::Play(Color::Black, Position::new(3, 3))).unwrap();
+/// let retry = update(&mut board, Message::Play(Color::White, Position::new(3, 3)));
+/// assert_eq!(retry.err(), Some(RuleViolation::Occupied));
+/// ```
+pub fn update(board: &mut Board, msg: Message) -> Result<Message, RuleViolation> {
+    match msg {
+        Message::None => Ok(Message::None),
+        Message::Play(..) | Message::Pass | Message::Resign if board.result().is_some() => {
+            Err(RuleViolation::GameOver)
+        }
+        Message::Play(color, pos) => {
+            if board.player_turn != color {
+                return Err(RuleViolation::OutOfTurn);
+            }
+
+            let index = pos.to_board_index(&board.size);
+            if index.is_some() && index == board.ko {
+                return Err(RuleViolation::Ko);
+            }
+
+            let before = Snapshot::of(board);
+            place_stone(board, color, pos)?;
+            let index = index.expect("placed stones are on the board");
+            let mut captured = board.attempt_captures(index, &color);
+            let group = groups::find_group(index, &color, &board.board, &board.size);
+            if group.liberties.is_empty() {
+                if !board.ruleset.allows_suicide() || group.stones.len() == 1 {
+                    before.restore(board);
+                    return Err(RuleViolation::Suicide);
+                }
+                // the ruleset lets a group of several stones capture itself
+                group.stones.iter().for_each(|index| board.set_state(*index, State::Empty));
+                match color {
+                    Color::Black => board.white_captures += group.stones.len() as u16,
+                    Color::White => board.black_captures += group.stones.len() as u16,
+                }
+                captured.extend(group.stones);
+            }
+            if board.ko_rule == KoRule::PositionalSuperko && board.history.contains(&board.hash) {
+                before.restore(board);
+                return Err(RuleViolation::Superko);
+            }
+
+            board.history.push(before.hash());
+            board.player_turn = board.player_turn.opposite_color();
+            board.move_number += 1;
+            board.moves.push((color, Some(pos)));
+            let after = Snapshot::of(board);
+            board.tree.play((color, Some(pos)), after);
+            let size = board.size;
+            Ok(Message::Played(MoveEffects {
+                captured: captured.into_iter().map(|index| Position::from_board_index(index, &size)).collect(),
+                ko: board.ko(),
+                result: None,
+            }))
+        }
+        Message::PlaceStone(color, pos) => {
+            let tree = &board.tree;
+            let started = tree.node(tree.root()).is_some_and(|root| !root.children().is_empty());
+            if tree.current() != tree.root() || started {
+                return place_setup_stone(board, color, pos);
+            }
+
+            let placed = place_stone(board, color, pos);
+            if placed.is_ok() {
+                // the game has not started, so the tree starts anew from this position
+                board.setup.push((color, pos));
+                board.tree = GameTree::new(Snapshot::of(board));
+            }
+            placed
+        }
+        Message::Pass => {
+            let mov = (board.player_turn, None);
+            board.moves.push(mov);
+            board.player_turn = board.player_turn.opposite_color();
+            board.move_number += 1;
+            board.ko = None;
+            let after = Snapshot::of(board);
+            board.tree.play(mov, after);
+            Ok(Message::Played(MoveEffects { result: board.result(), ..MoveEffects::default() }))
+        }
+        Message::Resign => {
+            board.resigned = Some(board.player_turn);
+            let result = GameResult::Resignation(board.player_turn.opposite_color());
+            Ok(Message::Played(MoveEffects { result: Some(result), ..MoveEffects::default() }))
+        }
+        Message::Played(_) => Ok(Message::None),
+        Message::Clear => {
+            board.board = init_board(&board.size);
+            board.hash = 0;
+            board.history.clear();
+            board.ko = None;
+            board.black_captures = 0;
+            board.white_captures = 0;
+            board.moves.clear();
+            board.setup.clear();
+            board.resigned = None;
+            board.tree = GameTree::new(Snapshot::of(board));
+            Ok(Message::None)
+        }
+        Message::SetSize(size) => {
+            board.size = size;
+            update(board, Message::Clear)
+        }
+        Message::SetRuleset(ruleset) => {
+            board.ruleset = ruleset;
+            board.ko_rule = ruleset.ko_rule();
+            Ok(Message::None)
+        }
+        Message::SetKoRule(ko_rule) => {
+            board.ko_rule = ko_rule;
+            Ok(Message::None)
+        }
+        Message::SetKomi(komi) => {
+            board.komi = komi;
+            Ok(Message::None)
+        }
+        Message::Undo if board.resigned.is_some() => {
+            board.resigned = None;
+            Ok(Message::None)
+        }
+        Message::Undo => {
+            let current = board.tree.node(board.tree.current()).expect("the current node is in the tree");
+            let parent = current.parent().ok_or(RuleViolation::NothingToUndo)?;
+            go_to(board, parent)
+        }
+        Message::Redo => {
+            let child = board.tree.active_child().ok_or(RuleViolation::NothingToRedo)?;
+            go_to(board, child)
+        }
+        Message::GoTo(id) => go_to(board, id),
+        Message::ReturnToMainLine => {
+            let path = board.tree.path(board.tree.current()).expect("the current node is in the tree");
+            let main_line = board.tree.main_line();
+            let shared = path.iter().zip(&main_line).take_while(|(a, b)| a == b).count();
+            go_to(board, path[shared - 1])?;
+            board.tree.follow_main_line();
+            Ok(Message::None)
+        }
+        Message::Comment(comment) => {
+            board.tree.set_comment(comment);
+            Ok(Message::None)
+        }
+    }
+}
+
+/// Moves the [`Board`] to the position of the given node of its [`GameTree`], taking back the
+/// moves of the current variation down to where it meets the way to the node.
+fn go_to(board: &mut Board, target: NodeId) -> Result<Message, RuleViolation> {
+    let tree = &board.tree;
+    let path = tree.path(target).ok_or(RuleViolation::NoSuchNode)?;
+    let current = tree.path(tree.current()).expect("the current node is in the tree");
+    let shared = path.iter().zip(&current).take_while(|(a, b)| a == b).count();
+
+    let node = |id: NodeId| tree.node(id).expect("nodes on a path are in the tree");
+    for id in current[shared..].iter().rev() {
+        match node(*id).mov() {
+            Some((_, mov)) => {
+                board.moves.pop();
+                if mov.is_some() {
+                    board.history.pop();
+                }
+            }
+            None => board.setup.truncate(board.setup.len() - node(*id).setup().len()),
+        }
+    }
+    for step in path[shared - 1..].windows(2) {
+        match node(step[1]).mov() {
+            Some(mov) => {
+                board.moves.push(mov);
+                if mov.1.is_some() {
+                    board.history.push(node(step[0]).position.hash());
+                }
+            }
+            None => board.setup.extend_from_slice(node(step[1]).setup()),
+        }
+    }
+
+    let position = node(target).position.clone();
+    board.resigned = None;
+    board.tree.set_current(target);
+    position.restore(board);
+    Ok(Message::None)
+}
+
+/// Places a stone of [`Color`] at the given [`Position`] as a node of setup stones of the
+/// [`GameTree`], so that taking the node back removes it, however far the game has gone.
+pub(crate) fn place_setup_stone(
+    board: &mut Board,
+    color: Color,
+    pos: Position,
+) -> Result<Message, RuleViolation> {
+    let placed = place_stone(board, color, pos);
+    if placed.is_ok() {
+        board.setup.push((color, pos));
+        let after = Snapshot::of(board);
+        board.tree.place((color, pos), after);
+    }
+    placed
+}
+
+/// Places a stone of [`Color`] at the given [`Position`] without considering turn or capture logic.
+fn place_stone(board: &mut Board, color: Color, pos: Position) -> Result<Message, RuleViolation> {
+    if let Some(index) = pos.to_board_index(&board.size) {
+        match board.board[index] {
+            State::Empty => {
+                board.set_state(index, State::Occupied(color));
+                Ok(Message::None)
+            }
+            State::Occupied(_) => Err(RuleViolation::Occupied),
+            State::Offboard => Err(RuleViolation::OutOfBounds),
+        }
+    } else {
+        Err(RuleViolation::OutOfBounds)
+    }
+}
+
+/// An indexed position on the Go Board.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Position {
+    pub(crate) row: u16,
+    pub(crate) col: u16,
+}
+
+impl Position {
+    /// Creates a new [`Position`] at the given row and column, both counted from 0 at the
+    /// bottom left corner of the board.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row or column is off the largest, 19x19, board. Use [`Position::try_new`]
+    /// for rows and columns given from outside the program.
+    pub fn new(row: u16, col: u16) -> Self {
+        match Position::try_new(row, col) {
+            Some(pos) => pos,
+            None => panic!("Row {row} and column {col} are off the 19x19 board"),
+        }
+    }
+
+    /// Creates a new [`Position`] at the given row and column like [`Position::new`], or
+    /// [`None`] if either is off the largest, 19x19, board.
+    ///
+    /// ```
+    /// use go_board::Position;
+    ///
+    /// assert_eq!(Position::try_new(18, 0), Some(Position::new(18, 0)));
+    /// assert_eq!(Position::try_new(0, 19), None);
+    /// ```
+    pub fn try_new(row: u16, col: u16) -> Option<Self> {
+        let size = BoardSize::Nineteen.to_u16();
+        (row < size && col < size).then_some(Position { row, col })
+    }
+
+    /// The row of this [`Position`], counted from 0 at the bottom of the board.
+    pub fn row(&self) -> u16 {
+        self.row
+    }
+
+    /// The column of this [`Position`], counted from 0 at the left of the board.
+    pub fn col(&self) -> u16 {
+        self.col
+    }
+
+    /// Parses a vertex in Go notation like [`Position::from_str`], which must be on a board of the
+    /// given [`BoardSize`], or `pass`, given as [`None`].
+    ///
+    /// ```
+    /// use go_board::{BoardSize, Position};
+    ///
+    /// assert_eq!(Position::parse_vertex("E5", BoardSize::Nine), Ok(Some(Position::new(4, 4))));
+    /// assert_eq!(Position::parse_vertex("PASS", BoardSize::Nine), Ok(None));
+    /// assert!(Position::parse_vertex("Q16", BoardSize::Nine).is_err());
+    /// ```
+    pub fn parse_vertex(vertex: &str, size: BoardSize) -> Result<Option<Self>, ParsePositionError> {
+        let pos = match vertex.parse::<Position>() {
+            Ok(pos) => pos,
+            Err(ParsePositionError::Pass) => return Ok(None),
+            Err(error) => return Err(error),
+        };
+        if pos.row >= size.to_u16() || pos.col >= size.to_u16() {
+            return Err(ParsePositionError::OutOfBounds(vertex.to_string(), size));
+        }
+        Ok(Some(pos))
+    }
+
+    /// Computes the [`Position`] of the given position index on a board of size [`BoardSize`].
+    pub(crate) fn from_board_index(index: usize, boardsize: &BoardSize) -> Self {
+        let size = boardsize.to_u16();
+        let row_length = size + 2;
+        let index = index as u16;
+        Position::new(size - index / row_length, index % row_length - 1)
+    }
+
+    /// Computes the position index of this [`Position`] on a board of size [`BoardSize`], if valid.
+    pub(crate) fn to_board_index(self, boardsize: &BoardSize) -> Option<usize> {
+        let size = boardsize.to_u16();
+        if self.row >= size || self.col >= size {
+            return None;
+        }
+
+        // rows are stored from the top of the board, after a row of the off-board border
+        let row_length = usize::from(size) + 2;
+        let row_index = usize::from(size - self.row).checked_mul(row_length)?;
+        row_index.checked_add(usize::from(self.col) + 1)
+    }
+}
+
+/// The reasons a vertex in Go notation could not be parsed to a [`Position`].
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+pub enum ParsePositionError {
+    /// The vertex is not a column letter followed by a row number, ex. `D4`.
+    #[error("Invalid vertex: {0}")]
+    Invalid(String),
+    /// The vertex names a point past the edge of the board of the given [`BoardSize`].
+    #[error("{0} is not a point of a {size}x{size} board", size = .1.to_u16())]
+    OutOfBounds(String, BoardSize),
+    /// The vertex is `pass`, which names no point of the board.
+    #[error("A pass is not a point of the board")]
+    Pass,
+}
+
+/// Parses a vertex in Go notation, a column letter skipping `I` followed by a row number counted
+/// from 1 at the bottom of the board, ex. `Q16` or `a1`, up to the 19x19 board.
+///
+/// Use [`Position::parse_vertex`] to also accept `pass` and check the vertex is on a board of a
+/// smaller [`BoardSize`].
+///
+/// ```
+/// use go_board::update::ParsePositionError;
+/// use go_board::Position;
+///
+/// assert_eq!("Q16".parse(), Ok(Position::new(15, 15)));
+/// assert_eq!("a1".parse(), Ok(Position::new(0, 0)));
+/// assert_eq!("pass".parse::<Position>(), Err(ParsePositionError::Pass));
+/// assert!("I5".parse::<Position>().is_err());
+/// assert!("A+1".parse::<Position>().is_err());
+/// assert!("A01".parse::<Position>().is_err());
+/// ```
+impl FromStr for Position {
+    type Err = ParsePositionError;
+
+    fn from_str(vertex: &str) -> Result<Self, Self::Err> {
+        if vertex.eq_ignore_ascii_case("pass") {
+            return Err(ParsePositionError::Pass);
+        }
+
+        let invalid = || ParsePositionError::Invalid(vertex.to_string());
+        let mut chars = vertex.chars();
+        let column = chars.next().ok_or_else(invalid)?.to_ascii_uppercase();
+        let col = COLUMN_LETTERS.find(column).ok_or_else(invalid)? as u16;
+        // only plain digits, so neither signs nor leading zeros name the same point twice
+        let digits = chars.as_str();
+        if digits.starts_with('0') || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let row = digits.parse::<u16>().map_err(|_| invalid())?;
+        match row {
+            1..=19 => Ok(Position::new(row - 1, col)),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// Writes the vertex of this [`Position`] in Go notation, a column letter skipping `I` followed
+/// by the row counted from 1 at the bottom of the board, ex. `D4`, as [`Position::from_str`]
+/// parses it.
+///
+/// ```
+/// use go_board::Position;
+///
+/// assert_eq!(Position::new(3, 3).to_string(), "D4");
+/// assert_eq!(Position::new(15, 8).to_string(), "J16");
+/// ```
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // every Position is within the 19 columns of Go notation
+        let column = COLUMN_LETTERS.as_bytes()[usize::from(self.col)] as char;
+        write!(f, "{column}{}", self.row + 1)
+    }
+}
@@ -0,0 +1,34 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use engine::{bench_workloads, seed_rng, BENCH_SEED};
+use std::time::Duration;
+/******************************************************\
+|****************      CONSTANTS       ****************|
+\******************************************************/
+
+// Workloads the bench run mode times fewer than this many times are full searches or playouts,
+// measured with the fewest samples criterion allows
+const SLOW_WORKLOAD_RUNS: u32 = 100;
+const SLOW_WORKLOAD_SAMPLES: usize = 10;
+const SLOW_WORKLOAD_TIME: Duration = Duration::from_secs(20);
+
+/*****************************************************\
+|****************      BENCHMARKS     ****************|
+\*****************************************************/
+
+// Measures every workload of the bench run mode, grouped by operation, each from BENCH_SEED
+fn core_operations(c: &mut Criterion) {
+    for mut workload in bench_workloads() {
+        let mut group = c.benchmark_group(workload.operation);
+        if workload.runs < SLOW_WORKLOAD_RUNS {
+            group.sample_size(SLOW_WORKLOAD_SAMPLES);
+            group.measurement_time(SLOW_WORKLOAD_TIME);
+        }
+
+        seed_rng(BENCH_SEED);
+        group.bench_function(workload.case.clone(), |b| b.iter(|| workload.run()));
+        group.finish();
+    }
+}
+
+criterion_group!(benches, core_operations);
+criterion_main!(benches);
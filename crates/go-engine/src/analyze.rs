@@ -0,0 +1,219 @@
+use super::*;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+/******************************************************\
+|****************      CONSTANTS       ****************|
+\******************************************************/
+
+// Number of mistakes listed in the summary after analysis
+const MISTAKES_REPORTED: usize = 5;
+
+// Number of the engine's preferred moves listed as alternatives to each move of the game
+const ALTERNATIVES_REPORTED: usize = 3;
+
+/****************************************************\
+|****************    GLOBAL TYPES    ****************|
+\****************************************************/
+
+// Settings of an analysis of an SGF game, parsed from the command line
+pub(crate) struct AnalyzeSettings {
+    path: PathBuf,
+    visits: u16,
+    output: Option<PathBuf>,
+    threads: usize,
+}
+
+// The change in evaluation caused by a single move of the game
+pub struct MoveReview {
+    pub move_number: usize,
+    pub color: Color,
+    pub played: Move,
+    pub suggested: Move,
+    // the winrate the move lost for its Color, and Black's winrate and the score after it
+    pub winrate_loss: f64,
+    pub black_winrate: f64,
+    pub score: f64,
+    // the moves the engine preferred in the position before the move, most visited first, each
+    // with the winrate it gives the Color to move
+    pub alternatives: Vec<Analysis>,
+}
+
+/*****************************************************\
+|****************        SETUP        ****************|
+\*****************************************************/
+
+impl AnalyzeSettings {
+    // Parses analysis settings from the arguments following the analyze run mode
+    // Accepts "<file.sgf> --visits N --output <file.sgf> --threads N", where the options are
+    // optional and the threads default to every available core
+    pub(crate) fn from_args(args: &[String]) -> Result<AnalyzeSettings, String> {
+        let mut path: Option<PathBuf> = None;
+        let mut visits: u16 = 30;
+        let mut output: Option<PathBuf> = None;
+        let mut threads = 0;
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--visits" => {
+                    let value = args.next().ok_or("No value given to analyze option --visits")?;
+                    visits = value
+                        .parse()
+                        .map_err(|_| format!("Invalid visit count given to analyze: {value}"))?;
+                }
+                "--output" => {
+                    let value = args.next().ok_or("No value given to analyze option --output")?;
+                    output = Some(PathBuf::from(value));
+                }
+                "--threads" => {
+                    let value = args.next().ok_or("No value given to analyze option --threads")?;
+                    threads = value
+                        .parse()
+                        .map_err(|_| format!("Invalid thread count given to analyze: {value}"))?;
+                }
+                _ if path.is_none() => path = Some(PathBuf::from(arg)),
+                _ => return Err(format!("Unexpected analyze argument given: {arg}")),
+            }
+        }
+
+        Ok(AnalyzeSettings {
+            path: path.ok_or("No SGF file given to analyze")?,
+            visits,
+            output,
+            threads,
+        })
+    }
+
+    // The path the annotated SGF is written to, defaulting to "<name>_analyzed.sgf" beside the input
+    fn output_path(&self) -> PathBuf {
+        self.output.clone().unwrap_or_else(|| {
+            let stem = self.path.file_stem().unwrap_or_default().to_string_lossy();
+            self.path.with_file_name(format!("{stem}_analyzed.sgf"))
+        })
+    }
+}
+
+/****************************************************\
+|****************       HELPER       ****************|
+\****************************************************/
+
+// Converts a winrate for the given Color to play into a winrate for the given perspective Color
+fn winrate_for(analysis: &Analysis, to_play: Color, perspective: Color) -> f64 {
+    if to_play == perspective {
+        analysis.winrate
+    } else {
+        1.0 - analysis.winrate
+    }
+}
+
+// Formats the given Move for display, as the engine's GTP responses do
+fn move_to_string(mov: &Move) -> String {
+    match mov {
+        Move::MOVE(intsc, _) => intsc.to_string(),
+        Move::PASS => "pass".to_string(),
+        Move::RESIGN => "resign".to_string(),
+    }
+}
+
+// Formats the given score from Black's perspective in result notation, ex. "B+3.5"
+fn score_to_string(score: f64) -> String {
+    if score >= 0.0 {
+        format!("B+{score:.1}")
+    } else {
+        format!("W+{:.1}", -score)
+    }
+}
+
+/*****************************************************\
+|****************      ANALYSIS       ****************|
+\*****************************************************/
+
+// Annotates every move of the given record with the engine's evaluation of the position after it,
+// and returns the review of each move
+fn annotate(
+    record: &mut SgfRecord,
+    positions: &[(Board, Color)],
+    analyses: &[(Analysis, Vec<Analysis>)],
+) -> Vec<MoveReview> {
+    let mut reviews: Vec<MoveReview> = vec![];
+
+    for (index, node) in record.nodes.iter_mut().enumerate() {
+        let (before, after) = (&analyses[index].0, &analyses[index + 1].0);
+        let (to_play_before, to_play_after) = (positions[index].1, positions[index + 1].1);
+
+        let winrate_before = winrate_for(before, to_play_before, node.color);
+        let winrate_after = winrate_for(after, to_play_after, node.color);
+        let black_winrate = winrate_for(after, to_play_after, Color::Black);
+
+        node.comment = Some(format!(
+            "Black winrate: {:.1}%\nScore estimate: {}\nEngine preferred: {} ({} visits)",
+            black_winrate * 100.0,
+            score_to_string(after.score),
+            move_to_string(&before.best_move),
+            before.visits
+        ));
+
+        reviews.push(MoveReview {
+            move_number: index + 1,
+            color: node.color,
+            played: node.mov,
+            suggested: before.best_move,
+            winrate_loss: winrate_before - winrate_after,
+            black_winrate,
+            score: after.score,
+            alternatives: analyses[index].1.iter().take(ALTERNATIVES_REPORTED).copied().collect(),
+        });
+    }
+
+    reviews
+}
+
+/********************************************************\
+|****************     PUBLIC METHODS     ****************|
+\********************************************************/
+
+// Analyzes every position of the given game record with the given visits, annotating each move
+// of the record with the engine's evaluation, and returns the review of each move
+pub fn review_game(record: &mut SgfRecord, visits: u16) -> Result<Vec<MoveReview>, String> {
+    let positions = record.positions()?;
+    tracing::info!(positions = positions.len(), visits, "Analyzing game");
+    let analyses = analyze_positions_with_candidates(&positions, visits);
+    Ok(annotate(record, &positions, &analyses))
+}
+
+// Analyzes every position of the SGF game given by the settings, writes an annotated copy of the
+// game, and prints a summary of the biggest mistakes
+pub(crate) fn run_analyze(settings: AnalyzeSettings) -> io::Result<()> {
+    let invalid_data = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+
+    let mut record = read_sgf_file(&settings.path)?;
+    set_search_threads(settings.threads);
+    let mut reviews = review_game(&mut record, settings.visits).map_err(invalid_data)?;
+
+    let output_path = settings.output_path();
+    fs::write(&output_path, record.to_string())?;
+    println!("Annotated game written to {}", output_path.display());
+
+    reviews.sort_by(|a, b| b.winrate_loss.total_cmp(&a.winrate_loss));
+    println!("\nBiggest mistakes:");
+    for review in reviews
+        .iter()
+        .filter(|review| review.winrate_loss > 0.0)
+        .take(MISTAKES_REPORTED)
+    {
+        println!(
+            "  Move {:>3} ({}): played {}, engine preferred {}, winrate loss {:.1}%",
+            review.move_number,
+            match review.color {
+                Color::Black => "Black",
+                Color::White => "White",
+            },
+            move_to_string(&review.played),
+            move_to_string(&review.suggested),
+            review.winrate_loss * 100.0
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,159 @@
+use super::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+/******************************************************\
+|****************      CONSTANTS       ****************|
+\******************************************************/
+
+// Quick playouts end after this many moves per point of the Board even if no one passes
+const QUICK_PLAYOUT_MOVES_PER_POINT: usize = 2;
+
+/****************************************************\
+|****************    GLOBAL TYPES    ****************|
+\****************************************************/
+
+// How surely each point of a position belongs to each Color, from the final positions of
+// playouts, indexed like Board positions
+// 1.0 is always Black's area, -1.0 always White's, and 0.0 contested or neither's
+pub struct Ownership {
+    size: BoardSize,
+    values: Vec<f64>,
+}
+
+/****************************************************\
+|****************       HELPER       ****************|
+\****************************************************/
+
+impl Ownership {
+    // The ownership of the given Intersection, or None if it is not on the Board
+    pub(crate) fn at(&self, intsc: &Intersection) -> Option<f64> {
+        let index = intsc.to_position_index(&self.size)? as usize;
+        self.values.get(index).copied()
+    }
+
+    // The ownership of every point, as rows from the top of the Board
+    pub fn rows(&self) -> Vec<Vec<f64>> {
+        let size = self.size.to_u16();
+        (1..=size)
+            .rev()
+            .map(|row| {
+                (0..size)
+                    .filter_map(|column| self.at(&Intersection::new(ColumnIdentifier::from_u16(column)?, row)))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+// Runs the given batch on a thread pool of search_threads() threads
+pub(crate) fn in_search_pool<T: Send>(batch: impl FnOnce() -> T + Send) -> T {
+    match rayon::ThreadPoolBuilder::new().num_threads(search_threads()).build() {
+        Ok(pool) => pool.install(batch),
+        Err(error) => {
+            tracing::warn!("Could not start search threads, running the batch on this thread: {error}");
+            batch()
+        }
+    }
+}
+
+// Counts how often each position index was in Black's area minus in White's, over the final
+// positions of the given number of playouts from the given Board
+fn ownership_of(position: &Board, color: Color, playouts: u16) -> Ownership {
+    ownership_from(position, color, playouts, |position, color, _| playout_position(position, color))
+}
+
+// Counts ownership like ownership_of, over the final positions of the given playout, which is
+// also given the number of each playout it runs
+fn ownership_from(
+    position: &Board,
+    color: Color,
+    playouts: u16,
+    playout: fn(&Board, Color, u16) -> Board,
+) -> Ownership {
+    let length = position.size.to_u16() as usize + 2;
+    let counts = (0..playouts)
+        .into_par_iter()
+        .fold(
+            || vec![0i32; length * length],
+            |mut counts, number| {
+                let end = playout(position, color, number);
+                let (black_area, white_area) = end.area_owners();
+                black_area.indices().for_each(|index| counts[index] += 1);
+                white_area.indices().for_each(|index| counts[index] -= 1);
+                recycle(end);
+                counts
+            },
+        )
+        .reduce(
+            || vec![0i32; length * length],
+            |mut total, counts| {
+                total.iter_mut().zip(counts).for_each(|(total, count)| *total += count);
+                total
+            },
+        );
+
+    Ownership {
+        size: position.size,
+        values: counts
+            .into_iter()
+            .map(|count| count as f64 / playouts.max(1) as f64)
+            .collect(),
+    }
+}
+
+// Plays random legal moves from the given Board, the given Color first, until both players pass,
+// never filling a point surrounded by the mover's own stones so that living groups keep their eyes
+// and dead ones are captured
+// The moves are chosen from the given playout number, so the same position always settles the
+// same way, whichever thread plays it out
+fn quick_playout(position: &Board, color: Color, number: u16) -> Board {
+    let points = position.size.to_u16() as usize * position.size.to_u16() as usize;
+    let mut rng = StdRng::seed_from_u64(number as u64);
+    let mut end = pooled_copy(position);
+    let (mut color, mut passes) = (color, 0);
+    for _ in 0..points * QUICK_PLAYOUT_MOVES_PER_POINT {
+        // tries the empty points in a random order, where play refuses illegal ones
+        let mut candidates = end.empty_intersections();
+        let mut played = false;
+        while !played && !candidates.is_empty() {
+            let intsc = candidates.swap_remove(rng.gen_range(0..candidates.len()));
+            played = end.diamond(&intsc) != Some(color) && end.play(Move::MOVE(intsc, color));
+        }
+
+        passes = if played { 0 } else { passes + 1 };
+        if passes == 2 {
+            break;
+        }
+        color = color.opposite_color();
+    }
+    end
+}
+
+/********************************************************\
+|****************     PUBLIC METHODS     ****************|
+\********************************************************/
+
+// Scores each of the given finished games with the given ScoringRules, in order
+// The positions are scored in parallel on search_threads() threads
+pub(crate) fn score_positions(positions: &[Board], method: ScoringRules) -> Vec<f64> {
+    in_search_pool(|| positions.par_iter().map(|board| board.final_score(method)).collect())
+}
+
+// Estimates the ownership of each of the given Boards for the paired Color to play, in order,
+// from the given number of playouts each
+// The playouts of every position are run in parallel on search_threads() threads
+pub fn ownership_positions(positions: &[(Board, Color)], playouts: u16) -> Vec<Ownership> {
+    in_search_pool(|| {
+        positions
+            .par_iter()
+            .map(|(position, color)| ownership_of(position, *color, playouts))
+            .collect()
+    })
+}
+
+// Estimates the ownership of the given finished Board for the given Color to play from the given
+// number of quick playouts, which settle the position by capturing its dead stones
+pub(crate) fn settled_ownership(position: &Board, color: Color, playouts: u16) -> Ownership {
+    ownership_from(position, color, playouts, quick_playout)
+}
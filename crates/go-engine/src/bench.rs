@@ -0,0 +1,283 @@
+use super::*;
+use std::fmt;
+use std::fmt::Formatter;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+/******************************************************\
+|****************      CONSTANTS       ****************|
+\******************************************************/
+
+// Seed of the random number generator the workloads are run with unless given with --seed
+pub const BENCH_SEED: u64 = 2_024;
+
+// Number of moves played and taken back
+const PLAY_RUNS: u32 = 10_000;
+
+// Number of playouts timed per starting position
+const PLAYOUT_RUNS: u32 = 20;
+
+// MCTS iteration budgets that genmove latency is measured at
+const GENMOVE_BUDGETS: [u16; 3] = [10, 30, 100];
+
+// Number of genmove calls timed per budget
+const GENMOVE_RUNS: u32 = 3;
+
+// Number of group searches timed
+const GROUP_SEARCH_RUNS: u32 = 10_000;
+
+// Number of times the midgame board is scored
+const SCORING_RUNS: u32 = 2_000;
+
+// Fixed opening sequence on a 19x19 board used as the midgame position
+const MIDGAME_MOVES: [(ColumnIdentifier, u16); 30] = {
+    use ColumnIdentifier::*;
+    [
+        (Q, 16), (D, 4), (Q, 3), (D, 16), (R, 5), (C, 14), (F, 17), (O, 4), (Q, 6), (R, 3),
+        (R, 2), (S, 2), (P, 3), (Q, 2), (P, 2), (R, 1), (F, 3), (C, 6), (J, 4), (Q, 10),
+        (C, 3), (D, 3), (C, 4), (C, 5), (B, 5), (D, 5), (B, 6), (C, 7), (K, 16), (O, 17),
+    ]
+};
+
+/****************************************************\
+|****************    GLOBAL TYPES    ****************|
+\****************************************************/
+
+// Settings of the bench run mode, parsed from the command line
+pub(crate) struct BenchSettings {
+    seed: u64,
+}
+
+// An operation timed by the bench run mode and by the criterion benches in benches/core.rs,
+// such as playouts on one starting position
+pub struct Workload {
+    // the operation measured, and the case of it, named "operation (case)" in the report
+    pub operation: &'static str,
+    pub case: String,
+    // number of times the bench run mode times the workload
+    pub runs: u32,
+    workload: Box<dyn FnMut()>,
+}
+
+/*****************************************************\
+|****************    PRIVATE TYPES    ****************|
+\*****************************************************/
+
+// The timing of a single benchmark workload
+struct BenchResult {
+    name: String,
+    runs: u32,
+    elapsed: Duration,
+}
+
+/*****************************************************\
+|****************        SETUP        ****************|
+\*****************************************************/
+
+impl BenchSettings {
+    // Parses bench settings from the arguments following the bench run mode
+    // Accepts "--seed N", where the seed defaults to BENCH_SEED
+    pub(crate) fn from_args(args: &[String]) -> Result<BenchSettings, String> {
+        let mut settings = BenchSettings { seed: BENCH_SEED };
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--seed" => {
+                    let value = args.next().ok_or("No value given to bench option --seed")?;
+                    settings.seed = value
+                        .parse()
+                        .map_err(|_| format!("Invalid value given to bench option --seed: {value}"))?;
+                }
+                _ => return Err(format!("Unknown bench option given: {arg}")),
+            }
+        }
+
+        Ok(settings)
+    }
+}
+
+impl Workload {
+    // Creates a workload of the given operation and case, run the given number of times by the
+    // bench run mode
+    fn new(operation: &'static str, case: impl Into<String>, runs: u32, workload: impl FnMut() + 'static) -> Workload {
+        Workload {
+            operation,
+            case: case.into(),
+            runs,
+            workload: Box::new(workload),
+        }
+    }
+}
+
+/****************************************************\
+|****************       HELPER       ****************|
+\****************************************************/
+
+impl Workload {
+    // Runs the workload once
+    pub fn run(&mut self) {
+        (self.workload)();
+    }
+
+    // The name of the workload in the report
+    pub fn name(&self) -> String {
+        format!("{} ({})", self.operation, self.case)
+    }
+}
+
+impl BenchResult {
+    // Average time taken by a single run of the workload
+    fn per_run(&self) -> Duration {
+        self.elapsed / self.runs
+    }
+
+    // Number of runs of the workload completed per second
+    fn runs_per_second(&self) -> f64 {
+        self.runs as f64 / self.elapsed.as_secs_f64()
+    }
+}
+
+impl fmt::Display for BenchResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<32}{:>8}{:>14.3}{:>14.1}{:>14.1}",
+            self.name,
+            self.runs,
+            self.elapsed.as_secs_f64() * 1000.0,
+            self.per_run().as_secs_f64() * 1_000_000.0,
+            self.runs_per_second()
+        )
+    }
+}
+
+// Times the given workload over its number of runs
+fn time_workload(mut workload: Workload) -> BenchResult {
+    let start = Instant::now();
+    for _ in 0..workload.runs {
+        workload.run();
+    }
+
+    BenchResult {
+        name: workload.name(),
+        runs: workload.runs,
+        elapsed: start.elapsed(),
+    }
+}
+
+// Creates the 19x19 Board reached after playing MIDGAME_MOVES, alternating colors from Black
+fn midgame_board() -> Board {
+    let mut board = Board::new(BoardSize::Nineteen);
+    let mut color = Color::Black;
+    for (column, row) in MIDGAME_MOVES {
+        board.play(Move::MOVE(Intersection::new(column, row), color));
+        color = color.opposite_color();
+    }
+
+    board
+}
+
+/*****************************************************\
+|****************      WORKLOADS      ****************|
+\*****************************************************/
+
+// Measures playing a move on the midgame board and restoring the board to before it
+// The engine Board has no undo, so the move is taken back by copying the position back in place
+fn play_workloads() -> Vec<Workload> {
+    let midgame = midgame_board();
+    let mut scratch = midgame.deepcopy();
+    let mov = Move::MOVE(Intersection::new(ColumnIdentifier::K, 10), Color::Black);
+
+    vec![Workload::new("play/undo", "midgame 19x19", PLAY_RUNS, move || {
+        black_box(scratch.play(mov));
+        scratch.copy_from(&midgame);
+    })]
+}
+
+// Measures finding the stones and liberties of a group on the midgame board
+fn group_search_workloads() -> Vec<Workload> {
+    let midgame = midgame_board();
+    let (column, row) = MIDGAME_MOVES[0];
+    let group_index = Intersection::new(column, row)
+        .to_position_index(&midgame.size)
+        .unwrap() as usize; // MIDGAME_MOVES are all on the board
+
+    vec![Workload::new("group search", "midgame 19x19", GROUP_SEARCH_RUNS, move || {
+        black_box(midgame.count(group_index, Color::Black));
+    })]
+}
+
+// Measures the area count of the midgame board, as done at the end of every playout
+fn scoring_workloads() -> Vec<Workload> {
+    let midgame = midgame_board();
+
+    vec![Workload::new("area score", "midgame 19x19", SCORING_RUNS, move || {
+        black_box(midgame.estimate_score());
+    })]
+}
+
+// Measures playouts from an empty board and the midgame board
+fn playout_workloads() -> Vec<Workload> {
+    let empty = Board::new(BoardSize::Nineteen);
+    let midgame = midgame_board();
+
+    vec![
+        Workload::new("playout", "empty 19x19", PLAYOUT_RUNS, move || {
+            black_box(playout(&empty, Color::Black));
+        }),
+        Workload::new("playout", "midgame 19x19", PLAYOUT_RUNS, move || {
+            black_box(playout(&midgame, Color::Black));
+        }),
+    ]
+}
+
+// Measures genmove latency on the midgame board at each budget in GENMOVE_BUDGETS
+fn genmove_workloads() -> Vec<Workload> {
+    GENMOVE_BUDGETS
+        .iter()
+        .map(|budget| {
+            let (midgame, budget) = (midgame_board(), *budget);
+            Workload::new("genmove", format!("{budget} iterations"), GENMOVE_RUNS, move || {
+                black_box(generate_move(&midgame, Color::Black, budget));
+            })
+        })
+        .collect()
+}
+
+/********************************************************\
+|****************     PUBLIC METHODS     ****************|
+\********************************************************/
+
+// Every benchmark workload, from the cheapest board operations to full searches
+// Random choices in the workloads are repeatable once seed_rng has been given BENCH_SEED
+pub fn bench_workloads() -> Vec<Workload> {
+    play_workloads()
+        .into_iter()
+        .chain(group_search_workloads())
+        .chain(scoring_workloads())
+        .chain(playout_workloads())
+        .chain(genmove_workloads())
+        .collect()
+}
+
+// Runs every benchmark workload and prints a report comparable across versions
+pub(crate) fn run_bench(settings: BenchSettings) {
+    println!("Go Engine benchmark, version {}", env!("CARGO_PKG_VERSION"));
+    println!("Board operations: {}", simd_backend());
+    println!("Seed: {}", settings.seed);
+    if cfg!(debug_assertions) {
+        println!("note: built without optimizations, use a release build for comparable numbers");
+    }
+
+    println!(
+        "\n{:<32}{:>8}{:>14}{:>14}{:>14}",
+        "workload", "runs", "total (ms)", "per run (us)", "runs/sec"
+    );
+
+    seed_rng(settings.seed);
+    for workload in bench_workloads() {
+        let result = time_workload(workload);
+        tracing::debug!(workload = %result.name, elapsed = ?result.elapsed, "Benchmark finished");
+        println!("{result}");
+    }
+}
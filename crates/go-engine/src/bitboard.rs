@@ -0,0 +1,331 @@
+use super::*;
+/******************************************************\
+|****************      CONSTANTS       ****************|
+\******************************************************/
+
+// Words of a BitSet, enough for the padded 21x21 position of a 19x19 Board and rounded up to
+// two 256 bit vectors
+const WORDS: usize = 8;
+
+/****************************************************\
+|****************    GLOBAL TYPES    ****************|
+\****************************************************/
+
+// A set of position indices of a Board, one bit per index
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub(crate) struct BitSet {
+    words: [u64; WORDS],
+}
+
+// The stones of a Board as one BitSet per color, indexed like Board positions, so that the
+// OFFBOARD border around the Board keeps neighbor shifts from wrapping between rows
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub(crate) struct BitBoard {
+    black: BitSet,
+    white: BitSet,
+    // every index on the Board, excluding the border
+    on_board: BitSet,
+    // length of a padded row of the position, the distance between vertical neighbors
+    row_length: usize,
+}
+
+/*****************************************************\
+|****************         SIMD        ****************|
+\*****************************************************/
+
+// The vector instructions the hot BitBoard operations are compiled for, "avx2" or "scalar"
+// AVX2 is only used when built with the simd feature and supported by the running CPU
+pub(crate) fn simd_backend() -> &'static str {
+    if avx2_available() {
+        "avx2"
+    } else {
+        "scalar"
+    }
+}
+
+// Can the AVX2 versions of the hot BitBoard operations be used?
+// Each is a copy of the portable operation compiled with AVX2 and POPCNT enabled, with every
+// BitSet operation inlined into it, so the dispatch happens once per flood fill
+fn avx2_available() -> bool {
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    {
+        std::is_x86_feature_detected!("avx2") && std::is_x86_feature_detected!("popcnt")
+    }
+    #[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+    {
+        false
+    }
+}
+
+/*****************************************************\
+|****************       BIT SETS      ****************|
+\*****************************************************/
+
+impl BitSet {
+    // Creates a BitSet holding only the given index
+    pub(crate) fn single(index: usize) -> BitSet {
+        let mut set = BitSet::default();
+        set.insert(index);
+        set
+    }
+
+    // Adds the given index to this BitSet
+    pub(crate) fn insert(&mut self, index: usize) {
+        self.words[index / 64] |= 1 << (index % 64);
+    }
+
+    // Removes the given index from this BitSet
+    pub(crate) fn remove(&mut self, index: usize) {
+        self.words[index / 64] &= !(1 << (index % 64));
+    }
+
+    // Does this BitSet hold the given index?
+    pub(crate) fn contains(&self, index: usize) -> bool {
+        self.words[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    // Does this BitSet hold no indices?
+    pub(crate) fn is_empty(&self) -> bool {
+        self.words.iter().all(|word| *word == 0)
+    }
+
+    // Number of indices held
+    #[inline(always)]
+    pub(crate) fn len(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    // The indices held, in ascending order
+    pub(crate) fn indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(word_index, word)| {
+            let mut word = *word;
+            std::iter::from_fn(move || {
+                (word != 0).then(|| {
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    word_index * 64 + bit
+                })
+            })
+        })
+    }
+
+    // Applies the given operation to each pair of words of this BitSet and the other
+    #[inline(always)]
+    fn zip_with(&self, other: &BitSet, operation: impl Fn(u64, u64) -> u64) -> BitSet {
+        let mut words = [0; WORDS];
+        for (word, (left, right)) in words.iter_mut().zip(self.words.iter().zip(other.words)) {
+            *word = operation(*left, right);
+        }
+        BitSet { words }
+    }
+
+    // Indices held by both BitSets
+    #[inline(always)]
+    pub(crate) fn and(&self, other: &BitSet) -> BitSet {
+        self.zip_with(other, |left, right| left & right)
+    }
+
+    // Indices held by either BitSet
+    #[inline(always)]
+    pub(crate) fn or(&self, other: &BitSet) -> BitSet {
+        self.zip_with(other, |left, right| left | right)
+    }
+
+    // Indices held by this BitSet but not the other
+    #[inline(always)]
+    pub(crate) fn and_not(&self, other: &BitSet) -> BitSet {
+        self.zip_with(other, |left, right| left & !right)
+    }
+
+    // Moves every index up by the given distance, dropping indices past the end
+    #[inline(always)]
+    fn shift_up(&self, distance: usize) -> BitSet {
+        let (word_shift, bit_shift) = (distance / 64, distance % 64);
+        let mut words = [0; WORDS];
+        for index in (word_shift..WORDS).rev() {
+            let source = index - word_shift;
+            words[index] = self.words[source] << bit_shift;
+            if bit_shift > 0 && source > 0 {
+                words[index] |= self.words[source - 1] >> (64 - bit_shift);
+            }
+        }
+        BitSet { words }
+    }
+
+    // Moves every index down by the given distance, dropping indices below 0
+    #[inline(always)]
+    fn shift_down(&self, distance: usize) -> BitSet {
+        let (word_shift, bit_shift) = (distance / 64, distance % 64);
+        let mut words = [0; WORDS];
+        for (index, word) in words.iter_mut().enumerate().take(WORDS - word_shift) {
+            let source = index + word_shift;
+            *word = self.words[source] >> bit_shift;
+            if bit_shift > 0 && source + 1 < WORDS {
+                *word |= self.words[source + 1] << (64 - bit_shift);
+            }
+        }
+        BitSet { words }
+    }
+}
+
+/*****************************************************\
+|****************        SETUP        ****************|
+\*****************************************************/
+
+impl BitBoard {
+    // Creates an empty BitBoard for a Board of the given size
+    pub(crate) fn new(size: &BoardSize) -> BitBoard {
+        let numeric_size = size.to_u16() as usize;
+        let row_length = numeric_size + 2;
+
+        let mut on_board = BitSet::default();
+        for row in 1..=numeric_size {
+            for column in 1..=numeric_size {
+                on_board.insert(row * row_length + column);
+            }
+        }
+
+        BitBoard {
+            black: BitSet::default(),
+            white: BitSet::default(),
+            on_board,
+            row_length,
+        }
+    }
+}
+
+/*****************************************************\
+|****************      OPERATIONS     ****************|
+\*****************************************************/
+
+impl BitBoard {
+    // Records the given State at the given position index
+    pub(crate) fn set(&mut self, index: usize, state: State) {
+        self.black.remove(index);
+        self.white.remove(index);
+        match state {
+            State::Occupied(Color::Black) => self.black.insert(index),
+            State::Occupied(Color::White) => self.white.insert(index),
+            State::Empty | State::Offboard => {}
+        }
+    }
+
+    // The stones of the given Color
+    #[inline(always)]
+    pub(crate) fn stones(&self, color: Color) -> &BitSet {
+        match color {
+            Color::Black => &self.black,
+            Color::White => &self.white,
+        }
+    }
+
+    // The empty points of the Board
+    #[inline(always)]
+    pub(crate) fn empty(&self) -> BitSet {
+        self.on_board.and_not(&self.black.or(&self.white))
+    }
+
+    // The points on the Board orthogonally adjacent to any index of the given set, excluding the set
+    #[inline(always)]
+    pub(crate) fn neighbors(&self, set: &BitSet) -> BitSet {
+        set.shift_up(1)
+            .or(&set.shift_down(1))
+            .or(&set.shift_up(self.row_length))
+            .or(&set.shift_down(self.row_length))
+            .and(&self.on_board)
+            .and_not(set)
+    }
+
+    // The group of the given Color connected to the given position index, grown one ring
+    // of neighbors at a time
+    // Returns an empty set if there is no stone of that Color at the index
+    pub(crate) fn group(&self, index: usize, color: Color) -> BitSet {
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        if avx2_available() {
+            // Safety: AVX2 and POPCNT support was just detected
+            return unsafe { self.group_avx2(index, color) };
+        }
+        self.group_portable(index, color)
+    }
+
+    // The group operation compiled for AVX2
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    #[target_feature(enable = "avx2,popcnt")]
+    unsafe fn group_avx2(&self, index: usize, color: Color) -> BitSet {
+        self.group_portable(index, color)
+    }
+
+    #[inline(always)]
+    fn group_portable(&self, index: usize, color: Color) -> BitSet {
+        let stones = self.stones(color);
+        if !stones.contains(index) {
+            return BitSet::default();
+        }
+
+        let mut group = BitSet::single(index);
+        loop {
+            let grown = group.or(&self.neighbors(&group).and(stones));
+            if grown == group {
+                return group;
+            }
+            group = grown;
+        }
+    }
+
+    // The empty points adjacent to the given group
+    pub(crate) fn liberties(&self, group: &BitSet) -> BitSet {
+        self.neighbors(group).and(&self.empty())
+    }
+
+    // The empty points connected through empty points to a stone of the given Color
+    #[inline(always)]
+    fn reaches(&self, color: Color) -> BitSet {
+        let empty = self.empty();
+        let mut reached = self.neighbors(self.stones(color)).and(&empty);
+        loop {
+            let grown = reached.or(&self.neighbors(&reached).and(&empty));
+            if grown == reached {
+                return reached;
+            }
+            reached = grown;
+        }
+    }
+
+    // The Tromp-Taylor area of Black minus that of White, where a Color's area is its stones
+    // and the empty points that reach only its stones
+    pub(crate) fn area_difference(&self) -> i32 {
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        if avx2_available() {
+            // Safety: AVX2 and POPCNT support was just detected
+            return unsafe { self.area_difference_avx2() };
+        }
+        self.area_difference_portable()
+    }
+
+    // The area_difference operation compiled for AVX2
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    #[target_feature(enable = "avx2,popcnt")]
+    unsafe fn area_difference_avx2(&self) -> i32 {
+        self.area_difference_portable()
+    }
+
+    #[inline(always)]
+    fn area_difference_portable(&self) -> i32 {
+        let (black_area, white_area) = self.areas_portable();
+        black_area.len() as i32 - white_area.len() as i32
+    }
+
+    // The Tromp-Taylor areas of Black and White, as counted by area_difference
+    pub(crate) fn areas(&self) -> (BitSet, BitSet) {
+        self.areas_portable()
+    }
+
+    #[inline(always)]
+    fn areas_portable(&self) -> (BitSet, BitSet) {
+        let (reaches_black, reaches_white) = (self.reaches(Color::Black), self.reaches(Color::White));
+        (
+            self.black.or(&reaches_black.and_not(&reaches_white)),
+            self.white.or(&reaches_white.and_not(&reaches_black)),
+        )
+    }
+}
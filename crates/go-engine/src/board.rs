@@ -0,0 +1,1123 @@
+use super::batch::settled_ownership;
+use super::bitboard::{BitBoard, BitSet};
+use super::random::engine_rng;
+use go_board::{BoardSize, Color, KoRule, ParsePositionError, Position, Ruleset, ScoringRules, State};
+use num_traits::{Bounded, NumCast, Signed, Unsigned};
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::fmt::{Debug, Formatter};
+use std::ops::{Add, Sub};
+/******************************************************\
+|****************      CONSTANTS       ****************|
+\******************************************************/
+
+// Number of quick playouts from a finished position whose ownership decides its dead stones
+const DEAD_STONE_PLAYOUTS: u16 = 32;
+
+// A chain is dead when the points it stands on end up in the opponent's area by at least this
+// ownership on average, so in about 90% of the playouts
+const DEAD_STONE_OWNERSHIP: f64 = 0.8;
+
+/****************************************************\
+|****************    GLOBAL TYPES    ****************|
+\****************************************************/
+
+// How a finished game was won
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum GameResult {
+    SCORE(f64),         // both players passed, scored as the margin, positive if Black wins
+    RESIGNATION(Color), // the given Color won when the other resigned
+}
+
+// Moves performed on a Board
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Move {
+    PASS,
+    MOVE(Intersection, Color),
+    RESIGN,
+}
+
+// Reasons a move may not be played
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum IllegalMove {
+    OUTOFTURN, // the other Color is to move
+    OFFBOARD,  // the Intersection is outside the Board
+    OCCUPIED,  // a stone is already at the Intersection
+    KO,        // the move retakes a ko, or recreates an earlier position under positional superko
+    SUICIDE,   // the move leaves its own group without liberties where the rules forbid it
+}
+
+// Go Board structure
+#[derive(PartialEq)]
+pub struct Board {
+    pub size: BoardSize,
+    position: Vec<Cell>,
+    // the stones of position as bitsets, kept in step with it for group and liberty operations
+    bits: BitBoard,
+    side: Color,
+    ko: Option<Intersection>,
+    pub komi: f64,
+    pub(crate) last_move: Move,
+    pub white_captures: u16,
+    pub black_captures: u16,
+    pub(crate) move_number: u16,
+    pub ruleset: Ruleset,
+    // the KoRule moves are played with in place of the ruleset's, if set
+    pub ko_rule: Option<KoRule>,
+    // Zobrist hash of the stones of position, kept in step with it
+    hash: u64,
+    // hashes of every earlier position of the game, only recorded under positional superko
+    history: Vec<u64>,
+}
+
+// Identifiers of columns on the Go Board, used primarily for position notation
+#[derive(PartialEq, Debug, Eq, Hash, Clone, Copy)]
+pub enum ColumnIdentifier {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    J,
+    K,
+    L,
+    M,
+    N,
+    O,
+    P,
+    Q,
+    R,
+    S,
+    T,
+}
+
+// A structure that represents playable intersections on the Go Board
+#[derive(PartialEq, Debug, Eq, Hash, Clone, Copy)]
+pub struct Intersection {
+    column: ColumnIdentifier,
+    row: u16,
+}
+
+/*****************************************************\
+|****************    PRIVATE TYPES    ****************|
+\*****************************************************/
+
+// The State of a position index as stored in a Board, one byte per index with a fixed layout,
+// so the full Board scans of scoring and group search walk a densely packed position
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub(crate) enum Cell {
+    EMPTY = 0,
+    BLACK = 1,
+    WHITE = 2,
+    OFFBOARD = 3,
+}
+
+const _: () = assert!(std::mem::size_of::<Cell>() == 1);
+
+impl Cell {
+    // Converts the given State to the Cell storing it
+    pub(crate) fn from_state(state: State) -> Cell {
+        match state {
+            State::Empty => Cell::EMPTY,
+            State::Occupied(Color::Black) => Cell::BLACK,
+            State::Occupied(Color::White) => Cell::WHITE,
+            State::Offboard => Cell::OFFBOARD,
+        }
+    }
+
+    // The State stored in this Cell
+    #[inline]
+    pub(crate) fn state(self) -> State {
+        match self {
+            Cell::EMPTY => State::Empty,
+            Cell::BLACK => State::Occupied(Color::Black),
+            Cell::WHITE => State::Occupied(Color::White),
+            Cell::OFFBOARD => State::Offboard,
+        }
+    }
+}
+
+// Three state Option, where Yes is analogous to Some, No to None, and Unknown for a non-set state
+enum Tristate<T> {
+    Unknown,
+    Yes(T),
+    No,
+}
+
+impl<T> Tristate<T> {
+    // Returns true if this Tristate is Yes
+    fn is_yes(&self) -> bool {
+        matches!(self, Tristate::Yes(_))
+    }
+
+    // Attempts to retrieve the value stored inside this Tristate.
+    // Panics if impossible
+    fn unwrap(self) -> T {
+        match self {
+            Tristate::Yes(value) => value,
+            _ => panic!("Cannot unwrap Tristate that is not Tristate::Yes"),
+        }
+    }
+}
+
+/*****************************************************\
+|****************        SETUP        ****************|
+\*****************************************************/
+
+impl Board {
+    // Creates a new empty Board
+    pub fn new(size: BoardSize) -> Board {
+        let numeric_size = size.to_u16();
+        Board {
+            size,
+            position: Board::empty_board(numeric_size),
+            bits: BitBoard::new(&size),
+            side: Color::Black,
+            ko: None,
+            komi: 6.5,
+            last_move: Move::PASS,
+            white_captures: 0,
+            black_captures: 0,
+            move_number: 0,
+            ruleset: Ruleset::Japanese,
+            ko_rule: None,
+            hash: 0,
+            history: vec![],
+        }
+    }
+
+    // Creates a Vec<Cell> representing an empty Go board
+    fn empty_board(size: u16) -> Vec<Cell> {
+        let mut position: Vec<Cell> = vec![];
+        for row in 0..size + 2 {
+            for col in 0..size + 2 {
+                if row == 0 || row == size + 1 || col == 0 || col == size + 1 {
+                    position.push(Cell::OFFBOARD);
+                } else {
+                    position.push(Cell::EMPTY);
+                }
+            }
+        }
+
+        position
+    }
+
+    // Creates and returns a new identical Board to this one
+    // which has no aliasing nor relation to this Board
+    pub fn deepcopy(&self) -> Board {
+        let position_copy: Vec<Cell> = self.position.clone();
+
+        Board {
+            size: self.size,
+            position: position_copy,
+            bits: self.bits,
+            side: self.side,
+            ko: self.ko,
+            komi: self.komi,
+            last_move: self.last_move,
+            white_captures: self.white_captures,
+            black_captures: self.black_captures,
+            move_number: self.move_number,
+            ruleset: self.ruleset,
+            ko_rule: self.ko_rule,
+            hash: self.hash,
+            history: self.history.clone(),
+        }
+    }
+
+    // Makes this Board identical to the given one, reusing this Board's position allocation
+    pub(crate) fn copy_from(&mut self, other: &Board) {
+        self.position.clone_from(&other.position);
+        self.size = other.size;
+        self.bits = other.bits;
+        self.side = other.side;
+        self.ko = other.ko;
+        self.komi = other.komi;
+        self.last_move = other.last_move;
+        self.white_captures = other.white_captures;
+        self.black_captures = other.black_captures;
+        self.move_number = other.move_number;
+        self.ruleset = other.ruleset;
+        self.ko_rule = other.ko_rule;
+        self.hash = other.hash;
+        self.history.clone_from(&other.history);
+    }
+}
+
+impl Debug for Board {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // TODO: try to maybe find a way to add the /n before the }
+        f.debug_struct("Board")
+            .field("\n\tSize", &self.size)
+            .field("\n\tPosition", &self.position.iter().map(|cell| cell.state()).collect::<Vec<State>>())
+            .field("\n\tKo", &self.ko)
+            //.field("\n\tKomi", &self.komi)
+            .field("\n\tLast Move", &self.last_move)
+            .field("\n\tWhite Captures", &self.white_captures)
+            .field("\n\tBlack Captures", &self.black_captures)
+            .finish()
+    }
+}
+
+// Represents an intersection on the Go board
+impl Intersection {
+    // Creates a new Intersection with the given column and row
+    pub fn new(column: ColumnIdentifier, row: u16) -> Intersection {
+        Intersection { column, row }
+    }
+
+    // Returns the column of this Intersection
+    pub fn column(&self) -> ColumnIdentifier {
+        self.column
+    }
+
+    // Returns the row of this Intersection, counted from 1 at the bottom of the Board
+    pub fn row(&self) -> u16 {
+        self.row
+    }
+}
+
+// The Intersection of a Position of go-board, whose rows are counted from 0
+impl From<Position> for Intersection {
+    fn from(pos: Position) -> Intersection {
+        let column = ColumnIdentifier::from_u16(pos.col()).expect("Positions are within the 19 columns");
+        Intersection::new(column, pos.row() + 1)
+    }
+}
+
+// The Position of go-board of an Intersection of a Board, which every Intersection on a Board has
+impl From<Intersection> for Position {
+    fn from(intsc: Intersection) -> Position {
+        Position::new(intsc.row - 1, intsc.column.to_u16())
+    }
+}
+
+/****************************************************\
+|****************       HELPER       ****************|
+\****************************************************/
+
+// The Zobrist key of the given State at the given position index, where points without a stone
+// have no key so that the hash of a Board is the XOR of the keys of its stones
+// Keys are the splitmix64 mix of the index and color, the same on every run
+pub(crate) fn zobrist_key(position_index: usize, state: State) -> u64 {
+    let color_bit = match state {
+        State::Occupied(Color::Black) => 0,
+        State::Occupied(Color::White) => 1,
+        State::Empty | State::Offboard => return 0,
+    };
+
+    let mut key = ((position_index as u64) << 1 | color_bit).wrapping_add(0x9E37_79B9_7F4A_7C15);
+    key = (key ^ (key >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    key = (key ^ (key >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    key ^ (key >> 31)
+}
+
+// Adds the given i16 value to the base usize value.
+// If an underflow or overflow occurs, returns None.
+// Else, returns Some(sum as usize)
+pub(crate) fn add_signed_to_unsigned<U, S>(base: U, to_add: S) -> Option<U>
+where
+    U: Unsigned
+        + Copy
+        + Add<Output = U>
+        + Sub<Output = U>
+        + Bounded
+        + NumCast
+        + std::cmp::PartialOrd,
+    S: Signed + Copy + NumCast + std::cmp::PartialOrd,
+{
+    if to_add >= S::zero() {
+        let add_u: U = NumCast::from(to_add)?;
+        if U::max_value() - add_u < base {
+            return None;
+        }
+        Some(base + add_u)
+    } else {
+        let sub_u: U = NumCast::from(to_add.abs())?;
+        if sub_u > base {
+            return None;
+        }
+        Some(base - sub_u)
+    }
+}
+
+// Formats the given score, positive if Black wins, as a game result (ex. "B+3.5", or "0" for a draw)
+pub fn result_string(score: f64) -> String {
+    if score > 0.0 {
+        format!("B+{score}")
+    } else if score < 0.0 {
+        format!("W+{}", -score)
+    } else {
+        "0".to_string()
+    }
+}
+
+// Converts the given Move to its GTP vertex, ex. "D4", "pass", or "resign"
+pub fn move_to_vertex(mov: &Move) -> String {
+    match mov {
+        Move::MOVE(intsc, _) => Position::from(*intsc).to_string(),
+        Move::PASS => "pass".to_string(),
+        Move::RESIGN => "resign".to_string(),
+    }
+}
+
+// Converts the given GTP vertex played by the given Color into a Move, reading vertices and "pass" as
+// go-board parses Positions, and "resign"
+pub fn vertex_to_move(vertex: &str, color: Color) -> Option<Move> {
+    if vertex.eq_ignore_ascii_case("resign") {
+        return Some(Move::RESIGN);
+    }
+    match vertex.parse::<Position>() {
+        Ok(pos) => Some(Move::MOVE(pos.into(), color)),
+        Err(ParsePositionError::Pass) => Some(Move::PASS),
+        Err(_) => None,
+    }
+}
+
+impl ColumnIdentifier {
+    // Converts numeric column indices to their respective ColumnIdentifier
+    // TODO: seems messy, likely cleaner way to do this
+    pub fn from_u16(column_index: u16) -> Option<ColumnIdentifier> {
+        use ColumnIdentifier::*;
+        match column_index {
+            0 => Some(A),
+            1 => Some(B),
+            2 => Some(C),
+            3 => Some(D),
+            4 => Some(E),
+            5 => Some(F),
+            6 => Some(G),
+            7 => Some(H),
+            8 => Some(J),
+            9 => Some(K),
+            10 => Some(L),
+            11 => Some(M),
+            12 => Some(N),
+            13 => Some(O),
+            14 => Some(P),
+            15 => Some(Q),
+            16 => Some(R),
+            17 => Some(S),
+            18 => Some(T),
+            _ => None,
+        }
+    }
+
+    // Converts a ColumnIdentifier to its respective u16 column index
+    pub fn to_u16(&self) -> u16 {
+        use ColumnIdentifier::*;
+        match self {
+            A => 0,
+            B => 1,
+            C => 2,
+            D => 3,
+            E => 4,
+            F => 5,
+            G => 6,
+            H => 7,
+            J => 8,
+            K => 9,
+            L => 10,
+            M => 11,
+            N => 12,
+            O => 13,
+            P => 14,
+            Q => 15,
+            R => 16,
+            S => 17,
+            T => 18,
+        }
+    }
+}
+
+impl fmt::Display for ColumnIdentifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        use ColumnIdentifier::*;
+        write!(
+            f,
+            "{}",
+            match self {
+                A => "A",
+                B => "B",
+                C => "C",
+                D => "D",
+                E => "E",
+                F => "F",
+                G => "G",
+                H => "H",
+                J => "J",
+                K => "K",
+                L => "L",
+                M => "M",
+                N => "N",
+                O => "O",
+                P => "P",
+                Q => "Q",
+                R => "R",
+                S => "S",
+                T => "T",
+            }
+        )
+    }
+}
+
+impl Intersection {
+    // Converts this Intersection into its index in a position vector on the given BoardSize Board
+    pub fn to_position_index(&self, size: &BoardSize) -> Option<u16> {
+        let position_length = size.to_u16() + 2;
+        let column_index = self.column.to_u16();
+        if column_index >= size.to_u16() || self.row > size.to_u16() || self.row == 0 {
+            None
+        } else {
+            // row_index must be defined here in now impossible the event self.row is 0
+            let row_index = (position_length - self.row - 1) * position_length;
+            Some(column_index + row_index + 1)
+        }
+    }
+
+    // Given a position index on a Board, returns the Intersection that correlates to the
+    // index if valid for the given BoardSize. Else, returns None
+    pub(crate) fn from_position_index(
+        position_index: u16,
+        size: &BoardSize,
+    ) -> Option<Intersection> {
+        let position_length = size.to_u16() + 2;
+
+        if position_index >= position_length * position_length {
+            return None;
+        }
+
+        let col = position_index % position_length;
+        let row = position_index / position_length;
+
+        if col == 0 || col == position_length - 1 || row == 0 || row == position_length - 1 {
+            return None;
+        }
+
+        Some(Intersection {
+            column: ColumnIdentifier::from_u16(col - 1).unwrap(),
+            row: position_length - row - 1,
+        })
+    }
+}
+
+/*****************************************************\
+|****************      RENDERING      ****************|
+\*****************************************************/
+
+impl fmt::Display for Board {
+    // Writes each row with its number, the column letters, then the komi, ko, and captures,
+    // straight into the formatter
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let position_length = (self.size.to_u16() + 2) as usize;
+        writeln!(f)?;
+        for row in 1..position_length - 1 {
+            write!(f, "{:>2} ", position_length - row - 1)?;
+            for col in 1..position_length - 1 {
+                match self.state_at(row * position_length + col) {
+                    State::Occupied(Color::Black) => f.write_str("X ")?,
+                    State::Occupied(Color::White) => f.write_str("O ")?,
+                    State::Empty => f.write_str(". ")?,
+                    State::Offboard => {}
+                }
+            }
+            writeln!(f)?;
+        }
+
+        f.write_str("  ")?;
+        for col in 0..position_length as u16 - 2 {
+            write!(f, " {}", ColumnIdentifier::from_u16(col).unwrap())?;
+        }
+        write!(f, "\nKomi:     {}", self.komi)?;
+        match self.ko {
+            Some(intersection) => write!(f, "\nKo:       {intersection}")?,
+            None => f.write_str("\nKo:       None")?,
+        }
+        writeln!(
+            f,
+            "\nCaptures: [B: {}, W: {}]",
+            self.black_captures, self.white_captures
+        )
+    }
+}
+
+impl fmt::Display for Intersection {
+    // Writes the vertex of this Intersection as go-board writes its Position (ex. "D4")
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Position::from(*self))
+    }
+}
+
+impl fmt::Display for GameResult {
+    // Writes the result as in SGF (ex. "B+3.5", "W+R", or "0" for a draw)
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            GameResult::SCORE(score) => f.write_str(&result_string(*score)),
+            GameResult::RESIGNATION(Color::Black) => f.write_str("B+R"),
+            GameResult::RESIGNATION(Color::White) => f.write_str("W+R"),
+        }
+    }
+}
+
+impl fmt::Display for IllegalMove {
+    // Writes the reason as a single word (ex. "out_of_turn")
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            IllegalMove::OUTOFTURN => "out_of_turn",
+            IllegalMove::OFFBOARD => "off_board",
+            IllegalMove::OCCUPIED => "occupied",
+            IllegalMove::KO => "ko",
+            IllegalMove::SUICIDE => "suicide",
+        })
+    }
+}
+
+/****************************************************\
+|****************     GAME LOGIC     ****************|
+\****************************************************/
+
+impl Board {
+    // For a group of stones starting at the given position_index,
+    // returns a tuple of HashSet<Intersections> containing the stones in the group
+    // and the group's liberties respectively
+    // If the position_index is empty, the group is empty and its only liberty is itself
+    pub(crate) fn count(
+        &self,
+        position_index: usize,
+        color: Color,
+    ) -> (HashSet<Intersection>, HashSet<Intersection>) {
+        let (group, liberties) = match self.state_at(position_index) {
+            State::Empty => (BitSet::default(), BitSet::single(position_index)),
+            _ => {
+                let group = self.bits.group(position_index, color);
+                let liberties = self.bits.liberties(&group);
+                (group, liberties)
+            }
+        };
+
+        (self.intersections(&group), self.intersections(&liberties))
+    }
+
+    // Converts the given set of position indices into the Intersections they represent
+    fn intersections(&self, set: &BitSet) -> HashSet<Intersection> {
+        set.indices()
+            .filter_map(|index| Intersection::from_position_index(index as u16, &self.size))
+            .collect()
+    }
+
+    // The State at the given position index
+    #[inline]
+    fn state_at(&self, position_index: usize) -> State {
+        self.position[position_index].state()
+    }
+
+    // Sets the State at the given position index, keeping the bitsets and hash in step
+    fn set_state(&mut self, position_index: usize, state: State) {
+        self.hash ^= zobrist_key(position_index, self.state_at(position_index)) ^ zobrist_key(position_index, state);
+        self.position[position_index] = Cell::from_state(state);
+        self.bits.set(position_index, state);
+    }
+
+    // Captures the stones found in the given group, setting each intersection in the board position
+    // to State::Empty and incrementing the appropriate Board capture field by the number of
+    // stones captured
+    pub(crate) fn capture_group(&mut self, group: HashSet<Intersection>, color: Color) {
+        let stones = group.len() as u16;
+
+        for intsc in group {
+            if let Some(stone) = intsc.to_position_index(&self.size) {
+                self.set_state(stone as usize, State::Empty);
+            }
+        }
+
+        match color {
+            Color::White => self.white_captures += stones,
+            Color::Black => self.black_captures += stones,
+        }
+    }
+
+    // If there is a diamond shape completely surrounding the given Intersection on this Board,
+    // return an Option containing its color. Else, return None
+    pub(crate) fn diamond(&self, intsc: &Intersection) -> Option<Color> {
+        if let Some(position_index) = intsc.to_position_index(&self.size) {
+            let mut diamond_color: Option<Color> = None;
+            let numeric_size = self.size.to_u16() as i16;
+
+            for dir in [1, -1, numeric_size + 2, -numeric_size - 2] {
+                let surrounding_position_index =
+                    add_signed_to_unsigned(position_index as usize, dir);
+                if let Some(surrounding_position_index) = surrounding_position_index {
+                    match self.state_at(surrounding_position_index) {
+                        State::Empty => return None,
+                        State::Occupied(color) => match diamond_color {
+                            Some(cur_color) => {
+                                if cur_color != color {
+                                    return None;
+                                }
+                            }
+                            None => diamond_color = Some(color),
+                        },
+                        State::Offboard => {}
+                    }
+                }
+            }
+            return diamond_color;
+        };
+        None
+    }
+
+    // Attempts to play the given Move on this Board. If successful, updates the current Board
+    // accordingly and returns true. Else returns false.
+    pub fn play(&mut self, mov: Move) -> bool {
+        use Move::*;
+        let was_move_played = match mov {
+            PASS => {
+                self.ko = None; // the ko may be retaken after a pass
+                true
+            }
+            MOVE(intersection, color) => self.play_intersection(intersection, color),
+            RESIGN => false,
+        };
+        
+        if was_move_played {
+            self.move_number += 1;
+        }
+        
+        was_move_played
+    }
+
+    // Checks whether a stone of the given Color may be played at the given Intersection, without
+    // playing it
+    // Returns Ok if it may, else the reason it may not
+    pub fn check_move(&self, intsc: &Intersection, color: Color) -> Result<(), IllegalMove> {
+        let position_index = intsc.to_position_index(&self.size).ok_or(IllegalMove::OFFBOARD)? as usize;
+        if self.state_at(position_index) != State::Empty {
+            return Err(IllegalMove::OCCUPIED);
+        }
+
+        let superko = self.ko_rule.unwrap_or(self.ruleset.ko_rule()) == KoRule::PositionalSuperko;
+        if self.ko.as_ref() == Some(intsc) || (superko && self.recreates_earlier_position(position_index, color)) {
+            return Err(IllegalMove::KO);
+        }
+
+        // a stone on an empty point outside of ko can only be refused for taking its last liberty
+        match self.deepcopy().play_intersection(*intsc, color) {
+            true => Ok(()),
+            false => Err(IllegalMove::SUICIDE),
+        }
+    }
+
+    // Attempts to play a stone of the given Color and the given Intersection on this Board.
+    // If successful, updates this Board accordingly and returns true. Else returns false.
+    fn play_intersection(&mut self, intsc: Intersection, color: Color) -> bool {
+        //TODO: absolutely update this function with helpers, completely unreadable in current state
+        if let Some(ko) = self.ko.as_ref() {
+            if ko == &intsc {
+                return false;
+            }
+        }
+
+        if let Some(position_index_u16) = intsc.to_position_index(&self.size) {
+            let position_index = position_index_u16 as usize;
+            if self.state_at(position_index) != State::Empty {
+                return false;
+            }
+
+            let superko = self.ko_rule.unwrap_or(self.ruleset.ko_rule()) == KoRule::PositionalSuperko;
+            if superko && self.recreates_earlier_position(position_index, color) {
+                return false;
+            }
+
+            let mut new_ko: Option<Intersection> = None;
+            let previous_hash = self.hash;
+
+            self.set_state(position_index, State::Occupied(color));
+
+            // capture logic, where adjacent opponent groups without liberties are removed
+            let numeric_size = self.size.to_u16() as i16;
+            for dir in [1, -1, numeric_size + 2, -numeric_size - 2] {
+                if let Some(surrounding_intsc_index) = add_signed_to_unsigned(position_index, dir) {
+                    let group_bits = self.bits.group(surrounding_intsc_index, color.opposite_color());
+                    if !group_bits.is_empty() && self.bits.liberties(&group_bits).is_empty() {
+                        if group_bits.len() == 1 {
+                            // ensures not OFFBOARD for diamond check
+                            let surrounding_intsc = Intersection::from_position_index(
+                                surrounding_intsc_index as u16,
+                                &self.size,
+                            )
+                            .unwrap();
+                            if let Some(surrounding_color) = self.diamond(&intsc) {
+                                if surrounding_color != color {
+                                    new_ko = Some(surrounding_intsc);
+                                }
+                            }
+                        }
+                        self.capture_group(self.intersections(&group_bits), color);
+                    }
+                }
+            }
+
+            // ensure not suicide, unless the ruleset lets a group of several stones capture itself
+            let played_group = self.bits.group(position_index, color);
+            if self.bits.liberties(&played_group).is_empty() {
+                if !self.ruleset.allows_suicide() || played_group.len() == 1 {
+                    self.set_state(position_index, State::Empty);
+                    return false;
+                }
+                self.capture_group(self.intersections(&played_group), color.opposite_color());
+            }
+
+            // move goes through
+            if superko {
+                self.history.push(previous_hash);
+            }
+            self.ko = new_ko;
+            self.side = color.opposite_color();
+            self.last_move = Move::MOVE(intsc, color);
+
+            return true;
+        };
+        false
+    }
+
+    // Would playing a stone of the given Color at the given empty position index recreate an earlier
+    // position of the game? The position after the move is hashed without playing it, from the
+    // opposing groups whose only liberty is the played point, or else the group the move would
+    // capture itself with when suicide is allowed
+    fn recreates_earlier_position(&self, position_index: usize, color: Color) -> bool {
+        let opponent = color.opposite_color();
+        let point = BitSet::single(position_index);
+        let mut captured = BitSet::default();
+        let mut joined = BitSet::default();
+        for neighbor in self.bits.neighbors(&point).indices() {
+            let group = self.bits.group(neighbor, opponent);
+            if !group.is_empty() && self.bits.liberties(&group) == point {
+                captured = captured.or(&group);
+            }
+            joined = joined.or(&self.bits.group(neighbor, color));
+        }
+
+        if captured.is_empty() && self.ruleset.allows_suicide() && self.bits.liberties(&joined.or(&point)).is_empty() {
+            // the played stone and the stones it joins are all removed
+            let hash = joined.indices().fold(self.hash, |hash, index| hash ^ zobrist_key(index, State::Occupied(color)));
+            return self.history.contains(&hash);
+        }
+
+        let hash = captured
+            .indices()
+            .fold(self.hash ^ zobrist_key(position_index, State::Occupied(color)), |hash, index| {
+                hash ^ zobrist_key(index, State::Occupied(opponent))
+            });
+        self.history.contains(&hash)
+    }
+
+    // Places a stone of the given Color at the given Intersection outside of play, as a setup or
+    // handicap stone, without capturing
+    // Returns false if the Intersection is not an empty point of this Board
+    pub fn place_stone(&mut self, intsc: &Intersection, color: Color) -> bool {
+        match intsc.to_position_index(&self.size) {
+            Some(position_index) if self.state_at(position_index as usize) == State::Empty => {
+                self.set_state(position_index as usize, State::Occupied(color));
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/*******************************************************\
+|****************        SCORING        ****************|
+\*******************************************************/
+
+impl Board {
+    // Estimates the score at the end of the Go game on this Board by Tromp-Taylor area counting,
+    // positive if Black leads
+    pub fn estimate_score(&self) -> f64 {
+        self.bits.area_difference() as f64 - self.komi
+    }
+
+    // The position indices in the Tromp-Taylor areas of Black and White on this Board
+    pub(crate) fn area_owners(&self) -> (BitSet, BitSet) {
+        self.bits.areas()
+    }
+
+    fn tromp_taylor_count(
+        &self,
+        root_intsc: Intersection,
+    ) -> (HashSet<Intersection>, Tristate<Color>) {
+        use Tristate::*;
+        let mut intsc_seen: HashSet<Intersection> = HashSet::new();
+        let mut reaches_color: Tristate<Color> = Unknown;
+        let mut work_list: VecDeque<Intersection> = VecDeque::new();
+        work_list.push_back(root_intsc);
+
+        while !work_list.is_empty() {
+            let intsc = work_list.pop_front().unwrap(); // work_list is not empty, safe
+            if !intsc_seen.contains(&intsc) {
+                let intsc_index = intsc.to_position_index(&self.size).unwrap(); // later logic ensures safety
+                let intsc_state = self.state_at(intsc_index as usize);
+
+                match intsc_state {
+                    State::Offboard => {}
+                    State::Occupied(color) => {
+                        reaches_color = match reaches_color {
+                            Unknown => Yes(color),
+                            Yes(reached_color) => {
+                                if color == reached_color {
+                                    Yes(color)
+                                } else {
+                                    No
+                                }
+                            }
+                            No => No,
+                        }
+                    }
+                    State::Empty => {
+                        work_list.extend(self.neighboring_intersections(&intsc));
+                    }
+                }
+
+                intsc_seen.insert(intsc);
+            }
+        }
+
+        (intsc_seen, reaches_color)
+    }
+
+    // Finds the stones on this Board that are dead at the end of the game
+    // Runs DEAD_STONE_PLAYOUTS quick playouts from this position, and a chain is dead when the
+    // points it stands on mostly end up in the opponent's area, as its stones are captured in them
+    pub(crate) fn dead_stones(&self) -> HashSet<Intersection> {
+        let ownership = settled_ownership(self, self.side, DEAD_STONE_PLAYOUTS);
+        let mut dead: HashSet<Intersection> = HashSet::new();
+        let mut intsc_seen: HashSet<Intersection> = HashSet::new();
+
+        for index in 0..self.position.len() {
+            if let State::Occupied(color) = self.state_at(index) {
+                let intsc = Intersection::from_position_index(index as u16, &self.size).unwrap();
+                if !intsc_seen.contains(&intsc) {
+                    let (group, _) = self.count(index, color);
+                    intsc_seen.extend(group.iter().copied());
+
+                    // ownership is positive for Black, so flip it to favour the chain's own Color
+                    let sign = if color == Color::Black { 1.0 } else { -1.0 };
+                    let owned: f64 = group.iter().filter_map(|stone| ownership.at(stone)).sum();
+                    if sign * owned / group.len() as f64 <= -DEAD_STONE_OWNERSHIP {
+                        dead.extend(group);
+                    }
+                }
+            }
+        }
+
+        dead
+    }
+
+    // Scores the finished game on this Board with the given ScoringRules, after removing dead stones
+    // Returns the margin of the game, positive if Black wins
+    pub fn final_score(&self, method: ScoringRules) -> f64 {
+        let mut scored = self.deepcopy();
+        for intsc in self.dead_stones() {
+            let index = intsc.to_position_index(&self.size).unwrap() as usize;
+            match scored.state_at(index) {
+                State::Occupied(Color::Black) => scored.white_captures += 1,
+                State::Occupied(Color::White) => scored.black_captures += 1,
+                _ => {}
+            }
+            scored.set_state(index, State::Empty);
+        }
+
+        match method {
+            ScoringRules::Area => scored.estimate_score(),
+            ScoringRules::Territory => scored.territory_score(),
+        }
+    }
+
+    // Counts the territory of each color plus the prisoners each color captured, minus komi
+    fn territory_score(&self) -> f64 {
+        let mut intsc_seen: HashSet<Intersection> = HashSet::new();
+        let mut black_points = self.black_captures as i16;
+        let mut white_points = self.white_captures as i16;
+
+        for index in 0..self.position.len() {
+            if self.state_at(index) == State::Empty {
+                let intsc = Intersection::from_position_index(index as u16, &self.size).unwrap();
+                if !intsc_seen.contains(&intsc) {
+                    let (region, reaches_color) = self.tromp_taylor_count(intsc);
+                    let territory: Vec<Intersection> = region
+                        .into_iter()
+                        .filter(|region_intsc| {
+                            self.state_at(region_intsc.to_position_index(&self.size).unwrap() as usize)
+                                == State::Empty
+                        })
+                        .collect();
+                    if reaches_color.is_yes() {
+                        match reaches_color.unwrap() {
+                            Color::Black => black_points += territory.len() as i16,
+                            Color::White => white_points += territory.len() as i16,
+                        }
+                    }
+                    intsc_seen.extend(territory);
+                }
+            }
+        }
+
+        (black_points - white_points) as f64 - self.komi
+    }
+
+    fn neighboring_intersections(&self, intsc: &Intersection) -> Vec<Intersection> {
+        let mut neighbors: Vec<Intersection> = vec![];
+        if let Some(index) = intsc.to_position_index(&self.size) {
+            let numeric_size = self.size.to_u16() as i16;
+            for dir in [1, -1, numeric_size + 2, -numeric_size - 2] {
+                if let Some(neighbor) = Intersection::from_position_index(
+                    add_signed_to_unsigned(index, dir).unwrap(),
+                    &self.size,
+                ) {
+                    neighbors.push(neighbor);
+                }
+            }
+        }
+
+        neighbors
+    }
+}
+
+impl Board {
+    // Returns the Intersections of every stone of the given Color on this Board
+    pub fn stones(&self, color: Color) -> Vec<Intersection> {
+        (0..self.position.len())
+            .filter(|index| self.state_at(*index) == State::Occupied(color))
+            .map(|index| Intersection::from_position_index(index as u16, &self.size).unwrap())
+            .collect()
+    }
+
+    // Returns the Intersections of every empty point on this Board
+    pub fn empty_intersections(&self) -> Vec<Intersection> {
+        (0..self.position.len())
+            .filter(|index| self.state_at(*index) == State::Empty)
+            .map(|index| Intersection::from_position_index(index as u16, &self.size).unwrap())
+            .collect()
+    }
+
+    // Returns the States of the 8 intersections surrounding the given Intersection, row by row
+    // from the top left, where points past the edge are OFFBOARD
+    // Returns None if the Intersection is not on this Board
+    pub(crate) fn neighborhood(&self, intsc: &Intersection) -> Option<[State; 8]> {
+        let index = intsc.to_position_index(&self.size)? as usize;
+        let row_length = self.size.to_u16() as usize + 2;
+        Some([
+            self.state_at(index - row_length - 1),
+            self.state_at(index - row_length),
+            self.state_at(index - row_length + 1),
+            self.state_at(index - 1),
+            self.state_at(index + 1),
+            self.state_at(index + row_length - 1),
+            self.state_at(index + row_length),
+            self.state_at(index + row_length + 1),
+        ])
+    }
+
+    // Returns the number of stones both colors have captured on this Board
+    pub(crate) fn total_captures(&self) -> u16 {
+        self.black_captures + self.white_captures
+    }
+
+    // Returns the States of every point on this Board, as rows from the top
+    pub fn rows(&self) -> Vec<Vec<State>> {
+        let row_length = self.size.to_u16() as usize + 2;
+        (1..row_length - 1)
+            .map(|row| (1..row_length - 1).map(|col| self.state_at(row * row_length + col)).collect())
+            .collect()
+    }
+
+    // Returns the Intersection the last capture forbids retaking a ko at, if any
+    pub fn ko(&self) -> Option<Intersection> {
+        self.ko
+    }
+}
+
+/*******************************************************\
+|****************  PLAYOUT (TEMPORARY)  ****************|
+\*******************************************************/
+
+impl Board {
+    // Finds the weakest group on the board, and returns its liberties
+    // todo: TEMP METHOD, perhaps add better logic
+    pub(crate) fn weakest_group(&self, color: &Color) -> Vec<Intersection> {
+        let mut liberties: Vec<Intersection> = vec![];
+        let mut smallest_size: usize = 1000; // actually needed for when liberties is empty as init
+        let mut intsc_seen: HashSet<Intersection> = HashSet::new();
+
+        for index in 0..self.position.len() {
+            if self.state_at(index) == State::Occupied(*color) {
+                let intsc = Intersection::from_position_index(index as u16, &self.size).unwrap();
+                if !intsc_seen.contains(&intsc) {
+                    let (group, group_libs) = self.count(index, *color);
+
+                    for group_intsc in group {
+                        intsc_seen.insert(group_intsc);
+                    }
+
+                    if group_libs.len() < smallest_size {
+                        smallest_size = group_libs.len();
+                        liberties = group_libs.into_iter().collect();
+                    }
+                }
+            }
+        }
+
+        // in board order rather than hashing order, so that seeded searches repeat
+        liberties.sort_by_key(|intsc| intsc.to_position_index(&self.size));
+        liberties
+    }
+
+    // Returns a random intersection found on this Board
+    // todo: TEMP METHOD
+    pub(crate) fn random_intersection(&self, offset: u16) -> Intersection {
+        use rand::Rng;
+        let mut moves: Vec<Intersection> = vec![];
+        for row in 1 + offset..self.size.to_u16() - offset {
+            for col in 1 + offset..self.size.to_u16() - offset {
+                let col_iden = ColumnIdentifier::from_u16(col).unwrap();
+                moves.push(Intersection::new(col_iden, row));
+            }
+        }
+
+        let ind = engine_rng().gen_range(0..moves.len());
+        moves[ind]
+    }
+
+    // Ensures playing a stone at this position is not suicide
+    // todo: TEMP METHOD AND ITS ALSO REALLY BAD
+    pub(crate) fn not_suicide(&self, intsc: &Intersection) -> bool {
+        if let Some(position_index) = intsc.to_position_index(&self.size) {
+            let mut liberties = 0;
+            let numeric_size = self.size.to_u16() as i16;
+            for dir in [1, -1, numeric_size, -numeric_size] {
+                let neighbor = add_signed_to_unsigned(position_index as usize, dir);
+                if neighbor.is_some() && self.state_at(neighbor.unwrap()) == State::Empty {
+                    liberties += 1;
+                }
+            }
+
+            self.state_at(position_index as usize) == State::Empty
+                && liberties > 0
+                && Some(intsc) != self.ko.as_ref()
+        } else {
+            false
+        }
+    }
+
+    // Is it possible to place a stone at the given Intersection on this Board?
+    pub(crate) fn can_place_stone_at(&self, intsc: &Intersection) -> bool {
+        if let Some(position_index) = intsc.to_position_index(&self.size) {
+            self.state_at(position_index as usize) == State::Empty && self.not_suicide(intsc)
+        } else {
+            false
+        }
+    }
+}
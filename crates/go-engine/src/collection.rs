@@ -0,0 +1,363 @@
+use super::*;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+/******************************************************\
+|****************      CONSTANTS       ****************|
+\******************************************************/
+
+// First line of every index file, identifying its format
+const INDEX_HEADER: &str = "go-engine-index 1";
+
+// Offset basis and prime of the 64 bit FNV-1a hash
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/****************************************************\
+|****************    GLOBAL TYPES    ****************|
+\****************************************************/
+
+// Settings of indexing a directory of SGF games, parsed from the command line
+pub(crate) struct IndexSettings {
+    directory: PathBuf,
+    output: Option<PathBuf>,
+}
+
+// Settings of a search for a position or local pattern, parsed from the command line
+pub(crate) struct SearchSettings {
+    query: PathBuf,
+    target: SearchTarget,
+}
+
+// A position reached in a game of a collection
+#[derive(Debug, PartialEq)]
+pub(crate) struct GameReference {
+    pub(crate) file: String,
+    pub(crate) move_number: usize,
+}
+
+// The stones of a position as (column, row, color), with columns and rows counted from 0
+pub(crate) type Stones = Vec<(u16, u16, Color)>;
+
+/*****************************************************\
+|****************    PRIVATE TYPES    ****************|
+\*****************************************************/
+
+// Where a search looks for its query
+enum SearchTarget {
+    // whole positions, looked up in an index file
+    INDEX(PathBuf),
+    // local patterns, matched by scanning a directory of games
+    PATTERN(PathBuf),
+}
+
+/*****************************************************\
+|****************        SETUP        ****************|
+\*****************************************************/
+
+impl IndexSettings {
+    // Parses index settings from the arguments following the index run mode
+    // Accepts "<directory> --output <file>", where the output defaults to "<directory>/positions.idx"
+    pub(crate) fn from_args(args: &[String]) -> Result<IndexSettings, String> {
+        let mut directory: Option<PathBuf> = None;
+        let mut output: Option<PathBuf> = None;
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--output" => {
+                    let value = args.next().ok_or("No value given to index option --output")?;
+                    output = Some(PathBuf::from(value));
+                }
+                _ if directory.is_none() => directory = Some(PathBuf::from(arg)),
+                _ => return Err(format!("Unexpected index argument given: {arg}")),
+            }
+        }
+
+        Ok(IndexSettings {
+            directory: directory.ok_or("No directory of SGF games given to index")?,
+            output,
+        })
+    }
+}
+
+impl SearchSettings {
+    // Parses search settings from the arguments following the search run mode
+    // Accepts "<query.sgf> --index <file>" to find the query's final position in an index, or
+    // "<query.sgf> --pattern <directory>" to find the query's stones as a local pattern
+    pub(crate) fn from_args(args: &[String]) -> Result<SearchSettings, String> {
+        let mut query: Option<PathBuf> = None;
+        let mut target: Option<SearchTarget> = None;
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--index" | "--pattern" => {
+                    let value = args
+                        .next()
+                        .ok_or(format!("No value given to search option {arg}"))?;
+                    target = Some(match arg.as_str() {
+                        "--index" => SearchTarget::INDEX(PathBuf::from(value)),
+                        _ => SearchTarget::PATTERN(PathBuf::from(value)),
+                    });
+                }
+                _ if query.is_none() => query = Some(PathBuf::from(arg)),
+                _ => return Err(format!("Unexpected search argument given: {arg}")),
+            }
+        }
+
+        Ok(SearchSettings {
+            query: query.ok_or("No query SGF given to search")?,
+            target: target.ok_or("Search requires --index <file> or --pattern <directory>")?,
+        })
+    }
+}
+
+/****************************************************\
+|****************       HELPER       ****************|
+\****************************************************/
+
+// Lists the SGF files directly inside the given directory, in order
+fn sgf_files_in(directory: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = fs::read_dir(directory)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("sgf")))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+// Collects the stones of the given Board
+pub(crate) fn stones_of(board: &Board) -> Stones {
+    let numeric_size = board.size.to_u16();
+    [Color::Black, Color::White]
+        .into_iter()
+        .flat_map(|color| {
+            board.stones(color).into_iter().map(move |intsc| {
+                (intsc.column().to_u16(), intsc.row() - 1, color)
+            })
+        })
+        .filter(|(column, row, _)| *column < numeric_size && *row < numeric_size)
+        .collect()
+}
+
+// Maps the given column and row through the given one of the 8 symmetries of a square board
+fn transform(symmetry: u8, column: u16, row: u16, size: u16) -> (u16, u16) {
+    let last = size - 1;
+    match symmetry {
+        0 => (column, row),
+        1 => (last - column, row),
+        2 => (column, last - row),
+        3 => (last - column, last - row),
+        4 => (row, column),
+        5 => (last - row, column),
+        6 => (row, last - column),
+        _ => (last - row, last - column),
+    }
+}
+
+// Hashes the given stones on a board of the given size, independent of their order
+fn hash_stones(stones: &Stones, size: u16) -> u64 {
+    let mut keys: Vec<u32> = stones
+        .iter()
+        .map(|(column, row, color)| {
+            let point = (*row as u32) * size as u32 + *column as u32;
+            point * 2 + matches!(color, Color::White) as u32
+        })
+        .collect();
+    keys.sort_unstable();
+
+    let mut hash = FNV_OFFSET;
+    for byte in std::iter::once(size as u32).chain(keys).flat_map(u32::to_le_bytes) {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+// Hashes the given stones on a board of the given size identically for all 8 symmetries
+pub(crate) fn canonical_hash(stones: &Stones, size: u16) -> u64 {
+    (0..8)
+        .map(|symmetry| {
+            let transformed: Stones = stones
+                .iter()
+                .map(|(column, row, color)| {
+                    let (column, row) = transform(symmetry, *column, *row, size);
+                    (column, row, *color)
+                })
+                .collect();
+            hash_stones(&transformed, size)
+        })
+        .min()
+        .unwrap() // there are always 8 symmetries
+}
+
+// Checks whether the given pattern appears on the given position under any symmetry
+// Within the bounding box of the pattern, grown by one point, the position must hold exactly
+// the pattern's stones, so the empty points around a pattern are part of it
+pub(crate) fn matches_pattern(pattern: &Stones, position: &Stones, size: u16) -> bool {
+    if pattern.is_empty() {
+        return false;
+    }
+
+    let occupant: HashMap<(u16, u16), Color> = position
+        .iter()
+        .map(|(column, row, color)| ((*column, *row), *color))
+        .collect();
+
+    (0..8).any(|symmetry| {
+        let transformed: Vec<(u16, u16, Color)> = pattern
+            .iter()
+            .map(|(column, row, color)| {
+                let (column, row) = transform(symmetry, *column, *row, size);
+                (column, row, *color)
+            })
+            .collect();
+        let expected: HashMap<(u16, u16), Color> = transformed
+            .iter()
+            .map(|(column, row, color)| ((*column, *row), *color))
+            .collect();
+
+        let min_column = transformed.iter().map(|stone| stone.0).min().unwrap().saturating_sub(1);
+        let max_column = (transformed.iter().map(|stone| stone.0).max().unwrap() + 1).min(size - 1);
+        let min_row = transformed.iter().map(|stone| stone.1).min().unwrap().saturating_sub(1);
+        let max_row = (transformed.iter().map(|stone| stone.1).max().unwrap() + 1).min(size - 1);
+
+        (min_column..=max_column).all(|column| {
+            (min_row..=max_row).all(|row| occupant.get(&(column, row)) == expected.get(&(column, row)))
+        })
+    })
+}
+
+/*****************************************************\
+|****************      INDEXING       ****************|
+\*****************************************************/
+
+// Builds the index of every position of every game in the given directory, mapping canonical
+// position hashes to the games and move numbers they occur at
+pub(crate) fn build_index(directory: &Path) -> io::Result<HashMap<u64, Vec<GameReference>>> {
+    let mut index: HashMap<u64, Vec<GameReference>> = HashMap::new();
+
+    for file in sgf_files_in(directory)? {
+        let record = read_sgf_file(&file)?;
+        let positions = match record.positions() {
+            Ok(positions) => positions,
+            Err(message) => {
+                tracing::warn!(file = %file.display(), "Skipping game: {message}");
+                continue;
+            }
+        };
+
+        let name = file.file_name().unwrap_or_default().to_string_lossy().to_string();
+        for (move_number, (board, _)) in positions.iter().enumerate() {
+            let hash = canonical_hash(&stones_of(board), board.size.to_u16());
+            index.entry(hash).or_default().push(GameReference {
+                file: name.clone(),
+                move_number,
+            });
+        }
+    }
+
+    Ok(index)
+}
+
+// Writes the given index as lines of "<hash> <move number> <file>"
+fn write_index(index: &HashMap<u64, Vec<GameReference>>, path: &Path) -> io::Result<()> {
+    let mut lines: Vec<String> = index
+        .iter()
+        .flat_map(|(hash, references)| {
+            references
+                .iter()
+                .map(move |reference| format!("{hash:016x} {} {}", reference.move_number, reference.file))
+        })
+        .collect();
+    lines.sort();
+
+    fs::write(path, format!("{INDEX_HEADER}\n{}\n", lines.join("\n")))
+}
+
+// Reads the games and move numbers recorded for the given hash in the index file at the given path
+fn lookup_index(path: &Path, hash: u64) -> io::Result<Vec<GameReference>> {
+    let index = fs::read_to_string(path)?;
+    let invalid = || io::Error::new(io::ErrorKind::InvalidData, format!("{} is not an index file", path.display()));
+    let mut lines = index.lines();
+    if lines.next() != Some(INDEX_HEADER) {
+        return Err(invalid());
+    }
+
+    let key = format!("{hash:016x}");
+    lines
+        .filter(|line| line.starts_with(&key))
+        .map(|line| {
+            let mut columns = line.splitn(3, ' ').skip(1);
+            let move_number = columns.next().and_then(|number| number.parse().ok()).ok_or_else(invalid)?;
+            let file = columns.next().ok_or_else(invalid)?.to_string();
+            Ok(GameReference { file, move_number })
+        })
+        .collect()
+}
+
+// Finds every position of every game in the given directory containing the given local pattern
+fn scan_for_pattern(directory: &Path, pattern: &Stones, size: BoardSize) -> io::Result<Vec<GameReference>> {
+    let mut found: Vec<GameReference> = vec![];
+
+    for file in sgf_files_in(directory)? {
+        let Ok(positions) = read_sgf_file(&file)?.positions() else {
+            continue;
+        };
+
+        let name = file.file_name().unwrap_or_default().to_string_lossy().to_string();
+        for (move_number, (board, _)) in positions.iter().enumerate() {
+            if board.size == size && matches_pattern(pattern, &stones_of(board), size.to_u16()) {
+                found.push(GameReference { file: name.clone(), move_number });
+            }
+        }
+    }
+
+    Ok(found)
+}
+
+/********************************************************\
+|****************     PUBLIC METHODS     ****************|
+\********************************************************/
+
+// Indexes every position of the SGF games in the settings directory, writing the index file
+pub(crate) fn run_index(settings: IndexSettings) -> io::Result<()> {
+    let index = build_index(&settings.directory)?;
+    let output = settings
+        .output
+        .unwrap_or_else(|| settings.directory.join("positions.idx"));
+    write_index(&index, &output)?;
+
+    println!(
+        "Indexed {} positions ({} distinct) to {}",
+        index.values().map(Vec::len).sum::<usize>(),
+        index.len(),
+        output.display()
+    );
+    Ok(())
+}
+
+// Finds the games containing the final position of the query SGF, or its stones as a local
+// pattern, and prints the game and move number of each occurrence
+pub(crate) fn run_search(settings: SearchSettings) -> io::Result<()> {
+    let record = read_sgf_file(&settings.query)?;
+    let (board, _) = record
+        .positions()
+        .map_err(|message| io::Error::new(io::ErrorKind::InvalidData, message))?
+        .pop()
+        .unwrap(); // the final position always exists
+    let stones = stones_of(&board);
+
+    let found = match &settings.target {
+        SearchTarget::INDEX(index) => lookup_index(index, canonical_hash(&stones, board.size.to_u16()))?,
+        SearchTarget::PATTERN(directory) => scan_for_pattern(directory, &stones, board.size)?,
+    };
+
+    for reference in &found {
+        println!("{} after move {}", reference.file, reference.move_number);
+    }
+    println!("Found {} occurrences", found.len());
+    Ok(())
+}
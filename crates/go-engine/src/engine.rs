@@ -0,0 +1,1003 @@
+use super::*;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+use thunderdome::*;
+/******************************************************\
+|****************      CONSTANTS       ****************|
+\******************************************************/
+
+// Estimated score lead, in points, past which the engine resigns a lost position, unless changed
+// in the SearchParams
+const RESIGNATION_THRESHOLD: f64 = 60.0;
+
+// Weight of exploring less visited moves against the win rate of a move in the UCT formula,
+// unless changed in the SearchParams
+const EXPLORATION_CONSTANT: f64 = std::f64::consts::SQRT_2;
+
+// MCTS iterations of a search without time limits, unless changed in the SearchParams
+const DEFAULT_ITERATIONS: u16 = 30;
+
+// Most moves of the principal variation a search reports its progress with
+const PV_LENGTH: usize = 10;
+
+// Opening points on a 19x19 board the engine chooses its first moves from, and its free
+// handicap stones from past the star points
+const FUSEKI: [(ColumnIdentifier, u16); 20] = {
+    use ColumnIdentifier::*;
+    [
+        (D, 4), (Q, 4), (Q, 16), (F, 17), (C, 14), (F, 3), (C, 6), (R, 6), (O, 3), (R, 14),
+        (O, 17), (C, 10), (R, 10), (K, 17), (K, 3), (E, 10), (P, 10), (K, 15), (K, 5), (K, 10),
+    ]
+};
+
+// Threads used by batches of searches, playouts, and scoring, where 0 uses every available core
+static SEARCH_THREADS: AtomicUsize = AtomicUsize::new(0);
+
+// MCTS iterations and microseconds searched by every move generated, and the iterations per
+// second of the last search, as the bits of an f64
+static SEARCHED_ITERATIONS: AtomicU64 = AtomicU64::new(0);
+static SEARCHED_MICROS: AtomicU64 = AtomicU64::new(0);
+static LAST_SEARCH_RATE: AtomicU64 = AtomicU64::new(0);
+
+/******************************************************\
+|****************     GLOBAL TYPES     ****************|
+\******************************************************/
+
+// The engine's evaluation of a position after searching it
+#[derive(Copy, Clone)]
+pub struct Analysis {
+    // The move the engine would play
+    pub best_move: Move,
+    // The estimated probability that the Color to play wins
+    pub winrate: f64,
+    // The average playout score, positive when Black leads
+    pub score: f64,
+    // The number of visits given to the best move
+    pub visits: u16,
+}
+
+// The state of a search in progress: the move it would play so far, with its visits, the
+// estimated probability that the Color to play wins by it, and the moves expected to follow it
+pub struct SearchProgress {
+    pub iterations: u32,
+    pub best_move: Move,
+    pub visits: u16,
+    pub winrate: f64,
+    // the best move, then the most visited reply to each move in turn
+    pub pv: Vec<Move>,
+}
+
+// A move of a search tree, with the visits it was given, the estimated probability that the Color
+// playing it wins, and the moves searched after it, most visited first
+pub struct SearchNode {
+    pub mov: Move,
+    pub visits: u16,
+    pub winrate: f64,
+    pub children: Vec<SearchNode>,
+}
+
+// Reports the progress of a search to whoever waits on it, at most once per interval
+pub struct ProgressReport<'a> {
+    pub interval: Duration,
+    pub report: &'a mut dyn FnMut(SearchProgress),
+}
+
+// A search for the move of a Color that runs a batch of iterations at a time, so that whoever
+// waits on it can do other work between batches, ex. let a browser draw the page
+pub(crate) struct SteppedSearch {
+    tree: MCTSTree,
+    resign: bool,
+    // time spent searching so far, not counting the time between batches
+    time: Duration,
+    // when the search started and how long it may run for, if limited, in place of the
+    // iterations of the params
+    limit: Option<(Instant, Duration)>,
+}
+
+// The searches of every move generated so far, for monitoring the engine
+pub struct SearchTotals {
+    pub iterations: u64,
+    pub time: Duration,
+    // MCTS iterations per second of the last search, or 0 before any
+    pub last_rate: f64,
+}
+
+// The knobs of a search, which can be changed at runtime
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct SearchParams {
+    // MCTS iterations of a search without time limits
+    pub iterations: u16,
+    // longest a search may take, however much time the clock allows, if limited
+    pub max_time: Option<Duration>,
+    // weight of exploring less visited moves in the UCT formula
+    pub exploration: f64,
+    // estimated score lead, in points, past which a position is resigned
+    pub resignation_threshold: f64,
+}
+
+/******************************************************\
+|****************    PRIVATE TYPES     ****************|
+\******************************************************/
+
+// Wrapper class that contains the entire Monte Carlo Tree
+struct MCTSTree {
+    root_index: Index,
+    arena: Arena<MCTSNode>,
+    params: SearchParams,
+    // set to end the search early with the best move found so far
+    stop: Option<Arc<AtomicBool>>,
+    // MCTS iterations run so far, and when the progress of the search was last reported
+    iterations: u32,
+    last_report: Instant,
+}
+
+// Monte Carlo Tree Nodes
+struct MCTSNode {
+    state: Board,
+    played_last_move: Color,
+    parent: Option<Index>,
+    children: Vec<Index>,
+    total_visits: u16,
+    winning_visits: u16,
+    score: f64,
+    simulated: bool
+}
+
+/*****************************************************\
+|****************        SETUP        ****************|
+\*****************************************************/
+
+impl MCTSNode {
+    // Creates a new MCTSNode with the given parameters, and setting the others to their default value
+    fn new(state: Board, played_last_move: Color) -> MCTSNode {
+        MCTSNode {
+            state,
+            played_last_move,
+            parent: None,
+            children: vec![],
+            total_visits: 0,
+            winning_visits: 0,
+            score: 0.0,
+            simulated: false,
+        }
+    }
+}
+
+impl PartialEq for MCTSNode {
+    // Custom equality function that only checks board state fields,
+    // does not check any Monte Carlo related fields
+    fn eq(&self, other: &Self) -> bool {
+        self.state == other.state && self.played_last_move == other.played_last_move
+    }
+}
+
+impl MCTSTree {
+    // Creates a new MCTSTree and
+    fn new(initial_state: &Board, player_to_generate: Color) -> MCTSTree {
+        let root = MCTSNode::new(pooled_copy(initial_state), player_to_generate.opposite_color());
+        let mut arena: Arena<MCTSNode> = Arena::new();
+        let root_index = arena.insert(root);
+        MCTSTree {
+            root_index,
+            arena,
+            params: SearchParams::default(),
+            stop: None,
+            iterations: 0,
+            last_report: Instant::now(),
+        }
+    }
+
+    // Creates a new node in this MCTSTree from the given parameters and returns its Index.
+    // If a node of these parameters already exists, returns its Index
+    fn node(&mut self, state: Board, played_last_move: Color) -> Index {
+        let new_node = MCTSNode::new(state, played_last_move);
+        for (index, node) in &self.arena {
+            if *node == new_node {
+                recycle(new_node.state);
+                return index;
+            }
+        }
+
+        self.arena.insert(new_node)
+    }
+}
+
+impl Drop for MCTSTree {
+    // Gives the Boards of every node back to the pool, so the next search on this thread reuses them
+    fn drop(&mut self) {
+        for (_, node) in self.arena.drain() {
+            recycle(node.state);
+        }
+    }
+}
+
+impl Default for SearchParams {
+    // The SearchParams the engine searches with unless they are changed
+    fn default() -> SearchParams {
+        SearchParams {
+            iterations: DEFAULT_ITERATIONS,
+            max_time: None,
+            exploration: EXPLORATION_CONSTANT,
+            resignation_threshold: RESIGNATION_THRESHOLD,
+        }
+    }
+}
+
+/******************************************************\
+|****************        HELPER        ****************|
+\******************************************************/
+
+impl SearchParams {
+    // Names of the params get and set read and change
+    pub const NAMES: [&'static str; 4] = ["playouts", "max_time", "exploration", "resign_threshold"];
+
+    // The value of the named param, with max_time in seconds and 0 for no limit, or None if
+    // there is no such param
+    pub fn get(&self, name: &str) -> Option<String> {
+        match name {
+            "playouts" => Some(self.iterations.to_string()),
+            "max_time" => Some(self.max_time.map_or(0.0, |time| time.as_secs_f64()).to_string()),
+            "exploration" => Some(self.exploration.to_string()),
+            "resign_threshold" => Some(self.resignation_threshold.to_string()),
+            _ => None,
+        }
+    }
+
+    // Sets the named param to the given value, read like get writes it
+    // Returns an error describing an unknown param or an invalid value
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        let invalid = || format!("Invalid value given to {name}: {value}");
+        let number = || value.parse::<f64>().ok().filter(|number| number.is_finite() && *number >= 0.0);
+        match name {
+            "playouts" => self.iterations = value.parse::<u16>().ok().filter(|n| *n > 0).ok_or_else(invalid)?,
+            "max_time" => {
+                let seconds = number().ok_or_else(invalid)?;
+                self.max_time = (seconds > 0.0).then(|| Duration::from_secs_f64(seconds));
+            }
+            "exploration" => self.exploration = number().ok_or_else(invalid)?,
+            "resign_threshold" => self.resignation_threshold = number().filter(|n| *n > 0.0).ok_or_else(invalid)?,
+            _ => return Err(format!("Unknown param: {name}")),
+        }
+        Ok(())
+    }
+}
+
+impl MCTSNode {
+    // Scores Monte Carlo Tree nodes using the Upper Confidence for Trees formula, weighing
+    // exploration by the given constant
+    fn uct_score(&self, parent: &MCTSNode, exploration: f64) -> f64 {
+        let node_wins = self.winning_visits as f64;
+        let node_visits = self.total_visits as f64;
+        let parent_visits = parent.total_visits as f64;
+
+        if node_visits == 0.0 {
+            return f64::MAX;
+        }
+
+        node_wins / node_visits + exploration * f64::sqrt(f64::ln(parent_visits) / node_visits)
+    }
+
+    // The move leading to this node from the given parent node, where a pass leaves the last move
+    // of the board as it was
+    fn move_from(&self, parent: &MCTSNode) -> Move {
+        match self.state.last_move == parent.state.last_move {
+            true => Move::PASS,
+            false => self.state.last_move,
+        }
+    }
+}
+
+impl MCTSTree {
+    // Returns the root node of this MCTSTree
+    fn root(&self) -> &MCTSNode {
+        self.arena.get(self.root_index).unwrap()
+    }
+
+    // Adds the node represented at the given child_index as a child of the node at the given parent_index
+    // The first parent a node is added to is kept as its parent for backpropagation
+    fn set_child(&mut self, parent_index: Index, child_index: Index) {
+        if self.arena.contains(parent_index) && self.arena.contains(child_index) {
+            let parent = self.arena.get_mut(parent_index).unwrap();
+            parent.children.push(child_index);
+
+            let child = self.arena.get_mut(child_index).unwrap();
+            if child.parent.is_none() && child_index != parent_index {
+                child.parent = Some(parent_index);
+            }
+        }
+    }
+
+    // Returns the most visited child of the root node, if any child has been visited
+    fn best_child(&self) -> Option<&MCTSNode> {
+        self.most_visited_child(self.root())
+    }
+
+    // Returns the most visited child of the given node, if any child has been visited
+    fn most_visited_child(&self, node: &MCTSNode) -> Option<&MCTSNode> {
+        let mut best_child: Option<&MCTSNode> = None;
+        for child_idx in &node.children {
+            let child = self.arena.get(*child_idx).unwrap();
+            if child.total_visits > best_child.map_or(0, |best| best.total_visits) {
+                best_child = Some(child);
+            }
+        }
+
+        best_child
+    }
+
+    // The state of the search so far
+    fn progress(&self) -> SearchProgress {
+        let best = self.best_child();
+        let mut pv = vec![];
+        let (mut parent, mut node) = (self.root(), best);
+        while let Some(current) = node.filter(|current| pv.len() < PV_LENGTH && !std::ptr::eq(*current, parent)) {
+            pv.push(current.move_from(parent));
+            (parent, node) = (current, self.most_visited_child(current));
+        }
+
+        SearchProgress {
+            iterations: self.iterations,
+            best_move: best.map_or(Move::PASS, |child| child.state.last_move),
+            visits: best.map_or(0, |child| child.total_visits),
+            winrate: best.map_or(0.0, |child| child.winning_visits as f64 / child.total_visits as f64),
+            pv,
+        }
+    }
+
+    // The principal variation from the given child of the root: its move, then the most visited
+    // reply to each move in turn, up to the first that cannot be played on the given Board of the
+    // root, as the search may reach a position again by other moves
+    fn variation(&self, position: &Board, child: &MCTSNode) -> Vec<Move> {
+        let mut board = pooled_copy(position);
+        let mut pv = vec![];
+        let (mut parent, mut node) = (self.root(), Some(child));
+        while let Some(current) = node.filter(|current| pv.len() < PV_LENGTH && !std::ptr::eq(*current, parent)) {
+            let mov = current.move_from(parent);
+            if !board.play(mov) {
+                break;
+            }
+            pv.push(mov);
+            (parent, node) = (current, self.most_visited_child(current));
+        }
+        recycle(board);
+        pv
+    }
+
+    // The moves searched after the given node down to the given depth, most visited first, where
+    // only the most visited node of each move is kept
+    fn subtree(&self, node: &MCTSNode, depth: usize) -> Vec<SearchNode> {
+        if depth == 0 {
+            return vec![];
+        }
+        let mut children: Vec<&MCTSNode> = node
+            .children
+            .iter()
+            .map(|child_idx| self.arena.get(*child_idx).unwrap())
+            .filter(|child| child.total_visits > 0 && !std::ptr::eq(*child, node))
+            .collect();
+        children.sort_by_key(|child| std::cmp::Reverse(child.total_visits)); // stable, so ties keep search order
+
+        let mut searched: Vec<SearchNode> = vec![];
+        for child in children {
+            let mov = child.move_from(node);
+            if !searched.iter().any(|kept| kept.mov == mov) {
+                searched.push(SearchNode {
+                    mov,
+                    visits: child.total_visits,
+                    winrate: child.winning_visits as f64 / child.total_visits as f64,
+                    children: self.subtree(child, depth - 1),
+                });
+            }
+        }
+        searched
+    }
+}
+
+// Adds a search of the given iterations, taking the given time, to the SearchTotals
+fn record_search(iterations: u32, time: Duration) {
+    SEARCHED_ITERATIONS.fetch_add(iterations as u64, Ordering::Relaxed);
+    SEARCHED_MICROS.fetch_add(time.as_micros() as u64, Ordering::Relaxed);
+    let rate = iterations as f64 / time.as_secs_f64().max(f64::EPSILON);
+    LAST_SEARCH_RATE.store(rate.to_bits(), Ordering::Relaxed);
+}
+
+/*******************************************************\
+|****************      TREE SEARCH      ****************|
+\*******************************************************/
+
+impl MCTSTree {
+    // Selection phase of Monte Carlo Tree Search
+    // Selects the "most interesting" node to explore further
+    fn selection(&self) -> Index {
+        let mut best_node = self.root();
+        let mut best_index = self.root_index;
+        let mut best_score = 0.0;
+        while !best_node.children.is_empty() && !best_node.simulated {
+            let mut best_child_index = best_index;
+            for child_idx in &best_node.children {
+                if self.arena.contains(*child_idx) {
+                    let child = self.arena.get(*child_idx).unwrap();
+                    let child_score = child.uct_score(best_node, self.params.exploration);
+                    if child_score > best_score {
+                        best_child_index = *child_idx;
+                        best_score = child_score;
+                    }
+                } else {
+                    panic!("Node index does not exist in the MCTS Tree");
+                }
+            }
+            best_index = best_child_index;
+            // this one is definitely safe tho
+            best_node = self.arena.get(best_child_index).unwrap();
+        }
+        best_index
+    }
+
+    // Expansion phase of Monte Carlo Tree Search
+    // Expands the node at the given index, creating new child nodes in the tree where candidate nodes have been played
+    fn expansion(&mut self, node_index: Index) {
+        if !self.arena.contains(node_index) {
+            panic!("Node index does not exist in the MCTS Tree");
+        }
+
+        let (child_player, candidate_moves, current_state) = {
+            let node = self.arena.get_mut(node_index).unwrap();
+            node.simulated = false;
+            if node.is_game_over() {
+                return; // maybe should panic?
+            }
+            let child_player = node.played_last_move.opposite_color();
+            let candidate_moves = node.generate_candidate_moves();
+            let current_state = pooled_copy(&node.state);
+            (child_player, candidate_moves, current_state)
+        };
+
+        for candidate in candidate_moves {
+            let mut child_state = pooled_copy(&current_state);
+            if child_state.play(Move::MOVE(candidate, child_player)) {
+                let child_idx = self.node(child_state, child_player);
+                self.set_child(node_index, child_idx);
+            } else {
+                recycle(child_state);
+            }
+        }
+        recycle(current_state);
+    }
+
+    // Simulation phase of Monte Carlo Tree Search
+    // Simulates a full game of Go for the given node, creating child nodes along the way for each move played
+    // Returns the final node of the simulation and the score of the end board state
+    fn simulation(&mut self, node_index: Index) -> (Index, f64) {
+        // todo: placeholder logic, replace with tromp-taylor scoring, forfeit cutoffs to reduce moves played, etc.
+        if !self.arena.contains(node_index) {
+            panic!("Node index does not exist in the MCTS Tree");
+        }
+
+        self.arena.get_mut(node_index).unwrap().simulated = true;
+        let (end_index, end_state) = {
+            let mut cur_index = node_index;
+            for _ in 0..1500 {
+                let cur_node = self.arena.get(node_index).unwrap();
+                if cur_node.should_resign(self.params.resignation_threshold) {
+                    break;
+                }
+
+                let player = cur_node.played_last_move.opposite_color();
+                let mov = cur_node.generate_playout_move(player);
+
+                if mov == Move::PASS {
+                    continue; // kind of want to end playout after two passes but whatever
+                } else {
+                    // scratch boards come from the pool, and go back to it when the move is illegal
+                    let mut cur_state = pooled_copy(&cur_node.state);
+                    if cur_state.play(mov) {
+                        let next_node_index = self.node(cur_state, player);
+                        self.set_child(cur_index, next_node_index);
+                        cur_index = next_node_index;
+                    } else {
+                        recycle(cur_state);
+                    }
+                }
+            }
+            (cur_index, &self.arena.get(cur_index).unwrap().state)
+        };
+
+        (end_index, end_state.estimate_score())
+    }
+
+    // Backpropagation phase of Monte Carlo Tree Search
+    // Traverses up the tree from the given leaf node, incrementing visit counts dependent on the given score
+    fn backpropagation(&mut self, leaf_index: Index, score: f64) {
+        if !self.arena.contains(leaf_index) {
+            panic!("Node index does not exist in the MCTS Tree");
+        }
+
+        let mut node_index = Some(leaf_index);
+        while node_index.is_some() {
+            let cur_node = self.arena.get_mut(node_index.unwrap()).unwrap();
+            if (score > 0.0 && cur_node.played_last_move == Color::Black)
+                || (score < 0.0 && cur_node.played_last_move == Color::White)
+            {
+                cur_node.winning_visits += 1;
+            }
+            cur_node.total_visits += 1;
+            cur_node.score += score;
+
+            node_index = cur_node.parent;
+        }
+    }
+}
+
+impl MCTSTree {
+    // Runs the given number of iterations of Monte Carlo Tree Search from the root node,
+    // reporting its progress to the given ProgressReport, if any, as it goes
+    // A stopped search still runs its first iteration, so that it always has a move to give
+    fn search(&mut self, iterations: u16, progress: &mut Option<ProgressReport>) {
+        for iteration in 0..iterations {
+            if iteration > 0 && self.stopped() {
+                break;
+            }
+            tracing::trace!(iteration, "MCTS iteration");
+            let node_index = self.selection();
+            self.expansion(node_index);
+            let (leaf_index, score) = self.simulation(node_index);
+            self.backpropagation(leaf_index, score);
+
+            self.iterations += 1;
+            let due = |progress: &&mut ProgressReport| self.last_report.elapsed() >= progress.interval;
+            if let Some(progress) = progress.as_mut().filter(due) {
+                (progress.report)(self.progress());
+                self.last_report = Instant::now();
+            }
+        }
+    }
+
+    // Whether the search was asked to stop early
+    fn stopped(&self) -> bool {
+        self.stop.as_ref().is_some_and(|stop| stop.load(Ordering::Relaxed))
+    }
+
+    // Runs iterations of Monte Carlo Tree Search from the root node until the given time has
+    // passed or the search is stopped, always running at least one
+    fn search_for(&mut self, time: Duration, progress: &mut Option<ProgressReport>) {
+        let start = Instant::now();
+        let mut iterations: u32 = 0;
+        while iterations == 0 || (start.elapsed() < time && !self.stopped()) {
+            self.search(1, progress);
+            iterations += 1;
+        }
+        tracing::debug!(iterations, ?time, "Searched for the move's time budget");
+    }
+}
+
+/*********************************************************\
+|************   MOVE GENERATION HEURISTICS   *************|
+\*********************************************************/
+
+impl MCTSNode {
+    // generates a move to simulate playouts with
+    // todo: currently temporary random logic. implement influence maps, move and board scoring, shape moves, etc.
+    fn generate_playout_move(&self, color: Color) -> Move {
+        if self.state.size == BoardSize::Nineteen {
+            if let Some(intsc) = self.generate_opening_move() {
+                return Move::MOVE(intsc, color);
+            }
+        }
+
+        let weakest_engine_group = self.state.weakest_group(&color);
+        let weakest_opponent_group = self.state.weakest_group(&color.opposite_color());
+
+        // attempt to capture group if possible
+        if weakest_opponent_group.len() == 1
+            && self.state.can_place_stone_at(&weakest_opponent_group[0])
+        {
+            return Move::MOVE(weakest_opponent_group[0], color);
+        }
+
+        // attempt to save threatened group
+        if weakest_engine_group.len() == 1
+            && self.state.can_place_stone_at(&weakest_engine_group[0])
+        {
+            return Move::MOVE(weakest_engine_group[0], color);
+        }
+
+        // surround opponent group, choosing liberties by their local pattern
+        if !weakest_opponent_group.is_empty()
+            && weakest_opponent_group.len() <= weakest_engine_group.len()
+        {
+            let intsc = choose_weighted(&self.state, color, &weakest_opponent_group).unwrap();
+            return Move::MOVE(intsc, color);
+        } else if !weakest_engine_group.is_empty()
+            && weakest_engine_group.len() <= weakest_opponent_group.len()
+        {
+            // extend own group
+            let intsc = choose_weighted(&self.state, color, &weakest_engine_group).unwrap();
+            return Move::MOVE(intsc, color);
+        }
+
+        // random tenuki, never tried for now as the range of offsets is empty
+        #[allow(clippy::reversed_empty_ranges)]
+        for offset in 2..0 {
+            let random_intsc = self.state.random_intersection(offset);
+            if self.state.can_place_stone_at(&random_intsc)
+                && self.state.diamond(&random_intsc).is_none()
+            {
+                return Move::MOVE(random_intsc, color);
+            }
+        }
+
+        Move::PASS
+    }
+
+    // Generates a move meant to be played in the opening of the game
+    // todo: temp moves, and probably shouldn't be chosen randomly
+    fn generate_opening_move(&self) -> Option<Intersection> {
+        use rand::Rng;
+        let fuseki: Vec<Intersection> =
+            FUSEKI.iter().map(|(column, row)| Intersection::new(*column, *row)).collect();
+        let rand_idx = engine_rng().gen_range(0..fuseki.len());
+        if self.state.can_place_stone_at(&fuseki[rand_idx]) {
+            Some(fuseki[rand_idx])
+        } else {
+            None
+        }
+    }
+
+    // Generates candidate moves for the engine to consider playing
+    // todo: terrible logic
+    fn generate_candidate_moves(&self) -> Vec<Intersection> {
+        use ColumnIdentifier::*;
+        let mut moves = vec![
+            Intersection::new(D, 16),
+            Intersection::new(D, 4),
+            Intersection::new(Q, 4),
+            Intersection::new(Q, 16),
+        ];
+
+        for intsc in self.state.weakest_group(&Color::Black) {
+            moves.push(intsc);
+        }
+
+        for intsc in self.state.weakest_group(&Color::White) {
+            moves.push(intsc);
+        }
+
+        moves.push(self.state.random_intersection(2));
+
+        moves
+    }
+
+    // Checks if the game is over by seeing if there are any candidate moves to play
+    // todo: terrible logic
+    fn is_game_over(&self) -> bool {
+        self.generate_candidate_moves().is_empty()
+    }
+
+    // should the engine resign in this position
+    fn should_resign(&self, resign_threshold: f64) -> bool {
+        if self.state.move_number > 100 {
+            let to_play = self.played_last_move.opposite_color();
+            let score = self.state.estimate_score();
+
+            match to_play {
+                Color::Black => score < -resign_threshold,
+                Color::White => score > resign_threshold,
+            }
+        } else {
+            false
+        }
+    }
+}
+
+/********************************************************\
+|****************     PUBLIC METHODS     ****************|
+\********************************************************/
+
+// Generates a move using this Go Engine (MCTS) to play on the given Board
+pub fn generate_move(position: &Board, color: Color, iterations: u16) -> Move {
+    let params = SearchParams { iterations, ..SearchParams::default() };
+    generate_move_with(position, color, &params, None, None)
+}
+
+// Generates a move like generate_move with the given SearchParams, searching for the given time
+// if any, else for the iterations of the params, and stopping early with the best move found so
+// far once the given stop flag, if any, is set
+pub fn generate_move_with(
+    position: &Board,
+    color: Color,
+    params: &SearchParams,
+    time: Option<Duration>,
+    stop: Option<Arc<AtomicBool>>,
+) -> Move {
+    generate_move_reporting(position, color, params, time, stop, None)
+}
+
+// Generates a move like generate_move_with, reporting the progress of the search to the given
+// ProgressReport, if any, while it runs
+pub fn generate_move_reporting(
+    position: &Board,
+    color: Color,
+    params: &SearchParams,
+    time: Option<Duration>,
+    stop: Option<Arc<AtomicBool>>,
+    mut progress: Option<ProgressReport>,
+) -> Move {
+    let mut tree = MCTSTree::new(position, color);
+    tree.params = *params;
+    tree.stop = stop;
+    if tree.root().should_resign(params.resignation_threshold) {
+        return Move::RESIGN;
+    }
+
+    let start = Instant::now();
+    match time {
+        Some(time) => tree.search_for(time, &mut progress),
+        None => tree.search(params.iterations, &mut progress),
+    }
+    record_search(tree.iterations, start.elapsed());
+
+    let best_move = tree
+        .best_child()
+        .map_or(Move::PASS, |child| child.state.last_move);
+    tracing::debug!(?best_move, "Generated move");
+    best_move
+}
+
+impl SteppedSearch {
+    // Starts a search like generate_move_with for the given Color to play on the given Board, for
+    // the given time if any, which runs no iterations until stepped
+    pub(crate) fn new(position: &Board, color: Color, params: &SearchParams, time: Option<Duration>) -> SteppedSearch {
+        let mut tree = MCTSTree::new(position, color);
+        tree.params = *params;
+        let resign = tree.root().should_resign(params.resignation_threshold);
+        let limit = time.map(|time| (Instant::now(), time));
+        SteppedSearch { tree, resign, time: Duration::ZERO, limit }
+    }
+
+    // Runs up to the given number of iterations, never past the time limit, if any, or else the
+    // iterations of the params
+    // Returns true once the search is done
+    pub(crate) fn step(&mut self, iterations: u16) -> bool {
+        if self.is_done() {
+            return true;
+        }
+        let start = Instant::now();
+        match self.limit {
+            // like search_for, a search always runs at least one iteration
+            Some((started, limit)) => {
+                let mut run = 0;
+                while run < iterations && (self.tree.iterations == 0 || started.elapsed() < limit) {
+                    self.tree.search(1, &mut None);
+                    run += 1;
+                }
+            }
+            None => {
+                let remaining = (self.tree.params.iterations as u32).saturating_sub(self.tree.iterations);
+                self.tree.search(iterations.min(remaining as u16), &mut None);
+            }
+        }
+        self.time += start.elapsed();
+        self.is_done()
+    }
+
+    // Whether the search has run all of its iterations or time, or has nothing to search
+    pub(crate) fn is_done(&self) -> bool {
+        match self.limit {
+            _ if self.resign => true,
+            Some((started, limit)) => self.tree.iterations > 0 && started.elapsed() >= limit,
+            None => self.tree.iterations >= self.tree.params.iterations as u32,
+        }
+    }
+
+    // The state of the search so far, whose best move is RESIGN if the engine resigns
+    pub(crate) fn progress(&self) -> SearchProgress {
+        let mut progress = self.tree.progress();
+        if self.resign {
+            progress.best_move = Move::RESIGN;
+        }
+        progress
+    }
+
+    // Ends the search, giving the best move it found so far
+    pub(crate) fn finish(self) -> Move {
+        if self.resign {
+            return Move::RESIGN;
+        }
+        record_search(self.tree.iterations, self.time);
+        let best_move = self.progress().best_move;
+        tracing::debug!(?best_move, "Generated move");
+        best_move
+    }
+}
+
+// Searches the given Board for the given Color to play, and reports the engine's evaluation
+// of every move it considered, most visited first
+pub fn analyze_candidates(position: &Board, color: Color, iterations: u16) -> Vec<Analysis> {
+    analyze_variations(position, color, iterations)
+        .into_iter()
+        .map(|(candidate, _)| candidate)
+        .collect()
+}
+
+// Searches like analyze_candidates, giving each move considered along with its principal
+// variation, which starts with the move
+pub(crate) fn analyze_variations(position: &Board, color: Color, iterations: u16) -> Vec<(Analysis, Vec<Move>)> {
+    let mut tree = MCTSTree::new(position, color);
+    tree.search(iterations, &mut None);
+
+    let mut children: Vec<&MCTSNode> = tree
+        .root()
+        .children
+        .iter()
+        .map(|child_idx| tree.arena.get(*child_idx).unwrap())
+        .filter(|child| child.total_visits > 0)
+        .collect();
+
+    children.sort_by_key(|child| std::cmp::Reverse(child.total_visits)); // stable, so ties keep search order
+
+    // the root may hold several children for the same move, only the most visited is kept
+    let mut unique: Vec<(Analysis, Vec<Move>)> = vec![];
+    for child in children {
+        if !unique.iter().any(|(kept, _)| kept.best_move == child.state.last_move) {
+            let candidate = Analysis {
+                best_move: child.state.last_move,
+                winrate: child.winning_visits as f64 / child.total_visits as f64,
+                score: child.score / child.total_visits as f64,
+                visits: child.total_visits,
+            };
+            unique.push((candidate, tree.variation(position, child)));
+        }
+    }
+    unique
+}
+
+// Searches the given Board for the given Color to play for the iterations of the given
+// SearchParams, and gives the moves searched down to the given depth, most visited first
+pub fn search_tree(position: &Board, color: Color, params: &SearchParams, depth: usize) -> Vec<SearchNode> {
+    let mut tree = MCTSTree::new(position, color);
+    tree.params = *params;
+    tree.search(params.iterations, &mut None);
+    tree.subtree(tree.root(), depth)
+}
+
+// Searches the given Board for the given Color to play, and reports the engine's evaluation
+pub(crate) fn analyze_position(position: &Board, color: Color, iterations: u16) -> Analysis {
+    evaluation(position, color, &analyze_candidates(position, color, iterations))
+}
+
+// The engine's evaluation of the given Board for the given Color to play, from the moves its
+// search considered, most visited first
+fn evaluation(position: &Board, color: Color, candidates: &[Analysis]) -> Analysis {
+    match candidates.first() {
+        Some(analysis) => *analysis,
+        None => {
+            // nothing was searched, so fall back on the static evaluation of the position
+            let score = position.estimate_score();
+            let color_leads = match color {
+                Color::Black => score > 0.0,
+                Color::White => score < 0.0,
+            };
+            Analysis {
+                best_move: Move::PASS,
+                winrate: if color_leads { 1.0 } else { 0.0 },
+                score,
+                visits: 0,
+            }
+        }
+    }
+}
+
+// Analyzes each of the given Boards for the paired Color to play, in order, along with every move
+// considered in each, most visited first
+// The positions are searched in parallel on search_threads() threads
+pub(crate) fn analyze_positions_with_candidates(
+    positions: &[(Board, Color)],
+    iterations: u16,
+) -> Vec<(Analysis, Vec<Analysis>)> {
+    use rayon::prelude::*;
+    in_search_pool(|| {
+        positions
+            .par_iter()
+            .map(|(position, color)| {
+                let candidates = analyze_candidates(position, *color, iterations);
+                (evaluation(position, *color, &candidates), candidates)
+            })
+            .collect()
+    })
+}
+
+// Chooses where to place the given number of free handicap stones on a board of the given size:
+// the star points of fixed handicap, then on 19x19 the opening points of FUSEKI not taken yet
+// Returns None unless there are at least 2 stones and no more than the engine knows places for
+pub fn free_handicap_points(size: BoardSize, stones: u16) -> Option<Vec<Intersection>> {
+    let stars = size.handicap_points(stones.min(9))?;
+    let mut points: Vec<Intersection> = stars.into_iter().map(Intersection::from).collect();
+    if size == BoardSize::Nineteen {
+        for (column, row) in FUSEKI {
+            let intsc = Intersection::new(column, row);
+            if points.len() < stones as usize && !points.contains(&intsc) {
+                points.push(intsc);
+            }
+        }
+    }
+
+    (points.len() == stones as usize).then_some(points)
+}
+
+// Simulates a single game from the given Board with the given Color to move
+// Returns the estimated score of the final position of the simulation
+pub fn playout(position: &Board, color: Color) -> f64 {
+    let mut tree = MCTSTree::new(position, color);
+    let (_, score) = tree.simulation(tree.root_index);
+    score
+}
+
+// Simulates a single game from the given Board with the given Color to move
+// Returns the final position of the simulation
+pub(crate) fn playout_position(position: &Board, color: Color) -> Board {
+    let mut tree = MCTSTree::new(position, color);
+    let (end_index, _) = tree.simulation(tree.root_index);
+    tree.arena.remove(end_index).unwrap().state // the simulation ends on a node of the tree
+}
+
+// Sets the number of threads batches of searches, playouts, and scoring run on,
+// where 0 uses every available core
+pub(crate) fn set_search_threads(threads: usize) {
+    SEARCH_THREADS.store(threads, Ordering::Relaxed);
+}
+
+// The number of threads batches of searches, playouts, and scoring run on
+pub fn search_threads() -> usize {
+    match SEARCH_THREADS.load(Ordering::Relaxed) {
+        0 => std::thread::available_parallelism().map_or(1, |threads| threads.get()),
+        threads => threads,
+    }
+}
+
+// The searches of every move generated so far
+pub fn search_totals() -> SearchTotals {
+    SearchTotals {
+        iterations: SEARCHED_ITERATIONS.load(Ordering::Relaxed),
+        time: Duration::from_micros(SEARCHED_MICROS.load(Ordering::Relaxed)),
+        last_rate: f64::from_bits(LAST_SEARCH_RATE.load(Ordering::Relaxed)),
+    }
+}
+
+#[test]
+fn test_should_resign() {
+    use ColumnIdentifier::*;
+    let mut b = Board::new(BoardSize::Nine);
+
+    b.play(Move::MOVE(Intersection::new(C, 7), Color::Black));
+    b.play(Move::MOVE(Intersection::new(G, 3), Color::White));
+    b.play(Move::MOVE(Intersection::new(D, 7), Color::Black));
+    b.play(Move::MOVE(Intersection::new(G, 2), Color::White));
+    b.play(Move::MOVE(Intersection::new(D, 8), Color::Black));
+    b.play(Move::MOVE(Intersection::new(G, 1), Color::White));
+    b.play(Move::MOVE(Intersection::new(D, 9), Color::Black));
+    b.play(Move::MOVE(Intersection::new(H, 3), Color::White));
+    b.play(Move::MOVE(Intersection::new(C, 6), Color::Black));
+    b.play(Move::MOVE(Intersection::new(J, 3), Color::White));
+    b.play(Move::MOVE(Intersection::new(B, 6), Color::Black));
+    b.play(Move::MOVE(Intersection::new(J, 4), Color::White));
+    b.play(Move::MOVE(Intersection::new(A, 6), Color::Black));
+    b.play(Move::MOVE(Intersection::new(F, 1), Color::White));
+    b.move_number = 101;
+    
+    let mcts_black = MCTSTree::new(&b, Color::Black);
+    let mcts_white = MCTSTree::new(&b, Color::White);
+
+    assert_eq!(b.estimate_score(), -2.5); // black = 15, white = 11, komi = 6.5
+    assert_eq!(mcts_black.root().played_last_move, Color::White);
+    assert_eq!(mcts_white.root().played_last_move, Color::Black);
+    
+    assert!(mcts_black.root().should_resign(1.0)); // black should resign at threshold of 1.0
+    assert!(!mcts_white.root().should_resign(1.0)); // white should not resign at threshold of 1.0
+
+    assert!(!mcts_black.root().should_resign(5.0)); // black should not resign at threshold of 5.0
+}
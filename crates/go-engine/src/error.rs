@@ -0,0 +1,70 @@
+use std::fmt;
+use std::fmt::Formatter;
+use std::io;
+/****************************************************\
+|****************    GLOBAL TYPES    ****************|
+\****************************************************/
+
+// Errors that end the program, reported to the user instead of panicking
+#[derive(Debug)]
+pub enum AgentError {
+    MissingMode,
+    UnknownMode(String),
+    InvalidArguments(String),
+    MissingFeature(&'static str, &'static str),
+    IO(io::Error),
+}
+
+/****************************************************\
+|****************       HELPER       ****************|
+\****************************************************/
+
+impl AgentError {
+    // The process exit code for this error, 2 for usage errors and 1 for failures while running
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            AgentError::MissingMode
+            | AgentError::UnknownMode(_)
+            | AgentError::InvalidArguments(_)
+            | AgentError::MissingFeature(..) => 2,
+            AgentError::IO(_) => 1,
+        }
+    }
+}
+
+impl From<io::Error> for AgentError {
+    fn from(error: io::Error) -> AgentError {
+        AgentError::IO(error)
+    }
+}
+
+/*****************************************************\
+|****************      RENDERING      ****************|
+\*****************************************************/
+
+impl fmt::Display for AgentError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        const RUN_MODES: &str = "debug, play, gtp, tcp, api, grpc, png, bench, selfplay, analyze, score, puzzles, match, regress, patterns, index, or search";
+        match self {
+            AgentError::MissingMode => write!(f, "No run mode given, run mode must be {RUN_MODES}"),
+            AgentError::UnknownMode(mode) => {
+                write!(f, "Invalid run mode given: {mode}, run mode must be {RUN_MODES}")
+            }
+            AgentError::InvalidArguments(message) => write!(f, "{message}"),
+            AgentError::MissingFeature(mode, feature) => write!(
+                f,
+                "Run mode {mode} is unavailable, rebuild with the \"{feature}\" feature enabled"
+            ),
+            AgentError::IO(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for AgentError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AgentError::IO(error) => Some(error),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,271 @@
+use super::*;
+use std::time::Duration;
+/******************************************************\
+|****************      CONSTANTS       ****************|
+\******************************************************/
+
+// MCTS iterations used by genmove unless changed with set_playouts
+const DEFAULT_PLAYOUTS: u16 = 30;
+
+/****************************************************\
+|****************    GLOBAL TYPES    ****************|
+\****************************************************/
+
+// A handle to the engine for embedding it in other programs, holding a single game
+// Colors, Positions, and scoring rules are the types of go-board, and the engine's moves are Moves
+pub struct Engine {
+    board: Board,
+    moves: Vec<(Color, Move)>,
+    to_play: Color,
+    playouts: u16,
+    // how long genmove searches for instead of the playouts, if limited
+    time_limit: Option<Duration>,
+}
+
+// A search for the engine's move started by Engine::start_genmove, run a batch of playouts at a
+// time with step and played by Engine::finish_genmove
+pub struct MoveSearch {
+    search: SteppedSearch,
+    color: Color,
+    // the moves of the game when the search started, to catch moves played during it
+    moves: usize,
+}
+
+/*****************************************************\
+|****************        SETUP        ****************|
+\*****************************************************/
+
+impl Engine {
+    // Creates a new Engine with an empty board of the given size and the given komi
+    pub fn new(size: BoardSize, komi: f64) -> Engine {
+        let mut board = Board::new(size);
+        board.komi = komi;
+
+        Engine {
+            board,
+            moves: vec![],
+            to_play: Color::Black,
+            playouts: DEFAULT_PLAYOUTS,
+            time_limit: None,
+        }
+    }
+
+    // Creates a new Engine holding the main line of the given SGF game
+    pub fn from_sgf(sgf: &str) -> Result<Engine, String> {
+        let record = SgfRecord::parse(sgf)?;
+        let mut engine = Engine::new(record.size, record.komi);
+        for (move_number, node) in record.nodes.iter().enumerate() {
+            if !engine.board.play(node.mov) {
+                return Err(format!("Illegal move in SGF at move {}", move_number + 1));
+            }
+            engine.record_move(node.color, node.mov);
+        }
+
+        Ok(engine)
+    }
+
+    // Sets the number of MCTS iterations genmove searches for
+    pub fn set_playouts(&mut self, playouts: u16) {
+        self.playouts = playouts;
+    }
+
+    // Sets how long genmove searches for, in place of the playouts, or removes the limit
+    pub fn set_time_limit(&mut self, time: Option<Duration>) {
+        self.time_limit = time;
+    }
+
+    // Sets the number of threads batches of playouts, such as ownership, run on, where 0 uses
+    // every available core
+    // The thread count is shared by every Engine of the program
+    pub fn set_threads(&mut self, threads: usize) {
+        set_search_threads(threads);
+    }
+}
+
+/****************************************************\
+|****************       HELPER       ****************|
+\****************************************************/
+
+impl Engine {
+    // Records the given Move as played by the given Color
+    fn record_move(&mut self, color: Color, mov: Move) {
+        self.moves.push((color, mov));
+        self.to_play = color.opposite_color();
+    }
+
+    // The board size of the game
+    pub fn size(&self) -> BoardSize {
+        self.board.size
+    }
+
+    // The komi of the game
+    pub fn komi(&self) -> f64 {
+        self.board.komi
+    }
+
+    // The Color to play next
+    pub fn to_play(&self) -> Color {
+        self.to_play
+    }
+
+    // The current position and the Color to play in it
+    pub fn position(&self) -> (&Board, Color) {
+        (&self.board, self.to_play)
+    }
+
+    // The Color and Position of every move of the game in order, None for a pass
+    pub fn moves(&self) -> Vec<(Color, Option<Position>)> {
+        self.moves
+            .iter()
+            .map(|&(color, mov)| match mov {
+                Move::MOVE(intsc, _) => (color, Some(Position::from(intsc))),
+                _ => (color, None),
+            })
+            .collect()
+    }
+
+    // The Positions of every stone of the given Color on the board
+    pub fn stones(&self, color: Color) -> Vec<Position> {
+        self.board.stones(color).into_iter().map(Position::from).collect()
+    }
+}
+
+/*****************************************************\
+|****************      GAME PLAY      ****************|
+\*****************************************************/
+
+impl Engine {
+    // Plays a stone of the given Color at the given Position
+    // Returns an error if the move is illegal
+    pub fn play(&mut self, color: Color, pos: Position) -> Result<(), String> {
+        let size = self.board.size.to_u16();
+        if pos.row() >= size || pos.col() >= size {
+            return Err(format!("Off the board: {pos}"));
+        }
+        let mov = Move::MOVE(pos.into(), color);
+        if !self.board.play(mov) {
+            return Err(format!("Illegal move: {pos}"));
+        }
+        self.record_move(color, mov);
+        Ok(())
+    }
+
+    // Passes for the given Color
+    pub fn pass(&mut self, color: Color) {
+        self.board.play(Move::PASS);
+        self.record_move(color, Move::PASS);
+    }
+
+    // Generates and plays the engine's move for the given Color
+    // Returns the Move played, which may be a pass or resignation
+    pub fn genmove(&mut self, color: Color) -> Move {
+        let params = SearchParams { iterations: self.playouts, ..SearchParams::default() };
+        let mov = generate_move_with(&self.board, color, &params, self.time_limit, None);
+        self.play_generated(color, mov)
+    }
+
+    // Starts a search for the engine's move for the given Color like genmove, which runs once
+    // stepped and is played by finish_genmove
+    pub fn start_genmove(&self, color: Color) -> MoveSearch {
+        let params = SearchParams { iterations: self.playouts, ..SearchParams::default() };
+        let search = SteppedSearch::new(&self.board, color, &params, self.time_limit);
+        MoveSearch { search, color, moves: self.moves.len() }
+    }
+
+    // Plays the best move found by the given search, even if it was not stepped to the end
+    // Returns the Move played, or an error if a move was played since the search started
+    pub fn finish_genmove(&mut self, search: MoveSearch) -> Result<Move, String> {
+        if search.moves != self.moves.len() {
+            return Err("The game changed during the search".to_string());
+        }
+        let color = search.color;
+        Ok(self.play_generated(color, search.search.finish()))
+    }
+
+    // Plays the given Move generated for the given Color, or a pass if it is illegal
+    // Returns the Move played
+    fn play_generated(&mut self, color: Color, mut mov: Move) -> Move {
+        if mov == Move::RESIGN {
+            return mov;
+        }
+        if !self.board.play(mov) {
+            mov = Move::PASS;
+            self.board.play(mov);
+        }
+
+        self.record_move(color, mov);
+        mov
+    }
+}
+
+impl MoveSearch {
+    // Runs up to the given number of playouts of the search, never past the engine's time limit,
+    // if any, or else its playouts
+    // Returns true once the search is done
+    pub fn step(&mut self, playouts: u16) -> bool {
+        self.search.step(playouts)
+    }
+
+    // The playouts run so far
+    pub fn playouts(&self) -> u32 {
+        self.search.progress().iterations
+    }
+
+    // The Move the search would play so far
+    pub fn best_move(&self) -> Move {
+        self.search.progress().best_move
+    }
+}
+
+/*****************************************************\
+|****************       SCORING       ****************|
+\*****************************************************/
+
+impl Engine {
+    // Scores the current position after removing dead stones, under the given ScoringRules,
+    // positive when Black leads
+    pub fn score(&self, rules: ScoringRules) -> f64 {
+        self.board.final_score(rules)
+    }
+
+    // The result of the current position under the given ScoringRules, ex. "B+3.5"
+    pub fn result(&self, rules: ScoringRules) -> String {
+        result_string(self.score(rules))
+    }
+
+    // Estimates who owns each point of the current position from the given number of playouts,
+    // as rows from the top of the board of values from -1.0 (White's) to 1.0 (Black's)
+    pub fn ownership(&self, playouts: u16) -> Vec<Vec<f64>> {
+        ownership_positions(&[(self.board.deepcopy(), self.to_play)], playouts)
+            .pop()
+            .unwrap() // one Ownership is returned per position
+            .rows()
+    }
+
+    // Searches the current position for the given number of playouts, giving every Move the
+    // search considered, which may be a pass, with its visits, winrate for the color to play, and
+    // principal variation, most visited first
+    // The principal variation is the move, then the most visited reply to each move in turn
+    pub fn candidates(&self, playouts: u16) -> Vec<(Move, u16, f64, Vec<Move>)> {
+        analyze_variations(&self.board, self.to_play, playouts)
+            .into_iter()
+            .map(|(candidate, pv)| (candidate.best_move, candidate.visits, candidate.winrate, pv))
+            .collect()
+    }
+}
+
+/*****************************************************\
+|****************      RENDERING      ****************|
+\*****************************************************/
+
+impl Engine {
+    // Writes the game so far as an SGF record
+    pub fn to_sgf(&self) -> String {
+        let mut record = SgfRecord::new(self.board.size, self.board.komi);
+        for (color, mov) in &self.moves {
+            record.nodes.push(SgfNode::new(*color, *mov));
+        }
+
+        record.to_string()
+    }
+}
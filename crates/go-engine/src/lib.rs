@@ -0,0 +1,100 @@
+#![doc(hidden)]
+// Enum variants are named in screaming case, ex. Move::PASS
+#![allow(clippy::upper_case_acronyms)]
+
+// The colors, board sizes, rulesets, ko and scoring rules, and positions of the engine are the types of go-board
+// Its Board, Move, and GameResult are still its own, the Board being a bitboard built for the speed of playouts,
+// and Intersections convert from go-board Positions at the boundary; searching on go_board::Board is future work
+
+mod board;
+mod bitboard;
+mod tests;
+mod engine;
+#[cfg(feature = "image")]
+mod png;
+mod logging;
+mod bench;
+mod selfplay;
+mod sgf;
+mod analyze;
+mod score;
+mod puzzles;
+mod error;
+mod handle;
+mod collection;
+mod patterns;
+mod pool;
+mod random;
+mod batch;
+
+pub use board::*;
+pub use go_board::{BoardSize, Color, KoRule, Position, Ruleset, ScoringRules, State};
+use bitboard::simd_backend;
+pub use engine::*;
+#[cfg(feature = "image")]
+pub use png::*;
+pub use logging::*;
+use bench::*;
+pub use bench::{bench_workloads, Workload, BENCH_SEED};
+use selfplay::*;
+pub use sgf::*;
+pub use analyze::*;
+use score::*;
+use puzzles::*;
+pub use error::AgentError;
+pub use handle::{Engine, MoveSearch};
+use collection::*;
+use patterns::*;
+pub use pool::*;
+use random::engine_rng;
+pub use batch::*;
+pub use random::seed_rng;
+/****************************************************\
+|****************        MAIN        ****************|
+\****************************************************/
+
+// Debug commands
+fn debug() {
+    let b: Board = Board::new(BoardSize::Nineteen);
+    
+    println!("{:?}", generate_move(&b, Color::Black, 30));
+}
+
+// Main library function, controls the runmode of the program, except for the GTP run modes of the go-gtp crate
+// and the API run modes of the go-api crate
+// Returns an AgentError describing what went wrong instead of panicking, so the caller can
+// report it to the user and exit with its exit code
+pub fn start_go_agent(args: Vec<String>) -> Result<(), AgentError> {
+    init_logging();
+    let mode = args.get(1).ok_or(AgentError::MissingMode)?;
+    let mode_args = &args[2..];
+
+    if mode.eq_ignore_ascii_case("debug") {
+        debug();
+    } else if mode.eq_ignore_ascii_case("png") {
+        #[cfg(feature = "image")]
+        run_png(PngSettings::from_args(mode_args).map_err(AgentError::InvalidArguments)?)?;
+        #[cfg(not(feature = "image"))]
+        return Err(AgentError::MissingFeature("png", "image"));
+    } else if mode.eq_ignore_ascii_case("bench") {
+        run_bench(BenchSettings::from_args(mode_args).map_err(AgentError::InvalidArguments)?);
+    } else if mode.eq_ignore_ascii_case("selfplay") {
+        run_selfplay(SelfplaySettings::from_args(mode_args).map_err(AgentError::InvalidArguments)?)?;
+    } else if mode.eq_ignore_ascii_case("analyze") {
+        run_analyze(AnalyzeSettings::from_args(mode_args).map_err(AgentError::InvalidArguments)?)?;
+    } else if mode.eq_ignore_ascii_case("score") {
+        run_score(ScoreSettings::from_args(mode_args).map_err(AgentError::InvalidArguments)?)?;
+    } else if mode.eq_ignore_ascii_case("puzzles") {
+        run_puzzles(PuzzleSettings::from_args(mode_args).map_err(AgentError::InvalidArguments)?)?;
+    } else if mode.eq_ignore_ascii_case("patterns") {
+        run_patterns(PatternSettings::from_args(mode_args).map_err(AgentError::InvalidArguments)?)?;
+    } else if mode.eq_ignore_ascii_case("index") {
+        run_index(IndexSettings::from_args(mode_args).map_err(AgentError::InvalidArguments)?)?;
+    } else if mode.eq_ignore_ascii_case("search") {
+        run_search(SearchSettings::from_args(mode_args).map_err(AgentError::InvalidArguments)?)?;
+    } else {
+        return Err(AgentError::UnknownMode(mode.clone()));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use std::fs::File;
+use std::sync::Mutex;
+use tracing_subscriber::EnvFilter;
+/******************************************************\
+|****************      CONSTANTS       ****************|
+\******************************************************/
+
+// Environment variable containing the filter directives for logging (ex. "info" or "engine::gtp=debug")
+const LOG_FILTER_VAR: &str = "GO_ENGINE_LOG";
+
+// Environment variable containing a file path to write logs to instead of stderr
+const LOG_FILE_VAR: &str = "GO_ENGINE_LOG_FILE";
+
+// Filter used when no filter directives are given
+const DEFAULT_FILTER: &str = "warn";
+
+/********************************************************\
+|****************     PUBLIC METHODS     ****************|
+\********************************************************/
+
+// Installs the global tracing subscriber for the program
+// Logs are written to stderr, or to the file given by GO_ENGINE_LOG_FILE, and never to stdout
+// as stdout is reserved for the Go Text Protocol stream
+pub fn init_logging() {
+    let filter = EnvFilter::try_from_env(LOG_FILTER_VAR)
+        .unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+    let subscriber = tracing_subscriber::fmt().with_env_filter(filter);
+
+    let log_file = std::env::var(LOG_FILE_VAR).ok().map(|path| (File::create(&path), path));
+    let installed = match log_file {
+        Some((Ok(file), _)) => subscriber
+            .with_ansi(false)
+            .with_writer(Mutex::new(file))
+            .try_init(),
+        Some((Err(error), path)) => {
+            let installed = subscriber.with_writer(std::io::stderr).try_init();
+            tracing::warn!("Could not open log file {path}, logging to stderr instead: {error}");
+            installed
+        }
+        None => subscriber.with_writer(std::io::stderr).try_init(),
+    };
+
+    if installed.is_err() {
+        tracing::debug!("Global tracing subscriber was already installed");
+    }
+}
@@ -0,0 +1,317 @@
+use super::*;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+/******************************************************\
+|****************      CONSTANTS       ****************|
+\******************************************************/
+
+// Environment variable containing the path of a pattern table to weight playouts with
+const PATTERNS_VAR: &str = "GO_ENGINE_PATTERNS";
+
+// First bytes of every pattern table file, followed by its format version
+const TABLE_MAGIC: &[u8; 4] = b"GOPT";
+const TABLE_VERSION: u8 = 1;
+
+// Bytes of each pattern entry: a u16 code followed by u32 played and seen counts
+const ENTRY_LENGTH: usize = 10;
+
+// Times a pattern is assumed to have been seen without being played before any games are counted,
+// so that rare patterns are not given extreme weights
+const SEEN_PRIOR: u32 = 50;
+
+// Positions of the 8 neighbors of a pattern as (column, row) offsets, row by row from the top left,
+// in the order of Board::neighborhood
+const NEIGHBOR_OFFSETS: [(i8, i8); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/****************************************************\
+|****************    GLOBAL TYPES    ****************|
+\****************************************************/
+
+// Settings of a pattern table import from SGF games, parsed from the command line
+pub(crate) struct PatternSettings {
+    inputs: Vec<PathBuf>,
+    output: PathBuf,
+}
+
+// How often each local 3x3 pattern around a move was played, out of the times it was available,
+// keyed by the pattern code shared by its 8 symmetries
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct PatternTable {
+    counts: HashMap<u16, (u32, u32)>,
+}
+
+/*****************************************************\
+|****************        SETUP        ****************|
+\*****************************************************/
+
+impl PatternSettings {
+    // Parses pattern settings from the arguments following the patterns run mode
+    // Accepts "<file.sgf or directory>... --output <file>", where the output defaults to "patterns.bin"
+    pub(crate) fn from_args(args: &[String]) -> Result<PatternSettings, String> {
+        let mut settings = PatternSettings {
+            inputs: vec![],
+            output: PathBuf::from("patterns.bin"),
+        };
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--output" => {
+                    let value = args.next().ok_or("No value given to patterns option --output")?;
+                    settings.output = PathBuf::from(value);
+                }
+                _ => settings.inputs.push(PathBuf::from(arg)),
+            }
+        }
+
+        if settings.inputs.is_empty() {
+            return Err("No SGF files or directories given to patterns".to_string());
+        }
+
+        Ok(settings)
+    }
+}
+
+/****************************************************\
+|****************       HELPER       ****************|
+\****************************************************/
+
+// Encodes a State in 2 bits relative to the given Color to play
+fn state_code(state: State, color: Color) -> u16 {
+    match state {
+        State::Empty => 0,
+        State::Occupied(stone) if stone == color => 1,
+        State::Occupied(_) => 2,
+        State::Offboard => 3,
+    }
+}
+
+// Maps the given neighbor offset through the given one of the 8 symmetries of a square
+fn transform(symmetry: u8, (column, row): (i8, i8)) -> (i8, i8) {
+    match symmetry {
+        0 => (column, row),
+        1 => (-column, row),
+        2 => (column, -row),
+        3 => (-column, -row),
+        4 => (row, column),
+        5 => (-row, column),
+        6 => (row, -column),
+        _ => (-row, -column),
+    }
+}
+
+// Returns the code of the 3x3 pattern around the given Intersection for the given Color to play,
+// identical for all 8 symmetries of the pattern, or None if the Intersection is not on the Board
+pub(crate) fn pattern_code(board: &Board, intsc: &Intersection, color: Color) -> Option<u16> {
+    let neighborhood = board.neighborhood(intsc)?;
+    (0..8)
+        .map(|symmetry| {
+            neighborhood
+                .iter()
+                .zip(NEIGHBOR_OFFSETS)
+                .map(|(state, offset)| {
+                    let moved = transform(symmetry, offset);
+                    let slot = NEIGHBOR_OFFSETS.iter().position(|other| *other == moved).unwrap();
+                    state_code(*state, color) << (2 * slot)
+                })
+                .sum::<u16>()
+        })
+        .min()
+}
+
+// The empty Intersections of the given Board where a stone may be placed
+fn available_points(board: &Board) -> Vec<Intersection> {
+    let numeric_size = board.size.to_u16();
+    (0..numeric_size)
+        .flat_map(|column| (1..=numeric_size).map(move |row| (column, row)))
+        .map(|(column, row)| Intersection::new(ColumnIdentifier::from_u16(column).unwrap(), row))
+        .filter(|intsc| board.can_place_stone_at(intsc))
+        .collect()
+}
+
+/*****************************************************\
+|****************       COUNTING      ****************|
+\*****************************************************/
+
+impl PatternTable {
+    // Counts every move of the given game, and the patterns available to each mover
+    pub(crate) fn add_game(&mut self, record: &SgfRecord) -> Result<(), String> {
+        for ((before, _), node) in record.positions()?.iter().zip(&record.nodes) {
+            let Move::MOVE(played, mover) = node.mov else {
+                continue;
+            };
+
+            for intsc in available_points(before) {
+                if let Some(code) = pattern_code(before, &intsc, mover) {
+                    self.counts.entry(code).or_default().1 += 1;
+                }
+            }
+            if let Some(code) = pattern_code(before, &played, mover) {
+                self.counts.entry(code).or_default().0 += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    // The weight of playing a move with the given pattern code, the smoothed rate at which
+    // the pattern was played when available
+    pub(crate) fn weight(&self, code: u16) -> f64 {
+        let (played, seen) = self.counts.get(&code).copied().unwrap_or_default();
+        (played as f64 + 1.0) / (seen as f64 + SEEN_PRIOR as f64)
+    }
+
+    // The number of distinct patterns counted
+    pub(crate) fn len(&self) -> usize {
+        self.counts.len()
+    }
+}
+
+/*****************************************************\
+|****************    SERIALIZATION    ****************|
+\*****************************************************/
+
+impl PatternTable {
+    // Encodes this table as its magic, version, entry count, and entries sorted by code,
+    // all little endian
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        let mut codes: Vec<&u16> = self.counts.keys().collect();
+        codes.sort();
+
+        let mut bytes: Vec<u8> = TABLE_MAGIC.to_vec();
+        bytes.push(TABLE_VERSION);
+        bytes.extend((codes.len() as u32).to_le_bytes());
+        for code in codes {
+            let (played, seen) = self.counts[code];
+            bytes.extend(code.to_le_bytes());
+            bytes.extend(played.to_le_bytes());
+            bytes.extend(seen.to_le_bytes());
+        }
+        bytes
+    }
+
+    // Decodes a table written by to_bytes
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Result<PatternTable, String> {
+        let header_length = TABLE_MAGIC.len() + 5;
+        if bytes.len() < header_length || &bytes[..4] != TABLE_MAGIC {
+            return Err("Not a pattern table".to_string());
+        }
+        if bytes[4] != TABLE_VERSION {
+            return Err(format!("Unsupported pattern table version {}", bytes[4]));
+        }
+
+        let count = u32::from_le_bytes(bytes[5..9].try_into().unwrap()) as usize;
+        let entries = &bytes[header_length..];
+        if entries.len() != count * ENTRY_LENGTH {
+            return Err("Truncated pattern table".to_string());
+        }
+
+        let counts = entries
+            .chunks_exact(ENTRY_LENGTH)
+            .map(|entry| {
+                let code = u16::from_le_bytes(entry[0..2].try_into().unwrap());
+                let played = u32::from_le_bytes(entry[2..6].try_into().unwrap());
+                let seen = u32::from_le_bytes(entry[6..10].try_into().unwrap());
+                (code, (played, seen))
+            })
+            .collect();
+        Ok(PatternTable { counts })
+    }
+
+    // Reads the table file at the given path
+    pub(crate) fn read(path: &Path) -> io::Result<PatternTable> {
+        PatternTable::from_bytes(&fs::read(path)?).map_err(|message| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("{}: {message}", path.display()))
+        })
+    }
+}
+
+/*****************************************************\
+|****************       WEIGHTING     ****************|
+\*****************************************************/
+
+// The pattern table given by GO_ENGINE_PATTERNS, loaded on first use
+// Playouts are unweighted when the variable is unset or the table cannot be read
+fn loaded_table() -> Option<&'static PatternTable> {
+    static TABLE: OnceLock<Option<PatternTable>> = OnceLock::new();
+    TABLE
+        .get_or_init(|| {
+            let path = std::env::var(PATTERNS_VAR).ok()?;
+            match PatternTable::read(Path::new(&path)) {
+                Ok(table) => {
+                    tracing::info!(patterns = table.len(), "Loaded pattern table {path}");
+                    Some(table)
+                }
+                Err(error) => {
+                    tracing::warn!("Could not load pattern table {path}, playouts are unweighted: {error}");
+                    None
+                }
+            }
+        })
+        .as_ref()
+}
+
+// Chooses one of the given Intersections for the given Color to play, weighted by the loaded
+// pattern table, or uniformly when there is none
+// Returns None if no Intersections are given
+pub(crate) fn choose_weighted(board: &Board, color: Color, choices: &[Intersection]) -> Option<Intersection> {
+    use rand::Rng;
+    if choices.is_empty() {
+        return None;
+    }
+
+    let Some(table) = loaded_table() else {
+        return Some(choices[engine_rng().gen_range(0..choices.len())]);
+    };
+    let weights: Vec<f64> = choices
+        .iter()
+        .map(|intsc| pattern_code(board, intsc, color).map_or(0.0, |code| table.weight(code)))
+        .collect();
+
+    let mut target = engine_rng().gen_range(0.0..weights.iter().sum::<f64>().max(f64::MIN_POSITIVE));
+    for (intsc, weight) in choices.iter().zip(weights) {
+        if target < weight {
+            return Some(*intsc);
+        }
+        target -= weight;
+    }
+    choices.last().copied()
+}
+
+/********************************************************\
+|****************     PUBLIC METHODS     ****************|
+\********************************************************/
+
+// Counts the local patterns of every game given by the settings and writes the table file
+pub(crate) fn run_patterns(settings: PatternSettings) -> io::Result<()> {
+    let mut table = PatternTable::default();
+    let mut games = 0;
+
+    for file in sgf_files(&settings.inputs)? {
+        let record = read_sgf_file(&file)?;
+        match table.add_game(&record) {
+            Ok(()) => games += 1,
+            Err(message) => tracing::warn!(file = %file.display(), "Skipping game: {message}"),
+        }
+    }
+
+    fs::write(&settings.output, table.to_bytes())?;
+    println!(
+        "Counted {} patterns from {games} games to {}",
+        table.len(),
+        settings.output.display()
+    );
+    Ok(())
+}
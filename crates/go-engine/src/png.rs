@@ -0,0 +1,195 @@
+use super::*;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use tiny_skia::{FillRule, Paint, PathBuilder, Pixmap, Stroke, Transform};
+/******************************************************\
+|****************      CONSTANTS       ****************|
+\******************************************************/
+
+// Pixels between neighboring lines of the grid
+const CELL: f32 = 32.0;
+
+// Pixels between the outermost lines of the grid and the edge of the image
+const MARGIN: f32 = 32.0;
+
+// Colors of the board, its lines, and its stones, as RGB
+const BOARD_COLOR: (u8, u8, u8) = (0xdc, 0xb3, 0x5c);
+const LINE_COLOR: (u8, u8, u8) = (0x00, 0x00, 0x00);
+const BLACK_STONE_COLOR: (u8, u8, u8) = (0x00, 0x00, 0x00);
+const WHITE_STONE_COLOR: (u8, u8, u8) = (0xff, 0xff, 0xff);
+
+/****************************************************\
+|****************    GLOBAL TYPES    ****************|
+\****************************************************/
+
+// Settings of an export of a position of an SGF game as a PNG image, parsed from the command line
+pub(crate) struct PngSettings {
+    input: PathBuf,
+    output: PathBuf,
+    move_number: Option<usize>,
+}
+
+/*****************************************************\
+|****************        SETUP        ****************|
+\*****************************************************/
+
+impl PngSettings {
+    // Parses export settings from the arguments following the png run mode
+    // Accepts "<game.sgf> --output <file.png> --move N", where the image is written to board.png
+    // and shows the final position unless the options are given
+    pub(crate) fn from_args(args: &[String]) -> Result<PngSettings, String> {
+        let mut input: Option<PathBuf> = None;
+        let mut output = PathBuf::from("board.png");
+        let mut move_number: Option<usize> = None;
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--output" => {
+                    let value = args.next().ok_or("No value given to png option --output")?;
+                    output = PathBuf::from(value);
+                }
+                "--move" => {
+                    let value = args.next().ok_or("No value given to png option --move")?;
+                    let number = value
+                        .parse()
+                        .map_err(|_| format!("Invalid move number given to png: {value}"))?;
+                    move_number = Some(number);
+                }
+                _ if input.is_none() => input = Some(PathBuf::from(arg)),
+                _ => return Err(format!("Unexpected argument given to png: {arg}")),
+            }
+        }
+
+        let input = input.ok_or("No SGF file given to png")?;
+        Ok(PngSettings { input, output, move_number })
+    }
+}
+
+/****************************************************\
+|****************       HELPER       ****************|
+\****************************************************/
+
+// The pixel offset of the line at the given index of the grid, from the left or top of the image
+fn offset(index: u16) -> f32 {
+    MARGIN + index as f32 * CELL
+}
+
+// A solid Paint of the given RGB color
+fn paint((red, green, blue): (u8, u8, u8)) -> Paint<'static> {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(red, green, blue, 0xff);
+    paint.anti_alias = true;
+    paint
+}
+
+// The fill color of a stone of the given Color
+fn stone_color(color: Color) -> (u8, u8, u8) {
+    match color {
+        Color::Black => BLACK_STONE_COLOR,
+        Color::White => WHITE_STONE_COLOR,
+    }
+}
+
+// The column and row of the given Intersection on a board of the given size, counted from the top
+// left of the grid
+fn grid_point(intsc: &Intersection, size: u16) -> (u16, u16) {
+    (intsc.column().to_u16(), size - intsc.row())
+}
+
+// Draws a circle of the given radius around the given grid point, filled with the fill color and
+// outlined with the stroke color of the given width, if any
+fn circle(
+    pixmap: &mut Pixmap,
+    (column, row): (u16, u16),
+    radius: f32,
+    fill: Option<(u8, u8, u8)>,
+    stroke: Option<((u8, u8, u8), f32)>,
+) {
+    let Some(path) = PathBuilder::from_circle(offset(column), offset(row), radius) else { return };
+    if let Some(fill) = fill {
+        pixmap.fill_path(&path, &paint(fill), FillRule::Winding, Transform::identity(), None);
+    }
+    if let Some((color, width)) = stroke {
+        let stroke = Stroke { width, ..Stroke::default() };
+        pixmap.stroke_path(&path, &paint(color), &stroke, Transform::identity(), None);
+    }
+}
+
+/********************************************************\
+|****************     PUBLIC METHODS     ****************|
+\********************************************************/
+
+// Draws the given Board as a PNG image, with its star points and stones, marking the stone of the
+// given last move, if any
+pub fn board_png(board: &Board, last_move: Option<Intersection>) -> io::Result<Vec<u8>> {
+    let size = board.size.to_u16();
+    let (start, end) = (offset(0), offset(size - 1));
+    let width = (end + MARGIN) as u32;
+    let mut pixmap = Pixmap::new(width, width).unwrap(); // boards are never empty
+    let (red, green, blue) = BOARD_COLOR;
+    pixmap.fill(tiny_skia::Color::from_rgba8(red, green, blue, 0xff));
+
+    let mut grid = PathBuilder::new();
+    for index in 0..size {
+        let line = offset(index);
+        grid.move_to(line, start);
+        grid.line_to(line, end);
+        grid.move_to(start, line);
+        grid.line_to(end, line);
+    }
+    if let Some(grid) = grid.finish() {
+        let stroke = Stroke { width: 1.0, ..Stroke::default() };
+        pixmap.stroke_path(&grid, &paint(LINE_COLOR), &stroke, Transform::identity(), None);
+    }
+
+    let stars = if board.size == BoardSize::Nine { 5 } else { 9 };
+    for point in board.size.handicap_points(stars).unwrap_or_default() {
+        circle(&mut pixmap, grid_point(&point.into(), size), 3.0, Some(LINE_COLOR), None);
+    }
+
+    let rows = board.rows();
+    let radius = CELL / 2.0 - 1.0;
+    for (row, states) in rows.iter().enumerate() {
+        for (column, state) in states.iter().enumerate() {
+            if let go_board::State::Occupied(color) = state {
+                let point = (column as u16, row as u16);
+                circle(&mut pixmap, point, radius, Some(stone_color(*color)), Some((LINE_COLOR, 1.0)));
+            }
+        }
+    }
+
+    // the last move is marked by a ring of the other color on its stone, if it is still there
+    if let Some((column, row)) = last_move.map(|intsc| grid_point(&intsc, size)) {
+        if let go_board::State::Occupied(color) = rows[row as usize][column as usize] {
+            let ring = stone_color(color.opposite_color());
+            circle(&mut pixmap, (column, row), CELL / 4.0, None, Some((ring, 2.0)));
+        }
+    }
+
+    pixmap.encode_png().map_err(io::Error::other)
+}
+
+// Writes the position of the SGF game given by the settings, after the given move or else at the
+// end of the game, to a PNG image
+pub(crate) fn run_png(settings: PngSettings) -> io::Result<()> {
+    let invalid_data = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+
+    let record = read_sgf_file(&settings.input)?;
+    let mut positions = record.positions().map_err(invalid_data)?;
+    let move_number = settings.move_number.unwrap_or(record.nodes.len());
+    if move_number >= positions.len() {
+        let moves = record.nodes.len();
+        return Err(invalid_data(format!("Move {move_number} is past the end of the game, which has {moves} moves")));
+    }
+
+    let (board, _) = positions.swap_remove(move_number);
+    let last_move = match move_number.checked_sub(1).map(|index| record.nodes[index].mov) {
+        Some(Move::MOVE(intsc, _)) => Some(intsc),
+        _ => None,
+    };
+    fs::write(&settings.output, board_png(&board, last_move)?)?;
+    tracing::info!(output = %settings.output.display(), move_number, "Wrote position");
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use super::*;
+use std::cell::RefCell;
+/******************************************************\
+|****************      CONSTANTS       ****************|
+\******************************************************/
+
+// Most spare Boards kept by each thread, enough for the nodes of a typical search tree
+const MAX_SPARE_BOARDS: usize = 4096;
+
+/*****************************************************\
+|****************    PRIVATE TYPES    ****************|
+\*****************************************************/
+
+// Boards no longer in use by this thread, whose allocations are reused for the scratch Boards
+// of later playouts instead of allocating a fresh position for every step
+thread_local! {
+    static SPARE_BOARDS: RefCell<Vec<Board>> = const { RefCell::new(Vec::new()) };
+}
+
+/********************************************************\
+|****************     PUBLIC METHODS     ****************|
+\********************************************************/
+
+// Returns a copy of the given Board, reusing a spare Board of this thread when there is one
+pub fn pooled_copy(source: &Board) -> Board {
+    match SPARE_BOARDS.with(|spare| spare.borrow_mut().pop()) {
+        Some(mut board) => {
+            board.copy_from(source);
+            board
+        }
+        None => source.deepcopy(),
+    }
+}
+
+// Gives the given Board back to this thread's spares for pooled_copy to reuse
+// Boards past MAX_SPARE_BOARDS are dropped
+pub fn recycle(board: Board) {
+    SPARE_BOARDS.with(|spare| {
+        let mut spare = spare.borrow_mut();
+        if spare.len() < MAX_SPARE_BOARDS {
+            spare.push(board);
+        }
+    });
+}
+
+// Drops every spare Board of this thread, giving their allocations back
+pub fn clear_spares() {
+    SPARE_BOARDS.with(|spare| *spare.borrow_mut() = Vec::new());
+}
+
+// Number of spare Boards held by this thread, for tests of the commands that recycle them
+pub fn spare_boards() -> usize {
+    SPARE_BOARDS.with(|spare| spare.borrow().len())
+}
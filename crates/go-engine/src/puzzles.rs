@@ -0,0 +1,268 @@
+use super::*;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+/******************************************************\
+|****************      CONSTANTS       ****************|
+\******************************************************/
+
+// Number of moves in the solution line of an exported puzzle, starting with the key move
+const SOLUTION_LENGTH: usize = 3;
+
+/****************************************************\
+|****************    GLOBAL TYPES    ****************|
+\****************************************************/
+
+// Settings of a puzzle extraction from SGF games, parsed from the command line
+pub(crate) struct PuzzleSettings {
+    inputs: Vec<PathBuf>,
+    directory: PathBuf,
+    visits: u16,
+    min_stones: u16,
+}
+
+/*****************************************************\
+|****************    PRIVATE TYPES    ****************|
+\*****************************************************/
+
+// A tactical position found in a game, with the moves that solve it
+struct Puzzle {
+    position: Board,
+    to_play: Color,
+    solution: Vec<Move>,
+    captured: u16,
+    source: String,
+    move_number: usize,
+}
+
+/*****************************************************\
+|****************        SETUP        ****************|
+\*****************************************************/
+
+impl PuzzleSettings {
+    // Parses puzzle settings from the arguments following the puzzles run mode
+    // Accepts "<file.sgf or directory>... --output <directory> --visits N --min-stones N",
+    // where the options are optional
+    pub(crate) fn from_args(args: &[String]) -> Result<PuzzleSettings, String> {
+        let mut settings = PuzzleSettings {
+            inputs: vec![],
+            directory: PathBuf::from("puzzles"),
+            visits: 30,
+            min_stones: 3,
+        };
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--output" => {
+                    let value = args.next().ok_or("No value given to puzzles option --output")?;
+                    settings.directory = PathBuf::from(value);
+                }
+                "--visits" => {
+                    let value = args.next().ok_or("No value given to puzzles option --visits")?;
+                    settings.visits = value
+                        .parse()
+                        .map_err(|_| format!("Invalid visit count given to puzzles: {value}"))?;
+                }
+                "--min-stones" => {
+                    let value = args
+                        .next()
+                        .ok_or("No value given to puzzles option --min-stones")?;
+                    settings.min_stones = value
+                        .parse()
+                        .ok()
+                        .filter(|stones| *stones > 0)
+                        .ok_or(format!("Invalid stone count given to puzzles: {value}"))?;
+                }
+                _ => settings.inputs.push(PathBuf::from(arg)),
+            }
+        }
+
+        if settings.inputs.is_empty() {
+            return Err("No SGF files or directories given to puzzles".to_string());
+        }
+
+        Ok(settings)
+    }
+}
+
+/****************************************************\
+|****************       HELPER       ****************|
+\****************************************************/
+
+// Collects the SGF files given directly, or found directly inside the given directories
+pub(crate) fn sgf_files(inputs: &[PathBuf]) -> io::Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = vec![];
+    for input in inputs {
+        if input.is_dir() {
+            let mut found: Vec<PathBuf> = fs::read_dir(input)?
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("sgf")))
+                .collect();
+            found.sort();
+            files.extend(found);
+        } else {
+            files.push(input.clone());
+        }
+    }
+
+    Ok(files)
+}
+
+// Returns the number of stones captured by playing the given Move on the given Board,
+// or None if the Move is illegal
+fn stones_captured(board: &Board, mov: Move) -> Option<u16> {
+    let mut after = board.deepcopy();
+    after
+        .play(mov)
+        .then(|| after.total_captures() - board.total_captures())
+}
+
+// Returns every legal move of the given Color on the given Board that captures at least
+// the given number of stones
+pub(crate) fn large_captures(board: &Board, color: Color, min_stones: u16) -> Vec<Move> {
+    let numeric_size = board.size.to_u16();
+    (0..numeric_size * numeric_size)
+        .filter_map(|index| {
+            let column = ColumnIdentifier::from_u16(index % numeric_size)?;
+            Some(Move::MOVE(Intersection::new(column, index / numeric_size + 1), color))
+        })
+        .filter(|mov| stones_captured(board, *mov).is_some_and(|captured| captured >= min_stones))
+        .collect()
+}
+
+// Extends the given key move into a solution line, letting the engine answer for both sides
+fn solution_line(position: &Board, key_move: Move, visits: u16) -> Vec<Move> {
+    let mut board = position.deepcopy();
+    board.play(key_move);
+    let mut solution = vec![key_move];
+    let mut color = key_move_color(&key_move).opposite_color();
+
+    while solution.len() < SOLUTION_LENGTH {
+        let mov = generate_move(&board, color, visits);
+        if !matches!(mov, Move::MOVE(..)) || !board.play(mov) {
+            break;
+        }
+        solution.push(mov);
+        color = color.opposite_color();
+    }
+
+    solution
+}
+
+// The Color playing the given key move, which is always a stone placement
+fn key_move_color(mov: &Move) -> Color {
+    match mov {
+        Move::MOVE(_, color) => *color,
+        _ => unreachable!("Puzzle key moves always place a stone"),
+    }
+}
+
+impl Puzzle {
+    // Converts this puzzle to a tsumego SGF record, set up with the position's stones and
+    // followed by the solution line
+    fn to_sgf(&self) -> SgfRecord {
+        let mut record = SgfRecord::new(self.position.size, self.position.komi);
+        record.setup_black = self.position.stones(Color::Black);
+        record.setup_white = self.position.stones(Color::White);
+        record.to_play = Some(self.to_play);
+        record.comment = Some(format!(
+            "{} to play and capture {} stones\nFrom {}, move {}",
+            match self.to_play {
+                Color::Black => "Black",
+                Color::White => "White",
+            },
+            self.captured,
+            self.source,
+            self.move_number
+        ));
+
+        let mut color = self.to_play;
+        for mov in &self.solution {
+            record.nodes.push(SgfNode::new(color, *mov));
+            color = color.opposite_color();
+        }
+        if let Some(key_node) = record.nodes.first_mut() {
+            key_node.comment = Some("Correct".to_string());
+        }
+
+        record
+    }
+}
+
+/*****************************************************\
+|****************     EXTRACTION      ****************|
+\*****************************************************/
+
+// Finds the puzzles in the given game: positions where the engine's preferred move is the only
+// move capturing at least the minimum number of stones given by the settings
+fn find_puzzles(path: &Path, settings: &PuzzleSettings) -> Result<Vec<Puzzle>, String> {
+    let positions = read_sgf_file(path)
+        .map_err(|error| error.to_string())?
+        .positions()?;
+    let source = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let mut puzzles: Vec<Puzzle> = vec![];
+
+    for (index, (position, to_play)) in positions.iter().enumerate() {
+        // the cheap capture scan filters positions before the engine is asked for its move
+        let captures = large_captures(position, *to_play, settings.min_stones);
+        if captures.len() != 1 {
+            continue;
+        }
+
+        let analysis = analyze_position(position, *to_play, settings.visits);
+        if analysis.best_move != captures[0] {
+            continue;
+        }
+
+        tracing::debug!(source = %source, move_number = index + 1, "Found puzzle");
+        puzzles.push(Puzzle {
+            position: position.deepcopy(),
+            to_play: *to_play,
+            solution: solution_line(position, captures[0], settings.visits),
+            captured: stones_captured(position, captures[0]).unwrap_or_default(),
+            source: source.clone(),
+            move_number: index + 1,
+        });
+    }
+
+    Ok(puzzles)
+}
+
+/********************************************************\
+|****************     PUBLIC METHODS     ****************|
+\********************************************************/
+
+// Scans every SGF game given by the settings for tactical puzzles, writing each one found as a
+// tsumego SGF in the settings directory
+pub(crate) fn run_puzzles(settings: PuzzleSettings) -> io::Result<()> {
+    fs::create_dir_all(&settings.directory)?;
+    let files = sgf_files(&settings.inputs)?;
+    let mut puzzle_count = 0;
+
+    for file in &files {
+        let puzzles = match find_puzzles(file, &settings) {
+            Ok(puzzles) => puzzles,
+            Err(message) => {
+                tracing::warn!(file = %file.display(), "Skipping game: {message}");
+                continue;
+            }
+        };
+
+        for puzzle in puzzles {
+            puzzle_count += 1;
+            let path = settings.directory.join(format!("puzzle_{puzzle_count:04}.sgf"));
+            fs::write(&path, puzzle.to_sgf().to_string())?;
+            println!(
+                "Puzzle {puzzle_count}: {} move {}, captures {} stones ({})",
+                puzzle.source,
+                puzzle.move_number,
+                puzzle.captured,
+                path.display()
+            );
+        }
+    }
+
+    println!("\nFound {puzzle_count} puzzles in {} games", files.len());
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use super::*;
+use std::io;
+use std::path::PathBuf;
+/****************************************************\
+|****************    GLOBAL TYPES    ****************|
+\****************************************************/
+
+// Settings of the scoring of finished SGF games, parsed from the command line
+pub(crate) struct ScoreSettings {
+    paths: Vec<PathBuf>,
+    method: ScoringRules,
+    threads: usize,
+}
+
+/*****************************************************\
+|****************        SETUP        ****************|
+\*****************************************************/
+
+impl ScoreSettings {
+    // Parses scoring settings from the arguments following the score run mode
+    // Accepts "<position.sgf or directory>... --rules <rules> --threads N", where the rules
+    // default to chinese and the threads to every available core
+    pub(crate) fn from_args(args: &[String]) -> Result<ScoreSettings, String> {
+        let mut paths: Vec<PathBuf> = vec![];
+        let mut method = ScoringRules::Area;
+        let mut threads = 0;
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--rules" => {
+                    let value = args.next().ok_or("No value given to score option --rules")?;
+                    method = Ruleset::from_name(value).map(|ruleset| ruleset.scoring())
+                        .ok_or(format!("Unknown rules given to score: {value}"))?;
+                }
+                "--threads" => {
+                    let value = args.next().ok_or("No value given to score option --threads")?;
+                    threads = value
+                        .parse()
+                        .map_err(|_| format!("Invalid thread count given to score: {value}"))?;
+                }
+                _ => paths.push(PathBuf::from(arg)),
+            }
+        }
+
+        if paths.is_empty() {
+            return Err("No SGF file given to score".to_string());
+        }
+
+        Ok(ScoreSettings { paths, method, threads })
+    }
+}
+
+/********************************************************\
+|****************     PUBLIC METHODS     ****************|
+\********************************************************/
+
+// Loads the final position of each SGF game given by the settings, removes its dead stones,
+// and prints the result of the game, prefixed by its file when several games are scored
+pub(crate) fn run_score(settings: ScoreSettings) -> io::Result<()> {
+    let invalid_data = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+
+    let files = sgf_files(&settings.paths)?;
+    let mut boards: Vec<Board> = vec![];
+    for file in &files {
+        let record = read_sgf_file(file)?;
+        let (board, _) = record.positions().map_err(invalid_data)?.pop().unwrap(); // final position always exists
+
+        let dead_stones: Vec<String> = board.dead_stones().iter().map(|intsc| intsc.to_string()).collect();
+        tracing::info!(file = %file.display(), ?dead_stones, method = ?settings.method, "Scoring final position");
+        boards.push(board);
+    }
+
+    set_search_threads(settings.threads);
+    let scores = score_positions(&boards, settings.method);
+    for (file, score) in files.iter().zip(scores) {
+        match files.len() {
+            1 => println!("{}", result_string(score)),
+            _ => println!("{} {}", file.display(), result_string(score)),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,254 @@
+use super::*;
+use rand::Rng;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+/******************************************************\
+|****************      CONSTANTS       ****************|
+\******************************************************/
+
+// Number of attempts made at finding a random legal move when noise is applied
+const RANDOM_MOVE_ATTEMPTS: u16 = 50;
+
+/****************************************************\
+|****************    GLOBAL TYPES    ****************|
+\****************************************************/
+
+// Settings of a batch of self-play games, parsed from the command line
+pub(crate) struct SelfplaySettings {
+    games: u16,
+    directory: PathBuf,
+    size: BoardSize,
+    playouts: u16,
+    noise: f64,
+}
+
+/*****************************************************\
+|****************    PRIVATE TYPES    ****************|
+\*****************************************************/
+
+// The record of a single finished self-play game
+struct SelfplayGame {
+    moves: Vec<Move>,
+    komi: f64,
+    winner: Color,
+    result: String,
+    resigned: bool,
+}
+
+/*****************************************************\
+|****************        SETUP        ****************|
+\*****************************************************/
+
+impl SelfplaySettings {
+    // Parses self-play settings from the arguments following the selfplay run mode
+    // Accepts "[games] [directory] --size N --playouts N --noise F", all of which are optional
+    pub(crate) fn from_args(args: &[String]) -> Result<SelfplaySettings, String> {
+        let mut settings = SelfplaySettings {
+            games: 10,
+            directory: PathBuf::from("selfplay"),
+            size: BoardSize::Nineteen,
+            playouts: 30,
+            noise: 0.0,
+        };
+
+        let mut positional = 0;
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--size" | "--playouts" | "--noise" => {
+                    let value = args
+                        .next()
+                        .ok_or(format!("No value given to selfplay option {arg}"))?;
+                    settings.set_option(arg, value)?;
+                }
+                _ if positional == 0 => {
+                    settings.games = arg
+                        .parse()
+                        .map_err(|_| format!("Invalid number of selfplay games given: {arg}"))?;
+                    positional += 1;
+                }
+                _ if positional == 1 => {
+                    settings.directory = PathBuf::from(arg);
+                    positional += 1;
+                }
+                _ => return Err(format!("Unexpected selfplay argument given: {arg}")),
+            }
+        }
+
+        Ok(settings)
+    }
+
+    // Sets the self-play option of the given name to the given value
+    fn set_option(&mut self, option: &str, value: &str) -> Result<(), String> {
+        match option {
+            "--size" => {
+                self.size = value
+                    .parse::<u16>()
+                    .ok()
+                    .and_then(BoardSize::from_u16)
+                    .ok_or(format!("Invalid board size given to selfplay: {value}"))?;
+            }
+            "--playouts" => {
+                self.playouts = value
+                    .parse()
+                    .map_err(|_| format!("Invalid playout count given to selfplay: {value}"))?;
+            }
+            "--noise" => {
+                self.noise = value
+                    .parse()
+                    .ok()
+                    .filter(|noise| (0.0..=1.0).contains(noise))
+                    .ok_or(format!("Noise must be between 0 and 1, given: {value}"))?;
+            }
+            _ => return Err(format!("Unknown selfplay option given: {option}")),
+        }
+
+        Ok(())
+    }
+}
+
+/****************************************************\
+|****************       HELPER       ****************|
+\****************************************************/
+
+// Picks a random legal move for the given Color on the given Board, or passes if none is found
+fn random_move(board: &Board, color: Color) -> Move {
+    for _ in 0..RANDOM_MOVE_ATTEMPTS {
+        let intsc = board.random_intersection(0);
+        if board.can_place_stone_at(&intsc) {
+            return Move::MOVE(intsc, color);
+        }
+    }
+
+    Move::PASS
+}
+
+impl SelfplayGame {
+    // Converts this game to an SGF record played with the given settings
+    fn to_sgf(&self, settings: &SelfplaySettings) -> SgfRecord {
+        let mut record = SgfRecord::new(settings.size, self.komi);
+        record.black_player = Some("Go_Engine".to_string());
+        record.white_player = Some("Go_Engine".to_string());
+        record.result = Some(self.result.clone());
+
+        let mut color = Color::Black;
+        for mov in &self.moves {
+            if let Move::MOVE(_, played) = mov {
+                color = *played;
+            }
+            record.nodes.push(SgfNode::new(color, *mov));
+            color = color.opposite_color();
+        }
+
+        record
+    }
+}
+
+/*****************************************************\
+|****************      SELF-PLAY      ****************|
+\*****************************************************/
+
+// Plays a single game of the engine against itself with the given settings
+fn play_game(settings: &SelfplaySettings) -> SelfplayGame {
+    let mut board = Board::new(settings.size);
+    let mut moves: Vec<Move> = vec![];
+    let mut color = Color::Black;
+    let mut consecutive_passes = 0;
+    let max_moves = settings.size.to_u16() as usize * settings.size.to_u16() as usize * 2;
+
+    while consecutive_passes < 2 && moves.len() < max_moves {
+        let mut mov = if engine_rng().gen_bool(settings.noise) {
+            random_move(&board, color)
+        } else {
+            generate_move(&board, color, settings.playouts)
+        };
+
+        if mov == Move::RESIGN {
+            return SelfplayGame {
+                moves,
+                komi: board.komi,
+                winner: color.opposite_color(),
+                result: format!(
+                    "{}+R",
+                    match color {
+                        Color::Black => "W",
+                        Color::White => "B",
+                    }
+                ),
+                resigned: true,
+            };
+        }
+
+        if !board.play(mov) {
+            mov = Move::PASS;
+            board.play(mov);
+        }
+
+        consecutive_passes = if mov == Move::PASS { consecutive_passes + 1 } else { 0 };
+        moves.push(mov);
+        color = color.opposite_color();
+    }
+
+    let score = board.final_score(ScoringRules::Area);
+    SelfplayGame {
+        moves,
+        komi: board.komi,
+        winner: if score > 0.0 { Color::Black } else { Color::White },
+        result: if score > 0.0 {
+            format!("B+{score}")
+        } else {
+            format!("W+{}", -score)
+        },
+        resigned: false,
+    }
+}
+
+/********************************************************\
+|****************     PUBLIC METHODS     ****************|
+\********************************************************/
+
+// Plays the number of self-play games given by the settings, writing each to an SGF file
+// in the settings directory and printing aggregate results once all games are finished
+pub(crate) fn run_selfplay(settings: SelfplaySettings) -> io::Result<()> {
+    fs::create_dir_all(&settings.directory)?;
+
+    let mut black_wins = 0;
+    let mut resignations = 0;
+    let mut total_moves = 0;
+
+    for game_number in 1..=settings.games {
+        let game = play_game(&settings);
+        let path = settings.directory.join(format!("game_{game_number:04}.sgf"));
+        fs::write(&path, game.to_sgf(&settings).to_string())?;
+
+        tracing::info!(game_number, result = %game.result, "Self-play game finished");
+        println!(
+            "Game {game_number}: {} in {} moves ({})",
+            game.result,
+            game.moves.len(),
+            path.display()
+        );
+
+        if game.winner == Color::Black {
+            black_wins += 1;
+        }
+        if game.resigned {
+            resignations += 1;
+        }
+        total_moves += game.moves.len();
+    }
+
+    if settings.games > 0 {
+        println!(
+            "\nGames: {}  Black wins: {}  White wins: {}  Resignations: {}  Average length: {:.1}",
+            settings.games,
+            black_wins,
+            settings.games - black_wins,
+            resignations,
+            total_moves as f64 / settings.games as f64
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,335 @@
+use super::*;
+use std::fmt;
+use std::fmt::Formatter;
+use std::fs;
+use std::io;
+use std::path::Path;
+/****************************************************\
+|****************    GLOBAL TYPES    ****************|
+\****************************************************/
+
+// A game record in the Smart Game Format, following the main line of play
+pub struct SgfRecord {
+    pub size: BoardSize,
+    pub komi: f64,
+    pub black_player: Option<String>,
+    pub white_player: Option<String>,
+    pub result: Option<String>,
+    pub setup_black: Vec<Intersection>,
+    pub(crate) setup_white: Vec<Intersection>,
+    pub to_play: Option<Color>,
+    pub(crate) comment: Option<String>,
+    pub nodes: Vec<SgfNode>,
+}
+
+// A single move of an SGF game record
+pub struct SgfNode {
+    pub color: Color,
+    pub mov: Move,
+    pub(crate) comment: Option<String>,
+}
+
+/*****************************************************\
+|****************        SETUP        ****************|
+\*****************************************************/
+
+impl SgfRecord {
+    // Creates a new SgfRecord with no moves for a game on the given BoardSize with the given komi
+    pub fn new(size: BoardSize, komi: f64) -> SgfRecord {
+        SgfRecord {
+            size,
+            komi,
+            black_player: None,
+            white_player: None,
+            result: None,
+            setup_black: vec![],
+            setup_white: vec![],
+            to_play: None,
+            comment: None,
+            nodes: vec![],
+        }
+    }
+}
+
+impl SgfNode {
+    // Creates a new SgfNode for the given Move by the given Color, without a comment
+    pub fn new(color: Color, mov: Move) -> SgfNode {
+        SgfNode {
+            color,
+            mov,
+            comment: None,
+        }
+    }
+}
+
+/****************************************************\
+|****************       HELPER       ****************|
+\****************************************************/
+
+// Converts the given Intersection to its SGF coordinate on a Board of the given size
+pub(crate) fn to_sgf_coordinate(intsc: &Intersection, size: &BoardSize) -> String {
+    let column = (b'a' + intsc.column().to_u16() as u8) as char;
+    let row = (b'a' + (size.to_u16() - intsc.row()) as u8) as char;
+    format!("{column}{row}")
+}
+
+// Converts the given SGF coordinate on a Board of the given size into a Move for the given Color
+// Empty coordinates, and "tt" on boards of size 19 or less, are passes
+pub(crate) fn from_sgf_coordinate(
+    coordinate: &str,
+    size: &BoardSize,
+    color: Color,
+) -> Result<Move, String> {
+    let numeric_size = size.to_u16();
+    if coordinate.is_empty() || (coordinate == "tt" && numeric_size <= 19) {
+        return Ok(Move::PASS);
+    }
+
+    let bytes = coordinate.as_bytes();
+    if bytes.len() != 2 || !bytes.iter().all(|byte| byte.is_ascii_lowercase()) {
+        return Err(format!("Invalid SGF coordinate: {coordinate}"));
+    }
+
+    let column = (bytes[0] - b'a') as u16;
+    let row_from_top = (bytes[1] - b'a') as u16;
+    if column >= numeric_size || row_from_top >= numeric_size {
+        return Err(format!("SGF coordinate out of bounds: {coordinate}"));
+    }
+
+    let column_identifier = ColumnIdentifier::from_u16(column).unwrap(); // bounds checked above
+    Ok(Move::MOVE(
+        Intersection::new(column_identifier, numeric_size - row_from_top),
+        color,
+    ))
+}
+
+// The SGF identifier of moves played by the given Color
+fn color_identifier(color: &Color) -> &'static str {
+    match color {
+        Color::Black => "B",
+        Color::White => "W",
+    }
+}
+
+// Escapes the characters of the given text that are special inside an SGF property value
+fn escape_value(text: &str) -> String {
+    text.replace('\\', "\\\\").replace(']', "\\]")
+}
+
+/*****************************************************\
+|****************       PARSING       ****************|
+\*****************************************************/
+
+// The (identifier, values) properties of an SGF node
+type Properties = Vec<(String, Vec<String>)>;
+
+// Splits the given SGF text into the properties of each node on the main line
+// The main line is the first variation at every branch, so parsing ends at the first ')'
+fn main_line_properties(sgf: &str) -> Result<Vec<Properties>, String> {
+    let mut nodes: Vec<Properties> = vec![];
+    let mut chars = sgf.chars();
+    let mut identifier = String::new();
+
+    if !sgf.trim_start().starts_with('(') {
+        return Err("SGF does not begin with a game tree".to_string());
+    }
+
+    while let Some(c) = chars.next() {
+        match c {
+            '(' => {}
+            ')' => return Ok(nodes),
+            ';' => nodes.push(vec![]),
+            '[' => {
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('\\') => value.extend(chars.next()),
+                        Some(']') => break,
+                        Some(c) => value.push(c),
+                        None => return Err("Unterminated SGF property value".to_string()),
+                    }
+                }
+
+                let node = nodes.last_mut().ok_or("SGF property found outside of a node")?;
+                if identifier.is_empty() {
+                    // additional value of the previous property, ex. AB[aa][bb]
+                    match node.last_mut() {
+                        Some((_, values)) => values.push(value),
+                        None => return Err("SGF property value without an identifier".to_string()),
+                    }
+                } else {
+                    node.push((std::mem::take(&mut identifier), vec![value]));
+                }
+            }
+            c if c.is_ascii_uppercase() => identifier.push(c),
+            c if c.is_ascii_lowercase() || c.is_whitespace() => {} // lowercase is ignored in old SGF identifiers
+            c => return Err(format!("Unexpected character in SGF: {c}")),
+        }
+    }
+
+    Err("SGF game tree is not closed".to_string())
+}
+
+impl SgfRecord {
+    // Parses the main line of the given SGF text into an SgfRecord
+    pub fn parse(sgf: &str) -> Result<SgfRecord, String> {
+        let nodes = main_line_properties(sgf)?;
+        let mut record = SgfRecord::new(BoardSize::Nineteen, 0.0);
+
+        for properties in nodes {
+            let mut node: Option<SgfNode> = None;
+            let mut comment: Option<String> = None;
+            for (identifier, values) in properties {
+                let value = values[0].as_str();
+                match identifier.as_str() {
+                    "SZ" => {
+                        record.size = value
+                            .parse::<u16>()
+                            .ok()
+                            .and_then(BoardSize::from_u16)
+                            .ok_or(format!("Unsupported SGF board size: {value}"))?;
+                    }
+                    "KM" => {
+                        record.komi = value
+                            .parse()
+                            .map_err(|_| format!("Invalid SGF komi: {value}"))?;
+                    }
+                    "PB" => record.black_player = Some(value.to_string()),
+                    "PW" => record.white_player = Some(value.to_string()),
+                    "RE" => record.result = Some(value.to_string()),
+                    "B" | "W" => {
+                        let color = if identifier == "B" { Color::Black } else { Color::White };
+                        let mov = from_sgf_coordinate(value, &record.size, color)?;
+                        node = Some(SgfNode::new(color, mov));
+                    }
+                    "AB" | "AW" => {
+                        let color = if identifier == "AB" { Color::Black } else { Color::White };
+                        for value in &values {
+                            let Move::MOVE(intsc, _) = from_sgf_coordinate(value, &record.size, color)? else {
+                                return Err(format!("Invalid SGF setup stone: {value}"));
+                            };
+                            match color {
+                                Color::Black => record.setup_black.push(intsc),
+                                Color::White => record.setup_white.push(intsc),
+                            }
+                        }
+                    }
+                    "PL" => record.to_play = Color::from_name(value),
+                    "C" => comment = Some(value.to_string()),
+                    _ => {} // unsupported properties are ignored
+                }
+            }
+
+            if let Some(mut node) = node {
+                node.comment = comment;
+                record.nodes.push(node);
+            }
+        }
+
+        Ok(record)
+    }
+}
+
+// Reads and parses the SGF file at the given path, naming the file in any error
+pub fn read_sgf_file(path: &Path) -> io::Result<SgfRecord> {
+    let sgf = fs::read_to_string(path).map_err(|error| {
+        io::Error::new(error.kind(), format!("Could not read {}: {error}", path.display()))
+    })?;
+    SgfRecord::parse(&sgf).map_err(|message| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Could not parse {}: {message}", path.display()),
+        )
+    })
+}
+
+/*****************************************************\
+|****************      REPLAYING      ****************|
+\*****************************************************/
+
+impl SgfRecord {
+    // Replays this record from its setup stones, returning every position paired with the Color
+    // to play in it, including the final position after the last move
+    pub fn positions(&self) -> Result<Vec<(Board, Color)>, String> {
+        let mut board = Board::new(self.size);
+        board.komi = self.komi;
+        let mut positions: Vec<(Board, Color)> = vec![];
+
+        let setup = [(&self.setup_black, Color::Black), (&self.setup_white, Color::White)];
+        for (stones, color) in setup {
+            for intsc in stones {
+                if !board.place_stone(intsc, color) {
+                    return Err(format!("Invalid SGF setup stone: {intsc}"));
+                }
+            }
+        }
+
+        for (move_number, node) in self.nodes.iter().enumerate() {
+            positions.push((board.deepcopy(), node.color));
+            if !board.play(node.mov) {
+                return Err(format!("Illegal move in SGF at move {}", move_number + 1));
+            }
+        }
+
+        let final_color = self
+            .nodes
+            .last()
+            .map_or(self.to_play.unwrap_or(Color::Black), |node| node.color.opposite_color());
+        positions.push((board, final_color));
+
+        Ok(positions)
+    }
+}
+
+/*****************************************************\
+|****************      RENDERING      ****************|
+\*****************************************************/
+
+impl fmt::Display for SgfRecord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "(;GM[1]FF[4]CA[UTF-8]AP[Go_Engine:{}]SZ[{}]KM[{}]",
+            env!("CARGO_PKG_VERSION"),
+            self.size.to_u16(),
+            self.komi
+        )?;
+        if let Some(black_player) = &self.black_player {
+            write!(f, "PB[{}]", escape_value(black_player))?;
+        }
+        if let Some(white_player) = &self.white_player {
+            write!(f, "PW[{}]", escape_value(white_player))?;
+        }
+        if let Some(result) = &self.result {
+            write!(f, "RE[{}]", escape_value(result))?;
+        }
+        for (identifier, stones) in [("AB", &self.setup_black), ("AW", &self.setup_white)] {
+            if !stones.is_empty() {
+                write!(f, "{identifier}")?;
+                for intsc in stones {
+                    write!(f, "[{}]", to_sgf_coordinate(intsc, &self.size))?;
+                }
+            }
+        }
+        if let Some(to_play) = &self.to_play {
+            write!(f, "PL[{}]", color_identifier(to_play))?;
+        }
+        if let Some(comment) = &self.comment {
+            write!(f, "C[{}]", escape_value(comment))?;
+        }
+
+        for node in &self.nodes {
+            let identifier = color_identifier(&node.color);
+            let coordinate = match &node.mov {
+                Move::MOVE(intsc, _) => to_sgf_coordinate(intsc, &self.size),
+                _ => String::new(),
+            };
+            write!(f, ";{identifier}[{coordinate}]")?;
+            if let Some(comment) = &node.comment {
+                write!(f, "C[{}]", escape_value(comment))?;
+            }
+        }
+
+        write!(f, ")")
+    }
+}
@@ -0,0 +1,1055 @@
+#[cfg(test)]
+use super::*;
+/*****************************************************\
+|****************        SETUP        ****************|
+\*****************************************************/
+
+#[test]
+fn test_board_deepcopy() {
+    let mut board = Board::new(BoardSize::Nineteen);
+    let board_copy = board.deepcopy();
+    assert_eq!(board, board_copy); // copies of each other
+
+    board.play(Move::MOVE(
+        Intersection::new(ColumnIdentifier::A, 1),
+        Color::White,
+    ));
+
+    assert_ne!(board, board_copy); // board has updated, board_copy has not
+}
+
+/****************************************************\
+|****************       HELPER       ****************|
+\****************************************************/
+
+#[test]
+fn test_add_usize() {
+    // usize tests
+    assert_eq!(add_signed_to_unsigned(10usize, 5), Some(15)); // ensure adding works
+    assert_eq!(add_signed_to_unsigned(7usize, -5), Some(2)); // ensure subtraction works
+    assert_eq!(add_signed_to_unsigned(2usize, -3), None); // ensure underflow returns None
+    assert_eq!(add_signed_to_unsigned(usize::MAX, 1), None); // ensures overflow returns None
+
+    // tests of random types working together
+    assert_eq!(add_signed_to_unsigned(2u8, 4i64), Some(6));
+    assert_eq!(add_signed_to_unsigned(732146u128, -12047i64), Some(720099));
+}
+
+#[test]
+fn test_board_size_from_u16() {
+    assert_eq!(BoardSize::from_u16(9), Some(BoardSize::Nine));
+    assert_eq!(BoardSize::from_u16(13), Some(BoardSize::Thirteen));
+    assert_eq!(BoardSize::from_u16(19), Some(BoardSize::Nineteen));
+    assert_eq!(BoardSize::from_u16(0), None); // random number check
+    assert_eq!(BoardSize::from_u16(35), None); // second random number check
+}
+
+#[test]
+fn test_board_size_to_u16() {
+    assert_eq!(BoardSize::Nine.to_u16(), 9);
+    assert_eq!(BoardSize::Thirteen.to_u16(), 13);
+    assert_eq!(BoardSize::Nineteen.to_u16(), 19);
+}
+
+#[test]
+fn test_column_identifier_from_u16() {
+    use ColumnIdentifier::*;
+    assert_eq!(ColumnIdentifier::from_u16(0), Some(A));
+    assert_eq!(ColumnIdentifier::from_u16(1), Some(B));
+    assert_eq!(ColumnIdentifier::from_u16(2), Some(C));
+    assert_eq!(ColumnIdentifier::from_u16(3), Some(D));
+    assert_eq!(ColumnIdentifier::from_u16(4), Some(E));
+    assert_eq!(ColumnIdentifier::from_u16(5), Some(F));
+    assert_eq!(ColumnIdentifier::from_u16(6), Some(G));
+    assert_eq!(ColumnIdentifier::from_u16(7), Some(H));
+    assert_eq!(ColumnIdentifier::from_u16(8), Some(J));
+    assert_eq!(ColumnIdentifier::from_u16(9), Some(K));
+    assert_eq!(ColumnIdentifier::from_u16(10), Some(L));
+    assert_eq!(ColumnIdentifier::from_u16(11), Some(M));
+    assert_eq!(ColumnIdentifier::from_u16(12), Some(N));
+    assert_eq!(ColumnIdentifier::from_u16(13), Some(O));
+    assert_eq!(ColumnIdentifier::from_u16(14), Some(P));
+    assert_eq!(ColumnIdentifier::from_u16(15), Some(Q));
+    assert_eq!(ColumnIdentifier::from_u16(16), Some(R));
+    assert_eq!(ColumnIdentifier::from_u16(17), Some(S));
+    assert_eq!(ColumnIdentifier::from_u16(18), Some(T));
+    assert_eq!(ColumnIdentifier::from_u16(19), None); // random num check
+}
+
+#[test]
+fn test_column_identifier_to_u16() {
+    use ColumnIdentifier::*;
+    assert_eq!(A.to_u16(), 0);
+    assert_eq!(B.to_u16(), 1);
+    assert_eq!(C.to_u16(), 2);
+    assert_eq!(D.to_u16(), 3);
+    assert_eq!(E.to_u16(), 4);
+    assert_eq!(F.to_u16(), 5);
+    assert_eq!(G.to_u16(), 6);
+    assert_eq!(H.to_u16(), 7);
+    assert_eq!(J.to_u16(), 8);
+    assert_eq!(K.to_u16(), 9);
+    assert_eq!(L.to_u16(), 10);
+    assert_eq!(M.to_u16(), 11);
+    assert_eq!(N.to_u16(), 12);
+    assert_eq!(O.to_u16(), 13);
+    assert_eq!(P.to_u16(), 14);
+    assert_eq!(Q.to_u16(), 15);
+    assert_eq!(R.to_u16(), 16);
+    assert_eq!(S.to_u16(), 17);
+    assert_eq!(T.to_u16(), 18);
+}
+
+#[test]
+fn test_column_identifier_to_string() {
+    use ColumnIdentifier::*;
+    assert_eq!(A.to_string(), "A");
+    assert_eq!(B.to_string(), "B");
+    assert_eq!(C.to_string(), "C");
+    assert_eq!(D.to_string(), "D");
+    assert_eq!(E.to_string(), "E");
+    assert_eq!(F.to_string(), "F");
+    assert_eq!(G.to_string(), "G");
+    assert_eq!(H.to_string(), "H");
+    assert_eq!(J.to_string(), "J");
+    assert_eq!(K.to_string(), "K");
+    assert_eq!(L.to_string(), "L");
+    assert_eq!(M.to_string(), "M");
+    assert_eq!(N.to_string(), "N");
+    assert_eq!(O.to_string(), "O");
+    assert_eq!(P.to_string(), "P");
+    assert_eq!(Q.to_string(), "Q");
+    assert_eq!(R.to_string(), "R");
+    assert_eq!(S.to_string(), "S");
+    assert_eq!(T.to_string(), "T");
+}
+
+#[test]
+fn test_intersection_to_position_index() {
+    use ColumnIdentifier::*;
+    let intsc1 = Intersection::new(A, 1); // works on every board
+    let intsc2 = Intersection::new(K, 3); // does not work on 9x9, too high of column
+    let intsc3 = Intersection::new(J, 10); // does not work on 9x9, too high of row
+    let intsc4 = Intersection::new(M, 12); // does not work on 9x9, both dimensions
+    let intsc5 = Intersection::new(Q, 5); // only works on 19x19, too high of column
+    let intsc6 = Intersection::new(B, 15); // only works on 19x19, too high of row
+    let intsc7 = Intersection::new(S, 16); // only works on 19x19, both dimensions
+    let intsc8 = Intersection::new(A, 20); // never works
+    let intsc9 = Intersection::new(O, 0); // too low of row, never works
+
+    assert_eq!(intsc1.to_position_index(&BoardSize::Nine), Some(100));
+    assert_eq!(intsc1.to_position_index(&BoardSize::Thirteen), Some(196));
+    assert_eq!(intsc1.to_position_index(&BoardSize::Nineteen), Some(400));
+
+    assert_eq!(intsc2.to_position_index(&BoardSize::Nine), None);
+    assert_eq!(intsc2.to_position_index(&BoardSize::Thirteen), Some(175));
+    assert_eq!(intsc2.to_position_index(&BoardSize::Nineteen), Some(367));
+
+    assert_eq!(intsc3.to_position_index(&BoardSize::Nine), None);
+    assert_eq!(intsc3.to_position_index(&BoardSize::Thirteen), Some(69));
+    assert_eq!(intsc3.to_position_index(&BoardSize::Nineteen), Some(219));
+
+    assert_eq!(intsc4.to_position_index(&BoardSize::Nine), None);
+    assert_eq!(intsc4.to_position_index(&BoardSize::Thirteen), Some(42));
+    assert_eq!(intsc4.to_position_index(&BoardSize::Nineteen), Some(180));
+
+    assert_eq!(intsc5.to_position_index(&BoardSize::Nine), None);
+    assert_eq!(intsc5.to_position_index(&BoardSize::Thirteen), None);
+    assert_eq!(intsc5.to_position_index(&BoardSize::Nineteen), Some(331));
+
+    assert_eq!(intsc6.to_position_index(&BoardSize::Nine), None);
+    assert_eq!(intsc6.to_position_index(&BoardSize::Thirteen), None);
+    assert_eq!(intsc6.to_position_index(&BoardSize::Nineteen), Some(107));
+
+    assert_eq!(intsc7.to_position_index(&BoardSize::Nine), None);
+    assert_eq!(intsc7.to_position_index(&BoardSize::Thirteen), None);
+    assert_eq!(intsc7.to_position_index(&BoardSize::Nineteen), Some(102));
+
+    assert_eq!(intsc8.to_position_index(&BoardSize::Nine), None);
+    assert_eq!(intsc8.to_position_index(&BoardSize::Thirteen), None);
+    assert_eq!(intsc8.to_position_index(&BoardSize::Nineteen), None);
+
+    assert_eq!(intsc9.to_position_index(&BoardSize::Nine), None);
+    assert_eq!(intsc9.to_position_index(&BoardSize::Thirteen), None);
+    assert_eq!(intsc9.to_position_index(&BoardSize::Nineteen), None);
+}
+
+#[test]
+fn test_intersection_from_position_index() {
+    use ColumnIdentifier::*;
+
+    // Offboard always results in None
+    assert_eq!(Intersection::from_position_index(0, &BoardSize::Nine), None);
+    assert_eq!(
+        Intersection::from_position_index(0, &BoardSize::Thirteen),
+        None
+    );
+    assert_eq!(
+        Intersection::from_position_index(0, &BoardSize::Nineteen),
+        None
+    );
+
+    // Offboard on some Boards
+    assert_eq!(
+        Intersection::from_position_index(12, &BoardSize::Nine),
+        Some(Intersection::new(A, 9))
+    );
+    assert_eq!(
+        Intersection::from_position_index(12, &BoardSize::Thirteen),
+        None // Offboard on 13x13
+    );
+    assert_eq!(
+        Intersection::from_position_index(12, &BoardSize::Nineteen),
+        None // Offboard on 19x19
+    );
+
+    // Too large of position index returns None
+    assert_eq!(
+        Intersection::from_position_index(122, &BoardSize::Nine),
+        None
+    );
+    assert_eq!(
+        Intersection::from_position_index(122, &BoardSize::Thirteen),
+        Some(Intersection::new(B, 6))
+    );
+    assert_eq!(
+        Intersection::from_position_index(122, &BoardSize::Nineteen),
+        Some(Intersection::new(R, 15))
+    );
+
+    // Intersection that position index relates to depends on BoardSize
+    assert_eq!(
+        Intersection::from_position_index(73, &BoardSize::Nine),
+        Some(Intersection::new(G, 4))
+    );
+    assert_eq!(
+        Intersection::from_position_index(73, &BoardSize::Thirteen),
+        Some(Intersection::new(N, 10))
+    );
+    assert_eq!(
+        Intersection::from_position_index(73, &BoardSize::Nineteen),
+        Some(Intersection::new(K, 17))
+    );
+}
+
+#[test]
+fn test_vertex_to_move() {
+    use ColumnIdentifier::*;
+    assert_eq!(
+        vertex_to_move("Q16", Color::Black),
+        Some(Move::MOVE(Intersection::new(Q, 16), Color::Black))
+    ); // baseline test
+
+    assert_eq!(
+        vertex_to_move("d4", Color::White),
+        Some(Move::MOVE(Intersection::new(D, 4), Color::White))
+    ); // lowercase doesnt matter
+
+    assert_eq!(vertex_to_move("PASS", Color::Black), Some(Move::PASS));
+    assert_eq!(vertex_to_move("resign", Color::Black), Some(Move::RESIGN));
+
+    assert_eq!(vertex_to_move("A20", Color::Black), None); // rows stop at 19
+    assert_eq!(vertex_to_move("C0", Color::Black), None); // rows start at 1
+    assert_eq!(vertex_to_move("A01", Color::Black), None); // no leading zeros
+    assert_eq!(vertex_to_move("A+1", Color::Black), None); // no signs
+    assert_eq!(vertex_to_move("I5", Color::Black), None); // no I column
+    assert_eq!(vertex_to_move("F", Color::Black), None); // no row
+    assert_eq!(vertex_to_move("Some random string", Color::Black), None);
+}
+
+#[test]
+fn test_color_from_string() {
+    // Base cases:
+    assert_eq!(Color::from_name("B"), Some(Color::Black)); 
+    assert_eq!(Color::from_name("W"), Some(Color::White));
+    assert_eq!(Color::from_name("BLACK"), Some(Color::Black));
+    assert_eq!(Color::from_name("WHITE"), Some(Color::White));
+    
+    // case doesn't matter
+    assert_eq!(Color::from_name("b"), Some(Color::Black));
+    assert_eq!(Color::from_name("WhITe"), Some(Color::White));
+    
+    // other strings return None
+    assert_eq!(Color::from_name("A"), None);
+    assert_eq!(Color::from_name(""), None);
+    assert_eq!(Color::from_name("SOME random STUFF ahfdiuaf"), None);
+}
+
+#[test]
+fn test_opposite_color() {
+    assert_eq!(Color::White.opposite_color(), Color::Black);
+    assert_eq!(Color::Black.opposite_color(), Color::White);
+}
+
+// TODO: Potentially test RENDERING, currently omitted
+
+/****************************************************\
+|****************     GAME LOGIC     ****************|
+\****************************************************/
+
+#[test]
+fn test_count() {
+    use ColumnIdentifier::*;
+    use std::collections::HashSet;
+    let mut expected_group: HashSet<Intersection> = HashSet::new();
+    let mut expected_liberties: HashSet<Intersection> = HashSet::new();
+    let mut board = Board::new(BoardSize::Nineteen);
+    expected_group.insert(Intersection::new(B, 2));
+    expected_group.insert(Intersection::new(C, 2));
+    expected_group.insert(Intersection::new(D, 2));
+    expected_group.insert(Intersection::new(D, 1));
+    expected_group.insert(Intersection::new(E, 1));
+
+    expected_liberties.insert(Intersection::new(B, 1));
+    expected_liberties.insert(Intersection::new(C, 1));
+    expected_liberties.insert(Intersection::new(A, 2));
+    expected_liberties.insert(Intersection::new(D, 3));
+    expected_liberties.insert(Intersection::new(F, 1));
+
+    for intsc in &expected_group {
+        board.play(Move::MOVE(*intsc, Color::Black));
+    }
+
+    board.play(Move::MOVE(Intersection::new(F, 2), Color::Black));
+    board.play(Move::MOVE(Intersection::new(B, 3), Color::White));
+    board.play(Move::MOVE(Intersection::new(C, 3), Color::White));
+    board.play(Move::MOVE(Intersection::new(E, 2), Color::White));
+
+    assert_eq!(
+        board.count(
+            Intersection::new(D, 1) // intersection does not matter as long as it is part of the group
+                .to_position_index(&BoardSize::Nineteen)
+                .unwrap() as usize,
+            Color::Black
+        ),
+        (expected_group, expected_liberties)
+    );
+}
+
+#[test]
+fn test_diamond_corner() {
+    use ColumnIdentifier::*;
+    let mut board = Board::new(BoardSize::Nineteen);
+    assert_eq!(board.diamond(&Intersection::new(A, 1)), None); // no diamond by default
+    board.play(Move::MOVE(Intersection::new(A, 2), Color::White));
+    assert_eq!(board.diamond(&Intersection::new(A, 1)), None); // diamond not yet constructed
+    board.play(Move::MOVE(Intersection::new(B, 1), Color::White));
+    assert_eq!(board.diamond(&Intersection::new(A, 1)), Some(Color::White)); // diamond has been constructed on corner
+}
+
+#[test]
+fn test_diamond_side() {
+    use ColumnIdentifier::*;
+    let mut board = Board::new(BoardSize::Nineteen);
+    assert_eq!(board.diamond(&Intersection::new(E, 1)), None); // no diamond by default
+    board.play(Move::MOVE(Intersection::new(D, 1), Color::Black));
+    assert_eq!(board.diamond(&Intersection::new(E, 1)), None); // diamond not yet constructed
+    board.play(Move::MOVE(Intersection::new(E, 2), Color::Black));
+    assert_eq!(board.diamond(&Intersection::new(E, 1)), None); // diamond still not yet constructed
+    board.play(Move::MOVE(Intersection::new(F, 1), Color::Black));
+    assert_eq!(board.diamond(&Intersection::new(E, 1)), Some(Color::Black)); // diamond has been constructed on side
+}
+
+#[test]
+fn test_diamond_center() {
+    use ColumnIdentifier::*;
+    let mut board = Board::new(BoardSize::Nineteen);
+    assert_eq!(board.diamond(&Intersection::new(O, 13)), None); // no diamond by default
+    board.play(Move::MOVE(Intersection::new(O, 14), Color::Black));
+    assert_eq!(board.diamond(&Intersection::new(O, 13)), None); // diamond not yet constructed
+    board.play(Move::MOVE(Intersection::new(O, 12), Color::Black));
+    assert_eq!(board.diamond(&Intersection::new(O, 13)), None); // diamond still not yet constructed
+    board.play(Move::MOVE(Intersection::new(N, 13), Color::Black));
+    assert_eq!(board.diamond(&Intersection::new(O, 13)), None); // one more...
+    board.play(Move::MOVE(Intersection::new(P, 13), Color::Black));
+    assert_eq!(board.diamond(&Intersection::new(O, 13)), Some(Color::Black)); // diamond has been constructed on side
+}
+
+#[test]
+fn test_diamond_multiple_colors() {
+    use ColumnIdentifier::*;
+    let mut board = Board::new(BoardSize::Nineteen);
+    assert_eq!(board.diamond(&Intersection::new(O, 13)), None); // no diamond by default
+    board.play(Move::MOVE(Intersection::new(O, 14), Color::Black));
+    assert_eq!(board.diamond(&Intersection::new(O, 13)), None); // diamond not yet constructed
+    board.play(Move::MOVE(Intersection::new(O, 12), Color::Black));
+    assert_eq!(board.diamond(&Intersection::new(O, 13)), None); // diamond still not yet constructed
+    board.play(Move::MOVE(Intersection::new(N, 13), Color::Black));
+    assert_eq!(board.diamond(&Intersection::new(O, 13)), None); // one more...
+    board.play(Move::MOVE(Intersection::new(P, 13), Color::White)); // uh oh! opposite color present
+    assert_eq!(board.diamond(&Intersection::new(O, 13)), None); // there is no diamond of a singular color
+}
+
+#[test]
+fn test_play() {
+    use ColumnIdentifier::*;
+    let mut board = Board::new(BoardSize::Nineteen);
+    assert!(board.play(Move::PASS));
+    assert_eq!(board.move_number, 1);
+    assert!(board.play(Move::MOVE(Intersection::new(E, 4), Color::Black)));
+    assert_eq!(board.move_number, 2);
+    // detailed play move testing done in test_play_intersection() for convenience
+}
+
+#[test]
+fn test_play_intersection() {
+    use ColumnIdentifier::*;
+    let mut board = Board::new(BoardSize::Nine); // using BoardSize::Nine for out of bounds intersection tests
+    let board_copy = board.deepcopy();
+    assert!(!board.play(Move::MOVE(Intersection::new(E, 0), Color::Black)));
+    assert_eq!(board, board_copy); // board does not change on a failed play_intersection
+    assert!(!board.play(Move::MOVE(Intersection::new(A, 10), Color::Black))); // too high of row for current BoardSize
+    assert!(!board.play(Move::MOVE(Intersection::new(K, 1), Color::Black))); // too high of column for current BoardSize
+    assert!(
+        !board.play(Move::MOVE(Intersection::new(O, 10), Color::Black))
+    ); // both dimensions too high for current BoardSize
+
+    assert!(board.play(Move::MOVE(Intersection::new(E, 4), Color::Black))); // play regular move
+    assert_ne!(board, board_copy); // board does change after successful play_intersection
+    assert!(
+        !board.play(Move::MOVE(Intersection::new(E, 4), Color::Black))
+    ); // cannot play on occupied square with same color
+    assert!(
+        !board.play(Move::MOVE(Intersection::new(E, 4), Color::White))
+    ); // cannot play on occupied square with opposite color
+
+    // setup moves for Ko
+
+    assert!(board.play(Move::MOVE(Intersection::new(F, 3), Color::Black))); // can play multiple moves by same color with no issue
+    board.play(Move::MOVE(Intersection::new(G, 4), Color::Black));
+    board.play(Move::MOVE(Intersection::new(F, 5), Color::Black));
+    board.play(Move::MOVE(Intersection::new(E, 5), Color::White));
+    board.play(Move::MOVE(Intersection::new(F, 6), Color::White));
+    assert!(!board.play(Move::MOVE(Intersection::new(F, 4), Color::White))); // cannot play suicidal moves
+    board.play(Move::MOVE(Intersection::new(G, 5), Color::White));
+
+    assert!(board.play(Move::MOVE(Intersection::new(F, 4), Color::White))); // capture checks come before suicide
+    assert_eq!(board.white_captures, 1); // captures are correctly updated
+    assert!(!board.play(Move::MOVE(Intersection::new(F, 5), Color::Black))); // cannot play in Ko
+
+    board.play(Move::MOVE(Intersection::new(A, 1), Color::Black));
+    assert!(board.play(Move::MOVE(Intersection::new(F, 5), Color::Black))); // ko no longer exists after some other move
+
+    assert!(board.play(Move::PASS));
+    assert!(board.play(Move::MOVE(Intersection::new(F, 4), Color::White))); // nor after a pass
+}
+
+#[test]
+fn test_positional_superko() {
+    use ColumnIdentifier::*;
+    // plays out a double ko under the given KoRule, returning whether the final move, which
+    // recreates the starting position, is allowed
+    let double_ko_repeats = |ruleset: Ruleset| {
+        let mut board = Board::new(BoardSize::Nine);
+        board.ruleset = ruleset;
+        let setup = [
+            (B, 3, Color::White), (C, 2, Color::White), (C, 4, Color::White), (C, 3, Color::Black),
+            (D, 2, Color::Black), (D, 4, Color::Black), (E, 3, Color::Black),
+            (B, 7, Color::Black), (C, 6, Color::Black), (C, 8, Color::Black), (C, 7, Color::White),
+            (D, 6, Color::White), (D, 8, Color::White), (E, 7, Color::White),
+        ];
+        for (column, row, color) in setup {
+            assert!(board.play(Move::MOVE(Intersection::new(column, row), color)));
+        }
+
+        assert!(board.play(Move::MOVE(Intersection::new(D, 3), Color::White))); // takes the lower ko
+        assert!(board.play(Move::MOVE(Intersection::new(D, 7), Color::Black))); // takes the upper ko
+        assert!(board.play(Move::MOVE(Intersection::new(C, 3), Color::Black))); // retakes the lower ko
+        let before = board.deepcopy();
+        let repeated = board.play(Move::MOVE(Intersection::new(C, 7), Color::White)); // back to the start
+        if !repeated {
+            assert!(board == before); // a rejected move leaves the Board untouched
+        }
+        repeated
+    };
+
+    assert!(double_ko_repeats(Ruleset::Japanese));
+    assert!(!double_ko_repeats(Ruleset::Chinese));
+}
+
+#[test]
+fn test_rulesets() {
+    use ColumnIdentifier::*;
+    assert_eq!(Ruleset::from_name("Chinese"), Some(Ruleset::Chinese));
+    assert_eq!(Ruleset::from_name("korean"), Some(Ruleset::Japanese));
+    assert_eq!(Ruleset::from_name("new_zealand").map(|r| r.ko_rule()), Some(KoRule::PositionalSuperko));
+    assert_eq!(Ruleset::Japanese.scoring(), ScoringRules::Territory);
+    assert_eq!(Ruleset::from_name("go"), None);
+
+    // White fills the last liberty of its own two stones in the corner
+    let suicide = |ruleset: Ruleset| {
+        let mut board = Board::new(BoardSize::Nine);
+        board.ruleset = ruleset;
+        for (column, row, color) in [(A, 2, Color::White), (A, 3, Color::Black), (B, 2, Color::Black), (B, 1, Color::Black)] {
+            assert!(board.play(Move::MOVE(Intersection::new(column, row), color)));
+        }
+        let played = board.play(Move::MOVE(Intersection::new(A, 1), Color::White));
+        (played, board.black_captures)
+    };
+    assert_eq!(suicide(Ruleset::Chinese), (false, 0));
+    assert_eq!(suicide(Ruleset::TrompTaylor), (true, 2));
+}
+
+#[test]
+fn test_scoring() {
+    use ColumnIdentifier::*;
+    let mut b = Board::new(BoardSize::Nine);
+
+    b.play(Move::MOVE(Intersection::new(C, 7), Color::Black));
+    b.play(Move::MOVE(Intersection::new(G, 3), Color::White));
+    b.play(Move::MOVE(Intersection::new(D, 7), Color::Black));
+    b.play(Move::MOVE(Intersection::new(G, 2), Color::White));
+    b.play(Move::MOVE(Intersection::new(D, 8), Color::Black));
+    b.play(Move::MOVE(Intersection::new(G, 1), Color::White));
+    b.play(Move::MOVE(Intersection::new(D, 9), Color::Black));
+    b.play(Move::MOVE(Intersection::new(H, 3), Color::White));
+    b.play(Move::MOVE(Intersection::new(C, 6), Color::Black));
+    b.play(Move::MOVE(Intersection::new(J, 3), Color::White));
+    b.play(Move::MOVE(Intersection::new(B, 6), Color::Black));
+    b.play(Move::MOVE(Intersection::new(J, 4), Color::White));
+    b.play(Move::MOVE(Intersection::new(A, 6), Color::Black));
+    b.play(Move::MOVE(Intersection::new(F, 1), Color::White));
+
+    assert_eq!(b.estimate_score(), -2.5); // black = 15, white = 11, komi = 6.5
+}
+#[test]
+fn test_final_score_removes_dead_stones() {
+    use ColumnIdentifier::*;
+    // sets up a finished 9x9 game from its rows, top first, where each side's wall has two eyes
+    let setup = |rows: [&str; 9]| {
+        let mut b = Board::new(BoardSize::Nine);
+        for (row, line) in rows.iter().enumerate() {
+            for (column, point) in line.split(' ').enumerate() {
+                let intsc = Intersection::new(ColumnIdentifier::from_u16(column as u16).unwrap(), 9 - row as u16);
+                match point {
+                    "X" => assert!(b.place_stone(&intsc, Color::Black)),
+                    "O" => assert!(b.place_stone(&intsc, Color::White)),
+                    _ => {}
+                }
+            }
+        }
+        b
+    };
+
+    let b = setup([
+        "X X X X O O O O O",
+        ". X X X O O O O .",
+        "X X X X O O O O O",
+        ". X X X O O O O .",
+        ". O X X O O O O .", // dead stone in black's area
+        ". X X X O O O O .",
+        ". X X X O O O O .",
+        "X X X X O O O O O",
+        ". X X X O O O O .",
+    ]);
+
+    let dead_stones = b.dead_stones();
+    assert_eq!(dead_stones.len(), 1);
+    assert!(dead_stones.contains(&Intersection::new(B, 5)));
+
+    // black = 29 stones + 7 area, white = 39 stones + 6 area, komi = 6.5
+    assert_eq!(b.final_score(ScoringRules::Area), -15.5);
+    // black = 7 territory + 1 prisoner, white = 6 territory, komi = 6.5
+    assert_eq!(b.final_score(ScoringRules::Territory), -4.5);
+    assert_eq!(result_string(b.final_score(ScoringRules::Area)), "W+15.5");
+
+    let b = setup([
+        "X X X X O O O O O",
+        ". X X X O O O O .",
+        "X X X X O O O O O",
+        ". X X X O O O O .",
+        ". X X X O O O X X", // dead group in white's area
+        ". X X X O O O O .",
+        ". X X X O O O O .",
+        "X X X X O O O O O",
+        ". X X X O O O O .",
+    ]);
+
+    // the dead group splits white's area, which Tromp-Taylor counting then gives to no one
+    // black = 32 stones + 6 area, white = 38 stones + 2 area, komi = 6.5
+    assert_eq!(b.estimate_score(), -8.5);
+
+    let dead_stones = b.dead_stones();
+    assert_eq!(dead_stones.len(), 2);
+    assert!(dead_stones.contains(&Intersection::new(H, 5)));
+    assert!(dead_stones.contains(&Intersection::new(J, 5)));
+    assert_eq!(b.final_score(ScoringRules::Area), -15.5);
+}
+
+#[test]
+fn test_scoring_method_from_string() {
+    assert_eq!(Ruleset::from_name("chinese").map(|ruleset| ruleset.scoring()), Some(ScoringRules::Area));
+    assert_eq!(Ruleset::from_name("Japanese").map(|ruleset| ruleset.scoring()), Some(ScoringRules::Territory));
+    assert_eq!(Ruleset::from_name("some rules").map(|ruleset| ruleset.scoring()), None);
+}
+
+/*****************************************************\
+|****************         SGF         ****************|
+\*****************************************************/
+
+#[test]
+fn test_sgf_coordinates() {
+    use ColumnIdentifier::*;
+    let intsc = Intersection::new(D, 4);
+    assert_eq!(to_sgf_coordinate(&intsc, &BoardSize::Nineteen), "dp");
+    assert_eq!(to_sgf_coordinate(&intsc, &BoardSize::Nine), "df"); // rows are counted from the top
+
+    assert_eq!(
+        from_sgf_coordinate("dp", &BoardSize::Nineteen, Color::Black),
+        Ok(Move::MOVE(intsc, Color::Black))
+    );
+    assert_eq!(from_sgf_coordinate("", &BoardSize::Nine, Color::White), Ok(Move::PASS));
+    assert_eq!(from_sgf_coordinate("tt", &BoardSize::Nineteen, Color::White), Ok(Move::PASS));
+    assert!(from_sgf_coordinate("jj", &BoardSize::Nine, Color::Black).is_err()); // out of bounds
+}
+
+#[test]
+fn test_sgf_parse_main_line() {
+    use ColumnIdentifier::*;
+    let record = SgfRecord::parse(
+        "(;GM[1]SZ[9]KM[7]PB[Black \\] player];B[ee]C[first move];W[cc](;B[gg];W[])(;B[dd]))",
+    )
+    .unwrap();
+
+    assert_eq!(record.size, BoardSize::Nine);
+    assert_eq!(record.komi, 7.0);
+    assert_eq!(record.black_player, Some("Black ] player".to_string())); // escapes are resolved
+    assert_eq!(record.nodes.len(), 4); // only the first variation is followed
+    assert_eq!(record.nodes[0].mov, Move::MOVE(Intersection::new(E, 5), Color::Black));
+    assert_eq!(record.nodes[0].comment, Some("first move".to_string()));
+    assert_eq!(record.nodes[2].mov, Move::MOVE(Intersection::new(G, 3), Color::Black));
+    assert_eq!(record.nodes[3].mov, Move::PASS);
+    assert_eq!(record.nodes[3].color, Color::White);
+
+    assert!(SgfRecord::parse("(;SZ[9];B[ee]").is_err()); // unclosed game tree
+    assert!(SgfRecord::parse(";SZ[9])").is_err()); // no game tree
+}
+
+#[test]
+fn test_sgf_round_trip() {
+    use ColumnIdentifier::*;
+    let mut record = SgfRecord::new(BoardSize::Thirteen, 6.5);
+    record.result = Some("W+R".to_string());
+    record.nodes.push(SgfNode::new(Color::Black, Move::MOVE(Intersection::new(K, 10), Color::Black)));
+    record.nodes.push(SgfNode::new(Color::White, Move::PASS));
+    record.nodes[1].comment = Some("a comment with ] inside".to_string());
+
+    let parsed = SgfRecord::parse(&record.to_string()).unwrap();
+    assert_eq!(parsed.size, record.size);
+    assert_eq!(parsed.komi, record.komi);
+    assert_eq!(parsed.result, record.result);
+    assert_eq!(parsed.nodes.len(), 2);
+    assert_eq!(parsed.nodes[0].mov, record.nodes[0].mov);
+    assert_eq!(parsed.nodes[1].color, Color::White);
+    assert_eq!(parsed.nodes[1].comment, record.nodes[1].comment);
+}
+
+#[test]
+fn test_sgf_setup_stones() {
+    use ColumnIdentifier::*;
+    let mut record = SgfRecord::new(BoardSize::Nine, 0.0);
+    record.setup_black = vec![Intersection::new(A, 9), Intersection::new(B, 8)];
+    record.setup_white = vec![Intersection::new(J, 1)];
+    record.to_play = Some(Color::White);
+    record.comment = Some("White to play".to_string());
+
+    let sgf = record.to_string();
+    assert!(sgf.contains("AB[aa][bb]AW[ii]PL[W]C[White to play]"));
+}
+
+/*****************************************************\
+|****************       PUZZLES       ****************|
+\*****************************************************/
+
+#[test]
+fn test_large_captures() {
+    use ColumnIdentifier::*;
+    let mut b: Board = Board::new(BoardSize::Nine);
+    for row in 1..=3 {
+        b.play(Move::MOVE(Intersection::new(A, row), Color::White));
+        b.play(Move::MOVE(Intersection::new(B, row), Color::Black));
+    }
+
+    let key_move = Move::MOVE(Intersection::new(A, 4), Color::Black);
+    assert_eq!(large_captures(&b, Color::Black, 3), vec![key_move]);
+    assert!(large_captures(&b, Color::Black, 4).is_empty());
+    assert!(large_captures(&b, Color::White, 1).is_empty());
+}
+
+/*****************************************************\
+|****************       HANDLE        ****************|
+\*****************************************************/
+
+#[test]
+fn test_engine_handle() {
+    let vertex = |vertex: &str| vertex.parse::<Position>().unwrap();
+    let mut engine = Engine::new(BoardSize::Nine, 6.5);
+    engine.play(Color::Black, vertex("E5")).unwrap();
+    engine.pass(Color::White);
+    assert_eq!(engine.to_play(), Color::Black);
+    assert_eq!(engine.stones(Color::Black), vec![vertex("E5")]);
+    assert_eq!(engine.moves(), vec![(Color::Black, Some(vertex("E5"))), (Color::White, None)]);
+    assert!(engine.play(Color::Black, vertex("E5")).is_err());
+    assert!(engine.play(Color::Black, vertex("T19")).is_err());
+
+    let reloaded = Engine::from_sgf(&engine.to_sgf()).unwrap();
+    assert_eq!(reloaded.to_sgf(), engine.to_sgf());
+    assert_eq!(reloaded.result(ScoringRules::Area), "B+74.5");
+
+    // candidates are searched for the color to play, most visited first, without playing
+    engine.play(Color::Black, vertex("C3")).unwrap();
+    let candidates = engine.candidates(100);
+    assert!(candidates.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+    let e5 = Move::MOVE(vertex("E5").into(), Color::White);
+    assert!(candidates.iter().all(|(mov, _, winrate, _)| *mov != e5 && (0.0..=1.0).contains(winrate)));
+    assert_eq!(engine.to_play(), Color::White);
+
+    // each principal variation starts with its candidate, and follows the most visited replies
+    let mut engine = Engine::new(BoardSize::Nine, 6.5);
+    for (color, pos) in [(Color::Black, "E5"), (Color::White, "C3"), (Color::Black, "G7")] {
+        engine.play(color, vertex(pos)).unwrap();
+    }
+    let candidates = engine.candidates(100);
+    assert!(!candidates.is_empty());
+    assert!(candidates.iter().all(|(mov, _, _, pv)| pv[0] == *mov && pv.len() <= 10));
+    assert!(candidates.iter().any(|(_, _, _, pv)| pv.len() > 1));
+}
+
+#[test]
+fn test_engine_handle_stepped_genmove() {
+    use std::time::Duration;
+    let mut engine = Engine::new(BoardSize::Nine, 6.5);
+    engine.set_playouts(25);
+    engine.play(Color::Black, "E5".parse().unwrap()).unwrap();
+
+    // steps never run past the engine's playouts
+    let mut search = engine.start_genmove(Color::White);
+    assert!(!search.step(10));
+    assert_eq!(search.playouts(), 10);
+    assert!(!search.step(10));
+    assert!(search.step(10));
+    assert_eq!(search.playouts(), 25);
+    let best_move = search.best_move();
+    assert_eq!(engine.finish_genmove(search).unwrap(), best_move);
+    assert_eq!(engine.to_play(), Color::Black);
+
+    // a search is not played once the game has moved on
+    let search = engine.start_genmove(Color::Black);
+    engine.pass(Color::Black);
+    assert!(engine.finish_genmove(search).is_err());
+
+    // a time limit replaces the playouts, and a search always runs at least one
+    engine.set_time_limit(Some(Duration::from_millis(20)));
+    let mut search = engine.start_genmove(Color::White);
+    while !search.step(10) {}
+    assert!(search.playouts() > 0);
+    engine.set_time_limit(Some(Duration::ZERO));
+    let mut search = engine.start_genmove(Color::White);
+    assert!(search.step(10));
+    assert_eq!(search.playouts(), 1);
+    assert_ne!(engine.finish_genmove(search).unwrap(), Move::RESIGN);
+}
+
+/*****************************************************\
+|****************     COLLECTIONS     ****************|
+\*****************************************************/
+
+#[test]
+fn test_canonical_hash_symmetry() {
+    // a 3-4 point approach in the bottom left, and the same shape mirrored into the top right
+    let corner: Stones = vec![(2, 3, Color::Black), (4, 2, Color::White)];
+    let mirrored: Stones = vec![(16, 15, Color::Black), (14, 16, Color::White)];
+    let swapped: Stones = vec![(2, 3, Color::White), (4, 2, Color::Black)];
+    assert_eq!(canonical_hash(&corner, 19), canonical_hash(&mirrored, 19));
+    assert_ne!(canonical_hash(&corner, 19), canonical_hash(&swapped, 19));
+    assert_ne!(canonical_hash(&corner, 19), canonical_hash(&corner, 13));
+}
+
+#[test]
+fn test_matches_pattern() {
+    let pattern: Stones = vec![(2, 3, Color::Black), (4, 2, Color::White)];
+    let rotated: Stones = vec![(3, 16, Color::Black), (2, 14, Color::White), (15, 15, Color::Black)];
+    assert!(matches_pattern(&pattern, &rotated, 19));
+
+    // a stone next to the pattern changes its local shape
+    let crowded: Stones = vec![(2, 3, Color::Black), (4, 2, Color::White), (3, 3, Color::White)];
+    assert!(!matches_pattern(&pattern, &crowded, 19));
+    assert!(!matches_pattern(&vec![], &rotated, 19));
+}
+
+/*****************************************************\
+|****************    KGS EXTENSIONS   ****************|
+\*****************************************************/
+
+#[cfg(feature = "image")]
+#[test]
+fn test_board_png() {
+    use ColumnIdentifier::*;
+    let mut board = Board::new(BoardSize::Nine);
+    board.play(Move::MOVE(Intersection::new(C, 3), Color::Black));
+    board.play(Move::MOVE(Intersection::new(G, 7), Color::White));
+    let png = board_png(&board, Some(Intersection::new(G, 7))).unwrap();
+
+    // a 9x9 grid, 32 pixels apart inside a 32 pixel margin
+    let image = tiny_skia::Pixmap::decode_png(&png).unwrap();
+    assert_eq!((image.width(), image.height()), (320, 320));
+    let rgb = |x: u32, y: u32| {
+        let pixel = image.pixel(x, y).unwrap();
+        (pixel.red(), pixel.green(), pixel.blue())
+    };
+
+    // C3 and G7 are drawn in their colors, where G7 has a black ring as the last move
+    assert_eq!(rgb(0, 0), (0xdc, 0xb3, 0x5c));
+    assert_eq!(rgb(96 + 5, 224 + 5), (0x00, 0x00, 0x00));
+    assert_eq!(rgb(224 + 12, 96), (0xff, 0xff, 0xff));
+    assert_eq!(rgb(224 + 7, 96), (0x00, 0x00, 0x00));
+    assert!(PngSettings::from_args(&["game.sgf".to_string(), "--move".to_string()]).is_err());
+}
+
+#[test]
+fn test_search_progress() {
+    use std::time::Duration;
+    let mut board = Board::new(BoardSize::Nine);
+    board.play(Move::MOVE(Intersection::new(ColumnIdentifier::C, 3), Color::Black));
+    board.play(Move::MOVE(Intersection::new(ColumnIdentifier::G, 7), Color::White));
+
+    // without an interval between them, every iteration is reported
+    let mut reports = vec![];
+    let mut report = |progress: SearchProgress| reports.push(progress);
+    let params = SearchParams { iterations: 20, ..SearchParams::default() };
+    let progress = ProgressReport { interval: Duration::ZERO, report: &mut report };
+    let mov = generate_move_reporting(&board, Color::Black, &params, None, None, Some(progress));
+
+    assert_eq!(reports.len(), 20);
+    assert!(reports.iter().enumerate().all(|(index, progress)| progress.iterations == index as u32 + 1));
+    let last = reports.last().unwrap();
+    assert_eq!(last.best_move, mov);
+    assert_eq!(last.pv.first(), Some(&mov));
+    assert!(last.visits > 0 && (0.0..=1.0).contains(&last.winrate));
+}
+
+#[test]
+fn test_search_tree() {
+    let mut board = Board::new(BoardSize::Nine);
+    board.play(Move::MOVE(Intersection::new(ColumnIdentifier::C, 3), Color::Black));
+    board.play(Move::MOVE(Intersection::new(ColumnIdentifier::G, 7), Color::White));
+    let params = SearchParams { iterations: 60, ..SearchParams::default() };
+
+    // the moves below the root are most visited first, each once, and no deeper than asked for
+    let nodes = search_tree(&board, Color::Black, &params, 2);
+    assert!(!nodes.is_empty());
+    assert!(nodes.windows(2).all(|pair| pair[0].visits >= pair[1].visits));
+    assert!(nodes.iter().map(|node| node.visits as u32).sum::<u32>() <= 60);
+    for (index, node) in nodes.iter().enumerate() {
+        assert!(nodes[..index].iter().all(|earlier| earlier.mov != node.mov));
+        assert!((0.0..=1.0).contains(&node.winrate));
+        assert!(node.children.iter().all(|child| child.children.is_empty()));
+    }
+    assert!(nodes.iter().any(|node| !node.children.is_empty()));
+    assert!(search_tree(&board, Color::Black, &params, 0).is_empty());
+}
+
+#[test]
+fn test_review_game() {
+    let mut record = SgfRecord::parse("(;GM[1]FF[4]SZ[9]KM[6.5];B[ee];W[cc];B[gg])").unwrap();
+    let reviews = review_game(&mut record, 10).unwrap();
+
+    // every move is reviewed in order and annotated in the record
+    assert_eq!(reviews.iter().map(|review| review.move_number).collect::<Vec<_>>(), [1, 2, 3]);
+    assert_eq!(reviews[1].color, Color::White);
+    assert_eq!(reviews[2].played, Move::MOVE(Intersection::new(ColumnIdentifier::G, 3), Color::Black));
+    assert!(record.nodes.iter().all(|node| node.comment.as_deref().is_some_and(|c| c.starts_with("Black winrate"))));
+
+    // the alternatives are the engine's preferred moves, the first of which it suggests
+    for review in &reviews {
+        assert!(review.alternatives.len() <= 3);
+        if let Some(first) = review.alternatives.first() {
+            assert_eq!(first.best_move, review.suggested);
+        }
+    }
+}
+
+/*****************************************************\
+|****************      PATTERNS       ****************|
+\*****************************************************/
+
+#[test]
+fn test_pattern_code_symmetry() {
+    use ColumnIdentifier::*;
+    // a hane at the bottom left, and the same shape at the top right with colors as seen by White
+    let mut board = Board::new(BoardSize::Nine);
+    board.play(Move::MOVE(Intersection::new(C, 3), Color::Black));
+    board.play(Move::MOVE(Intersection::new(D, 2), Color::White));
+    board.play(Move::MOVE(Intersection::new(G, 7), Color::White));
+    board.play(Move::MOVE(Intersection::new(F, 8), Color::Black));
+    board.play(Move::MOVE(Intersection::new(C, 4), Color::Black));
+    board.play(Move::MOVE(Intersection::new(G, 6), Color::White));
+
+    let bottom_left = pattern_code(&board, &Intersection::new(D, 3), Color::Black);
+    let top_right = pattern_code(&board, &Intersection::new(F, 7), Color::White);
+    assert!(bottom_left.is_some());
+    assert_eq!(bottom_left, top_right);
+    assert_ne!(bottom_left, pattern_code(&board, &Intersection::new(D, 3), Color::White));
+    assert_ne!(bottom_left, pattern_code(&board, &Intersection::new(A, 1), Color::Black));
+}
+
+#[test]
+fn test_pattern_table_round_trip() {
+    let record = SgfRecord::parse("(;GM[1]SZ[9]KM[7.5];B[ee];W[gc];B[ce];W[dg])").unwrap();
+    let mut table = PatternTable::default();
+    table.add_game(&record).unwrap();
+    assert!(table.len() > 0);
+
+    let bytes = table.to_bytes();
+    assert_eq!(PatternTable::from_bytes(&bytes), Ok(table));
+    assert!(PatternTable::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(PatternTable::from_bytes(b"nope").is_err());
+}
+
+/*****************************************************\
+|****************      BITBOARDS      ****************|
+\*****************************************************/
+
+#[test]
+fn test_bitboard_groups() {
+    use ColumnIdentifier::*;
+    let size = BoardSize::Nineteen;
+    let index = |column, row| Intersection::new(column, row).to_position_index(&size).unwrap() as usize;
+    let mut bits = bitboard::BitBoard::new(&size);
+
+    // a black group on the right edge, which must not wrap around to the left edge of the next row
+    for (column, row) in [(S, 10), (T, 10), (T, 11)] {
+        bits.set(index(column, row), State::Occupied(Color::Black));
+    }
+    bits.set(index(A, 11), State::Occupied(Color::Black));
+    bits.set(index(S, 11), State::Occupied(Color::White));
+
+    let group = bits.group(index(T, 10), Color::Black);
+    assert_eq!(group.len(), 3);
+    assert!(!group.contains(index(A, 11)));
+    assert_eq!(
+        bits.liberties(&group).indices().collect::<Vec<usize>>(),
+        {
+            let mut expected = vec![index(S, 9), index(T, 9), index(R, 10), index(T, 12)];
+            expected.sort();
+            expected
+        }
+    );
+    assert!(bits.group(index(S, 11), Color::Black).is_empty());
+
+    bits.set(index(T, 11), State::Empty);
+    assert_eq!(bits.group(index(T, 10), Color::Black).len(), 2);
+}
+
+/*****************************************************\
+|****************      RENDERING      ****************|
+\*****************************************************/
+
+#[test]
+fn test_board_display() {
+    use ColumnIdentifier::*;
+    let mut board = Board::new(BoardSize::Nine);
+    board.play(Move::MOVE(Intersection::new(C, 3), Color::Black));
+    board.play(Move::MOVE(Intersection::new(D, 4), Color::White));
+    let expected = [
+        "",
+        " 9 . . . . . . . . . ",
+        " 8 . . . . . . . . . ",
+        " 7 . . . . . . . . . ",
+        " 6 . . . . . . . . . ",
+        " 5 . . . . . . . . . ",
+        " 4 . . . O . . . . . ",
+        " 3 . . X . . . . . . ",
+        " 2 . . . . . . . . . ",
+        " 1 . . . . . . . . . ",
+        "   A B C D E F G H J",
+        "Komi:     6.5",
+        "Ko:       None",
+        "Captures: [B: 0, W: 0]",
+        "",
+    ]
+    .join("\n");
+    assert_eq!(board.to_string(), expected);
+}
+
+#[test]
+fn test_bitset_operations() {
+    use bitboard::BitSet;
+    // alternating and shifted patterns across every word, checked against plain integer operations
+    let (mut left, mut right) = (BitSet::default(), BitSet::default());
+    for index in (0..441).filter(|index| index % 3 == 0) {
+        left.insert(index);
+    }
+    for index in (0..441).filter(|index| index % 5 == 0) {
+        right.insert(index);
+    }
+
+    let expected = |operation: fn(bool, bool) -> bool| -> Vec<usize> {
+        (0..441).filter(|index| operation(index % 3 == 0, index % 5 == 0)).collect()
+    };
+    assert_eq!(left.and(&right).indices().collect::<Vec<usize>>(), expected(|l, r| l && r));
+    assert_eq!(left.or(&right).indices().collect::<Vec<usize>>(), expected(|l, r| l || r));
+    assert_eq!(left.and_not(&right).indices().collect::<Vec<usize>>(), expected(|l, r| l && !r));
+}
+
+/*****************************************************\
+|****************     BENCHMARKING    ****************|
+\*****************************************************/
+
+#[test]
+fn test_seeded_playouts_repeat() {
+    let board = Board::new(BoardSize::Nine);
+    seed_rng(BENCH_SEED);
+    let first = playout(&board, Color::Black);
+    seed_rng(BENCH_SEED);
+    assert_eq!(playout(&board, Color::Black), first);
+
+    let names: Vec<String> = bench_workloads().iter().map(|workload| workload.name()).collect();
+    assert!(names.contains(&"play/undo (midgame 19x19)".to_string()));
+    assert!(names.contains(&"genmove (100 iterations)".to_string()));
+}
+
+#[test]
+fn test_cell_round_trip() {
+    let states = [
+        State::Empty,
+        State::Occupied(Color::Black),
+        State::Occupied(Color::White),
+        State::Offboard,
+    ];
+    for state in states {
+        assert_eq!(Cell::from_state(state).state(), state);
+    }
+    assert_eq!(std::mem::size_of::<Cell>(), 1);
+}
+
+/*****************************************************\
+|****************   BATCH EVALUATION  ****************|
+\*****************************************************/
+
+#[test]
+fn test_batch_scoring_and_ownership() {
+    use ColumnIdentifier::*;
+    let mut board = Board::new(BoardSize::Nine);
+    board.play(Move::MOVE(Intersection::new(C, 3), Color::Black));
+    board.play(Move::MOVE(Intersection::new(G, 7), Color::White));
+
+    // parallel scores come back in the order of the positions
+    let boards = vec![board.deepcopy(), Board::new(BoardSize::Nine)];
+    let expected: Vec<f64> = boards.iter().map(|b| b.final_score(ScoringRules::Area)).collect();
+    assert_eq!(score_positions(&boards, ScoringRules::Area), expected);
+
+    let ownership = ownership_positions(&[(board, Color::Black)], 8).pop().unwrap();
+    for column in 0..9 {
+        for row in 1..=9 {
+            let value = ownership.at(&Intersection::new(ColumnIdentifier::from_u16(column).unwrap(), row));
+            assert!(value.is_some_and(|value| (-1.0..=1.0).contains(&value)));
+        }
+    }
+
+    let mut engine = Engine::new(BoardSize::Nine, 7.5);
+    engine.play(Color::Black, "E5".parse().unwrap()).unwrap();
+    let rows = engine.ownership(4);
+    assert_eq!(rows.len(), 9);
+    assert!(rows.iter().all(|row| row.len() == 9));
+}
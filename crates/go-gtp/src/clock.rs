@@ -0,0 +1,310 @@
+use super::*;
+use std::time::{Duration, Instant};
+/******************************************************\
+|****************      CONSTANTS       ****************|
+\******************************************************/
+
+// A move in main time is given at least this share of the time left, however many moves the
+// board still has room for
+const MIN_MOVES_LEFT: u32 = 20;
+
+// Time kept back from every move for the round trip to the controller
+const SAFETY_MARGIN: Duration = Duration::from_millis(250);
+
+// Share of its time a move in overtime searches for, so that a slow move never uses up a period
+const OVERTIME_SHARE: f64 = 0.8;
+
+/****************************************************\
+|****************    GLOBAL TYPES    ****************|
+\****************************************************/
+
+// The time limits of a game: main time, followed by any overtime
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct TimeSettings {
+    main_time: Duration,
+    overtime: Overtime,
+}
+
+// The time a player has after main time runs out
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub(crate) enum Overtime {
+    NONE,                                      // absolute time, lost when main time runs out
+    CANADIAN { time: Duration, stones: u32 },  // periods of time to play stones moves in
+    JAPANESE { time: Duration, periods: u32 }, // a period for each move, used up by a move exceeding it
+}
+
+// The time limits of a game and the time each Color has left, which turns the time left into
+// a search budget for every move
+pub struct Clock {
+    // None when the game has no time limits
+    settings: Option<TimeSettings>,
+    black: TimeLeft,
+    white: TimeLeft,
+}
+
+// The time spent searching for moves since the engine started, on the wall clock and by the CPU
+// over every search thread
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub(crate) struct SearchTime {
+    pub(crate) wall: Duration,
+    pub(crate) cpu: Duration,
+}
+
+/*****************************************************\
+|****************    PRIVATE TYPES    ****************|
+\*****************************************************/
+
+// The time a Color has left, in main time while stones is 0, else in overtime, where stones
+// counts the moves left to play in the current Canadian period or the Japanese periods left
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct TimeLeft {
+    time: Duration,
+    stones: u32,
+}
+
+/*****************************************************\
+|****************        SETUP        ****************|
+\*****************************************************/
+
+impl TimeSettings {
+    // Creates TimeSettings from the arguments of time_settings: the seconds of main time and of
+    // each Canadian overtime period, and the number of moves of each period
+    // Returns None if the game has no time limits, which GTP gives as overtime without stones
+    pub(crate) fn new(main_time: u64, byo_yomi_time: u64, byo_yomi_stones: u32) -> Option<TimeSettings> {
+        if byo_yomi_time > 0 && byo_yomi_stones == 0 {
+            return None;
+        }
+
+        Some(TimeSettings {
+            main_time: Duration::from_secs(main_time),
+            overtime: match byo_yomi_stones {
+                0 => Overtime::NONE,
+                stones => Overtime::CANADIAN { time: Duration::from_secs(byo_yomi_time), stones },
+            },
+        })
+    }
+
+    // Creates TimeSettings from the arguments of kgs-time_settings: "none", "absolute" and the
+    // main time, "byoyomi" and the main time, period time, and number of periods, or "canadian"
+    // and the main time, period time, and moves per period, all times in seconds
+    // Returns Ok(None) if the game has no time limits, or an error describing invalid arguments
+    pub(crate) fn from_kgs(args: &[&str]) -> Result<Option<TimeSettings>, String> {
+        let numbers: Vec<u64> = match args[1..].iter().map(|arg| arg.parse::<u64>()).collect() {
+            Ok(numbers) => numbers,
+            Err(_) => return Err(format!("Non-numeric time given to kgs-time_settings: {}", args[1..].join(" "))),
+        };
+
+        let overtime = match (args[0], numbers.as_slice()) {
+            ("none", []) => return Ok(None),
+            ("absolute", [_]) => Overtime::NONE,
+            ("byoyomi", [_, time, periods]) if *periods > 0 => {
+                Overtime::JAPANESE { time: Duration::from_secs(*time), periods: *periods as u32 }
+            }
+            ("canadian", [_, time, stones]) if *stones > 0 => {
+                Overtime::CANADIAN { time: Duration::from_secs(*time), stones: *stones as u32 }
+            }
+            _ => return Err(format!("Unsupported time system given to kgs-time_settings: {}", args.join(" "))),
+        };
+        Ok(Some(TimeSettings { main_time: Duration::from_secs(numbers[0]), overtime }))
+    }
+
+    // The arguments of kgs-time_settings giving these TimeSettings, the inverse of from_kgs
+    pub fn to_kgs(&self) -> String {
+        let main_time = self.main_time.as_secs();
+        match self.overtime {
+            Overtime::NONE => format!("absolute {main_time}"),
+            Overtime::JAPANESE { time, periods } => format!("byoyomi {main_time} {} {periods}", time.as_secs()),
+            Overtime::CANADIAN { time, stones } => format!("canadian {main_time} {} {stones}", time.as_secs()),
+        }
+    }
+
+    // The time a Color has left at the start of a game under these TimeSettings
+    fn start(&self) -> TimeLeft {
+        if self.main_time.is_zero() {
+            self.overtime_period()
+        } else {
+            TimeLeft { time: self.main_time, stones: 0 }
+        }
+    }
+
+    // The time a Color has left at the start of overtime, or of the next Canadian period
+    fn overtime_period(&self) -> TimeLeft {
+        match self.overtime {
+            Overtime::NONE => TimeLeft { time: Duration::ZERO, stones: 0 },
+            Overtime::CANADIAN { time, stones } => TimeLeft { time, stones },
+            Overtime::JAPANESE { time, periods } => TimeLeft { time, stones: periods },
+        }
+    }
+}
+
+impl Clock {
+    // Creates a new Clock for a game without time limits
+    pub(crate) fn new() -> Clock {
+        let unlimited = TimeLeft { time: Duration::MAX, stones: 0 };
+        Clock { settings: None, black: unlimited, white: unlimited }
+    }
+
+    // Sets the time limits of the game, giving both Colors the whole of them
+    pub(crate) fn set_settings(&mut self, settings: Option<TimeSettings>) {
+        *self = Clock::new();
+        self.settings = settings;
+        self.restart();
+    }
+
+    // The time limits of the game, or None if it has none
+    pub fn settings(&self) -> Option<TimeSettings> {
+        self.settings
+    }
+
+    // Gives both Colors the whole of the time limits again, for a new game
+    pub(crate) fn restart(&mut self) {
+        if let Some(settings) = self.settings {
+            self.black = settings.start();
+            self.white = settings.start();
+        }
+    }
+}
+
+/*****************************************************\
+|****************        TIME         ****************|
+\*****************************************************/
+
+impl Clock {
+    // Sets the time the given Color has left, as reported by the controller: the seconds left of
+    // main time when stones is 0, else of the current overtime period, with stones moves left to
+    // play in it under Canadian overtime or stones periods left under Japanese byo-yomi
+    pub(crate) fn set_time_left(&mut self, color: Color, seconds: u64, stones: u32) {
+        *self.time_left_mut(color) = TimeLeft { time: Duration::from_secs(seconds), stones };
+    }
+
+    // Takes the given time spent on a move by the given Color off its time left, moving on to
+    // overtime when main time runs out, and through its periods
+    pub(crate) fn spend(&mut self, color: Color, elapsed: Duration) {
+        let settings = match self.settings {
+            Some(settings) => settings,
+            None => return,
+        };
+
+        let left = self.time_left_mut(color);
+        let mut elapsed = elapsed;
+        if left.stones == 0 {
+            if elapsed < left.time {
+                left.time -= elapsed;
+                return;
+            }
+            elapsed -= left.time;
+            *left = settings.overtime_period();
+        }
+
+        match settings.overtime {
+            Overtime::NONE => {}
+            Overtime::CANADIAN { .. } => {
+                left.time = left.time.saturating_sub(elapsed);
+                left.stones -= 1;
+                if left.stones == 0 {
+                    *left = settings.overtime_period();
+                }
+            }
+            Overtime::JAPANESE { time, .. } => {
+                // every whole period the move took is used up, and the next starts afresh, where
+                // using up the last period loses the game, so it is never left behind
+                let used = (elapsed.as_nanos() / time.as_nanos().max(1)) as u32;
+                left.stones = left.stones.saturating_sub(used).max(1);
+                left.time = time;
+            }
+        }
+    }
+
+    // The time the given Color may search its next move on the given Board for, or None if the
+    // game has no time limits
+    // Main time is shared out over the moves the board still has room for, but never below what
+    // a move in overtime gets, and overtime moves keep back a share of their time
+    pub(crate) fn move_budget(&self, color: Color, board: &Board) -> Option<Duration> {
+        let settings = self.settings?;
+        let left = self.time_left(color);
+        let overtime = match (settings.overtime, left.stones) {
+            (Overtime::NONE, _) => Duration::ZERO,
+            (_, 0) => self.overtime_budget(settings, settings.overtime_period()),
+            _ => self.overtime_budget(settings, left),
+        };
+        if left.stones > 0 {
+            return Some(overtime);
+        }
+
+        let moves = (board.empty_intersections().len() as u32 / 2).max(MIN_MOVES_LEFT);
+        Some((left.time / moves).saturating_sub(SAFETY_MARGIN).max(overtime))
+    }
+
+    // The time the given Color has left, of main time while its stones are 0, else of its current
+    // overtime period, with the moves left to play in it or the periods left, or None if the
+    // game has no time limits
+    pub fn remaining(&self, color: Color) -> Option<(Duration, u32)> {
+        self.settings?;
+        let left = self.time_left(color);
+        Some((left.time, left.stones))
+    }
+
+    // The time a move may take from the given time left in overtime under the given settings
+    fn overtime_budget(&self, settings: TimeSettings, left: TimeLeft) -> Duration {
+        let time = match settings.overtime {
+            Overtime::CANADIAN { .. } => left.time / left.stones.max(1),
+            _ => left.time,
+        };
+        time.mul_f64(OVERTIME_SHARE).saturating_sub(SAFETY_MARGIN)
+    }
+
+    // The time the given Color has left
+    fn time_left(&self, color: Color) -> TimeLeft {
+        match color {
+            Color::Black => self.black,
+            Color::White => self.white,
+        }
+    }
+
+    // The time the given Color has left, to update
+    fn time_left_mut(&mut self, color: Color) -> &mut TimeLeft {
+        match color {
+            Color::Black => &mut self.black,
+            Color::White => &mut self.white,
+        }
+    }
+}
+
+impl SearchTime {
+    // Adds the given time spent searching elsewhere, such as on another thread
+    pub(crate) fn add(&mut self, other: SearchTime) {
+        self.wall += other.wall;
+        self.cpu += other.cpu;
+    }
+
+    // Runs the given search, adding the time it takes to the time spent searching
+    pub(crate) fn measure<T>(&mut self, search: impl FnOnce() -> T) -> T {
+        let (wall, cpu) = (Instant::now(), process_cpu_time());
+        let result = search();
+        self.wall += wall.elapsed();
+        self.cpu += match (cpu, process_cpu_time()) {
+            (Some(start), Some(end)) => end.saturating_sub(start),
+            _ => wall.elapsed(), // the CPU time is unknown, so the search is taken to use one core
+        };
+        result
+    }
+}
+
+// The CPU time this process has used so far, over all of its threads, or None if it is unknown
+#[cfg(unix)]
+fn process_cpu_time() -> Option<Duration> {
+    let mut usage = std::mem::MaybeUninit::<libc::rusage>::uninit();
+    // Safety: getrusage only writes the usage it is given, which is read once it succeeds
+    if unsafe { libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) } != 0 {
+        return None;
+    }
+    let usage = unsafe { usage.assume_init() };
+    let seconds = |time: libc::timeval| Duration::new(time.tv_sec as u64, time.tv_usec as u32 * 1000);
+    Some(seconds(usage.ru_utime) + seconds(usage.ru_stime))
+}
+
+// The CPU time this process has used so far, which is unknown on this platform
+#[cfg(not(unix))]
+fn process_cpu_time() -> Option<Duration> {
+    None
+}
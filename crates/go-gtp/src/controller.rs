@@ -0,0 +1,150 @@
+use super::*;
+use std::fs::File;
+use std::io;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+/****************************************************\
+|****************    GLOBAL TYPES    ****************|
+\****************************************************/
+
+// A controller for an external engine speaking the Go Text Protocol in a child process
+pub(crate) struct GtpController {
+    command: Vec<String>,
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    log: Option<File>,
+}
+
+// Ways a command sent to an external engine can fail
+#[derive(Debug)]
+pub(crate) enum ControllerError {
+    // The engine answered the command with a GTP failure response, ex. "? illegal move"
+    REJECTED(String),
+    // The engine could not be reached, usually because it crashed or exited
+    IO(io::Error),
+}
+
+/*****************************************************\
+|****************        SETUP        ****************|
+\*****************************************************/
+
+impl GtpController {
+    // Starts the external engine with the given command line, ex. ["gnugo", "--mode", "gtp"]
+    pub(crate) fn start(command: &[String]) -> io::Result<GtpController> {
+        let (program, args) = command
+            .split_first()
+            .ok_or(io::Error::new(io::ErrorKind::InvalidInput, "No engine command given"))?;
+
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = BufReader::new(child.stdout.take().expect("stdout is piped"));
+
+        tracing::info!(command = %command.join(" "), "Started external engine");
+        Ok(GtpController {
+            command: command.to_vec(),
+            child,
+            stdin,
+            stdout,
+            log: None,
+        })
+    }
+
+    // Starts a new process of the same external engine, replacing this one
+    pub(crate) fn restart(&mut self) -> io::Result<()> {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let log = self.log.take();
+        *self = GtpController::start(&self.command)?;
+        self.log = log;
+        Ok(())
+    }
+
+    // Copies every command sent and response received to the given file
+    pub(crate) fn set_log(&mut self, log: Option<File>) {
+        self.log = log;
+    }
+}
+
+impl Drop for GtpController {
+    fn drop(&mut self) {
+        let _ = writeln!(self.stdin, "quit");
+        let _ = self.child.wait();
+    }
+}
+
+/****************************************************\
+|****************       HELPER       ****************|
+\****************************************************/
+
+// Splits a complete GTP response into its success, ex. "= D4", or failure, ex. "? illegal move"
+// Any command id following the status character is dropped
+pub(crate) fn parse_gtp_response(response: &str) -> Result<String, String> {
+    let response = response.trim();
+    let (status, rest) = response.split_at(response.chars().next().map_or(0, char::len_utf8));
+    let message = rest
+        .trim_start_matches(|c: char| c.is_ascii_digit())
+        .trim()
+        .to_string();
+
+    match status {
+        "=" => Ok(message),
+        "?" => Err(message),
+        _ => Err(format!("Malformed GTP response: {response}")),
+    }
+}
+
+// The GTP name of the given Color
+pub(crate) fn color_to_gtp(color: Color) -> &'static str {
+    match color {
+        Color::Black => "B",
+        Color::White => "W",
+    }
+}
+
+/*****************************************************\
+|****************     CONTROLLING     ****************|
+\*****************************************************/
+
+impl GtpController {
+    // Sends the given command to the external engine and waits for its response
+    pub(crate) fn send(&mut self, command: &str) -> Result<String, ControllerError> {
+        self.write_log(&format!(">> {command}"));
+        writeln!(self.stdin, "{command}").map_err(ControllerError::IO)?;
+        self.stdin.flush().map_err(ControllerError::IO)?;
+
+        // a response ends with an empty line, and any empty lines before it are ignored
+        let mut response = String::new();
+        loop {
+            let mut line = String::new();
+            if self.stdout.read_line(&mut line).map_err(ControllerError::IO)? == 0 {
+                return Err(ControllerError::IO(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "External engine exited",
+                )));
+            }
+            if line.trim().is_empty() {
+                if response.is_empty() {
+                    continue;
+                }
+                break;
+            }
+            response.push_str(&line);
+        }
+
+        self.write_log(&format!("<< {}", response.trim_end()));
+        parse_gtp_response(&response).map_err(ControllerError::REJECTED)
+    }
+
+    // Writes the given line to the log file, if there is one
+    fn write_log(&mut self, line: &str) {
+        if let Some(log) = self.log.as_mut() {
+            let _ = writeln!(log, "{line}");
+        }
+    }
+}
@@ -0,0 +1,1187 @@
+use super::*;
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::io::{BufRead, Write};
+use std::{fs, io, thread};
+use std::time::{Duration, Instant};
+/******************************************************\
+|****************      CONSTANTS       ****************|
+\******************************************************/
+
+// The name and version of this Go Engine, as reported to controllers
+const ENGINE_NAME: &str = "TBD";
+const ENGINE_VERSION: &str = "1";
+
+// Playouts ownership estimates the ownership of the board from, unless given a number
+const OWNERSHIP_PLAYOUTS: u16 = 100;
+
+// Commands the listener answers while genmove searches, which only read the game, where every
+// other command waits for the search to finish
+const DURING_SEARCH: &[&str] = &[
+    "protocol_version",
+    "name",
+    "version",
+    "known_command",
+    "list_commands",
+    "showboard",
+    "stop",
+    "get_rules",
+    "ownership",
+    "gogui-analyze_commands",
+    "gomill-describe_engine",
+    "cputime",
+    "gomill-cpu_time",
+];
+
+// Analysis commands offered to GoGui, each as its response type, label, and command
+const GOGUI_ANALYZE_COMMANDS: &[&str] = &["dboard/Ownership/ownership"];
+
+// Every command accepted from the Go Text Protocol, in the order list_commands gives them
+const COMMANDS: &[GtpCommand] = &[
+    GtpCommand { name: "protocol_version", arguments: &[], handler: |gtp, _| Some(gtp.protocol_version()) },
+    GtpCommand { name: "name", arguments: &[], handler: |gtp, _| Some(gtp.name()) },
+    GtpCommand { name: "version", arguments: &[], handler: |gtp, _| Some(gtp.version()) },
+    GtpCommand { name: "known_command", arguments: &["command_name"], handler: |gtp, args| Some(gtp.known_command(args)) },
+    GtpCommand { name: "list_commands", arguments: &[], handler: |gtp, _| Some(gtp.list_commands()) },
+    GtpCommand { name: "quit", arguments: &[], handler: |_, _| None },
+    GtpCommand { name: "boardsize", arguments: &["size"], handler: |gtp, args| Some(gtp.boardsize(args)) },
+    GtpCommand { name: "clear_board", arguments: &[], handler: |gtp, _| Some(gtp.clear_board()) },
+    GtpCommand { name: "komi", arguments: &["new_komi"], handler: |gtp, args| Some(gtp.komi(args)) },
+    GtpCommand { name: "play", arguments: &["color", "vertex"], handler: |gtp, args| Some(gtp.play(args)) },
+    GtpCommand { name: "genmove", arguments: &["color"], handler: |gtp, args| Some(gtp.genmove(args)) },
+    GtpCommand { name: "stop", arguments: &[], handler: |gtp, _| Some(gtp.stop()) },
+    GtpCommand { name: "reg_genmove", arguments: &["color"], handler: |gtp, args| Some(gtp.reg_genmove(args)) },
+    GtpCommand { name: "showboard", arguments: &[], handler: |gtp, _| Some(gtp.showboard()) },
+    GtpCommand { name: "score", arguments: &[], handler: |gtp, _| Some(gtp.score()) },
+    GtpCommand {
+        name: "kgs-chat",
+        arguments: &["channel", "sender", "message..."],
+        handler: |gtp, args| Some(gtp.kgs_chat(args)),
+    },
+    GtpCommand { name: "kgs-game_over", arguments: &[], handler: |gtp, _| Some(gtp.kgs_game_over()) },
+    GtpCommand { name: "kgs-rules", arguments: &["rules"], handler: |gtp, args| Some(gtp.kgs_rules(args)) },
+    GtpCommand {
+        name: "kgs-time_settings",
+        arguments: &["system", "[times...]"],
+        handler: |gtp, args| Some(gtp.kgs_time_settings(args)),
+    },
+    GtpCommand { name: "loadsgf", arguments: &["file", "[move_number]"], handler: |gtp, args| Some(gtp.loadsgf(args)) },
+    GtpCommand { name: "printsgf", arguments: &["[file]"], handler: |gtp, args| Some(gtp.printsgf(args)) },
+    GtpCommand { name: "undo", arguments: &[], handler: |gtp, _| Some(gtp.undo()) },
+    GtpCommand { name: "final_score", arguments: &[], handler: |gtp, _| Some(gtp.final_score()) },
+    GtpCommand {
+        name: "fixed_handicap",
+        arguments: &["number_of_stones"],
+        handler: |gtp, args| Some(gtp.fixed_handicap(args)),
+    },
+    GtpCommand {
+        name: "place_free_handicap",
+        arguments: &["number_of_stones"],
+        handler: |gtp, args| Some(gtp.place_free_handicap(args)),
+    },
+    // the vertices are checked by the handler, as too few of them is a bad vertex list
+    GtpCommand {
+        name: "set_free_handicap",
+        arguments: &["[vertices...]"],
+        handler: |gtp, args| Some(gtp.set_free_handicap(args)),
+    },
+    GtpCommand {
+        name: "time_settings",
+        arguments: &["main_time", "byo_yomi_time", "byo_yomi_stones"],
+        handler: |gtp, args| Some(gtp.time_settings(args)),
+    },
+    GtpCommand { name: "time_left", arguments: &["color", "time", "stones"], handler: |gtp, args| Some(gtp.time_left(args)) },
+    GtpCommand { name: "set_rules", arguments: &["rules", "[ko_rule]"], handler: |gtp, args| Some(gtp.set_rules(args)) },
+    GtpCommand { name: "get_rules", arguments: &[], handler: |gtp, _| Some(gtp.get_rules()) },
+    GtpCommand { name: "clear_cache", arguments: &[], handler: |gtp, _| Some(gtp.clear_cache()) },
+    GtpCommand { name: "ownership", arguments: &["[playouts]"], handler: |gtp, args| Some(gtp.ownership(args)) },
+    GtpCommand {
+        name: "gogui-analyze_commands",
+        arguments: &[],
+        handler: |gtp, _| Some(gtp.gogui_analyze_commands()),
+    },
+    GtpCommand {
+        name: "gomill-describe_engine",
+        arguments: &[],
+        handler: |gtp, _| Some(gtp.gomill_describe_engine()),
+    },
+    GtpCommand { name: "param", arguments: &["[name]", "[value]"], handler: |gtp, args| Some(gtp.param(args)) },
+    GtpCommand { name: "cputime", arguments: &[], handler: |gtp, _| Some(gtp.cputime()) },
+    GtpCommand { name: "gomill-cpu_time", arguments: &[], handler: |gtp, _| Some(gtp.cputime()) },
+    GtpCommand {
+        name: "enforce_alternation",
+        arguments: &["enabled"],
+        handler: |gtp, args| Some(gtp.enforce_alternation(args)),
+    },
+];
+
+/****************************************************\
+|****************    GLOBAL TYPES    ****************|
+\****************************************************/
+
+// Go Text Protocol instance
+pub struct GTP {
+    board: Board,
+    // the rules of the game, as set by kgs-rules or set_rules
+    ruleset: Ruleset,
+    // the ko rule of the game in place of the ruleset's, if set by set_rules
+    ko_rule: Option<KoRule>,
+    // the setup stones and moves leading to the board, as written by printsgf
+    record: SgfRecord,
+    // the position before each move of the record, most recent last, restored by undo
+    undo_stack: Vec<Board>,
+    // the Color that resigned the game, if play or genmove resigned it
+    resigned: Option<Color>,
+    // the knobs genmove and reg_genmove search with, as set by param
+    params: SearchParams,
+    // the time spent by genmove and reg_genmove searching, as reported by cputime
+    search_time: SearchTime,
+    // whether play and genmove refuse to move for the Color whose turn it is not, as set by
+    // enforce_alternation, where analysis leaves either Color free to move
+    enforce_alternation: bool,
+    // the time limits of the game and the time each player has left, as set by time_settings
+    // and time_left
+    clock: Clock,
+    // checked before each search of genmove and reg_genmove, which answer with its error in
+    // place of a move when it refuses, as set by set_search_limit
+    search_limit: Option<Box<dyn Fn() -> Result<(), String> + Send + Sync>>,
+}
+
+/*****************************************************\
+|****************    PRIVATE TYPES    ****************|
+\*****************************************************/
+
+// Enumerates all response types of the Go Text Protocol
+// and handles sending them to the Protocol
+enum GtpResponse {
+    SUCCESS(String),
+    ERROR(String),
+    DEBUG(String, String), // response to protocol, debug message
+}
+
+// A command accepted from the Go Text Protocol: the name it is given by, the arguments it takes,
+// where optional ones are in brackets and "..." takes the rest of the line, and the handler
+// answering it, which gives None if the Protocol should close
+struct GtpCommand {
+    name: &'static str,
+    arguments: &'static [&'static str],
+    handler: fn(&mut GTP, &[&str]) -> Option<GtpResponse>,
+}
+
+// Events the Go Text Protocol listener handles, in the order they happen
+enum ListenerEvent {
+    LINE(String),                // a line of input from the controller
+    CLOSED,                      // the controller closed the input
+    SEARCHED(Move, SearchTime),  // the search of the pending genmove found its move in the time given
+}
+
+// A genmove whose search runs on another thread while the listener handles other commands
+struct PendingGenmove {
+    id: Option<u32>,
+    color: Color,
+    // set to end the search early with the best move found so far
+    stop: Arc<AtomicBool>,
+}
+
+/****************************************************\
+|****************       HELPER       ****************|
+\****************************************************/
+
+impl GtpCommand {
+    // The registered command with the given name, or None if the command is not supported
+    fn find(name: &str) -> Option<&'static GtpCommand> {
+        COMMANDS.iter().find(|command| command.name == name)
+    }
+
+    // The number of arguments the command cannot be given without
+    fn required_arguments(&self) -> usize {
+        self.arguments.iter().filter(|argument| !argument.starts_with('[')).count()
+    }
+}
+
+/*****************************************************\
+|****************         GTP         ****************|
+\*****************************************************/
+
+impl GtpResponse {
+    // Writes the result of this GtpResponse to the Go Text Protocol, echoing the id of the
+    // command it answers if one was given
+    fn write_to_gtp(self, id: Option<u32>, output: &mut impl Write) -> io::Result<()> {
+        match self {
+            GtpResponse::SUCCESS(result) => write!(output, "{} {}", Self::status('=', id), Self::format_gtp_string(result))?,
+            GtpResponse::ERROR(result) => write!(output, "{} {}", Self::status('?', id), Self::format_gtp_string(result))?,
+            GtpResponse::DEBUG(protocol_message, debug_message) => {
+                // debug text is for the person running the engine, so it always reaches stderr
+                eprint!("d {}", Self::format_gtp_string(debug_message));
+                write!(output, "{} {}", Self::status('=', id), Self::format_gtp_string(protocol_message))?;
+            }
+        }
+        output.flush()
+    }
+
+    // The result of a success, or the message of an error
+    fn into_result(self) -> Result<String, String> {
+        match self {
+            GtpResponse::SUCCESS(result) | GtpResponse::DEBUG(result, _) => Ok(result),
+            GtpResponse::ERROR(message) => Err(message),
+        }
+    }
+
+    // The status character of a response followed by the id of the command it answers, if any
+    fn status(character: char, id: Option<u32>) -> String {
+        match id {
+            Some(id) => format!("{character}{id}"),
+            None => character.to_string(),
+        }
+    }
+
+    // Formats the given String such that it conforms to the Go Text Protocol
+    fn format_gtp_string(input: String) -> String {
+        if input.ends_with("\n\n") {
+            input
+        } else {
+            Self::format_gtp_string(format!("{input}\n"))
+        }
+    }
+}
+
+impl GTP {
+    // Preprocesses a line of input as the Go Text Protocol requires: drops control characters
+    // other than tabs and newlines, including the CR of CRLF line endings, turns tabs into
+    // spaces, and removes any comment starting with #
+    fn preprocess(line: &str) -> String {
+        let line = line.split('#').next().unwrap_or_default();
+        line.chars()
+            .filter(|c| !c.is_control() || *c == '\t' || *c == '\n')
+            .map(|c| if c == '\t' { ' ' } else { c })
+            .collect()
+    }
+
+    // Starts a Go Text Protocol listener for the Go Engine on stdin, answering on stdout
+    pub(crate) fn start_listener(self) -> io::Result<()> {
+        self.listen(io::BufReader::new(io::stdin()).lines(), &mut io::stdout())
+    }
+
+    // Listens for Go Text Protocol commands on the given lines of input, answering them on the
+    // given output, until the input closes or quit is given
+    // Input is read on its own thread, and genmove searches on another, so that stop and the
+    // commands that only read the game are answered while it thinks
+    pub fn listen(
+        mut self,
+        input: impl Iterator<Item = io::Result<String>> + Send + 'static,
+        output: &mut impl Write,
+    ) -> io::Result<()> {
+        let (events, listener) = mpsc::channel();
+        let sender = events.clone();
+        thread::spawn(move || {
+            for line in input {
+                let Ok(line) = line else { break };
+                if sender.send(ListenerEvent::LINE(line)).is_err() {
+                    break;
+                }
+            }
+            let _ = sender.send(ListenerEvent::CLOSED); // the listener may have quit already
+        });
+
+        let mut pending: Option<PendingGenmove> = None;
+        let mut queued: VecDeque<String> = VecDeque::new();
+        let mut closed = false;
+        for event in listener.iter() {
+            match event {
+                ListenerEvent::LINE(line) => {
+                    // stop and quit end the search as soon as they arrive, though they are
+                    // answered in turn
+                    let command = Self::preprocess(&line);
+                    let arguments: Vec<&str> = command.split_whitespace().collect();
+                    if let (Some(genmove), ["stop" | "quit", ..]) = (&pending, Self::split_id(&arguments).1) {
+                        genmove.stop.store(true, Ordering::Relaxed);
+                    }
+                    queued.push_back(line);
+                }
+                ListenerEvent::CLOSED => closed = true, // the controller closed the connection
+                ListenerEvent::SEARCHED(mov, search_time) => {
+                    let genmove = pending.take().unwrap(); // searches are only started for a pending genmove
+                    self.search_time.add(search_time);
+                    self.clock.spend(genmove.color, search_time.wall);
+                    self.commit_genmove(genmove.color, mov).write_to_gtp(genmove.id, output)?;
+                }
+            }
+
+            while let Some(line) = queued.pop_front() {
+                let line = Self::preprocess(&line);
+                let arguments: Vec<&str> = line.split_whitespace().collect();
+                if arguments.is_empty() {
+                    continue;
+                }
+                let (id, args) = Self::split_id(&arguments);
+                if pending.is_some() && !args.first().is_some_and(|command| DURING_SEARCH.contains(command)) {
+                    queued.push_front(line);
+                    break;
+                }
+
+                tracing::debug!(command = line.trim(), "Received GTP command");
+                if let ["genmove", color, ..] = args {
+                    match self.prepare_genmove(color) {
+                        Ok(color) => pending = Some(self.start_search(id, color, events.clone())),
+                        Err(response) => response.write_to_gtp(id, output)?,
+                    }
+                    continue;
+                }
+                match self.gtp_commands(&arguments) {
+                    Some((id, response)) => response.write_to_gtp(id, output)?,
+                    None => return GtpResponse::SUCCESS(String::new()).write_to_gtp(id, output), // quit
+                }
+            }
+
+            if closed && pending.is_none() {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Starts searching for a move for the given Color on another thread, which sends the move
+    // found to the given listener once the search finishes or is stopped
+    // Returns the genmove pending until then, answering the command of the given id
+    fn start_search(&self, id: Option<u32>, color: Color, listener: mpsc::Sender<ListenerEvent>) -> PendingGenmove {
+        let stop = Arc::new(AtomicBool::new(false));
+        let (board, params, time) = (self.board.deepcopy(), self.params, self.search_budget(color));
+        let search_stop = Arc::clone(&stop);
+        thread::spawn(move || {
+            let mut search_time = SearchTime::default();
+            let mov = search_time.measure(|| generate_move_with(&board, color, &params, time, Some(search_stop)));
+            let _ = listener.send(ListenerEvent::SEARCHED(mov, search_time)); // the listener may have quit
+        });
+        PendingGenmove { id, color, stop }
+    }
+
+    // Accepts individual command strings and returns the engine response from the command as a String
+    // Returns an empty String for lines the Go Text Protocol ignores, such as comments
+    pub fn accept_command(&mut self, command: String) -> String {
+        let line = Self::preprocess(&command);
+        let arguments: Vec<&str> = line.split_whitespace().collect();
+        if arguments.is_empty() {
+            return String::new();
+        }
+        tracing::debug!(command = line.trim(), "Received GTP command");
+        let response = self.gtp_commands(&arguments);
+        match response {
+            Some((id, GtpResponse::SUCCESS(result))) => format!("{} {result}", GtpResponse::status('=', id)),//, format_gtp_string(result)),
+            Some((id, GtpResponse::ERROR(result))) => format!("{} {result}", GtpResponse::status('?', id)),//, Self::format_gtp_string(result)),
+            Some((id, GtpResponse::DEBUG(protocol_message, debug_message))) => {
+                format!("d {debug_message}\n\n{} {protocol_message}", GtpResponse::status('=', id))
+                // format!("d {}", Self::format_gtp_string(debug_message));
+                // format!("= {}", Self::format_gtp_string(protocol_message));
+            },
+            None => String::from("quit"),
+        }
+    }
+
+    // Runs the command given with its arguments, giving the result of a success or the message
+    // of an error rather than a response to the Protocol, or None if the command closes it
+    pub fn execute(&mut self, arguments: &[&str]) -> Option<Result<String, String>> {
+        tracing::debug!(command = arguments.join(" "), "Executing GTP command");
+        Some(self.gtp_commands(arguments)?.1.into_result())
+    }
+
+    // The current position of the game
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    // The setup stones and moves of the game so far, with its result once it is over
+    pub fn record(&self) -> &SgfRecord {
+        &self.record
+    }
+
+    // Replaces the game with the given record, replaying its setup stones and moves, so that each
+    // can be taken back with undo
+    // Returns an error describing the first move that cannot be replayed, leaving the game as it was
+    pub fn load_record(&mut self, record: SgfRecord) -> Result<(), String> {
+        let mut positions = record.positions()?;
+        let (board, _) = positions.pop().unwrap(); // the final position always exists
+        self.reset_board(board);
+        self.record = record;
+        self.undo_stack = positions.into_iter().map(|(position, _)| position).collect();
+        Ok(())
+    }
+
+    // The time limits of the game and the time each player has left
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
+    // The knobs of the engine's searches
+    pub fn params(&self) -> SearchParams {
+        self.params
+    }
+
+    // Changes the knobs of the engine's searches, from its next search on
+    pub fn set_params(&mut self, params: SearchParams) {
+        self.params = params;
+    }
+
+    // Limits the searches of genmove and reg_genmove to those the given check allows, which gives
+    // the message of the error answered in place of a move when it refuses one
+    pub fn set_search_limit(&mut self, limit: impl Fn() -> Result<(), String> + Send + Sync + 'static) {
+        self.search_limit = Some(Box::new(limit));
+    }
+
+    // Removes the limit set by set_search_limit, if any, letting every search run
+    pub fn remove_search_limit(&mut self) {
+        self.search_limit = None;
+    }
+
+    // Takes the given time the given Color spent on its move off its time left, for moves the
+    // engine did not generate
+    pub fn spend_time(&mut self, color: Color, elapsed: Duration) {
+        self.clock.spend(color, elapsed);
+    }
+}
+
+
+
+impl Default for GTP {
+    fn default() -> GTP {
+        GTP::new()
+    }
+}
+
+impl GTP {
+    // Creates a new instance of the Go Text Protocol
+    pub fn new() -> GTP {
+        let board = Board::new(BoardSize::Nineteen);
+        GTP {
+            record: SgfRecord::new(board.size, board.komi),
+            board,
+            ruleset: Ruleset::Chinese,
+            ko_rule: None,
+            undo_stack: vec![],
+            resigned: None,
+            enforce_alternation: false,
+            params: SearchParams::default(),
+            search_time: SearchTime::default(),
+            clock: Clock::new(),
+            search_limit: None,
+        }
+    }
+
+    // Replaces the board with the given one under the game's rules, starting a new record of the
+    // game on it
+    fn reset_board(&mut self, mut board: Board) {
+        board.ruleset = self.ruleset;
+        board.ko_rule = self.ko_rule;
+        self.record = SgfRecord::new(board.size, board.komi);
+        self.board = board;
+        self.undo_stack.clear();
+        self.resigned = None;
+        self.clock.restart();
+    }
+
+    // Plays the given Move on the board, keeping the previous position for undo and recording
+    // the move in the game's record, along with the result if it ends the game
+    // Returns false, leaving the board untouched, if the Move is illegal
+    fn play_recorded(&mut self, mov: Move, color: Color) -> bool {
+        let previous = self.board.deepcopy();
+        if !self.board.play(mov) {
+            return false;
+        }
+
+        self.undo_stack.push(previous);
+        self.record.nodes.push(SgfNode::new(color, mov));
+        self.record.result = self.game_result().map(|result| result.to_string());
+        true
+    }
+
+    // Ends the game with the given Color resigning, recording the result
+    fn resign(&mut self, color: Color) {
+        tracing::info!(?color, "Resigned the game");
+        self.resigned = Some(color);
+        self.record.result = self.game_result().map(|result| result.to_string());
+    }
+
+    // The Color to move next: the opposite of the last move's, or as the record sets up the game
+    // before the first move, which is Black's unless stated otherwise
+    pub fn side_to_move(&self) -> Color {
+        match self.record.nodes.last() {
+            Some(node) => node.color.opposite_color(),
+            None => self.record.to_play.unwrap_or(Color::Black),
+        }
+    }
+
+    // Checks whether the given Move of the given Color may be played as the next move of the game,
+    // by the side to move, without playing it
+    // Returns Ok if it may, else the reason it may not
+    pub fn check_move(&self, mov: Move, color: Color) -> Result<(), IllegalMove> {
+        if color != self.side_to_move() {
+            return Err(IllegalMove::OUTOFTURN);
+        }
+        match mov {
+            Move::MOVE(intsc, _) => self.board.check_move(&intsc, color),
+            Move::PASS | Move::RESIGN => Ok(()),
+        }
+    }
+
+    // Whether the given Color may move next, which it always may unless alternation is enforced
+    fn may_move(&self, color: Color) -> bool {
+        !self.enforce_alternation || color == self.side_to_move()
+    }
+
+    // The result of the game if it is over, which it is once a player resigns or both players
+    // pass in a row
+    fn game_result(&self) -> Option<GameResult> {
+        if let Some(color) = self.resigned {
+            return Some(GameResult::RESIGNATION(color.opposite_color()));
+        }
+
+        let passes = self.record.nodes.iter().rev().take_while(|node| node.mov == Move::PASS).count();
+        if passes >= 2 {
+            Some(GameResult::SCORE(self.board.final_score(self.ruleset.scoring())))
+        } else {
+            None
+        }
+    }
+
+    // Handles input arguments given from the Go Text Protocol, which may start with a numeric id
+    // to echo in the response, and sends them to their respective command function
+    // Returns the id and the response of the command, or None if the Protocol should close
+    fn gtp_commands(&mut self, args: &[&str]) -> Option<(Option<u32>, GtpResponse)> {
+        let (id, args) = Self::split_id(args);
+        if args.is_empty() {
+            return Some((id, GtpResponse::ERROR("Unsupported command".to_string())));
+        }
+        self.run_command(args).map(|response| (id, response))
+    }
+
+    // Splits the numeric id a command may start with from the command name and its arguments
+    fn split_id<'a, 'b>(args: &'a [&'b str]) -> (Option<u32>, &'a [&'b str]) {
+        match args.first().map(|first| first.parse::<u32>()) {
+            Some(Ok(id)) => (Some(id), &args[1..]),
+            _ => (None, args),
+        }
+    }
+
+    // Sends the given command name and its arguments to the handler the command is registered with
+    // Returns the response of the command, or None if the Protocol should close
+    fn run_command(&mut self, args: &[&str]) -> Option<GtpResponse> {
+        let command = match GtpCommand::find(args[0]) {
+            Some(command) => command,
+            None => return Some(GtpResponse::ERROR("Unsupported command".to_string())),
+        };
+
+        if args.len() - 1 < command.required_arguments() {
+            return Some(GtpResponse::ERROR(format!(
+                "Not enough arguments given to {}, which takes: {}",
+                command.name,
+                command.arguments.join(" ")
+            )));
+        }
+        (command.handler)(self, &args[1..])
+    }
+
+    // Returns the Go Text Protocol version this program conforms to
+    fn protocol_version(&self) -> GtpResponse {
+        GtpResponse::SUCCESS("2".to_string())
+    }
+
+    // Returns the name of this Go Engine
+    fn name(&self) -> GtpResponse {
+        GtpResponse::SUCCESS(ENGINE_NAME.to_string())
+    }
+
+    // Returns the version of this Go Engine
+    fn version(&self) -> GtpResponse {
+        GtpResponse::SUCCESS(ENGINE_VERSION.to_string())
+    }
+
+    // Describes this Go Engine for gomill's tournament reports: its name and version, and the
+    // settings it searches and plays with
+    fn gomill_describe_engine(&self) -> GtpResponse {
+        let search = match (self.clock.move_budget(Color::Black, &self.board), self.params.max_time) {
+            (Some(_), None) => "as long as the clock allows".to_string(),
+            (_, Some(time)) => format!("at most {} seconds", time.as_secs_f64()),
+            (None, None) => format!("{} iterations", self.params.iterations),
+        };
+        let description = [
+            format!("{ENGINE_NAME} {ENGINE_VERSION}"),
+            format!("search: {search}"),
+            format!("exploration constant: {:.4}", self.params.exploration),
+            format!("resignation threshold: {} points", self.params.resignation_threshold),
+            format!("search threads: {}", search_threads()),
+            format!("ruleset: {}", self.ruleset),
+            format!("komi: {}", self.board.komi),
+            format!("board size: {}", self.board.size.to_u16()),
+        ];
+        GtpResponse::SUCCESS(description.join("\n"))
+    }
+
+    // args[0] = command name to check
+    // Checks if the given command name is a command this engine supports
+    // Gives a GtpResponse containing true if the command is known, false otherwise
+    fn known_command(&self, args: &[&str]) -> GtpResponse {
+        GtpResponse::SUCCESS(GtpCommand::find(args[0]).is_some().to_string())
+    }
+
+    // Lists all commands supported by this Go Engine
+    fn list_commands(&self) -> GtpResponse {
+        let mut command_list = String::from("");
+        for command in COMMANDS {
+            command_list = format!("{command_list}{}\n", command.name);
+        }
+        GtpResponse::SUCCESS(command_list)
+    }
+
+    // args[0] = new board size
+    // If given a valid BoardSize, clears the current board
+    // and sets its board size to the given size
+    // Returns an empty response unless an error occurs
+    fn boardsize(&mut self, args: &[&str]) -> GtpResponse {
+        if let Ok(num) = args[0].parse::<u16>() {
+            if let Some(size) = BoardSize::from_u16(num) {
+                let mut board = Board::new(size);
+                board.komi = self.board.komi;
+                self.reset_board(board);
+                GtpResponse::SUCCESS(String::new())
+            } else {
+                GtpResponse::ERROR(format!("Invalid size given to boardsize: {num}"))
+            }
+        } else {
+            GtpResponse::ERROR(format!(
+                "Non-numeric size argument given to boardsize: {}",
+                args[0]
+            ))
+        }
+    }
+
+    // Resets the board to an empty state
+    // Returns an empty response
+    fn clear_board(&mut self) -> GtpResponse {
+        let mut board = Board::new(self.board.size);
+        board.komi = self.board.komi;
+        self.reset_board(board);
+        GtpResponse::SUCCESS(String::new())
+    }
+
+    // args[0] = new decimal komi value
+    // Sets the komi of the current game to the given value
+    // Returns an empty response unless an error occurs
+    fn komi(&mut self, args: &[&str]) -> GtpResponse {
+        let komi_value = args[0].parse::<f64>();
+        if komi_value.is_err() {
+            return GtpResponse::ERROR(format!("Invalid komi argument given: {}", args[0]));
+        }
+
+        self.board.komi = komi_value.unwrap();
+        self.record.komi = self.board.komi;
+        GtpResponse::SUCCESS(String::new())
+    }
+
+    // args[0] = Color ("B", "W"), args[1] = intersection to play at in Go Notation (ex. "Q16"),
+    // "pass", or "resign"
+    // Attempts to play a stone for the given color at the given intersection, or passes or
+    // resigns for it, where resigning ends the game
+    // If successful, returns an empty successful response
+    // Else, returns an error response "Invalid move"
+    fn play(&mut self, args: &[&str]) -> GtpResponse {
+        let color = Color::from_name(args[0]);
+        let mov = color.and_then(|color| vertex_to_move(args[1], color));
+
+        if color.is_none() || mov.is_none() {
+            return GtpResponse::ERROR("syntax error".to_string()); // GTP required error message
+        }
+
+        if !self.may_move(color.unwrap()) {
+            return GtpResponse::ERROR("not the side to move".to_string());
+        }
+
+        if mov == Some(Move::RESIGN) {
+            self.resign(color.unwrap());
+        } else if !self.play_recorded(mov.unwrap(), color.unwrap()) {
+            return GtpResponse::ERROR("invalid move".to_string()); // GTP required error message
+        }
+
+        GtpResponse::SUCCESS(String::new())
+    }
+
+    // args[0] = Color ("B", "W")
+    // Generates and plays the engine's move for the given color in the current Board position
+    // Outputs the intersection played in Go Notation, "pass" if the engine passes, or "resign" if
+    // the engine resigns, which ends the game so that no more moves are generated for it
+    fn genmove(&mut self, args: &[&str]) -> GtpResponse {
+        self.genmove_with(args[0], None, None)
+    }
+
+    // Generates and plays a move for the given color like genmove, stopping its search early once
+    // the given stop flag, if any, is set, and reporting its progress to the given ProgressReport
+    // Returns the vertex of the move, else the message of the error genmove answers with
+    pub fn genmove_reporting(
+        &mut self,
+        color: &str,
+        stop: Option<Arc<AtomicBool>>,
+        progress: ProgressReport,
+    ) -> Result<String, String> {
+        self.genmove_with(color, stop, Some(progress)).into_result()
+    }
+
+    // Answers genmove for the given color, searching with the given stop flag and ProgressReport
+    fn genmove_with(
+        &mut self,
+        color: &str,
+        stop: Option<Arc<AtomicBool>>,
+        progress: Option<ProgressReport>,
+    ) -> GtpResponse {
+        let color = match self.prepare_genmove(color) {
+            Ok(color) => color,
+            Err(response) => return response,
+        };
+
+        let start = Instant::now();
+        let mov = self.generate(color, stop, progress);
+        self.clock.spend(color, start.elapsed());
+        self.commit_genmove(color, mov)
+    }
+
+    // Checks that a move can be generated for the given color in the current Board position
+    // Returns the Color to generate a move for, else the response genmove answers with instead
+    fn prepare_genmove(&self, color: &str) -> Result<Color, GtpResponse> {
+        let color = match Color::from_name(color) {
+            Some(color) => color,
+            None => return Err(GtpResponse::ERROR("Invalid color given to genmove".to_string())),
+        };
+        if !self.may_move(color) {
+            return Err(GtpResponse::ERROR("not the side to move".to_string()));
+        }
+        match self.game_result() {
+            Some(GameResult::RESIGNATION(_)) => Err(GtpResponse::ERROR("game is over".to_string())),
+            Some(_) => Err(GtpResponse::SUCCESS("pass".to_string())), // nothing is left to play once both pass
+            None => self.check_search_limit().map(|_| color),
+        }
+    }
+
+    // Checks that the search limit, if any, allows another search
+    // Returns the error genmove and reg_genmove answer with instead if it does not
+    fn check_search_limit(&self) -> Result<(), GtpResponse> {
+        match &self.search_limit {
+            Some(limit) => limit().map_err(GtpResponse::ERROR),
+            None => Ok(()),
+        }
+    }
+
+    // Plays the given Move generated for the given Color, resigning the game for a resignation
+    // Returns the vertex of the move
+    fn commit_genmove(&mut self, color: Color, mov: Move) -> GtpResponse {
+        match mov {
+            Move::MOVE(intsc, color) => {
+                self.play_recorded(mov, color);
+                GtpResponse::SUCCESS(Position::from(intsc).to_string())
+            }
+            Move::PASS => {
+                self.play_recorded(mov, color);
+                GtpResponse::SUCCESS("pass".to_string())
+            }
+            Move::RESIGN => {
+                self.resign(color);
+                GtpResponse::SUCCESS("resign".to_string())
+            }
+        }
+    }
+
+    // args[0] = Color ("B", "W")
+    // Generates an engine move for the given color in the current Board position like genmove,
+    // without playing it or taking its time off the clock, for regression tests
+    // Outputs the intersection, "pass", or "resign"
+    fn reg_genmove(&mut self, args: &[&str]) -> GtpResponse {
+        match (Color::from_name(args[0]), self.check_search_limit()) {
+            (Some(color), Ok(())) => GtpResponse::SUCCESS(move_to_vertex(&self.generate(color, None, None))),
+            (Some(_), Err(response)) => response,
+            (None, _) => GtpResponse::ERROR("Invalid color given to reg_genmove".to_string()),
+        }
+    }
+
+    // Searches for a move for the given Color in the current Board position, for as long as the
+    // clock allows up to the max_time param, or for the playouts param when neither limits it,
+    // adding the time taken to the time spent searching
+    // The search stops early once the given stop flag, if any, is set, and reports its progress to
+    // the given ProgressReport, if any
+    fn generate(
+        &mut self,
+        color: Color,
+        stop: Option<Arc<AtomicBool>>,
+        progress: Option<ProgressReport>,
+    ) -> Move {
+        let time = self.search_budget(color);
+        let (board, params) = (&self.board, &self.params);
+        self.search_time.measure(|| generate_move_reporting(board, color, params, time, stop, progress))
+    }
+
+    // The time a search for the given Color may take: what the clock allows, up to the max_time
+    // param, or None if neither limits it
+    fn search_budget(&self, color: Color) -> Option<Duration> {
+        match (self.clock.move_budget(color, &self.board), self.params.max_time) {
+            (Some(budget), Some(max_time)) => Some(budget.min(max_time)),
+            (budget, max_time) => budget.or(max_time),
+        }
+    }
+
+    // Stops the search of a genmove thinking on another thread, which then answers with the best
+    // move found so far
+    // Searches only run on another thread under start_listener, so there is otherwise nothing to
+    // stop, and an empty response is returned
+    fn stop(&self) -> GtpResponse {
+        GtpResponse::SUCCESS(String::new())
+    }
+
+    // args[0] = optional param name, args[1] = optional new value
+    // Sets the named param ("playouts", "max_time" in seconds where 0 is no limit, "exploration",
+    // or "resign_threshold" in points) to the given value, or shows its value if none is given
+    // Returns every param and its value, one per line, if no param is named
+    fn param(&mut self, args: &[&str]) -> GtpResponse {
+        match args {
+            [] => GtpResponse::SUCCESS(
+                SearchParams::NAMES
+                    .iter()
+                    .map(|name| format!("{name} {}", self.params.get(name).unwrap())) // every name is a param
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+            ),
+            [name] => match self.params.get(name) {
+                Some(value) => GtpResponse::SUCCESS(value),
+                None => GtpResponse::ERROR(format!("Unknown param: {name}")),
+            },
+            [name, value, ..] => match self.params.set(name, value) {
+                Ok(()) => GtpResponse::SUCCESS(String::new()),
+                Err(error) => GtpResponse::ERROR(error),
+            },
+        }
+    }
+
+    // Returns the CPU time in seconds spent searching for moves since the engine started, over
+    // every search thread, with the wall clock time the searches took as a debug message
+    fn cputime(&self) -> GtpResponse {
+        GtpResponse::DEBUG(
+            format!("{:.3}", self.search_time.cpu.as_secs_f64()),
+            format!("Searched for {:.3} seconds", self.search_time.wall.as_secs_f64()),
+        )
+    }
+
+    // args[0] = "true" or "false"
+    // Sets whether play and genmove only move for the side to move, which alternates between
+    // the Colors from Black, or White after handicap stones
+    // Returns an empty response unless an error occurs
+    fn enforce_alternation(&mut self, args: &[&str]) -> GtpResponse {
+        match args[0].parse::<bool>() {
+            Ok(enabled) => {
+                self.enforce_alternation = enabled;
+                GtpResponse::SUCCESS(String::new())
+            }
+            Err(_) => GtpResponse::ERROR("syntax error".to_string()), // GTP required error message
+        }
+    }
+
+    // args[0] = main time, args[1] = byo-yomi time, args[2] = byo-yomi stones
+    // Sets the time limits of the game: main time, then overtime periods of byo-yomi time for
+    // every byo-yomi stones moves, all in seconds, where byo-yomi time without stones means no
+    // time limits
+    // Returns an empty response unless an error occurs
+    fn time_settings(&mut self, args: &[&str]) -> GtpResponse {
+        match (args[0].parse::<u64>(), args[1].parse::<u64>(), args[2].parse::<u32>()) {
+            (Ok(main_time), Ok(byo_yomi_time), Ok(byo_yomi_stones)) => {
+                self.clock.set_settings(TimeSettings::new(main_time, byo_yomi_time, byo_yomi_stones));
+                GtpResponse::SUCCESS(String::new())
+            }
+            _ => GtpResponse::ERROR("syntax error".to_string()), // GTP required error message
+        }
+    }
+
+    // args[0] = Color ("B", "W"), args[1] = seconds left, args[2] = stones left
+    // Sets the time the given color has left: of main time when stones left is 0, else of the
+    // current overtime period, with stones left counting the moves to play in it under Canadian
+    // overtime, or the periods left under Japanese byo-yomi
+    // Returns an empty response unless an error occurs
+    fn time_left(&mut self, args: &[&str]) -> GtpResponse {
+        match (Color::from_name(args[0]), args[1].parse::<u64>(), args[2].parse::<u32>()) {
+            (Some(color), Ok(seconds), Ok(stones)) => {
+                self.clock.set_time_left(color, seconds, stones);
+                GtpResponse::SUCCESS(String::new())
+            }
+            _ => GtpResponse::ERROR("syntax error".to_string()), // GTP required error message
+        }
+    }
+
+    // Takes back a resignation, or else the last move, restoring the stones, captures, ko, and
+    // side to move of the position before it
+    // Returns an empty response unless there is nothing to take back
+    fn undo(&mut self) -> GtpResponse {
+        if self.resigned.take().is_some() {
+            self.record.result = self.game_result().map(|result| result.to_string());
+            return GtpResponse::SUCCESS(String::new());
+        }
+
+        match self.undo_stack.pop() {
+            Some(mut previous) => {
+                previous.komi = self.board.komi;
+                previous.ruleset = self.ruleset;
+                previous.ko_rule = self.ko_rule;
+                self.board = previous;
+                self.record.nodes.pop();
+                self.record.result = self.game_result().map(|result| result.to_string());
+                GtpResponse::SUCCESS(String::new())
+            }
+            None => GtpResponse::ERROR("cannot undo".to_string()), // GTP required error message
+        }
+    }
+
+    // args[0] = number of handicap stones
+    // Places the given number of Black handicap stones on the star points of the empty board
+    // Returns the vertices of the stones unless an error occurs
+    fn fixed_handicap(&mut self, args: &[&str]) -> GtpResponse {
+        match args[0].parse::<u16>().ok().and_then(|stones| self.board.size.handicap_points(stones)) {
+            Some(points) => self.place_handicap(points.into_iter().map(Intersection::from).collect(), true),
+            None => GtpResponse::ERROR("invalid number of stones".to_string()), // GTP required error message
+        }
+    }
+
+    // args[0] = number of handicap stones
+    // Places the given number of Black handicap stones on the empty board where the engine chooses
+    // Returns the vertices of the stones unless an error occurs
+    fn place_free_handicap(&mut self, args: &[&str]) -> GtpResponse {
+        match args[0].parse::<u16>().ok().and_then(|stones| free_handicap_points(self.board.size, stones)) {
+            Some(points) => self.place_handicap(points, true),
+            None => GtpResponse::ERROR("invalid number of stones".to_string()), // GTP required error message
+        }
+    }
+
+    // args = vertices of the handicap stones
+    // Places Black handicap stones on the given vertices of the empty board, as chosen by the
+    // controller
+    // Returns an empty response unless an error occurs
+    fn set_free_handicap(&mut self, args: &[&str]) -> GtpResponse {
+        let mut points: Vec<Intersection> = vec![];
+        for vertex in args {
+            match Position::parse_vertex(vertex, self.board.size) {
+                Ok(Some(pos)) if !points.contains(&Intersection::from(pos)) => points.push(pos.into()),
+                _ => return GtpResponse::ERROR("bad vertex list".to_string()), // GTP required error message
+            }
+        }
+
+        if points.len() < 2 {
+            return GtpResponse::ERROR("bad vertex list".to_string()); // GTP required error message
+        }
+        self.place_handicap(points, false)
+    }
+
+    // Places Black handicap stones on the given points of the empty board, as setup stones of the
+    // game's record, leaving White to play
+    // Returns the vertices of the stones if they are to be listed, else an empty response,
+    // unless the board is not empty
+    fn place_handicap(&mut self, points: Vec<Intersection>, list_vertices: bool) -> GtpResponse {
+        if !self.board.stones(Color::Black).is_empty() || !self.board.stones(Color::White).is_empty() {
+            return GtpResponse::ERROR("board not empty".to_string()); // GTP required error message
+        }
+
+        for intsc in &points {
+            self.board.place_stone(intsc, Color::Black);
+        }
+        let vertices: Vec<String> = points.iter().map(|&intsc| Position::from(intsc).to_string()).collect();
+        self.record.setup_black = points;
+        self.record.to_play = Some(Color::White);
+
+        GtpResponse::SUCCESS(if list_vertices { vertices.join(" ") } else { String::new() })
+    }
+
+    // Returns a successful GtpResponse containing the result of the game (ex. "B+3.5"), scored
+    // under the game's rules from the current position if it is not over yet
+    fn final_score(&self) -> GtpResponse {
+        let result = self
+            .game_result()
+            .unwrap_or_else(|| GameResult::SCORE(self.board.final_score(self.ruleset.scoring())));
+        GtpResponse::SUCCESS(result.to_string())
+    }
+
+    // args[0] = optional number of playouts
+    // Estimates who owns each point of the current Board position from the given number of
+    // playouts, or OWNERSHIP_PLAYOUTS, for the side to move
+    // Returns the ownership as a GoGui dboard: rows from the top of the board of values from
+    // -1.0 (White's) to 1.0 (Black's)
+    fn ownership(&self, args: &[&str]) -> GtpResponse {
+        let playouts = match args.first().map(|arg| arg.parse::<u16>()) {
+            None => OWNERSHIP_PLAYOUTS,
+            Some(Ok(playouts)) if playouts > 0 => playouts,
+            Some(_) => return GtpResponse::ERROR(format!("Invalid number of playouts given to ownership: {}", args[0])),
+        };
+
+        let ownership = ownership_positions(&[(self.board.deepcopy(), self.side_to_move())], playouts)
+            .pop()
+            .unwrap(); // one Ownership is returned per position
+        let rows: Vec<String> = ownership
+            .rows()
+            .iter()
+            .map(|row| row.iter().map(|value| format!("{value:.2}")).collect::<Vec<String>>().join(" "))
+            .collect();
+        GtpResponse::SUCCESS(rows.join("\n"))
+    }
+
+    // Lists the analysis commands GoGui may offer for this engine
+    fn gogui_analyze_commands(&self) -> GtpResponse {
+        GtpResponse::SUCCESS(GOGUI_ANALYZE_COMMANDS.join("\n"))
+    }
+
+    // Drops the search state kept between commands, leaving the board and game as they are
+    // Searches build their trees afresh for every move, so the only state kept is the spare
+    // Boards of earlier searches, pooled for the scratch Boards of later ones
+    // Returns an empty response
+    fn clear_cache(&self) -> GtpResponse {
+        clear_spares();
+        GtpResponse::SUCCESS(String::new())
+    }
+
+    // Returns a successful GtpResponse containing a rendering of the current Board position
+    fn showboard(&self) -> GtpResponse {
+        GtpResponse::SUCCESS(self.board.to_string())
+    }
+
+    // Returns a successful GtpResponse containing the score of the current Board position
+    fn score(&self) -> GtpResponse {
+        GtpResponse::SUCCESS(self.board.estimate_score().to_string())
+    }
+
+    // args[0] = "game" or "private", args[1] = name of the sender, args[2..] = chat message
+    // Answers a chat message sent to the engine on KGS, where a private "score" asks for the
+    // result of the current position under the game's rules
+    // Game chat is never answered, as kgsGtp sends nothing for an error response
+    fn kgs_chat(&self, args: &[&str]) -> GtpResponse {
+        let message = args[2..].join(" ");
+        tracing::info!(channel = args[0], sender = args[1], "KGS chat: {message}");
+        if args[0] != "private" {
+            return GtpResponse::ERROR("No reply to game chat".to_string());
+        }
+
+        if message.trim().eq_ignore_ascii_case("score") {
+            GtpResponse::SUCCESS(result_string(self.board.final_score(self.ruleset.scoring())))
+        } else {
+            GtpResponse::SUCCESS("I am a Monte Carlo tree search Go engine. Say \"score\" for my count of the game.".to_string())
+        }
+    }
+
+    // Acknowledges the end of a game on KGS
+    // Returns an empty response
+    fn kgs_game_over(&self) -> GtpResponse {
+        tracing::info!("KGS game over");
+        GtpResponse::SUCCESS(String::new())
+    }
+
+    // args[0] = KGS rules name ("japanese", "chinese", "aga", or "new_zealand")
+    // Sets the Ruleset the current game is scored and played under
+    // Returns an empty response unless an error occurs
+    fn kgs_rules(&mut self, args: &[&str]) -> GtpResponse {
+        match Ruleset::from_name(args[0]) {
+            Some(ruleset) => {
+                self.set_ruleset(ruleset, None);
+                GtpResponse::SUCCESS(String::new())
+            }
+            None => GtpResponse::ERROR(format!("Unsupported rules given to kgs-rules: {}", args[0])),
+        }
+    }
+
+    // args[0] = rules name (ex. "chinese", "japanese", or "tromp-taylor"), args[1] = optional
+    // ko rule ("simple" or "positional")
+    // Sets the Ruleset the current game is scored and played under and the komi usual for it,
+    // with the given ko rule in place of the ruleset's
+    // Returns an empty response unless an error occurs
+    fn set_rules(&mut self, args: &[&str]) -> GtpResponse {
+        let ruleset = match Ruleset::from_name(args[0]) {
+            Some(ruleset) => ruleset,
+            None => return GtpResponse::ERROR(format!("Unsupported rules given to set_rules: {}", args[0])),
+        };
+        let ko_rule = match args.get(1).map(|arg| KoRule::from_name(arg)) {
+            None => None,
+            Some(Some(ko_rule)) => Some(ko_rule),
+            Some(None) => return GtpResponse::ERROR(format!("Unsupported ko rule given to set_rules: {}", args[1])),
+        };
+
+        self.set_ruleset(ruleset, ko_rule);
+        self.board.komi = ruleset.default_komi();
+        self.record.komi = self.board.komi;
+        GtpResponse::SUCCESS(String::new())
+    }
+
+    // Returns the rules of the current game as a JSON object of the ruleset, its ko rule,
+    // scoring, and whether it allows suicide, and the komi
+    fn get_rules(&self) -> GtpResponse {
+        let scoring = match self.ruleset.scoring() {
+            ScoringRules::Area => "area",
+            ScoringRules::Territory => "territory",
+        };
+        GtpResponse::SUCCESS(format!(
+            "{{\"rules\":\"{}\",\"ko\":\"{}\",\"scoring\":\"{scoring}\",\"suicide\":{},\"komi\":{}}}",
+            self.ruleset,
+            self.ko_rule.unwrap_or(self.ruleset.ko_rule()),
+            self.ruleset.allows_suicide(),
+            self.board.komi
+        ))
+    }
+
+    // Sets the Ruleset and the ko rule in place of its own, if any, of the game and its board
+    fn set_ruleset(&mut self, ruleset: Ruleset, ko_rule: Option<KoRule>) {
+        self.ruleset = ruleset;
+        self.ko_rule = ko_rule;
+        self.board.ruleset = ruleset;
+        self.board.ko_rule = ko_rule;
+    }
+
+    // args[0] = time system ("none", "absolute", "byoyomi", or "canadian"), args[1..] = main
+    // time, then the period time and the number of periods or of moves per period, in seconds
+    // Sets the time limits of the game, including the Japanese byo-yomi time_settings cannot give
+    // Returns an empty response unless an error occurs
+    fn kgs_time_settings(&mut self, args: &[&str]) -> GtpResponse {
+        match TimeSettings::from_kgs(args) {
+            Ok(settings) => {
+                self.clock.set_settings(settings);
+                GtpResponse::SUCCESS(String::new())
+            }
+            Err(error) => GtpResponse::ERROR(error),
+        }
+    }
+
+    // args[0] = path of an SGF file, args[1] = optional move number
+    // Loads the game of the SGF file, replaying its setup stones and moves up to the position
+    // before the given move number, or to the end of the game if none is given
+    // Returns an empty response unless an error occurs
+    fn loadsgf(&mut self, args: &[&str]) -> GtpResponse {
+        let mut record = match read_sgf_file(Path::new(args[0])) {
+            Ok(record) => record,
+            Err(error) => return GtpResponse::ERROR(format!("cannot load file: {error}")),
+        };
+        let moves_played = match args.get(1).map(|arg| arg.parse::<usize>()) {
+            None => record.nodes.len(),
+            Some(Ok(move_number)) if move_number > 0 => (move_number - 1).min(record.nodes.len()),
+            Some(_) => return GtpResponse::ERROR(format!("Invalid move number given to loadsgf: {}", args[1])),
+        };
+        record.nodes.truncate(moves_played);
+
+        match self.load_record(record) {
+            Ok(()) => {
+                tracing::info!(file = args[0], moves_played, "Loaded SGF");
+                GtpResponse::SUCCESS(String::new())
+            }
+            Err(message) => GtpResponse::ERROR(format!("cannot load file: {message}")),
+        }
+    }
+
+    // args[0] = optional path of a file to write to
+    // Returns a successful GtpResponse containing the game so far as an SGF record, or writes
+    // the record to the given file and returns an empty response
+    fn printsgf(&self, args: &[&str]) -> GtpResponse {
+        let sgf = self.record.to_string();
+        match args.first().filter(|path| !path.is_empty()) {
+            None => GtpResponse::SUCCESS(sgf),
+            Some(path) => match fs::write(path, sgf) {
+                Ok(()) => GtpResponse::SUCCESS(String::new()),
+                Err(error) => GtpResponse::ERROR(format!("Could not write {path}: {error}")),
+            },
+        }
+    }
+}
@@ -0,0 +1,45 @@
+#![doc(hidden)]
+// Enum variants are named in screaming case, ex. GtpResponse::SUCCESS
+#![allow(clippy::upper_case_acronyms)]
+
+mod gtp;
+mod tests;
+mod controller;
+mod matches;
+mod regress;
+mod clock;
+mod tcp;
+
+use engine::*;
+pub use gtp::*;
+use controller::*;
+use matches::*;
+use regress::*;
+pub use clock::*;
+use tcp::*;
+/****************************************************\
+|****************        MAIN        ****************|
+\****************************************************/
+
+// Library function of the GTP run modes, controls the runmode of the program as start_go_agent does for the others
+// Returns an AgentError for a missing or unknown mode, invalid arguments, or a session whose input or output failed
+pub fn start_gtp_agent(args: Vec<String>) -> Result<(), AgentError> {
+    init_logging();
+    let mode = args.get(1).ok_or(AgentError::MissingMode)?;
+    let mode_args = &args[2..];
+
+    if mode.eq_ignore_ascii_case("gtp") {
+        tracing::info!("Starting GTP listener on stdin");
+        gtp::GTP::new().start_listener()?;
+    } else if mode.eq_ignore_ascii_case("tcp") {
+        run_tcp(TcpSettings::from_args(mode_args).map_err(AgentError::InvalidArguments)?)?;
+    } else if mode.eq_ignore_ascii_case("match") {
+        run_match(MatchSettings::from_args(mode_args).map_err(AgentError::InvalidArguments)?)?;
+    } else if mode.eq_ignore_ascii_case("regress") {
+        run_regress(RegressSettings::from_args(mode_args).map_err(AgentError::InvalidArguments)?)?;
+    } else {
+        return Err(AgentError::UnknownMode(mode.clone()));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,457 @@
+use super::*;
+use std::fs;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+/******************************************************\
+|****************      CONSTANTS       ****************|
+\******************************************************/
+
+// Number of times a crashed external engine is restarted within a single game
+const MAX_RESTARTS: u16 = 2;
+
+// Name of the results database kept in the match directory
+const RESULTS_FILE: &str = "results.csv";
+
+// Columns of the results database
+const RESULTS_HEADER: &str = "timestamp,game,engine_color,opponent,opening,result,winner,moves,restarts";
+
+/****************************************************\
+|****************    GLOBAL TYPES    ****************|
+\****************************************************/
+
+// Settings of a match against an external engine, parsed from the command line
+pub(crate) struct MatchSettings {
+    opponent: Vec<String>,
+    games: u16,
+    size: BoardSize,
+    komi: f64,
+    playouts: u16,
+    book: Option<PathBuf>,
+    directory: PathBuf,
+}
+
+/*****************************************************\
+|****************    PRIVATE TYPES    ****************|
+\*****************************************************/
+
+// A match in progress against an external engine
+struct MatchRunner<'a> {
+    settings: &'a MatchSettings,
+    controller: GtpController,
+}
+
+// The outcome of a single game of a match
+struct MatchGame {
+    engine_color: Color,
+    moves: Vec<(Color, Move)>,
+    result: String,
+    winner: Option<Color>,
+    restarts: u16,
+}
+
+// The tally of a match, or of every recorded game against an opponent
+#[derive(Default)]
+struct MatchTally {
+    wins: u32,
+    losses: u32,
+    voids: u32,
+}
+
+/*****************************************************\
+|****************        SETUP        ****************|
+\*****************************************************/
+
+impl MatchSettings {
+    // Parses match settings from the arguments following the match run mode
+    // Accepts "<engine command> --games N --size N --komi F --playouts N --book <file> --output <directory>",
+    // where the engine command is a single argument (ex. "gnugo --mode gtp") and the options are optional
+    pub(crate) fn from_args(args: &[String]) -> Result<MatchSettings, String> {
+        let mut settings = MatchSettings {
+            opponent: vec![],
+            games: 10,
+            size: BoardSize::Nineteen,
+            komi: 7.5,
+            playouts: 30,
+            book: None,
+            directory: PathBuf::from("matches"),
+        };
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if arg.starts_with("--") {
+                let value = args
+                    .next()
+                    .ok_or(format!("No value given to match option {arg}"))?;
+                settings.set_option(arg, value)?;
+            } else if settings.opponent.is_empty() {
+                settings.opponent = arg.split_whitespace().map(str::to_string).collect();
+            } else {
+                return Err(format!("Unexpected match argument given: {arg}"));
+            }
+        }
+
+        if settings.opponent.is_empty() {
+            return Err("No external engine command given to match".to_string());
+        }
+
+        Ok(settings)
+    }
+
+    // Sets the match option of the given name to the given value
+    fn set_option(&mut self, option: &str, value: &str) -> Result<(), String> {
+        let invalid = || format!("Invalid value given to match option {option}: {value}");
+        match option {
+            "--games" => self.games = value.parse().map_err(|_| invalid())?,
+            "--size" => {
+                self.size = value
+                    .parse::<u16>()
+                    .ok()
+                    .and_then(BoardSize::from_u16)
+                    .ok_or_else(invalid)?;
+            }
+            "--komi" => self.komi = value.parse().map_err(|_| invalid())?,
+            "--playouts" => self.playouts = value.parse().map_err(|_| invalid())?,
+            "--book" => self.book = Some(PathBuf::from(value)),
+            "--output" => self.directory = PathBuf::from(value),
+            _ => return Err(format!("Unknown match option given: {option}")),
+        }
+
+        Ok(())
+    }
+}
+
+/****************************************************\
+|****************       HELPER       ****************|
+\****************************************************/
+
+// Parses an opening book, with one opening of whitespace separated vertices per line
+// (ex. "Q16 D4 Q3"), ignoring empty lines and lines starting with '#'
+pub(crate) fn parse_opening_book(book: &str) -> Result<Vec<Vec<String>>, String> {
+    book.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            line.split_whitespace()
+                .map(|vertex| match vertex.parse::<Position>() {
+                    Ok(pos) => Ok(pos.to_string()),
+                    Err(_) => Err(format!("Invalid vertex in opening book: {vertex}")),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// The name of the given Color in the results database
+fn color_name(color: Color) -> &'static str {
+    match color {
+        Color::Black => "black",
+        Color::White => "white",
+    }
+}
+
+impl MatchGame {
+    // Converts this game to an SGF record between the engine and the named opponent
+    fn to_sgf(&self, settings: &MatchSettings, opponent: &str) -> SgfRecord {
+        let mut record = SgfRecord::new(settings.size, settings.komi);
+        let (engine, opponent) = ("Go_Engine".to_string(), opponent.to_string());
+        (record.black_player, record.white_player) = match self.engine_color {
+            Color::Black => (Some(engine), Some(opponent)),
+            Color::White => (Some(opponent), Some(engine)),
+        };
+        record.result = Some(self.result.clone());
+        for (color, mov) in &self.moves {
+            record.nodes.push(SgfNode::new(*color, *mov));
+        }
+
+        record
+    }
+}
+
+impl MatchTally {
+    // Counts the given game from the engine's perspective
+    fn record(&mut self, engine_color: Color, winner: Option<Color>) {
+        match winner {
+            Some(winner) if winner == engine_color => self.wins += 1,
+            Some(_) => self.losses += 1,
+            None => self.voids += 1,
+        }
+    }
+}
+
+/*****************************************************\
+|****************      MATCH PLAY      ****************|
+\*****************************************************/
+
+impl MatchRunner<'_> {
+    // Sends the given command to the opponent, restarting it and replaying the given moves
+    // if it crashed, at most MAX_RESTARTS times per game
+    fn send(
+        &mut self,
+        command: &str,
+        history: &[(Color, Move)],
+        restarts: &mut u16,
+    ) -> Result<String, ControllerError> {
+        loop {
+            match self.controller.send(command) {
+                Err(ControllerError::IO(error)) if *restarts < MAX_RESTARTS => {
+                    tracing::warn!(%error, "External engine crashed, restarting it");
+                    *restarts += 1;
+                    self.controller.restart().map_err(ControllerError::IO)?;
+                    self.replay(history)?;
+                }
+                response => return response,
+            }
+        }
+    }
+
+    // Sets up a new game on the opponent and plays the given moves on it
+    fn replay(&mut self, history: &[(Color, Move)]) -> Result<(), ControllerError> {
+        self.controller.send(&format!("boardsize {}", self.settings.size.to_u16()))?;
+        self.controller.send("clear_board")?;
+        self.controller.send(&format!("komi {}", self.settings.komi))?;
+        for (color, mov) in history {
+            self.controller
+                .send(&format!("play {} {}", color_to_gtp(*color), move_to_vertex(mov)))?;
+        }
+
+        Ok(())
+    }
+
+    // Plays a single game against the opponent, starting with the given opening
+    // Only fails if the opponent could not be restarted after crashing
+    fn play_game(&mut self, engine_color: Color, opening: &[String]) -> io::Result<MatchGame> {
+        let mut board = Board::new(self.settings.size);
+        board.komi = self.settings.komi;
+        let mut moves: Vec<(Color, Move)> = vec![];
+        let mut restarts = 0;
+        let mut color = Color::Black;
+        let mut consecutive_passes = 0;
+        let mut in_book = true;
+        let max_moves = self.settings.size.to_u16() as usize * self.settings.size.to_u16() as usize * 2;
+
+        let void = |moves, restarts, reason: &str| -> io::Result<MatchGame> {
+            tracing::warn!(reason, "Game voided");
+            Ok(MatchGame { engine_color, moves, result: "Void".to_string(), winner: None, restarts })
+        };
+        let resignation = |moves, restarts, loser: Color| -> io::Result<MatchGame> {
+            let winner = loser.opposite_color();
+            Ok(MatchGame {
+                engine_color,
+                moves,
+                result: format!("{}+R", color_to_gtp(winner)),
+                winner: Some(winner),
+                restarts,
+            })
+        };
+
+        let setup = [
+            format!("boardsize {}", self.settings.size.to_u16()),
+            "clear_board".to_string(),
+            format!("komi {}", self.settings.komi),
+        ];
+        for command in setup {
+            match self.send(&command, &[], &mut restarts) {
+                Ok(_) => {}
+                Err(ControllerError::IO(error)) => return Err(error),
+                Err(ControllerError::REJECTED(reason)) => {
+                    return void(moves, restarts, &format!("setup rejected: {reason}"))
+                }
+            }
+        }
+
+        while consecutive_passes < 2 && moves.len() < max_moves {
+            let book_move = opening
+                .get(moves.len())
+                .filter(|_| in_book)
+                .and_then(|vertex| vertex_to_move(vertex, color));
+
+            let mov = if let Some(mov) = book_move {
+                mov
+            } else if color == engine_color {
+                generate_move(&board, color, self.settings.playouts)
+            } else {
+                let command = format!("genmove {}", color_to_gtp(color));
+                match self.send(&command, &moves, &mut restarts) {
+                    Ok(vertex) => match vertex_to_move(&vertex, color) {
+                        Some(mov) => mov,
+                        None => return void(moves, restarts, "unreadable opponent move"),
+                    },
+                    Err(ControllerError::IO(error)) => return Err(error),
+                    Err(ControllerError::REJECTED(reason)) => {
+                        return void(moves, restarts, &format!("genmove rejected: {reason}"))
+                    }
+                }
+            };
+
+            if mov == Move::RESIGN {
+                return resignation(moves, restarts, color);
+            }
+            if !board.play(mov) {
+                if book_move.is_some() {
+                    tracing::warn!(move_number = moves.len() + 1, "Illegal opening book move, leaving the book");
+                    in_book = false;
+                    continue;
+                }
+                if color == engine_color {
+                    return void(moves, restarts, "engine generated an illegal move");
+                }
+                // an illegal move by the opponent forfeits the game
+                let winner = color.opposite_color();
+                return Ok(MatchGame {
+                    engine_color,
+                    moves,
+                    result: format!("{}+F", color_to_gtp(winner)),
+                    winner: Some(winner),
+                    restarts,
+                });
+            }
+
+            // the opponent already knows its own generated moves
+            if color == engine_color || book_move.is_some() {
+                let command = format!("play {} {}", color_to_gtp(color), move_to_vertex(&mov));
+                match self.send(&command, &moves, &mut restarts) {
+                    Ok(_) => {}
+                    Err(ControllerError::IO(error)) => return Err(error),
+                    Err(ControllerError::REJECTED(reason)) => {
+                        return void(moves, restarts, &format!("opponent rejected a move: {reason}"))
+                    }
+                }
+            }
+
+            consecutive_passes = if mov == Move::PASS { consecutive_passes + 1 } else { 0 };
+            moves.push((color, mov));
+            color = color.opposite_color();
+        }
+
+        let score = board.final_score(ScoringRules::Area);
+        Ok(MatchGame {
+            engine_color,
+            moves,
+            result: result_string(score),
+            winner: (score != 0.0).then_some(if score > 0.0 { Color::Black } else { Color::White }),
+            restarts,
+        })
+    }
+}
+
+/*****************************************************\
+|****************       RESULTS       ****************|
+\*****************************************************/
+
+// Appends the given game to the results database in the given directory
+fn record_result(
+    settings: &MatchSettings,
+    game_number: u16,
+    opponent: &str,
+    opening: &[String],
+    game: &MatchGame,
+) -> io::Result<()> {
+    let path = settings.directory.join(RESULTS_FILE);
+    let is_new = !path.exists();
+    let mut database = OpenOptions::new().create(true).append(true).open(&path)?;
+    if is_new {
+        writeln!(database, "{RESULTS_HEADER}")?;
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    writeln!(
+        database,
+        "{timestamp},{game_number},{},{},{},{},{},{},{}",
+        color_name(game.engine_color),
+        opponent.replace(',', " "),
+        opening.join(" "),
+        game.result,
+        game.winner.map_or("none", color_name),
+        game.moves.len(),
+        game.restarts
+    )
+}
+
+// Tallies every game against the given opponent recorded in the results database
+fn historical_tally(settings: &MatchSettings, opponent: &str) -> io::Result<MatchTally> {
+    let mut tally = MatchTally::default();
+    let database = fs::read_to_string(settings.directory.join(RESULTS_FILE))?;
+    let opponent = opponent.replace(',', " ");
+
+    for row in database.lines().skip(1) {
+        let columns: Vec<&str> = row.split(',').collect();
+        if columns.len() < 7 || columns[3] != opponent {
+            continue;
+        }
+        let engine_color = if columns[2] == "black" { Color::Black } else { Color::White };
+        let winner = match columns[6] {
+            "black" => Some(Color::Black),
+            "white" => Some(Color::White),
+            _ => None,
+        };
+        tally.record(engine_color, winner);
+    }
+
+    Ok(tally)
+}
+
+/********************************************************\
+|****************     PUBLIC METHODS     ****************|
+\********************************************************/
+
+// Plays the match given by the settings against the external engine, alternating colors and
+// cycling through the opening book, writing an SGF and GTP log of every game and appending the
+// results to the results database in the settings directory
+pub(crate) fn run_match(settings: MatchSettings) -> io::Result<()> {
+    let openings = match &settings.book {
+        Some(book) => parse_opening_book(&fs::read_to_string(book)?)
+            .map_err(|message| io::Error::new(io::ErrorKind::InvalidData, message))?,
+        None => vec![],
+    };
+    fs::create_dir_all(&settings.directory)?;
+
+    let mut runner = MatchRunner {
+        settings: &settings,
+        controller: GtpController::start(&settings.opponent)?,
+    };
+    let opponent = match runner.controller.send("name") {
+        Ok(name) if !name.is_empty() => name,
+        _ => settings.opponent[0].clone(),
+    };
+
+    let mut tally = MatchTally::default();
+    for game_number in 1..=settings.games {
+        let engine_color = if game_number % 2 == 1 { Color::Black } else { Color::White };
+        let opening: &[String] = match openings.is_empty() {
+            true => &[],
+            false => &openings[(game_number as usize - 1) / 2 % openings.len()], // each opening is played with both colors
+        };
+
+        let log = File::create(settings.directory.join(format!("game_{game_number:04}.log")))?;
+        runner.controller.set_log(Some(log));
+        let game = runner.play_game(engine_color, opening)?;
+        runner.controller.set_log(None);
+
+        let path = settings.directory.join(format!("game_{game_number:04}.sgf"));
+        fs::write(&path, game.to_sgf(&settings, &opponent).to_string())?;
+        record_result(&settings, game_number, &opponent, opening, &game)?;
+        tally.record(game.engine_color, game.winner);
+
+        tracing::info!(game_number, result = %game.result, "Match game finished");
+        println!(
+            "Game {game_number}: engine as {} vs {opponent}, {} in {} moves ({})",
+            color_name(engine_color),
+            game.result,
+            game.moves.len(),
+            path.display()
+        );
+    }
+
+    let history = historical_tally(&settings, &opponent)?;
+    println!(
+        "\nThis match: {} wins, {} losses, {} void\nAll recorded games against {opponent}: {} wins, {} losses, {} void",
+        tally.wins, tally.losses, tally.voids, history.wins, history.losses, history.voids
+    );
+
+    Ok(())
+}
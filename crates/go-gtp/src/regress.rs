@@ -0,0 +1,315 @@
+use super::*;
+use std::fs;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+/******************************************************\
+|****************      CONSTANTS       ****************|
+\******************************************************/
+
+// Columns of the regression history file
+const HISTORY_HEADER: &str = "timestamp,file,tests,passed,failed,unexpected_passes,unexpected_failures";
+
+/****************************************************\
+|****************    GLOBAL TYPES    ****************|
+\****************************************************/
+
+// Settings of a regression run over GTP test files, parsed from the command line
+pub(crate) struct RegressSettings {
+    files: Vec<PathBuf>,
+    engine: Vec<String>,
+    history: Option<PathBuf>,
+}
+
+// A single command of a regression test file, ex. "12 reg_genmove black" followed by "#? [Q16|R17]"
+#[derive(Debug, PartialEq)]
+pub(crate) struct TstEntry {
+    pub(crate) id: Option<u32>,
+    pub(crate) command: String,
+    pub(crate) expected: Option<Expectation>,
+}
+
+// The expected answer of a regression test
+#[derive(Debug, PartialEq)]
+pub(crate) struct Expectation {
+    pub(crate) alternatives: Vec<String>,
+    pub(crate) negated: bool,
+    pub(crate) known_failure: bool,
+}
+
+// Outcomes of a single regression test, following the GNU Go regression conventions
+#[derive(Debug, PartialEq)]
+pub(crate) enum TestOutcome {
+    PASSED,
+    FAILED,
+    // a test marked as a known failure with '*' now passes, an "unexpected PASS"
+    FIXED,
+    // a test not marked as a known failure fails, an "unexpected FAIL"
+    BROKEN,
+}
+
+/*****************************************************\
+|****************    PRIVATE TYPES    ****************|
+\*****************************************************/
+
+// The tally of the tests of a single regression file
+#[derive(Default)]
+struct RegressTally {
+    passed: u32,
+    failed: u32,
+    unexpected_passes: u32,
+    unexpected_failures: u32,
+}
+
+/*****************************************************\
+|****************        SETUP        ****************|
+\*****************************************************/
+
+impl RegressSettings {
+    // Parses regression settings from the arguments following the regress run mode
+    // Accepts "<file.tst>... --engine "<engine command>" --history <file.csv>", where the options
+    // are optional and the engine defaults to this program's own GTP mode
+    pub(crate) fn from_args(args: &[String]) -> Result<RegressSettings, String> {
+        let mut settings = RegressSettings {
+            files: vec![],
+            engine: vec![],
+            history: None,
+        };
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--engine" => {
+                    let value = args.next().ok_or("No value given to regress option --engine")?;
+                    settings.engine = value.split_whitespace().map(str::to_string).collect();
+                }
+                "--history" => {
+                    let value = args.next().ok_or("No value given to regress option --history")?;
+                    settings.history = Some(PathBuf::from(value));
+                }
+                _ => settings.files.push(PathBuf::from(arg)),
+            }
+        }
+
+        if settings.files.is_empty() {
+            return Err("No regression test files given to regress".to_string());
+        }
+        if settings.engine.is_empty() {
+            let program = std::env::current_exe()
+                .map_err(|error| format!("Could not locate this program to test it: {error}"))?;
+            settings.engine = vec![program.to_string_lossy().to_string(), "gtp".to_string()];
+        }
+
+        Ok(settings)
+    }
+}
+
+/*****************************************************\
+|****************       PARSING       ****************|
+\*****************************************************/
+
+// Parses the expected answer of a "#?" line, ex. "#? [!Q16|R17]*"
+fn parse_expectation(line: &str) -> Result<Expectation, String> {
+    let pattern = line.trim_start_matches("#?").trim();
+    let known_failure = pattern.ends_with('*');
+    let pattern = pattern.trim_end_matches('*').trim();
+    let inner = pattern
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .ok_or(format!("Malformed expected result: {line}"))?;
+
+    let negated = inner.starts_with('!');
+    Ok(Expectation {
+        alternatives: inner
+            .trim_start_matches('!')
+            .split('|')
+            .map(|alternative| alternative.trim().to_string())
+            .collect(),
+        negated,
+        known_failure,
+    })
+}
+
+// Parses the given regression test file into its commands
+// Expected answers attach to the numbered command before them, other comments are ignored
+pub(crate) fn parse_tst(tst: &str) -> Result<Vec<TstEntry>, String> {
+    let mut entries: Vec<TstEntry> = vec![];
+
+    for line in tst.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        if line.starts_with("#?") {
+            let entry = entries
+                .last_mut()
+                .filter(|entry| entry.id.is_some())
+                .ok_or(format!("Expected result without a numbered command: {line}"))?;
+            entry.expected = Some(parse_expectation(line)?);
+        } else if !line.starts_with('#') {
+            let (id, command) = match line.split_once(char::is_whitespace) {
+                Some((first, rest)) if first.parse::<u32>().is_ok() => {
+                    (first.parse().ok(), rest.trim().to_string())
+                }
+                _ => (None, line.to_string()),
+            };
+            entries.push(TstEntry { id, command, expected: None });
+        }
+    }
+
+    Ok(entries)
+}
+
+// Resolves the file argument of a loadsgf command against the directory of the test file,
+// as regression suites name their games relative to themselves
+fn resolve_paths(command: &str, directory: &Path) -> String {
+    let mut arguments = command.split_whitespace();
+    match (arguments.next(), arguments.next()) {
+        (Some("loadsgf"), Some(file)) if Path::new(file).is_relative() => {
+            let rest: Vec<&str> = arguments.collect();
+            let path = directory.join(file);
+            format!("loadsgf {} {}", path.display(), rest.join(" ")).trim_end().to_string()
+        }
+        _ => command.to_string(),
+    }
+}
+
+/*****************************************************\
+|****************       TESTING       ****************|
+\*****************************************************/
+
+impl Expectation {
+    // Checks whether the given engine response satisfies this expectation
+    pub(crate) fn matches(&self, response: &str) -> bool {
+        let found = self
+            .alternatives
+            .iter()
+            .any(|alternative| alternative.eq_ignore_ascii_case(response.trim()));
+        found != self.negated
+    }
+
+    // Classifies the given engine response against this expectation
+    pub(crate) fn outcome(&self, response: &str) -> TestOutcome {
+        match (self.matches(response), self.known_failure) {
+            (true, false) => TestOutcome::PASSED,
+            (true, true) => TestOutcome::FIXED,
+            (false, false) => TestOutcome::BROKEN,
+            (false, true) => TestOutcome::FAILED,
+        }
+    }
+}
+
+impl RegressTally {
+    // Counts the given outcome
+    fn record(&mut self, outcome: &TestOutcome) {
+        match outcome {
+            TestOutcome::PASSED => self.passed += 1,
+            TestOutcome::FAILED => self.failed += 1,
+            TestOutcome::FIXED => self.unexpected_passes += 1,
+            TestOutcome::BROKEN => self.unexpected_failures += 1,
+        }
+    }
+
+    // Total number of tests counted
+    fn tests(&self) -> u32 {
+        self.passed + self.failed + self.unexpected_passes + self.unexpected_failures
+    }
+}
+
+// Runs every command of the given regression test file against the engine, printing the
+// tests with unexpected outcomes
+fn run_file(controller: &mut GtpController, path: &Path) -> io::Result<RegressTally> {
+    let entries = parse_tst(&fs::read_to_string(path)?)
+        .map_err(|message| io::Error::new(io::ErrorKind::InvalidData, message))?;
+    let directory = path.parent().unwrap_or(Path::new("."));
+    let mut tally = RegressTally::default();
+
+    for entry in entries {
+        let command = resolve_paths(&entry.command, directory);
+        let response = match controller.send(&command) {
+            Ok(response) => response,
+            Err(ControllerError::REJECTED(reason)) => format!("? {reason}"),
+            Err(ControllerError::IO(error)) => {
+                tracing::warn!(%error, "Engine crashed during regression, restarting it");
+                controller.restart()?;
+                format!("? {error}")
+            }
+        };
+
+        let (Some(id), Some(expected)) = (entry.id, &entry.expected) else {
+            continue;
+        };
+        let outcome = expected.outcome(&response);
+        if matches!(outcome, TestOutcome::FIXED | TestOutcome::BROKEN) {
+            println!(
+                "{:>6} {}: expected {}[{}], got '{}'",
+                id,
+                match outcome {
+                    TestOutcome::FIXED => "unexpected PASS",
+                    _ => "unexpected FAIL",
+                },
+                if expected.negated { "!" } else { "" },
+                expected.alternatives.join("|"),
+                response
+            );
+        }
+        tally.record(&outcome);
+    }
+
+    Ok(tally)
+}
+
+// Appends the tally of the given regression file to the history file
+fn record_history(history: &Path, file: &Path, tally: &RegressTally) -> io::Result<()> {
+    let is_new = !history.exists();
+    let mut history = OpenOptions::new().create(true).append(true).open(history)?;
+    if is_new {
+        writeln!(history, "{HISTORY_HEADER}")?;
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    writeln!(
+        history,
+        "{timestamp},{},{},{},{},{},{}",
+        file.display().to_string().replace(',', " "),
+        tally.tests(),
+        tally.passed,
+        tally.failed,
+        tally.unexpected_passes,
+        tally.unexpected_failures
+    )
+}
+
+/********************************************************\
+|****************     PUBLIC METHODS     ****************|
+\********************************************************/
+
+// Runs every regression test file given by the settings against the engine, printing the
+// results of each file and appending them to the history file if one is given
+pub(crate) fn run_regress(settings: RegressSettings) -> io::Result<()> {
+    let mut controller = GtpController::start(&settings.engine)?;
+
+    for file in &settings.files {
+        controller.send("clear_board").map_err(|error| match error {
+            ControllerError::IO(error) => error,
+            ControllerError::REJECTED(reason) => io::Error::other(reason),
+        })?;
+
+        println!("{}", file.display());
+        let tally = run_file(&mut controller, file)?;
+        println!(
+            "  {} tests: {} passed, {} failed, {} unexpected passes, {} unexpected failures",
+            tally.tests(),
+            tally.passed,
+            tally.failed,
+            tally.unexpected_passes,
+            tally.unexpected_failures
+        );
+
+        if let Some(history) = &settings.history {
+            record_history(history, file, &tally)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use super::*;
+use std::io::{BufRead, BufReader};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::{io, thread};
+/******************************************************\
+|****************      CONSTANTS       ****************|
+\******************************************************/
+
+// Port the GTP server listens on unless given with --port
+const DEFAULT_PORT: u16 = 6969;
+
+/****************************************************\
+|****************    GLOBAL TYPES    ****************|
+\****************************************************/
+
+// Settings of the GTP server, parsed from the command line
+pub(crate) struct TcpSettings {
+    port: u16,
+}
+
+/*****************************************************\
+|****************        SETUP        ****************|
+\*****************************************************/
+
+impl TcpSettings {
+    // Parses GTP server settings from the arguments following the tcp run mode
+    // Accepts "--port N", where the port defaults to 6969
+    pub(crate) fn from_args(args: &[String]) -> Result<TcpSettings, String> {
+        let mut settings = TcpSettings { port: DEFAULT_PORT };
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--port" => {
+                    let value = args.next().ok_or("No value given to tcp option --port")?;
+                    settings.port = value
+                        .parse()
+                        .map_err(|_| format!("Invalid value given to tcp option --port: {value}"))?;
+                }
+                _ => return Err(format!("Unknown tcp option given: {arg}")),
+            }
+        }
+
+        Ok(settings)
+    }
+}
+
+/********************************************************\
+|****************     PUBLIC METHODS     ****************|
+\********************************************************/
+
+// Serves the Go Text Protocol on the port given by the settings until the process is stopped
+pub(crate) fn run_tcp(settings: TcpSettings) -> io::Result<()> {
+    let listener = TcpListener::bind(SocketAddr::from(([0, 0, 0, 0], settings.port)))?;
+    tracing::info!("GTP server listening on {}", listener.local_addr()?);
+    serve_gtp(listener)
+}
+
+// Accepts connections on the given listener, each its own GTP session with a new game, served
+// on its own thread until the controller quits or disconnects
+pub(crate) fn serve_gtp(listener: TcpListener) -> io::Result<()> {
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(error) => {
+                tracing::warn!("Could not accept GTP connection: {error}");
+                continue;
+            }
+        };
+        thread::spawn(move || {
+            let peer = stream.peer_addr().map_or("unknown peer".to_string(), |peer| peer.to_string());
+            tracing::info!("GTP session started with {peer}");
+            if let Err(error) = serve_session(stream) {
+                tracing::warn!("GTP session with {peer} failed: {error}");
+            }
+            tracing::info!("GTP session ended with {peer}");
+        });
+    }
+    Ok(())
+}
+
+/****************************************************\
+|****************       HELPER       ****************|
+\****************************************************/
+
+// Runs a GTP session with a new game over the given connection, closing it once the session ends
+// so that the thread reading it stops too
+fn serve_session(stream: TcpStream) -> io::Result<()> {
+    let input = BufReader::new(stream.try_clone()?).lines();
+    let mut output = stream.try_clone()?;
+    let result = gtp::GTP::new().listen(input, &mut output);
+    let _ = stream.shutdown(Shutdown::Both); // the controller may have disconnected already
+    result
+}
@@ -0,0 +1,639 @@
+#[cfg(test)]
+use super::*;
+/*****************************************************\
+|****************       MATCHES       ****************|
+\*****************************************************/
+
+#[test]
+fn test_parse_gtp_response() {
+    assert_eq!(parse_gtp_response("= D4\n"), Ok("D4".to_string()));
+    assert_eq!(parse_gtp_response("=12 pass\n"), Ok("pass".to_string()));
+    assert_eq!(parse_gtp_response("=\n"), Ok(String::new()));
+    assert_eq!(parse_gtp_response("? illegal move\n"), Err("illegal move".to_string()));
+    assert!(parse_gtp_response("garbage").is_err());
+}
+
+#[test]
+fn test_parse_opening_book() {
+    let book = parse_opening_book("# comment\nq16 D4\n\n  Q4 D16 C3  \n").unwrap();
+    assert_eq!(book, vec![vec!["Q16", "D4"], vec!["Q4", "D16", "C3"]]);
+    assert!(parse_opening_book("D4 Z99").is_err());
+}
+
+/*****************************************************\
+|****************     REGRESSION      ****************|
+\*****************************************************/
+
+#[test]
+fn test_parse_tst() {
+    let tst = "# opening tests\nloadsgf games/joseki.sgf 12\n1 reg_genmove black\n#? [Q16|r17]*\n\n2 genmove white\n#? [!pass]\n";
+    let entries = parse_tst(tst).unwrap();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0].id, None);
+    assert_eq!(entries[0].command, "loadsgf games/joseki.sgf 12");
+    assert_eq!(entries[1].id, Some(1));
+    assert_eq!(entries[1].command, "reg_genmove black");
+    assert_eq!(
+        entries[1].expected,
+        Some(Expectation {
+            alternatives: vec!["Q16".to_string(), "r17".to_string()],
+            negated: false,
+            known_failure: true,
+        })
+    );
+    assert!(entries[2].expected.as_ref().unwrap().negated);
+    assert!(parse_tst("#? [A1]").is_err());
+}
+
+#[test]
+fn test_regression_outcomes() {
+    let expected = parse_tst("1 reg_genmove black\n#? [Q16|R17]").unwrap().remove(0).expected.unwrap();
+    assert_eq!(expected.outcome("r17"), TestOutcome::PASSED);
+    assert_eq!(expected.outcome("D4"), TestOutcome::BROKEN);
+
+    let negated = parse_tst("1 genmove white\n#? [!pass]*").unwrap().remove(0).expected.unwrap();
+    assert_eq!(negated.outcome("D4"), TestOutcome::FIXED);
+    assert_eq!(negated.outcome("PASS"), TestOutcome::FAILED);
+}
+
+/*****************************************************\
+|****************    KGS EXTENSIONS   ****************|
+\*****************************************************/
+
+#[test]
+fn test_kgs_commands() {
+    let mut gtp = gtp::GTP::new();
+    assert_eq!(gtp.accept_command("known_command kgs-game_over".to_string()), "= true");
+    assert_eq!(gtp.accept_command("kgs-rules new_zealand".to_string()), "= ");
+    assert!(gtp.accept_command("kgs-rules ing".to_string()).starts_with('?'));
+    assert_eq!(gtp.accept_command("kgs-game_over".to_string()), "= ");
+    gtp.accept_command("komi 7.5".to_string());
+
+    // only private chat is answered, and "score" counts the game under its rules
+    assert!(gtp.accept_command("kgs-chat game someone hello there".to_string()).starts_with('?'));
+    assert!(gtp.accept_command("kgs-chat private someone hello".to_string()).starts_with('='));
+    assert_eq!(gtp.accept_command("kgs-chat private someone score".to_string()), "= W+7.5");
+}
+
+#[test]
+fn test_set_and_get_rules() {
+    let mut gtp = gtp::GTP::new();
+    assert_eq!(
+        gtp.accept_command("get_rules".to_string()),
+        r#"= {"rules":"chinese","ko":"positional","scoring":"area","suicide":false,"komi":6.5}"#
+    );
+
+    // the komi follows the rules, and the ko rule may be changed from theirs
+    assert_eq!(gtp.accept_command("set_rules japanese".to_string()), "= ");
+    assert_eq!(
+        gtp.accept_command("get_rules".to_string()),
+        r#"= {"rules":"japanese","ko":"simple","scoring":"territory","suicide":false,"komi":6.5}"#
+    );
+    assert_eq!(gtp.accept_command("set_rules tromp-taylor simple".to_string()), "= ");
+    assert_eq!(
+        gtp.accept_command("get_rules".to_string()),
+        r#"= {"rules":"tromp-taylor","ko":"simple","scoring":"area","suicide":true,"komi":7.5}"#
+    );
+
+    assert!(gtp.accept_command("set_rules ing".to_string()).starts_with('?'));
+    assert!(gtp.accept_command("set_rules chinese situational".to_string()).starts_with('?'));
+    assert_eq!(gtp.accept_command("kgs-rules aga".to_string()), "= ");
+    assert!(gtp.accept_command("get_rules".to_string()).contains(r#""ko":"positional""#));
+}
+
+#[test]
+fn test_loadsgf_and_printsgf() {
+    let path = std::env::temp_dir().join(format!("go_engine_loadsgf_{}.sgf", std::process::id()));
+    std::fs::write(&path, "(;GM[1]SZ[9]KM[5.5]AB[cc][gg]PL[W];W[ee];B[ce];W[dg])").unwrap();
+
+    // the position before move 3 holds the setup stones and the first two moves
+    let mut gtp = gtp::GTP::new();
+    assert_eq!(gtp.accept_command(format!("loadsgf {} 3", path.display())), "= ");
+    let sgf = gtp.accept_command("printsgf".to_string());
+    assert!(sgf.contains("SZ[9]KM[5.5]AB[cc][gg]"));
+    assert!(sgf.ends_with(";W[ee];B[ce])"));
+
+    // moves played afterwards extend the record, and the whole game replaces it when reloaded
+    gtp.accept_command("play W D2".to_string());
+    assert!(gtp.accept_command("printsgf".to_string()).ends_with(";B[ce];W[dh])"));
+    assert_eq!(gtp.accept_command(format!("loadsgf {}", path.display())), "= ");
+    assert!(gtp.accept_command("printsgf".to_string()).ends_with(";B[ce];W[dg])"));
+
+    assert!(gtp.accept_command("loadsgf /nonexistent/game.sgf".to_string()).starts_with('?'));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_undo() {
+    let mut gtp = gtp::GTP::new();
+    assert!(gtp.accept_command("undo".to_string()).starts_with('?')); // nothing to take back
+    gtp.accept_command("boardsize 9".to_string());
+    for command in ["play B A2", "play W A1", "play B E5"] {
+        assert_eq!(gtp.accept_command(command.to_string()), "= ");
+    }
+    let before_capture = gtp.accept_command("showboard".to_string());
+    let sgf_before_capture = gtp.accept_command("printsgf".to_string());
+
+    // the capture and the ko it leaves are taken back with the move
+    assert_eq!(gtp.accept_command("play B B1".to_string()), "= ");
+    assert_eq!(gtp.accept_command("undo".to_string()), "= ");
+    assert_eq!(gtp.accept_command("showboard".to_string()), before_capture);
+    assert_eq!(gtp.accept_command("printsgf".to_string()), sgf_before_capture);
+    assert_eq!(gtp.accept_command("play B B1".to_string()), "= ");
+
+    for _ in 0..4 {
+        assert_eq!(gtp.accept_command("undo".to_string()), "= ");
+    }
+    assert!(gtp.accept_command("undo".to_string()).starts_with('?'));
+    assert!(gtp.accept_command("printsgf".to_string()).ends_with("KM[6.5])"));
+}
+
+#[test]
+fn test_fixed_handicap() {
+    let mut gtp = gtp::GTP::new();
+    assert_eq!(gtp.accept_command("fixed_handicap 2".to_string()), "= D4 Q16");
+    assert!(gtp.accept_command("fixed_handicap 3".to_string()).starts_with('?')); // board not empty
+
+    for (size, stones, vertices) in [
+        (19, 5, "D4 Q16 D16 Q4 K10"),
+        (19, 9, "D4 Q16 D16 Q4 D10 Q10 K4 K16 K10"),
+        (13, 6, "D4 K10 D10 K4 D7 K7"),
+        (9, 4, "C3 G7 C7 G3"),
+        (9, 7, "C3 G7 C7 G3 C5 G5 E5"),
+    ] {
+        gtp.accept_command(format!("boardsize {size}"));
+        assert_eq!(gtp.accept_command(format!("fixed_handicap {stones}")), format!("= {vertices}"));
+    }
+
+    // the stones are setup stones of the record, not moves to undo
+    assert!(gtp.accept_command("printsgf".to_string()).contains("AB[cg][gc][cc][gg][ce][ge][ee]"));
+    assert!(gtp.accept_command("undo".to_string()).starts_with('?'));
+
+    gtp.accept_command("clear_board".to_string());
+    for stones in ["1", "10", "two"] {
+        assert!(gtp.accept_command(format!("fixed_handicap {stones}")).starts_with('?'));
+    }
+}
+
+#[test]
+fn test_free_handicap() {
+    let mut gtp = gtp::GTP::new();
+    gtp.accept_command("boardsize 9".to_string());
+    assert_eq!(gtp.accept_command("place_free_handicap 3".to_string()), "= C3 G7 E5");
+    assert!(gtp.accept_command("place_free_handicap 2".to_string()).starts_with('?')); // board not empty
+    gtp.accept_command("clear_board".to_string());
+    assert!(gtp.accept_command("place_free_handicap 10".to_string()).starts_with('?'));
+
+    // past the star points, stones go on the opening points the engine plays
+    gtp.accept_command("boardsize 19".to_string());
+    let response = gtp.accept_command("place_free_handicap 12".to_string());
+    assert!(response.starts_with("= D4 Q16 D16 Q4 D10 Q10 K4 K16 K10 F17 C14 F3"));
+
+    gtp.accept_command("clear_board".to_string());
+    for vertices in ["C3", "C3 C3", "C3 Z9", "C3 D25"] {
+        assert!(gtp.accept_command(format!("set_free_handicap {vertices}")).starts_with('?'));
+    }
+    assert_eq!(gtp.accept_command("set_free_handicap C3 D5 R17".to_string()), "= ");
+    assert!(gtp.accept_command("printsgf".to_string()).contains("AB[cq][do][qc]"));
+    assert!(gtp.accept_command("play B C3".to_string()).starts_with('?')); // the stones are on the board
+}
+
+#[test]
+fn test_clock() {
+    use std::time::Duration;
+    let board = Board::new(BoardSize::Nineteen);
+    let mut clock = Clock::new();
+    assert_eq!(clock.move_budget(Color::Black, &board), None);
+    assert_eq!(TimeSettings::new(0, 1, 0), None); // overtime without stones is no time limit
+
+    // main time is shared out over half the empty points, less the safety margin
+    clock.set_settings(TimeSettings::new(300, 0, 0));
+    assert_eq!(clock.move_budget(Color::Black, &board), Some(Duration::from_nanos(1_416_666_666)));
+    clock.set_time_left(Color::White, 10, 0);
+    assert_eq!(clock.move_budget(Color::White, &board), Some(Duration::ZERO));
+
+    // with Canadian overtime, a move gets at least its share of an overtime period
+    clock.set_settings(TimeSettings::new(300, 30, 5));
+    clock.set_time_left(Color::White, 10, 0);
+    assert_eq!(clock.move_budget(Color::White, &board), Some(Duration::from_millis(4_550)));
+
+    // running out of main time starts the overtime periods, which renew after their stones
+    clock.spend(Color::Black, Duration::from_secs(301));
+    assert_eq!(clock.move_budget(Color::Black, &board), Some(Duration::from_millis(5_550)));
+    for _ in 0..3 {
+        clock.spend(Color::Black, Duration::from_secs(1));
+    }
+    assert_eq!(clock.move_budget(Color::Black, &board), Some(Duration::from_millis(20_550)));
+    clock.spend(Color::Black, Duration::from_secs(1));
+    assert_eq!(clock.move_budget(Color::Black, &board), Some(Duration::from_millis(4_550)));
+
+    // under Japanese byo-yomi a move may take most of a period, which only a slower move uses up
+    let settings = TimeSettings::from_kgs(&["byoyomi", "60", "10", "3"]).unwrap();
+    clock.set_settings(settings);
+    clock.spend(Color::Black, Duration::from_secs(65));
+    assert_eq!(clock.move_budget(Color::Black, &board), Some(Duration::from_millis(7_750)));
+    clock.spend(Color::Black, Duration::from_secs(9));
+    clock.spend(Color::Black, Duration::from_secs(11));
+    assert_eq!(clock.move_budget(Color::Black, &board), Some(Duration::from_millis(7_750)));
+    clock.set_time_left(Color::White, 4, 1);
+    assert_eq!(clock.move_budget(Color::White, &board), Some(Duration::from_millis(2_950)));
+
+    assert_eq!(TimeSettings::from_kgs(&["none"]), Ok(None));
+    assert_eq!(TimeSettings::from_kgs(&["absolute", "600"]), Ok(TimeSettings::new(600, 0, 0)));
+    assert_eq!(TimeSettings::from_kgs(&["canadian", "600", "300", "25"]), Ok(TimeSettings::new(600, 300, 25)));
+    for args in [&["byoyomi", "60", "10"][..], &["canadian", "60", "x", "5"], &["hourglass", "60"]] {
+        assert!(TimeSettings::from_kgs(args).is_err());
+    }
+
+    let mut gtp = gtp::GTP::new();
+    for command in ["time_settings 10", "time_settings 10 0 x", "time_left B 10", "time_left X 10 0"] {
+        assert!(gtp.accept_command(command.to_string()).starts_with('?'));
+    }
+    assert_eq!(gtp.accept_command("boardsize 9".to_string()), "= ");
+    assert_eq!(gtp.accept_command("kgs-time_settings byoyomi 0 1 5".to_string()), "= ");
+    assert_eq!(gtp.accept_command("time_settings 1 0 0".to_string()), "= ");
+    assert_eq!(gtp.accept_command("time_left B 1 0".to_string()), "= ");
+    assert!(gtp.accept_command("genmove B".to_string()).starts_with("= "));
+}
+
+#[test]
+fn test_command_ids() {
+    let mut gtp = gtp::GTP::new();
+    assert_eq!(gtp.accept_command("1 name".to_string()), "=1 TBD");
+    assert_eq!(gtp.accept_command("name".to_string()), "= TBD");
+    assert_eq!(gtp.accept_command("2 boardsize 9".to_string()), "=2 ");
+    assert_eq!(gtp.accept_command("3 boardsize 4".to_string()), "?3 Invalid size given to boardsize: 4");
+    assert_eq!(gtp.accept_command("42 nonsense".to_string()), "?42 Unsupported command");
+    assert_eq!(gtp.accept_command("7".to_string()), "?7 Unsupported command");
+    assert_eq!(gtp.accept_command("5 quit".to_string()), "quit");
+}
+
+#[test]
+fn test_command_registry() {
+    // list_commands and known_command agree on every command
+    let mut gtp = gtp::GTP::new();
+    let listed = gtp.accept_command("list_commands".to_string());
+    let names: Vec<&str> = listed.trim_start_matches("= ").lines().collect();
+    assert!(names.contains(&"genmove") && names.contains(&"kgs-time_settings"));
+    for name in &names {
+        assert_eq!(gtp.accept_command(format!("known_command {name}")), "= true");
+    }
+    assert_eq!(gtp.accept_command("known_command resign".to_string()), "= false");
+
+    // arguments are checked against the signature a command is registered with
+    assert_eq!(
+        gtp.accept_command("play B".to_string()),
+        "? Not enough arguments given to play, which takes: color vertex"
+    );
+    assert!(gtp.accept_command("known_command".to_string()).starts_with('?'));
+    assert_eq!(gtp.accept_command("printsgf".to_string()).get(..4), Some("= (;"));
+    assert_eq!(gtp.accept_command("set_free_handicap".to_string()), "? bad vertex list");
+}
+
+#[test]
+fn test_gtp_preprocessing() {
+    let mut gtp = gtp::GTP::new();
+    assert_eq!(gtp.accept_command("name # what are you called?".to_string()), "= TBD");
+    assert_eq!(gtp.accept_command("1\tboardsize\t9\r\n".to_string()), "=1 ");
+    assert_eq!(gtp.accept_command("play\x07 B  E5".to_string()), "= ");
+    assert_eq!(gtp.accept_command("# a whole line of comment".to_string()), "");
+    assert_eq!(gtp.accept_command(" \t\r\n".to_string()), "");
+    assert_eq!(gtp.accept_command("play B E5".to_string()), "? invalid move");
+}
+
+#[test]
+fn test_genmove_resigns() {
+    // after 101 moves on points that never touch, White is hopelessly behind on komi
+    let mut gtp = gtp::GTP::new();
+    gtp.accept_command("komi -100".to_string());
+    let points = (0..19u16).flat_map(|y| (0..19u16).map(move |x| (x, y))).filter(|(x, y)| (x + y) % 2 == 0);
+    for (number, (x, y)) in points.take(101).enumerate() {
+        let color = if number % 2 == 0 { "B" } else { "W" };
+        let vertex = Intersection::new(ColumnIdentifier::from_u16(x).unwrap(), y + 1).to_string();
+        assert_eq!(gtp.accept_command(format!("play {color} {vertex}")), "= ");
+    }
+
+    // reg_genmove only reports the move, leaving the game going
+    let before = gtp.accept_command("printsgf".to_string());
+    assert_eq!(gtp.accept_command("reg_genmove W".to_string()), "= resign");
+    assert_eq!(gtp.accept_command("printsgf".to_string()), before);
+
+    assert_eq!(gtp.accept_command("genmove W".to_string()), "= resign");
+    assert_eq!(gtp.accept_command("final_score".to_string()), "= B+R");
+    assert_eq!(gtp.accept_command("genmove W".to_string()), "? game is over");
+    assert_eq!(gtp.accept_command("genmove B".to_string()), "? game is over");
+    assert!(gtp.accept_command("printsgf".to_string()).contains("RE[B+R]"));
+
+    // a new game is not over once the board is cleared
+    gtp.accept_command("clear_board".to_string());
+    assert!(!gtp.accept_command("printsgf".to_string()).contains("RE["));
+}
+
+#[test]
+fn test_side_to_move() {
+    // either Color may move at any time for analysis
+    let mut gtp = gtp::GTP::new();
+    gtp.accept_command("boardsize 9".to_string());
+    assert_eq!(gtp.accept_command("play B E5".to_string()), "= ");
+    assert_eq!(gtp.accept_command("play B C3".to_string()), "= ");
+
+    // once alternation is enforced, only the side to move may play or generate a move
+    assert!(gtp.accept_command("enforce_alternation maybe".to_string()).starts_with('?'));
+    assert_eq!(gtp.accept_command("enforce_alternation true".to_string()), "= ");
+    assert_eq!(gtp.accept_command("play B G7".to_string()), "? not the side to move");
+    assert_eq!(gtp.accept_command("genmove black".to_string()), "? not the side to move");
+    assert_eq!(gtp.accept_command("play W G7".to_string()), "= ");
+    assert_eq!(gtp.accept_command("play W C7".to_string()), "? not the side to move");
+    assert_eq!(gtp.accept_command("undo".to_string()), "= ");
+    assert_eq!(gtp.accept_command("play W C7".to_string()), "= ");
+
+    // White moves first after handicap stones
+    gtp.accept_command("clear_board".to_string());
+    gtp.accept_command("fixed_handicap 2".to_string());
+    assert_eq!(gtp.accept_command("play B E5".to_string()), "? not the side to move");
+    assert_eq!(gtp.accept_command("play W E5".to_string()), "= ");
+}
+
+#[test]
+fn test_play_pass_and_resign() {
+    let mut gtp = gtp::GTP::new();
+    gtp.accept_command("boardsize 9".to_string());
+    gtp.accept_command("enforce_alternation true".to_string());
+    assert_eq!(gtp.accept_command("play B pass".to_string()), "= ");
+    assert_eq!(gtp.accept_command("play B E5".to_string()), "? not the side to move");
+    assert_eq!(gtp.accept_command("play W PASS".to_string()), "= ");
+    assert!(gtp.accept_command("printsgf".to_string()).ends_with("RE[W+6.5];B[];W[])"));
+
+    // resigning ends the game, which genmove then refuses to play on
+    gtp.accept_command("clear_board".to_string());
+    assert_eq!(gtp.accept_command("play B E5".to_string()), "= ");
+    assert_eq!(gtp.accept_command("play W resign".to_string()), "= ");
+    assert_eq!(gtp.accept_command("final_score".to_string()), "= B+R");
+    assert_eq!(gtp.accept_command("genmove W".to_string()), "? game is over");
+    assert_eq!(gtp.accept_command("play W passes".to_string()), "? syntax error");
+}
+
+#[test]
+fn test_undo_resignation() {
+    let mut gtp = gtp::GTP::new();
+    gtp.accept_command("boardsize 9".to_string());
+    assert_eq!(gtp.accept_command("play B D4".to_string()), "= ");
+    assert_eq!(gtp.accept_command("play W resign".to_string()), "= ");
+
+    // undo takes back the resignation, leaving the move before it and the game going on
+    assert_eq!(gtp.accept_command("undo".to_string()), "= ");
+    let sgf = gtp.accept_command("printsgf".to_string());
+    assert!(sgf.ends_with(";B[df])") && !sgf.contains("RE["));
+    assert!(!gtp.accept_command("genmove W".to_string()).starts_with('?'));
+}
+
+#[test]
+fn test_search_limit() {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    let mut gtp = gtp::GTP::new();
+    gtp.accept_command("boardsize 9".to_string());
+    let searches = AtomicU32::new(0);
+    gtp.set_search_limit(move || match searches.fetch_add(1, Ordering::Relaxed) {
+        0 => Ok(()),
+        _ => Err("out of searches".to_string()),
+    });
+
+    // each search is checked, and one refused is answered with the error, leaving the game as it is
+    assert!(!gtp.accept_command("genmove B".to_string()).starts_with('?'));
+    let before = gtp.accept_command("printsgf".to_string());
+    assert_eq!(gtp.accept_command("genmove W".to_string()), "? out of searches");
+    assert_eq!(gtp.accept_command("reg_genmove W".to_string()), "? out of searches");
+    assert_eq!(gtp.accept_command("printsgf".to_string()), before);
+    assert_eq!(gtp.accept_command("play W pass".to_string()), "= ");
+    gtp.remove_search_limit();
+    assert!(!gtp.accept_command("reg_genmove W".to_string()).starts_with('?'));
+}
+
+#[test]
+fn test_ownership_command() {
+    let mut gtp = gtp::GTP::new();
+    gtp.accept_command("boardsize 9".to_string());
+    gtp.accept_command("play B E5".to_string());
+    let response = gtp.accept_command("ownership 8".to_string());
+    let rows: Vec<Vec<f64>> = response
+        .trim_start_matches("= ")
+        .lines()
+        .map(|row| row.split(' ').map(|value| value.parse().unwrap()).collect())
+        .collect();
+    assert_eq!(rows.len(), 9);
+    assert!(rows.iter().flatten().all(|value| (-1.0..=1.0).contains(value)));
+    assert!(rows.iter().all(|row| row.len() == 9));
+
+    assert!(gtp.accept_command("ownership 0".to_string()).starts_with('?'));
+    assert_eq!(gtp.accept_command("gogui-analyze_commands".to_string()), "= dboard/Ownership/ownership");
+}
+
+#[test]
+fn test_cputime() {
+    let mut gtp = gtp::GTP::new();
+    gtp.accept_command("boardsize 9".to_string());
+    assert_eq!(gtp.accept_command("cputime".to_string()), "d Searched for 0.000 seconds\n\n= 0.000");
+
+    // only searches count, and both commands report the same time
+    gtp.accept_command("reg_genmove B".to_string());
+    let response = gtp.accept_command("gomill-cpu_time".to_string());
+    let seconds: f64 = response.rsplit("= ").next().unwrap().parse().unwrap();
+    assert!(seconds > 0.0);
+    assert_eq!(gtp.accept_command("cputime".to_string()), response);
+}
+
+#[test]
+fn test_gomill_describe_engine() {
+    let mut gtp = gtp::GTP::new();
+    gtp.accept_command("kgs-rules japanese".to_string());
+    gtp.accept_command("komi 6.5".to_string());
+    let description = gtp.accept_command("gomill-describe_engine".to_string());
+    assert!(description.starts_with("= TBD 1\nsearch: 30 iterations\nexploration constant: 1.4142\n"));
+    assert!(description.ends_with("ruleset: japanese\nkomi: 6.5\nboard size: 19"));
+
+    gtp.accept_command("time_settings 600 0 0".to_string());
+    assert!(gtp.accept_command("gomill-describe_engine".to_string()).contains("search: as long as the clock allows"));
+}
+
+#[test]
+fn test_param() {
+    let mut gtp = gtp::GTP::new();
+    assert_eq!(
+        gtp.accept_command("param".to_string()),
+        "= playouts 30\nmax_time 0\nexploration 1.4142135623730951\nresign_threshold 60"
+    );
+    assert_eq!(gtp.accept_command("param playouts 5".to_string()), "= ");
+    assert_eq!(gtp.accept_command("param max_time 1.5".to_string()), "= ");
+    assert_eq!(gtp.accept_command("param exploration 0.7".to_string()), "= ");
+    assert_eq!(gtp.accept_command("param playouts".to_string()), "= 5");
+    assert_eq!(gtp.accept_command("param max_time".to_string()), "= 1.5");
+    assert!(gtp.accept_command("gomill-describe_engine".to_string()).contains("search: at most 1.5 seconds"));
+
+    for command in ["param playouts 0", "param exploration -1", "param resign_threshold x", "param komi 7"] {
+        assert!(gtp.accept_command(command.to_string()).starts_with('?'));
+    }
+    assert_eq!(gtp.accept_command("param exploration".to_string()), "= 0.7");
+
+    // a lower resignation threshold resigns positions the engine would otherwise play on
+    let mut gtp = gtp::GTP::new();
+    gtp.accept_command("komi -50".to_string());
+    let points = (0..19u16).flat_map(|y| (0..19u16).map(move |x| (x, y))).filter(|(x, y)| (x + y) % 2 == 0);
+    for (number, (x, y)) in points.take(101).enumerate() {
+        let color = if number % 2 == 0 { "B" } else { "W" };
+        let vertex = Intersection::new(ColumnIdentifier::from_u16(x).unwrap(), y + 1).to_string();
+        gtp.accept_command(format!("play {color} {vertex}"));
+    }
+    assert_eq!(gtp.accept_command("param resign_threshold 40".to_string()), "= ");
+    assert_eq!(gtp.accept_command("reg_genmove W".to_string()), "= resign");
+}
+
+#[test]
+fn test_stopped_search() {
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Arc;
+    // a stopped search ends after its first iteration, which would otherwise run for ages
+    let board = Board::new(BoardSize::Nineteen);
+    let params = SearchParams { iterations: u16::MAX, ..SearchParams::default() };
+    let stop = Arc::new(AtomicBool::new(true));
+    let mov = generate_move_with(&board, Color::Black, &params, None, Some(stop));
+    assert!(matches!(mov, Move::MOVE(_, Color::Black)));
+
+    // with no search running on another thread, stop has nothing to stop
+    let mut gtp = gtp::GTP::new();
+    assert_eq!(gtp.accept_command("stop".to_string()), "= ");
+}
+
+#[test]
+fn test_board_rows_and_ko() {
+    // Black F5 takes the White stone at E5, which White may not retake at once
+    let mut gtp = gtp::GTP::new();
+    gtp.accept_command("boardsize 9".to_string());
+    for mov in ["B D5", "W F6", "B E6", "W F4", "B E4", "W G5", "B A1", "W E5", "B F5"] {
+        assert_eq!(gtp.accept_command(format!("play {mov}")), "= ");
+    }
+    let board = gtp.board();
+    assert_eq!(board.ko(), Some(Intersection::new(ColumnIdentifier::E, 5)));
+    assert_eq!(board.black_captures, 1);
+
+    // rows run from the top of the board
+    let rows = board.rows();
+    assert_eq!(rows.len(), 9);
+    assert_eq!(rows[4][4], State::Empty);
+    assert_eq!(rows[4][5], State::Occupied(Color::Black));
+    assert_eq!(rows[8][0], State::Occupied(Color::Black));
+}
+
+#[test]
+fn test_check_move() {
+    use ColumnIdentifier::*;
+    let mut gtp = gtp::GTP::new();
+    gtp.accept_command("boardsize 9".to_string());
+    for mov in ["B D5", "W F6", "B E6", "W F4", "B E4", "W G5", "B A2", "W E5", "B F5", "W J9", "B B1"] {
+        assert_eq!(gtp.accept_command(format!("play {mov}")), "= ");
+    }
+    let check = |gtp: &gtp::GTP, column, row, color| {
+        gtp.check_move(Move::MOVE(Intersection::new(column, row), color), color)
+    };
+
+    // with moves played elsewhere since, White may retake the ko, but not play where it has no liberties
+    assert_eq!(check(&gtp, E, 5, Color::White), Ok(()));
+    assert_eq!(check(&gtp, F, 5, Color::White), Err(IllegalMove::OCCUPIED));
+    assert_eq!(check(&gtp, A, 1, Color::White), Err(IllegalMove::SUICIDE));
+    assert_eq!(check(&gtp, K, 10, Color::White), Err(IllegalMove::OFFBOARD));
+    assert_eq!(check(&gtp, C, 3, Color::Black), Err(IllegalMove::OUTOFTURN));
+    assert_eq!(gtp.check_move(Move::PASS, Color::White), Ok(()));
+    assert_eq!(gtp.record().nodes.len(), 11); // checking plays nothing
+
+    // right after Black takes the ko, White may not retake it
+    gtp.accept_command("undo".to_string());
+    gtp.accept_command("undo".to_string());
+    assert_eq!(check(&gtp, E, 5, Color::White), Err(IllegalMove::KO));
+    assert_eq!(IllegalMove::OUTOFTURN.to_string(), "out_of_turn");
+}
+
+#[test]
+fn test_gtp_execute() {
+    // results and errors come without the response status, and quit closes the Protocol
+    let mut gtp = gtp::GTP::new();
+    assert_eq!(gtp.execute(&["boardsize", "9"]), Some(Ok(String::new())));
+    assert_eq!(gtp.execute(&["play", "b", "E5"]), Some(Ok(String::new())));
+    assert_eq!(gtp.execute(&["play", "w", "E5"]), Some(Err("invalid move".to_string())));
+    assert_eq!(gtp.execute(&["name"]), Some(Ok("TBD".to_string())));
+    assert_eq!(gtp.side_to_move(), Color::White);
+    assert_eq!(gtp.record().nodes.len(), 1);
+    assert_eq!(gtp.execute(&["quit"]), None);
+}
+
+#[test]
+fn test_gtp_over_tcp() {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    std::thread::spawn(move || tcp::serve_gtp(listener));
+
+    // sends a command and reads its response up to the blank line ending it
+    let exchange = |stream: &mut BufReader<TcpStream>, command: &str| -> String {
+        writeln!(stream.get_mut(), "{command}").unwrap();
+        let mut response = String::new();
+        while !response.ends_with("\n\n") {
+            assert!(stream.read_line(&mut response).unwrap() > 0);
+        }
+        response.trim_end().to_string()
+    };
+
+    // each connection is its own session, with its own game
+    let mut first = BufReader::new(TcpStream::connect(address).unwrap());
+    let mut second = BufReader::new(TcpStream::connect(address).unwrap());
+    assert_eq!(exchange(&mut first, "1 boardsize 9"), "=1");
+    assert_eq!(exchange(&mut first, "play B E5"), "=");
+    assert_eq!(exchange(&mut second, "play W E5"), "=");
+    assert_eq!(exchange(&mut first, "play W E5"), "? invalid move");
+
+    // quit answers, then closes the connection
+    assert_eq!(exchange(&mut first, "2 quit"), "=2");
+    assert_eq!(first.read(&mut [0u8; 1]).unwrap(), 0);
+    assert_eq!(exchange(&mut second, "name"), "= TBD");
+}
+
+#[test]
+fn test_final_score() {
+    assert_eq!(GameResult::SCORE(3.5).to_string(), "B+3.5");
+    assert_eq!(GameResult::SCORE(0.0).to_string(), "0");
+    assert_eq!(GameResult::RESIGNATION(Color::White).to_string(), "W+R");
+
+    // an empty board is only worth komi
+    let mut gtp = gtp::GTP::new();
+    gtp.accept_command("boardsize 9".to_string());
+    gtp.accept_command("komi 0.5".to_string());
+    assert_eq!(gtp.accept_command("final_score".to_string()), "= W+0.5");
+}
+
+/*****************************************************\
+|****************     BOARD POOLS     ****************|
+\*****************************************************/
+
+#[test]
+fn test_board_pool() {
+    use ColumnIdentifier::*;
+    // a recycled 19x19 Board copied into from a 9x9 Board must become identical to it
+    let mut board = Board::new(BoardSize::Nine);
+    board.play(Move::MOVE(Intersection::new(C, 3), Color::Black));
+    board.play(Move::MOVE(Intersection::new(D, 4), Color::White));
+    recycle(Board::new(BoardSize::Nineteen));
+
+    let copy = pooled_copy(&board);
+    assert_eq!(copy, board);
+    assert_eq!(copy.to_string(), board.to_string());
+
+    // a playout gives the Boards of its tree back to the pool
+    playout(&board, Color::Black);
+    assert!(spare_boards() > 0);
+
+    // clear_cache drops the spares, but not the game
+    let mut gtp = gtp::GTP::new();
+    gtp.accept_command("play B D4".to_string());
+    assert_eq!(gtp.accept_command("clear_cache".to_string()), "= ");
+    assert_eq!(spare_boards(), 0);
+    assert!(gtp.accept_command("printsgf".to_string()).ends_with(";B[dp])"));
+}
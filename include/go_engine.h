@@ -0,0 +1,119 @@
+/* Generated by cbindgen from src/ffi.rs, do not edit by hand */
+
+#ifndef GO_ENGINE_H
+#define GO_ENGINE_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// The outcomes of [`go_engine_play`] and [`go_engine_genmove`].
+typedef enum {
+  // A stone was played.
+  GO_STATUS_PLAYED,
+  // The engine passed.
+  GO_STATUS_PASSED,
+  // The engine resigned.
+  GO_STATUS_RESIGNED,
+  // The move was illegal, or the engine pointer was null.
+  GO_STATUS_ERROR,
+} GoStatus;
+
+// The colors of stones, as passed over the C ABI.
+typedef enum {
+  // Black stones.
+  GO_COLOR_BLACK,
+  // White stones.
+  GO_COLOR_WHITE,
+} GoColor;
+
+// The rules used to score a game, as passed over the C ABI.
+typedef enum {
+  // Area scoring, as in Chinese rules.
+  GO_RULES_AREA,
+  // Territory scoring, as in Japanese rules.
+  GO_RULES_TERRITORY,
+} GoRules;
+
+// An engine holding a single game, opaque to C.
+typedef struct GoEngine GoEngine;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Creates a new engine holding an empty game on a board of the given size with the given komi.
+//
+// Returns null if the size is not 9, 13, or 19.
+GoEngine *go_engine_new(uint32_t size, double komi);
+
+// Releases an engine created by [`go_engine_new`].
+//
+// # Safety
+//
+// `engine` must be null or a pointer returned by [`go_engine_new`] that was not yet freed.
+void go_engine_free(GoEngine *engine);
+
+// Plays a stone of the given color at the given row and column, both counted from 0 at the
+// bottom left corner of the board.
+//
+// Returns [`GoStatus::Played`], or [`GoStatus::Error`] if the move is illegal or off the board.
+//
+// # Safety
+//
+// `engine` must be null or a live pointer returned by [`go_engine_new`].
+GoStatus go_engine_play(GoEngine *engine, GoColor color, uint16_t row, uint16_t col);
+
+// Passes for the given color.
+//
+// Returns [`GoStatus::Passed`], or [`GoStatus::Error`] if the engine pointer is null.
+//
+// # Safety
+//
+// `engine` must be null or a live pointer returned by [`go_engine_new`].
+GoStatus go_engine_pass(GoEngine *engine, GoColor color);
+
+// Generates and plays the engine's move for the given color.
+//
+// When a stone is played, returns [`GoStatus::Played`] and writes its row and column to the
+// given pointers. Otherwise returns [`GoStatus::Passed`] or [`GoStatus::Resigned`].
+//
+// # Safety
+//
+// `engine` must be null or a live pointer returned by [`go_engine_new`], and `row` and `col`
+// must be valid for writes.
+GoStatus go_engine_genmove(GoEngine *engine, GoColor color, uint16_t *row, uint16_t *col);
+
+// Scores the current position under the given rules after removing dead stones.
+//
+// The score includes komi and is positive when Black leads. Returns NaN if the engine pointer
+// is null.
+//
+// # Safety
+//
+// `engine` must be null or a live pointer returned by [`go_engine_new`].
+double go_engine_score(const GoEngine *engine, GoRules rules);
+
+// Records the game so far in the Smart Game Format.
+//
+// Returns a string that must be released with [`go_engine_string_free`], or null if the engine
+// pointer is null.
+//
+// # Safety
+//
+// `engine` must be null or a live pointer returned by [`go_engine_new`].
+char *go_engine_to_sgf(const GoEngine *engine);
+
+// Releases a string returned by this library.
+//
+// # Safety
+//
+// `string` must be null or a pointer returned by this library that was not yet freed.
+void go_engine_string_free(char *string);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* GO_ENGINE_H */
@@ -0,0 +1,304 @@
+//! The engine that plays, scores, and records games of Go.
+//!
+//! An [`Engine`] holds a single game, and can be embedded in other programs in place of driving
+//! the `Go_Engine` binary over the Go Text Protocol.
+
+use crate::board::{Board, BoardSize, Color, ScoringRules};
+use crate::update::{update, Message, Position};
+use engine::Move;
+use std::time::Duration;
+
+/// A move chosen by the [`Engine`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum EngineMove {
+    /// A stone played at the given [`Position`].
+    Play(Position),
+    /// A pass.
+    Pass,
+    /// A resignation, which ends the game without changing the board.
+    Resign,
+}
+
+/// A move the [`Engine`] considered in a search, with the statistics the search gathered for it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Candidate {
+    /// The move considered, a stone played or a pass.
+    pub mov: EngineMove,
+    /// The number of search iterations that visited the move.
+    pub visits: u16,
+    /// The estimated probability that the [`Color`] to play wins after the move.
+    pub winrate: f64,
+    /// The principal variation, the moves the search expects to be played from the move on: the
+    /// move itself, then the most visited reply to each move in turn.
+    pub pv: Vec<EngineMove>,
+}
+
+/// A search for the [`Engine`]'s move, run a batch of playouts at a time so that the program
+/// waiting on it can do other work between batches, ex. keep a user interface responsive.
+///
+/// Started by [`Engine::start_genmove`] and played by [`Engine::finish_genmove`].
+///
+/// ```
+/// use go_engine::{BoardSize, Color, Engine, Position};
+///
+/// let mut engine = Engine::new(BoardSize::Nine, 6.5);
+/// engine.set_playouts(40);
+/// engine.play(Color::Black, Position::new(4, 4)).unwrap();
+///
+/// let mut search = engine.start_genmove(Color::White);
+/// while !search.step(10) {
+///     // a front end would show search.best_move() and let its user interface run here
+/// }
+/// assert_eq!(search.playouts(), 40);
+/// let best_move = search.best_move();
+/// assert_eq!(engine.finish_genmove(search), Ok(best_move));
+/// assert_eq!(engine.to_play(), Color::Black);
+/// ```
+pub struct MoveSearch {
+    inner: engine::MoveSearch,
+}
+
+/// A game of Go played with the engine.
+///
+/// # Example
+///
+/// ```
+/// use go_engine::{BoardSize, Color, Engine, Position, ScoringRules};
+///
+/// let mut engine = Engine::new(BoardSize::Nine, 6.5);
+/// engine.play(Color::Black, Position::new(4, 4)).unwrap();
+/// engine.pass(Color::White);
+/// assert_eq!(engine.to_play(), Color::Black);
+/// assert!(engine.to_sgf().contains(";B[ee];W[]"));
+/// assert_eq!(engine.result(ScoringRules::Area), "B+74.5");
+/// ```
+pub struct Engine {
+    inner: engine::Engine,
+}
+
+impl Engine {
+    /// Constructs a new [`Engine`] holding an empty game on a board of [`BoardSize`] with the
+    /// given komi.
+    pub fn new(size: BoardSize, komi: f64) -> Self {
+        Engine { inner: engine::Engine::new(size, komi) }
+    }
+
+    /// Constructs a new [`Engine`] holding the main line of the given SGF game.
+    ///
+    /// Outputs a [`String`] detailing why the game could not be loaded on failure.
+    pub fn from_sgf(sgf: &str) -> Result<Self, String> {
+        engine::Engine::from_sgf(sgf).map(|inner| Engine { inner })
+    }
+
+    /// Constructs a new [`Engine`] holding the game on the given [`Board`], replaying its setup
+    /// stones and then its moves so that the engine knows the positions they passed through.
+    ///
+    /// Outputs a [`String`] detailing why a stone could not be replayed on failure, ex. a suicide
+    /// allowed by the [`Ruleset`](crate::Ruleset) of the [`Board`] but not by the engine.
+    pub fn from_board(board: &Board) -> Result<Self, String> {
+        let mut engine = Engine::new(board.size(), board.komi());
+        for &(color, pos) in board.setup() {
+            engine.play(color, pos)?;
+        }
+        for &(color, pos) in board.moves() {
+            match pos {
+                Some(pos) => engine.play(color, pos)?,
+                None => engine.pass(color),
+            }
+        }
+        Ok(engine)
+    }
+
+    /// Sets the number of search iterations [`Engine::genmove`] uses. More is stronger but slower.
+    pub fn set_playouts(&mut self, playouts: u16) {
+        self.inner.set_playouts(playouts);
+    }
+
+    /// Sets how long [`Engine::genmove`] searches for, running as many playouts as fit in the time
+    /// instead of those set by [`Engine::set_playouts`], or removes the limit given [`None`].
+    pub fn set_time_limit(&mut self, time: Option<Duration>) {
+        self.inner.set_time_limit(time);
+    }
+
+    /// The komi of the game.
+    pub fn komi(&self) -> f64 {
+        self.inner.komi()
+    }
+
+    /// The [`Color`] to play next.
+    pub fn to_play(&self) -> Color {
+        self.inner.to_play()
+    }
+
+    /// Plays a stone of [`Color`] at the given [`Position`] if it follows the rules of Go.
+    ///
+    /// Outputs a [`String`] detailing why the move was rejected on failure.
+    pub fn play(&mut self, color: Color, pos: Position) -> Result<(), String> {
+        self.inner.play(color, pos)
+    }
+
+    /// Passes for the given [`Color`].
+    pub fn pass(&mut self, color: Color) {
+        self.inner.pass(color);
+    }
+
+    /// Generates and plays the engine's move for the given [`Color`].
+    pub fn genmove(&mut self, color: Color) -> EngineMove {
+        engine_move(self.inner.genmove(color))
+    }
+
+    /// Starts a search for the engine's move for the given [`Color`] like [`Engine::genmove`],
+    /// which runs as it is stepped and is played by [`Engine::finish_genmove`].
+    pub fn start_genmove(&self, color: Color) -> MoveSearch {
+        MoveSearch { inner: self.inner.start_genmove(color) }
+    }
+
+    /// Plays the best move found by the given [`MoveSearch`], even if it was not stepped to the
+    /// end.
+    ///
+    /// Outputs a [`String`] detailing why the move was not played if a move was played since the
+    /// search started.
+    pub fn finish_genmove(&mut self, search: MoveSearch) -> Result<EngineMove, String> {
+        self.inner.finish_genmove(search.inner).map(engine_move)
+    }
+
+    /// Searches the current position for the given number of playouts without playing, giving
+    /// every move the search considered as a [`Candidate`], most visited first.
+    pub fn candidates(&self, playouts: u16) -> Vec<Candidate> {
+        self.inner
+            .candidates(playouts)
+            .into_iter()
+            .map(|(mov, visits, winrate, pv)| Candidate {
+                mov: engine_move(mov),
+                visits,
+                winrate,
+                pv: pv.into_iter().map(engine_move).collect(),
+            })
+            .collect()
+    }
+
+    /// Scores the current position under the given [`ScoringRules`] after removing dead stones.
+    ///
+    /// The score includes komi, and is positive when Black leads.
+    pub fn score(&self, rules: ScoringRules) -> f64 {
+        self.inner.score(rules)
+    }
+
+    /// The result of the current position under the given [`ScoringRules`], ex. `B+3.5`.
+    pub fn result(&self, rules: ScoringRules) -> String {
+        self.inner.result(rules)
+    }
+
+    /// Records the game so far in the Smart Game Format.
+    pub fn to_sgf(&self) -> String {
+        self.inner.to_sgf()
+    }
+
+    /// Constructs the [`Board`] of the current position, with the [`Color`] to play next.
+    ///
+    /// The moves of the game are replayed on the [`Board`] under the engine's rules, so that it
+    /// keeps the ko, captures, and earlier positions of the game. Stones played out of turn, or
+    /// after the game ended with two passes, are placed as setup stones.
+    pub fn board(&self) -> Board {
+        let (position, _) = self.inner.position();
+        let mut board = Board::builder()
+            .size(position.size)
+            .komi(self.komi())
+            .ruleset(position.ruleset)
+            .build()
+            .expect("an empty board always builds");
+        if let Some(ko_rule) = position.ko_rule {
+            update(&mut board, Message::SetKoRule(ko_rule)).expect("a ko rule can always be set");
+        }
+
+        for (color, pos) in self.inner.moves() {
+            let in_turn = board.player_turn() == color && board.result().is_none();
+            let msg = match pos {
+                Some(pos) if in_turn => Message::Play(color, pos),
+                Some(pos) => Message::PlaceStone(color, pos),
+                None if in_turn => Message::Pass,
+                // a pass out of turn leaves the board as it is, with the other color to play
+                None => continue,
+            };
+            update(&mut board, msg).expect("the engine's moves follow the rules it plays under");
+        }
+        board
+    }
+}
+
+impl MoveSearch {
+    /// Runs up to the given number of playouts, never past the time set by
+    /// [`Engine::set_time_limit`], if any, or else the playouts set by [`Engine::set_playouts`].
+    ///
+    /// Outputs whether the search is done.
+    pub fn step(&mut self, playouts: u16) -> bool {
+        self.inner.step(playouts)
+    }
+
+    /// The number of playouts run so far.
+    pub fn playouts(&self) -> u32 {
+        self.inner.playouts()
+    }
+
+    /// The move the search would play so far.
+    pub fn best_move(&self) -> EngineMove {
+        engine_move(self.inner.best_move())
+    }
+}
+
+/// Generates the engine's move for the given [`Color`] on the given [`Board`], searching for the
+/// given number of playouts, without changing the board.
+///
+/// Outputs a [`String`] detailing why the game on the [`Board`] could not be replayed by the
+/// engine on failure, as in [`Engine::from_board`].
+///
+/// ```
+/// use go_engine::{generate_move, update, Board, BoardSize, Color, EngineMove, Message, Position};
+///
+/// let mut board = Board::builder().size(BoardSize::Nine).build().unwrap();
+/// update(&mut board, Message::Play(Color::Black, Position::new(4, 4))).unwrap();
+///
+/// if let EngineMove::Play(pos) = generate_move(&board, Color::White, 50).unwrap() {
+///     update(&mut board, Message::Play(Color::White, pos)).unwrap();
+/// }
+/// ```
+pub fn generate_move(board: &Board, color: Color, playouts: u16) -> Result<EngineMove, String> {
+    let mut engine = Engine::from_board(board)?;
+    engine.set_playouts(playouts);
+    Ok(engine.genmove(color))
+}
+
+/// Reseeds the random numbers the engine's searches draw on the current thread, so that the
+/// searches run from now on make the same choices on every run, ex. for tests.
+///
+/// Otherwise the random numbers are seeded by the operating system, or in WebAssembly builds by
+/// the `crypto.getRandomValues` of the browser.
+///
+/// ```
+/// use go_engine::{seed_rng, BoardSize, Color, Engine, Position};
+///
+/// let analyze = || {
+///     let mut engine = Engine::new(BoardSize::Nine, 6.5);
+///     engine.play(Color::Black, Position::new(4, 4)).unwrap();
+///     engine.play(Color::White, Position::new(2, 2)).unwrap();
+///     engine.play(Color::Black, Position::new(6, 6)).unwrap();
+///     engine.candidates(50)
+/// };
+///
+/// seed_rng(7);
+/// let first = analyze();
+/// seed_rng(7);
+/// assert_eq!(analyze(), first);
+/// ```
+pub fn seed_rng(seed: u64) {
+    engine::seed_rng(seed);
+}
+
+/// The [`EngineMove`] of the given [`Move`] chosen by the engine.
+fn engine_move(mov: Move) -> EngineMove {
+    match mov {
+        Move::MOVE(intsc, _) => EngineMove::Play(intsc.into()),
+        Move::PASS => EngineMove::Pass,
+        Move::RESIGN => EngineMove::Resign,
+    }
+}
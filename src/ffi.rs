@@ -0,0 +1,208 @@
+//! C bindings for embedding the [`Engine`] in C, C++, and other language runtimes.
+//!
+//! Enabled with the `ffi` feature, which also generates the `include/go_engine.h` header. Every
+//! function takes the engine pointer returned by [`go_engine_new`], which must be released with
+//! [`go_engine_free`]. Functions given a null engine pointer fail instead of crashing.
+
+use crate::agent::{Engine, EngineMove};
+use crate::board::{BoardSize, Color, ScoringRules};
+use crate::update::Position;
+use std::ffi::{c_char, CString};
+
+/// An engine holding a single game, opaque to C.
+pub struct GoEngine(Engine);
+
+/// The colors of stones, as passed over the C ABI.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub enum GoColor {
+    /// Black stones.
+    Black,
+    /// White stones.
+    White,
+}
+
+/// The rules used to score a game, as passed over the C ABI.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub enum GoRules {
+    /// Area scoring, as in Chinese rules.
+    Area,
+    /// Territory scoring, as in Japanese rules.
+    Territory,
+}
+
+/// The outcomes of [`go_engine_play`] and [`go_engine_genmove`].
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GoStatus {
+    /// A stone was played.
+    Played,
+    /// The engine passed.
+    Passed,
+    /// The engine resigned.
+    Resigned,
+    /// The move was illegal, or the engine pointer was null.
+    Error,
+}
+
+impl From<GoColor> for Color {
+    fn from(color: GoColor) -> Self {
+        match color {
+            GoColor::Black => Color::Black,
+            GoColor::White => Color::White,
+        }
+    }
+}
+
+impl From<GoRules> for ScoringRules {
+    fn from(rules: GoRules) -> Self {
+        match rules {
+            GoRules::Area => ScoringRules::Area,
+            GoRules::Territory => ScoringRules::Territory,
+        }
+    }
+}
+
+/// Creates a new engine holding an empty game on a board of the given size with the given komi.
+///
+/// Returns null if the size is not 9, 13, or 19.
+#[no_mangle]
+pub extern "C" fn go_engine_new(size: u32, komi: f64) -> *mut GoEngine {
+    match u16::try_from(size).ok().and_then(BoardSize::from_u16) {
+        Some(size) => Box::into_raw(Box::new(GoEngine(Engine::new(size, komi)))),
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// Releases an engine created by [`go_engine_new`].
+///
+/// # Safety
+///
+/// `engine` must be null or a pointer returned by [`go_engine_new`] that was not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn go_engine_free(engine: *mut GoEngine) {
+    if !engine.is_null() {
+        drop(Box::from_raw(engine));
+    }
+}
+
+/// Plays a stone of the given color at the given row and column, both counted from 0 at the
+/// bottom left corner of the board.
+///
+/// Returns [`GoStatus::Played`], or [`GoStatus::Error`] if the move is illegal or off the board.
+///
+/// # Safety
+///
+/// `engine` must be null or a live pointer returned by [`go_engine_new`].
+#[no_mangle]
+pub unsafe extern "C" fn go_engine_play(
+    engine: *mut GoEngine,
+    color: GoColor,
+    row: u16,
+    col: u16,
+) -> GoStatus {
+    match engine.as_mut() {
+        Some(GoEngine(engine)) => {
+            match Position::try_new(row, col).map(|pos| engine.play(color.into(), pos)) {
+                Some(Ok(())) => GoStatus::Played,
+                _ => GoStatus::Error,
+            }
+        }
+        None => GoStatus::Error,
+    }
+}
+
+/// Passes for the given color.
+///
+/// Returns [`GoStatus::Passed`], or [`GoStatus::Error`] if the engine pointer is null.
+///
+/// # Safety
+///
+/// `engine` must be null or a live pointer returned by [`go_engine_new`].
+#[no_mangle]
+pub unsafe extern "C" fn go_engine_pass(engine: *mut GoEngine, color: GoColor) -> GoStatus {
+    match engine.as_mut() {
+        Some(GoEngine(engine)) => {
+            engine.pass(color.into());
+            GoStatus::Passed
+        }
+        None => GoStatus::Error,
+    }
+}
+
+/// Generates and plays the engine's move for the given color.
+///
+/// When a stone is played, returns [`GoStatus::Played`] and writes its row and column to the
+/// given pointers. Otherwise returns [`GoStatus::Passed`] or [`GoStatus::Resigned`].
+///
+/// # Safety
+///
+/// `engine` must be null or a live pointer returned by [`go_engine_new`], and `row` and `col`
+/// must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn go_engine_genmove(
+    engine: *mut GoEngine,
+    color: GoColor,
+    row: *mut u16,
+    col: *mut u16,
+) -> GoStatus {
+    let Some(GoEngine(engine)) = engine.as_mut() else {
+        return GoStatus::Error;
+    };
+
+    match engine.genmove(color.into()) {
+        EngineMove::Play(pos) => {
+            *row = pos.row();
+            *col = pos.col();
+            GoStatus::Played
+        }
+        EngineMove::Pass => GoStatus::Passed,
+        EngineMove::Resign => GoStatus::Resigned,
+    }
+}
+
+/// Scores the current position under the given rules after removing dead stones.
+///
+/// The score includes komi and is positive when Black leads. Returns NaN if the engine pointer
+/// is null.
+///
+/// # Safety
+///
+/// `engine` must be null or a live pointer returned by [`go_engine_new`].
+#[no_mangle]
+pub unsafe extern "C" fn go_engine_score(engine: *const GoEngine, rules: GoRules) -> f64 {
+    match engine.as_ref() {
+        Some(GoEngine(engine)) => engine.score(rules.into()),
+        None => f64::NAN,
+    }
+}
+
+/// Records the game so far in the Smart Game Format.
+///
+/// Returns a string that must be released with [`go_engine_string_free`], or null if the engine
+/// pointer is null.
+///
+/// # Safety
+///
+/// `engine` must be null or a live pointer returned by [`go_engine_new`].
+#[no_mangle]
+pub unsafe extern "C" fn go_engine_to_sgf(engine: *const GoEngine) -> *mut c_char {
+    match engine.as_ref() {
+        Some(GoEngine(engine)) => CString::new(engine.to_sgf())
+            .map_or(std::ptr::null_mut(), CString::into_raw),
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// Releases a string returned by this library.
+///
+/// # Safety
+///
+/// `string` must be null or a pointer returned by this library that was not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn go_engine_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
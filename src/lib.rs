@@ -0,0 +1,60 @@
+//! A custom agent that plays the game of Go.
+//!
+//! This library follows the Elm Architecture, with [`board`] as the model, [`update`] applying
+//! [`Message`]s to it, and [`view`] rendering it. An [`Engine`] plays, scores, and records games,
+//! and [`sgf`] writes the games played on a [`Board`] with the variations of its [`tree`].
+//!
+//! The model, [`builder`], [`update`], and [`sgf`] are re-exported from the [`go_board`] crate,
+//! which other projects can depend on for the rules of Go without the engine or its servers.
+//! The engine shares the colors, sizes, rules, and positions of [`go_board`], but searches on a
+//! board of its own built for fast playouts, so [`Engine`] and [`generate_move`] still convert
+//! games to it.
+//!
+//! # Stability
+//!
+//! The items re-exported at the root of this crate are its stable API, and follow semantic
+//! versioning: breaking changes to them only happen in a new major version (or a new minor
+//! version before `1.0`). Items hidden from the documentation, including the run modes of the
+//! `Go_Engine` binary, may change at any time.
+//!
+//! The entry points most programs need are [`Board`], [`BoardSize`], [`Color`], and [`Position`]
+//! to describe a game, [`Board::builder`] to start it from any position, [`update`] to apply
+//! [`Message`]s to it, and [`generate_move`] to ask the engine for its move.
+//!
+//! # Example
+//!
+//! ```
+//! use go_engine::{generate_move, update, Board, Color, EngineMove, Message, Position};
+//!
+//! let mut board = Board::new();
+//! update(&mut board, Message::Play(Color::Black, Position::new(3, 3))).unwrap();
+//!
+//! match generate_move(&board, Color::White, 20).unwrap() {
+//!     EngineMove::Play(pos) => update(&mut board, Message::Play(Color::White, pos)).unwrap(),
+//!     _ => update(&mut board, Message::Pass).unwrap(),
+//! };
+//! ```
+
+//#![deny(missing_docs)]
+#![warn(missing_docs)]
+
+pub mod agent;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(any(test, feature = "proptest"))]
+pub mod testing;
+pub mod view;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(test)]
+mod tests;
+
+pub use agent::{generate_move, seed_rng, Candidate, Engine, EngineMove, MoveSearch};
+pub use board::{Board, BoardSize, Color, GameResult, Group, KoRule, Ruleset, ScoringRules, State};
+pub use go_board::update::{
+    self, update, Message, MoveEffects, ParsePositionError, Position, RuleViolation,
+};
+pub use go_board::{board, builder, sgf, tree};
+pub use builder::{BoardBuilder, BuildError};
+pub use view::{AccessibleView, AsciiView, DiffView, HeatmapView, KifuView, TerminalView, View};
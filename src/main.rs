@@ -1,58 +1,374 @@
-//! A custom agent that plays the game of Go.
-//!
-//! This program follows the Elm Architecture, with [`board`] as the model.
+//! The `Go_Engine` binary, running the [`go_engine`] library in the mode given on the command line.
 
-//#![deny(missing_docs)]
-#![warn(missing_docs)]
+use go_engine::board::BoardSize;
+use go_engine::view::{view_named, View};
+use go_engine::{board, update, Color, Engine, EngineMove, Message, Position, ScoringRules};
+use std::io;
+use std::io::{BufRead, Write};
+use engine::AgentError;
+use std::process::ExitCode;
 
-pub mod board;
-pub mod update;
-pub mod view;
-
-mod groups;
-
-use board::Color;
-use update::{update, Message, Position};
+/// The run modes served by the `go_gtp` and `go_api` crates, each with the feature building it.
+const FEATURE_MODES: [(&str, &str); 6] = [
+    ("gtp", "gtp"),
+    ("tcp", "gtp"),
+    ("match", "gtp"),
+    ("regress", "gtp"),
+    ("api", "api"),
+    ("grpc", "grpc"),
+];
 
 /// Starting point of the program. Command line arguments detail functionality.
 ///
 /// `-- gtp` starts a Go Text Protocol listener on `std::in`, outputs on `std::out` and `std::err`.
 ///
-/// `-- api` starts a Go Text Protocol listener for HTTP requests on port 80.
-pub fn main() {
-    use engine::start_go_agent;
+/// `-- tcp --port N` listens for Go Text Protocol connections on port 6969 by default, each its
+/// own session with a new game, for relay tools and bot bridges to drive the engine remotely.
+/// Both require the default `gtp` feature, as do `match` and `regress`, which can be disabled
+/// along with `api` to build only the board and engine library.
+///
+/// `-- api --host ADDRESS --port N` starts a Go Text Protocol listener for HTTP requests, on
+/// `0.0.0.0:80` unless given by the options or the `GO_ENGINE_API_HOST` and `GO_ENGINE_API_PORT`
+/// environment variables (ex. an unprivileged port in a container). It requires the default `api`
+/// feature, which can be disabled to build without the async web stack. `--tls-cert <cert.pem>
+/// --tls-key <key.pem>`, or `GO_ENGINE_API_TLS_CERT` and `GO_ENGINE_API_TLS_KEY`, serve HTTPS
+/// with rustls instead, which requires the `tls` feature. The command and game routes share their
+/// games, removing them once they sit idle for 30 minutes. `--db <games.db>`, or
+/// `GO_ENGINE_API_DB`, saves the moves, clocks, and results of every game to an SQLite database,
+/// resuming a game by its id after a restart or once it was removed for sitting idle.
+/// `--cors-origins`, `--cors-methods`, and `--cors-headers`, or `GO_ENGINE_API_CORS_ORIGINS`,
+/// `GO_ENGINE_API_CORS_METHODS`, and `GO_ENGINE_API_CORS_HEADERS`, let browser clients on the
+/// comma separated origins (or `*` for any) call the API with the given methods and headers:
+/// - `POST /` runs the GTP command `next_command` of a JSON body in a new game set up with its
+///   `board_size`, `move_list`, and optional `ruleset`, `komi`, `handicap` (or `handicap_stones`),
+///   and `time_settings`, answering with the setting refused if one is invalid.
+/// - `POST /command/{session}` runs a GTP command in the game of the session identifier, started
+///   on its first command and ended by `quit`, where `POST /command` starts a game under a new
+///   identifier.
+/// - `POST /games`, `GET` and `DELETE /games/{id}`, `GET /games/{id}/board`, and
+///   `POST /games/{id}/moves` and `/games/{id}/genmove` manage and play games in JSON.
+/// - `GET /games/{id}/board.svg` draws the position of a game as an SVG image, marking the last
+///   move and never cached, for live overlays (ex. an OBS browser source), and
+///   `GET /games/{id}/board.png` draws it as a PNG image when built with the `image` feature.
+/// - `POST /games/{id}/check` tells whether a move may be played next, and if not why (out of turn,
+///   off the board, occupied, ko, or suicide), without playing it, for instant feedback in clients.
+/// - `POST /games/{id}/genmove/stream` plays the engine's move like `genmove`, streaming the best
+///   move, visits, winrate, and principal variation of its search as server-sent events.
+/// - `GET /games/{id}/spectate` streams a game as server-sent events, and the WebSocket
+///   `/games/{id}/spectate/ws` as messages, sending it as it is, then after every move or other
+///   change from any route, so any number of spectators can watch exhibition games live.
+/// - `GET /games/{id}/tree?depth=N` searches the position of a game with the engine's playouts,
+///   answering with the moves of the search tree down to the depth (1 by default, 10 at most),
+///   each with its visits and winrate, for visualizing and debugging the search.
+/// - `POST /analyze/sgf?visits=N` reviews the SGF game in the body, answering with the winrate,
+///   score estimate, and the engine's preferred alternatives for every move.
+/// - The WebSocket `/play` plays games in JSON messages: `join` an existing game or start a new
+///   one with an optional engine color and time limits, then `play` or `genmove`, receiving each
+///   move, the engine's replies, and the clocks as they change.
+/// - The WebSocket `/gtp` holds a game of its own, taking each line of a text message as a GTP
+///   command and answering each in a message of its own.
+/// - `genmove`, `genmove/stream`, `tree`, and `/analyze/sgf` are rate limited per client, by the
+///   API key given in the `X-API-Key` header or else the address, answering `429` with
+///   `Retry-After` once a client has used its burst of 5 requests, regained at one every 6 seconds.
+/// - `GET /engine/settings` describes the playouts, time per move, resign threshold, and ruleset of
+///   the engine, and `PATCH /engine/settings` changes them at runtime: every game searches with
+///   the new settings from its next move, and games started after the change use the new ruleset.
+/// - `GET /healthz` answers `ok` while serving, and `GET /metrics` exposes request counts, active
+///   games, search iterations per second, and genmove latencies in the Prometheus text format.
+/// - `GET /docs` answers with the OpenAPI document of every route, for generating typed clients.
+///
+/// `-- grpc --port N` serves game management, move play, scoring, and streaming analysis as the
+/// gRPC service of `crates/go-api/proto/go_engine.proto`, on port 50051 by default. It
+/// requires the `grpc` feature.
+///
+/// `-- play --size N --komi F --color <black or white> --playouts N --view <view>` plays a game
+/// against the engine on the terminal: the human types a coordinate (ex. `Q16`), `pass`, or
+/// `resign` on `std::in`, the board is printed after every move with the view (as for `debug`),
+/// and the engine answers, until both pass or one resigns. The game is on a 9x9 board with 6.5
+/// komi and the human plays Black unless the options are given.
+///
+/// `-- png <game.sgf> --output <file.png> --move N` draws the position of the game after the given
+/// move, or else its final position, as a PNG image written to `board.png` by default. It
+/// requires the `image` feature.
+///
+/// `-- bench --seed N` runs the standard benchmark workloads and prints a report on `std::out`,
+/// seeding the engine's random choices so runs are repeatable. The same workloads are measured
+/// statistically by `cargo bench -p go-engine`.
+///
+/// `-- selfplay [games] [directory] --size N --playouts N --noise F` plays the engine against
+/// itself, writing each game as an SGF file to the directory and printing aggregate results.
+///
+/// `-- analyze <game.sgf> --visits N --output <file.sgf> --threads N` annotates every move of the
+/// game with the engine's evaluation, writes the annotated SGF, and prints the biggest mistakes.
+/// Positions are searched in parallel, on every available core unless `--threads` is given.
+///
+/// `-- score <position.sgf or directory>... --rules <rules> --threads N` removes dead stones from
+/// the final position of each game and prints its result (ex. `B+3.5`), counting area or
+/// territory depending on the rules. Several games are scored in parallel, each result
+/// prefixed by its file.
+///
+/// `-- puzzles <games>... --output <directory> --visits N --min-stones N` scans SGF files (or
+/// directories of them) for positions with a single large capture the engine also finds, and
+/// exports each as a tsumego SGF with its solution line.
+///
+/// `-- match "<engine command>" --games N --size N --komi F --playouts N --book <file> --output <directory>`
+/// plays a match against an external GTP engine (ex. `"gnugo --mode gtp"`), alternating colors
+/// and openings from the book, writing an SGF and GTP log per game, restarting the engine if it
+/// crashes, and appending each result to `results.csv` in the directory.
+///
+/// `-- regress <file.tst>... --engine "<engine command>" --history <file.csv>` runs GNU Go style
+/// regression test files against this engine (or the given GTP engine), printing unexpected
+/// results and appending each file's totals to the history file.
+///
+/// `-- patterns <file.sgf or directory>... --output <file>` counts how often professionals played
+/// each local 3x3 pattern when it was available, writing the table to `patterns.bin` by default.
+/// Playouts choose between candidate moves by these frequencies when `GO_ENGINE_PATTERNS` holds
+/// the path of such a table.
+///
+/// `-- index <directory> --output <file>` indexes every position of the SGF games in the directory
+/// by a hash shared by its 8 symmetries, writing `positions.idx` in the directory by default.
+///
+/// `-- search <query.sgf> --index <file>` finds the games containing the final position of the
+/// query under any symmetry, and `-- search <query.sgf> --pattern <directory>` instead scans the
+/// games of the directory for the query's stones as a local pattern, ex. for joseki study.
+///
+/// `-- debug --view <ascii, terminal, kifu, or accessible>` plays a few moves, printing the board
+/// after each with the plain ASCII view by default, with box-drawing characters and ANSI colors in
+/// a terminal, as a diagram of the game with every stone numbered by its move, or described in
+/// plain sentences for screen readers.
+///
+/// Logging is written to `std::err` (never `std::out`) and configured with the `GO_ENGINE_LOG`
+/// filter (ex. `info` or `engine::gtp=debug`) and the optional `GO_ENGINE_LOG_FILE` path.
+///
+/// Errors (ex. an unknown run mode, bad arguments, or a failure to read or write a file) are
+/// reported on `std::err`, and the program exits with code 2 for usage errors and 1 otherwise.
+pub fn main() -> ExitCode {
     use std::env;
 
     let arguments: Vec<String> = env::args().collect();
 
     if arguments.len() > 1 && arguments[1].eq_ignore_ascii_case("debug") {
-        debug();
-    } else {
-        start_go_agent(arguments);
+        let view = match arguments.get(2..4) {
+            Some([flag, name]) if flag == "--view" => name.as_str(),
+            _ => "ascii",
+        };
+        let Some(view) = view_named(view) else {
+            eprintln!("error: unknown view {view}, expected ascii, terminal, kifu, or accessible");
+            return ExitCode::from(2);
+        };
+        debug(view.as_ref());
+        return ExitCode::SUCCESS;
+    }
+
+    if arguments.len() > 1 && arguments[1].eq_ignore_ascii_case("play") {
+        let settings = match PlaySettings::from_args(&arguments[2..]) {
+            Ok(settings) => settings,
+            Err(error) => {
+                eprintln!("error: {error}");
+                return ExitCode::from(2);
+            }
+        };
+        return match play(settings) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(error) => {
+                eprintln!("error: {error}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    match start_agent(arguments) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::from(error.exit_code())
+        }
+    }
+}
+
+/// Runs the run mode given on the command line with the crate serving it, or reports the feature
+/// to rebuild with if it was left out.
+fn start_agent(arguments: Vec<String>) -> Result<(), AgentError> {
+    let mode = arguments.get(1).map_or("", String::as_str);
+    match FEATURE_MODES.iter().find(|(name, _)| name.eq_ignore_ascii_case(mode)) {
+        #[cfg(feature = "gtp")]
+        Some((_, "gtp")) => go_gtp::start_gtp_agent(arguments),
+        #[cfg(any(feature = "api", feature = "grpc"))]
+        Some((_, "api" | "grpc")) => go_api::start_api_agent(arguments),
+        Some((mode, feature)) => Err(AgentError::MissingFeature(mode, feature)),
+        None => engine::start_go_agent(arguments),
     }
 }
 
 #[doc(hidden)]
-/// Debug method for testing work in progress code.
-fn debug() {
+/// Debug method for testing work in progress code, printing the board with the given [`View`].
+fn debug(view: &dyn View) {
     let mut board = board::Board::new();
     
     let move_list = vec![
-        (Color::Black, Position {row: 4, col: 4}),
-        (Color::White, Position {row: 3, col: 4}),
-        (Color::Black, Position {row: 3, col: 5}),
-        (Color::White, Position {row: 2, col: 5}),
-        (Color::Black, Position {row: 4, col: 6}),
-        (Color::White, Position {row: 3, col: 6}),
-        (Color::Black, Position {row: 5, col: 5}),
-        (Color::White, Position {row: 4, col: 5}),
-        (Color::Black, Position {row: 3, col: 5}),
+        (Color::Black, Position::new(4, 4)),
+        (Color::White, Position::new(3, 4)),
+        (Color::Black, Position::new(3, 5)),
+        (Color::White, Position::new(2, 5)),
+        (Color::Black, Position::new(4, 6)),
+        (Color::White, Position::new(3, 6)),
+        (Color::Black, Position::new(5, 5)),
+        (Color::White, Position::new(4, 5)),
+        (Color::Black, Position::new(3, 5)),
     ];
     
-    println!("{board}\n");
+    println!("{}", view.render(&board));
     
     for mov in move_list {
         update(&mut board, Message::Play(mov.0, mov.1)).expect("Something went wrong");
-        println!("{board}\n")
+        println!("{}", view.render(&board))
+    }
+}
+
+/// The settings of a game against the engine in the `play` run mode.
+struct PlaySettings {
+    size: BoardSize,
+    komi: f64,
+    human: Color,
+    playouts: Option<u16>,
+    view: Box<dyn View>,
+}
+
+impl PlaySettings {
+    /// Parses the settings from the arguments following the `play` run mode, giving why they are
+    /// invalid on failure.
+    fn from_args(args: &[String]) -> Result<PlaySettings, String> {
+        let mut settings = PlaySettings {
+            size: BoardSize::Nine,
+            komi: 6.5,
+            human: Color::Black,
+            playouts: None,
+            view: view_named("ascii").expect("the ascii view exists"),
+        };
+
+        let mut args = args.iter();
+        while let Some(option) = args.next() {
+            let value = args.next().ok_or(format!("No value given to play option {option}"))?;
+            match option.as_str() {
+                "--size" => {
+                    settings.size = value
+                        .parse()
+                        .ok()
+                        .and_then(BoardSize::from_u16)
+                        .ok_or(format!("Invalid board size given to play: {value}"))?;
+                }
+                "--komi" => {
+                    settings.komi = value
+                        .parse()
+                        .map_err(|_| format!("Invalid komi given to play: {value}"))?;
+                }
+                "--color" => {
+                    settings.human =
+                        Color::from_name(value).ok_or(format!("Invalid color given to play: {value}"))?;
+                }
+                "--playouts" => {
+                    let playouts = value
+                        .parse()
+                        .map_err(|_| format!("Invalid playout count given to play: {value}"))?;
+                    settings.playouts = Some(playouts);
+                }
+                "--view" => {
+                    settings.view =
+                        view_named(value).ok_or(format!("Invalid view given to play: {value}"))?;
+                }
+                _ => return Err(format!("Unknown play option: {option}")),
+            }
+        }
+
+        Ok(settings)
+    }
+}
+
+/// Plays a game between a human on `std::in` and the engine with the given [`PlaySettings`],
+/// printing the board after every move, until both players pass, one resigns, or the input ends.
+fn play(settings: PlaySettings) -> io::Result<()> {
+    let mut engine = Engine::new(settings.size, settings.komi);
+    if let Some(playouts) = settings.playouts {
+        engine.set_playouts(playouts);
+    }
+    // the board follows the engine's rules, so that both refuse the same moves
+    let mut board = engine.board();
+
+    let mut lines = io::stdin().lock().lines();
+    println!("{}", settings.view.render(&board));
+    loop {
+        let color = board.player_turn();
+        let name = player_name(color);
+        let mov = if color == settings.human {
+            print!("{name} to play (ex. D4, pass, or resign): ");
+            io::stdout().flush()?;
+            let Some(line) = lines.next().transpose()? else { return Ok(()) };
+            match line.trim().to_ascii_lowercase().as_str() {
+                "" => continue,
+                "pass" => EngineMove::Pass,
+                "resign" => EngineMove::Resign,
+                vertex => match Position::parse_vertex(vertex, settings.size) {
+                    Ok(Some(pos)) => EngineMove::Play(pos),
+                    _ => {
+                        println!("{} is not a point of the board", line.trim());
+                        continue;
+                    }
+                },
+            }
+        } else {
+            let mov = engine.genmove(color);
+            match mov {
+                EngineMove::Play(pos) => println!("{name} plays {pos}"),
+                EngineMove::Pass => println!("{name} passes"),
+                EngineMove::Resign => {}
+            }
+            mov
+        };
+
+        match mov {
+            EngineMove::Play(pos) => {
+                if let Err(error) = update(&mut board, Message::Play(color, pos)) {
+                    if color != settings.human {
+                        // the engine has played the move already, so the game cannot go on
+                        let error = format!("The engine played {pos}, which the board refused: {error}");
+                        return Err(io::Error::other(error));
+                    }
+                    println!("{error}");
+                    continue;
+                }
+                // the engine has played its own moves already
+                if color == settings.human {
+                    if let Err(error) = engine.play(color, pos) {
+                        update(&mut board, Message::Undo).map_err(io::Error::other)?;
+                        println!("{error}");
+                        continue;
+                    }
+                }
+            }
+            EngineMove::Pass => {
+                update(&mut board, Message::Pass).map_err(io::Error::other)?;
+                if color == settings.human {
+                    engine.pass(color);
+                }
+            }
+            EngineMove::Resign => {
+                println!("{name} resigns, {} wins", player_name(color.opposite_color()));
+                return Ok(());
+            }
+        }
+
+        println!("{}", settings.view.render(&board));
+        if board.result().is_some() {
+            println!("Both players passed: {}", engine.result(ScoringRules::Area));
+            return Ok(());
+        }
+    }
+}
+
+/// The name of the player of the given [`Color`].
+fn player_name(color: Color) -> &'static str {
+    match color {
+        Color::Black => "Black",
+        Color::White => "White",
     }
 }
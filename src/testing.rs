@@ -0,0 +1,99 @@
+//! Property-based testing support for the rules of Go and the [`Engine`].
+//!
+//! Enabled with the `proptest` feature. Re-exports the [`proptest`] strategies, [`Arbitrary`]
+//! implementations, and invariant checks of [`go_board::testing`], and adds checks that the
+//! [`Engine`] and the views agree with the rules of [`update`].
+//!
+//! ```
+//! use go_engine::testing::{check_liberties, Game};
+//! use proptest::prelude::*;
+//!
+//! proptest!(|(game: Game)| {
+//!     prop_assert!(check_liberties(&game.board()).is_ok());
+//! });
+//! ```
+//!
+//! [`Arbitrary`]: proptest::arbitrary::Arbitrary
+
+use crate::agent::Engine;
+use crate::board::{Board, Color, ScoringRules};
+use crate::update::{update, Message};
+use crate::view::{AsciiView, View};
+
+pub use go_board::testing::*;
+
+/// Replays this game in an [`Engine`] with no komi.
+fn engine_replay(game: &Game) -> Result<Engine, String> {
+    let mut engine = Engine::new(game.size, 0.0);
+    for (color, mov) in &game.moves {
+        match mov {
+            Some(pos) => engine.play(*color, *pos)?,
+            None => engine.pass(*color),
+        }
+    }
+
+    Ok(engine)
+}
+
+/// Checks that undoing every move of the [`Game`] returns to an empty [`Board`], and redoing them
+/// all returns to the final position.
+///
+/// Outputs a [`String`] describing the first mismatched position on failure.
+pub fn check_undo_redo(game: &Game) -> Result<(), String> {
+    let expected = game.board();
+    let mut board = game.board();
+    for _ in &game.moves {
+        update(&mut board, Message::Undo).map_err(|error| error.to_string())?;
+    }
+    if update(&mut board, Message::Undo).is_ok() || !board.moves().is_empty() || board.hash() != 0 {
+        let board = AsciiView.render(&board);
+        return Err(format!("Undoing every move reached\n{board}instead of an empty board"));
+    }
+
+    for _ in &game.moves {
+        update(&mut board, Message::Redo).map_err(|error| error.to_string())?;
+    }
+    let captures = |board: &Board| (board.captures(Color::Black), board.captures(Color::White));
+    let same_game = board.moves() == expected.moves()
+        && board.player_turn() == expected.player_turn()
+        && captures(&board) == captures(&expected);
+    if board.hash() != expected.hash() || board.to_string() != expected.to_string() || !same_game {
+        let (board, expected) = (AsciiView.render(&board), AsciiView.render(&expected));
+        return Err(format!("Redoing every move reached\n{board}instead of\n{expected}"));
+    }
+
+    Ok(())
+}
+
+/// Checks that the [`Engine`] accepts every move of the [`Game`] and reaches the same stones.
+///
+/// The engine implements the rules separately from [`update`], so any disagreement is a bug in
+/// one of them. Outputs a [`String`] describing the disagreement on failure.
+pub fn check_engine_agreement(game: &Game) -> Result<(), String> {
+    let engine = engine_replay(game).map_err(|error| format!("Engine rejected a move: {error}"))?;
+    let (expected, actual) = (game.board(), engine.board());
+    if expected.to_string() != actual.to_string() {
+        return Err(format!("Engine reached\n{actual}\ninstead of\n{expected}"));
+    }
+
+    Ok(())
+}
+
+/// Checks that swapping the colors of every move of the [`Game`] negates its score, under both
+/// [`ScoringRules`] and with no komi.
+///
+/// Outputs a [`String`] with the mismatched scores on failure.
+pub fn check_score_symmetry(game: &Game) -> Result<(), String> {
+    let engine = engine_replay(game)?;
+    let swapped = engine_replay(&game.swap_colors())?;
+    for rules in [ScoringRules::Area, ScoringRules::Territory] {
+        let (score, swapped_score) = (engine.score(rules), swapped.score(rules));
+        if score != -swapped_score {
+            return Err(format!(
+                "{rules:?} score {score} is not the negation of swapped score {swapped_score}"
+            ));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+//! Property-based tests of the engine and views against the rules of Go, using the strategies of
+//! [`crate::testing`].
+
+use crate::testing::*;
+use proptest::prelude::*;
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn undo_and_redo_walk_the_game(game: Game) {
+        prop_assert_eq!(check_undo_redo(&game), Ok(()));
+    }
+
+    #[test]
+    fn engine_agrees_with_update(game: Game) {
+        prop_assert_eq!(check_engine_agreement(&game), Ok(()));
+    }
+
+    #[test]
+    fn score_is_symmetric(game: Game) {
+        prop_assert_eq!(check_score_symmetry(&game), Ok(()));
+    }
+
+    #[test]
+    fn kifu_numbers_every_move(game: Game) {
+        use crate::view::{KifuView, View};
+
+        // every move labels a stone of the diagram, or else is listed below it
+        let kifu = KifuView.render(&game.board());
+        let labels = kifu
+            .lines()
+            .filter(|line| line.starts_with(|c: char| c == ' ' || c.is_ascii_digit()))
+            .flat_map(|row| {
+                let points: Vec<&str> = row.split_whitespace().collect();
+                points[1..points.len().saturating_sub(1)].to_vec()
+            })
+            .filter(|point| point.parse::<usize>().is_ok())
+            .count();
+        let notes = kifu.lines().filter(|line| line.contains(": ")).count();
+        prop_assert_eq!(labels + notes, game.moves.len());
+    }
+
+    #[test]
+    fn diff_against_itself_is_empty(game: Game) {
+        use crate::view::{DiffView, View};
+
+        let board = game.board();
+        let diff = DiffView::new(&board).render(&board);
+        prop_assert!(!diff.contains('+') && !diff.contains('-'));
+        prop_assert!(diff.ends_with("0 added, 0 removed\n"));
+    }
+}
+
+#[test]
+fn engine_board_keeps_the_ko() {
+    use crate::{BoardSize, Color, Engine, Position};
+
+    // Black captures the white stone at (4, 4) by playing at (4, 5), which White may not retake
+    let mut engine = Engine::new(BoardSize::Nine, 6.5);
+    let moves = [(4, 3), (3, 5), (3, 4), (5, 5), (5, 4), (4, 6), (0, 0), (4, 4), (4, 5)];
+    for (i, (row, col)) in moves.into_iter().enumerate() {
+        let color = if i % 2 == 0 { Color::Black } else { Color::White };
+        engine.play(color, Position::new(row, col)).unwrap();
+    }
+    let retake = Position::new(4, 4);
+    assert!(engine.play(Color::White, retake).is_err());
+
+    let board = engine.board();
+    assert_eq!(board.ko(), Some(retake));
+    assert!(board.try_play(Color::White, retake).is_err());
+    assert_eq!(board.captures(Color::Black), 1);
+    assert_eq!(board.moves().len(), moves.len());
+}
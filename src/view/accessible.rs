@@ -0,0 +1,83 @@
+//! A plain language description of the position, for screen readers.
+
+use std::cmp::Ordering;
+use std::fmt::Write;
+use crate::board::{Board, Color, GameResult, State};
+use crate::update::Position;
+use crate::view::{player_name, View};
+
+/// A [`View`] describing the position in plain sentences rather than drawing it, for players
+/// using screen readers: the size of the board, where each player's stones are, the last move,
+/// whose turn it is, and who leads in captures.
+///
+/// ```
+/// use go_engine::{update, AccessibleView, Board, BoardSize, Color, Message, Position, View};
+///
+/// let mut board = Board::new();
+/// update(&mut board, Message::SetSize(BoardSize::Nine)).unwrap();
+/// update(&mut board, Message::Play(Color::Black, Position::new(3, 3))).unwrap();
+/// update(&mut board, Message::Play(Color::White, Position::new(2, 6))).unwrap();
+/// update(&mut board, Message::Play(Color::Black, Position::new(6, 2))).unwrap();
+///
+/// assert_eq!(
+///     AccessibleView.render(&board),
+///     "9 by 9 board after 3 moves.\n\
+///      Black stones: C7, D4.\n\
+///      White stones: G3.\n\
+///      Last move: Black C7.\n\
+///      White to play.\n\
+///      Captures are even at 0.\n"
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct AccessibleView;
+
+impl View for AccessibleView {
+    fn render(&self, board: &Board) -> String {
+        let size = board.size().to_u16();
+        let moves = board.moves().len();
+        let mut out = String::new();
+
+        let plural = if moves == 1 { "move" } else { "moves" };
+        let _ = writeln!(out, "{size} by {size} board after {moves} {plural}.");
+
+        // stones are read column by column, as players name them
+        for color in [Color::Black, Color::White] {
+            let stones: Vec<String> = (0..size)
+                .flat_map(|col| (0..size).map(move |row| Position::new(row, col)))
+                .filter(|pos| board.state(*pos) == Some(State::Occupied(color)))
+                .map(|pos| pos.to_string())
+                .collect();
+            let stones = if stones.is_empty() { "none".to_string() } else { stones.join(", ") };
+            let _ = writeln!(out, "{} stones: {stones}.", player_name(color));
+        }
+
+        let _ = match board.moves().last() {
+            Some((color, Some(pos))) => {
+                writeln!(out, "Last move: {} {}.", player_name(*color), pos)
+            }
+            Some((color, None)) => writeln!(out, "Last move: {} passed.", player_name(*color)),
+            None => Ok(()),
+        };
+
+        let _ = match board.result() {
+            Some(GameResult::Score(color, margin)) => {
+                writeln!(out, "Game over, {} wins by {margin} points.", player_name(color))
+            }
+            Some(GameResult::Draw) => writeln!(out, "Game over, the game is a draw."),
+            Some(GameResult::Resignation(color)) => {
+                writeln!(out, "Game over, {} wins by resignation.", player_name(color))
+            }
+            None => writeln!(out, "{} to play.", player_name(board.player_turn())),
+        };
+
+        let (black, white) = (board.captures(Color::Black), board.captures(Color::White));
+        let _ = match black.cmp(&white) {
+            Ordering::Greater => writeln!(out, "Black leads captures {black} to {white}."),
+            Ordering::Less => writeln!(out, "White leads captures {white} to {black}."),
+            Ordering::Equal => writeln!(out, "Captures are even at {black}."),
+        };
+
+        out
+    }
+}
@@ -0,0 +1,73 @@
+//! A plain text view of the board.
+
+use std::fmt::Write;
+use crate::board::{Board, Color, State, COLUMN_LETTERS};
+use crate::update::Position;
+use crate::view::{is_star_point, last_stone, player_name, View};
+
+/// A [`View`] drawing the board in plain ASCII, with the columns labeled by letter and the rows
+/// by number, followed by the stones each player has captured and the player to move.
+///
+/// Empty star points are drawn as `+`, and the stone of the last move is wrapped in parentheses.
+///
+/// ```
+/// use go_engine::{update, AsciiView, Board, BoardSize, Color, Message, Position, View};
+///
+/// let mut board = Board::new();
+/// update(&mut board, Message::SetSize(BoardSize::Nine)).unwrap();
+/// update(&mut board, Message::Play(Color::Black, Position::new(2, 2))).unwrap();
+///
+/// let rendered = AsciiView.render(&board);
+/// assert!(rendered.starts_with("   A B C D E F G H J\n"));
+/// assert!(rendered.contains(" 3 . .(X). . . + . .  3\n"));
+/// assert!(rendered.contains(" 5 . . . . + . . . .  5\n"));
+/// assert!(rendered.ends_with("White (O) to play\n"));
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct AsciiView;
+
+impl View for AsciiView {
+    fn render(&self, board: &Board) -> String {
+        let size = board.size().to_u16();
+        let letters: String =
+            COLUMN_LETTERS.chars().take(size as usize).flat_map(|c| [' ', c]).collect();
+        let mut out = String::new();
+
+        let last_move = last_stone(board);
+        let _ = writeln!(out, "  {letters}");
+        for row in (0..size).rev() {
+            // each point follows a space, which the parentheses around the last move replace
+            let mut line: Vec<char> = vec![];
+            for col in 0..size {
+                let pos = Position::new(row, col);
+                line.push(' ');
+                line.push(match board.state(pos) {
+                    Some(State::Occupied(Color::Black)) => 'X',
+                    Some(State::Occupied(Color::White)) => 'O',
+                    _ if is_star_point(pos, size) => '+',
+                    _ => '.',
+                });
+            }
+            line.push(' ');
+            if let Some(last) = last_move.filter(|last| last.row() == row) {
+                let index = 2 * last.col() as usize;
+                (line[index], line[index + 2]) = ('(', ')');
+            }
+            let line: String = line.into_iter().collect();
+            let _ = writeln!(out, "{:>2}{line}{:>2}", row + 1, row + 1);
+        }
+        let _ = writeln!(out, "  {letters}");
+
+        for color in [Color::Black, Color::White] {
+            let captures = board.captures(color);
+            let _ = writeln!(out, "{} ({color}) captures: {captures}", player_name(color));
+        }
+        let to_play = board.player_turn();
+        let _ = match board.result() {
+            Some(result) => writeln!(out, "Game over: {result}"),
+            None => writeln!(out, "{} ({to_play}) to play", player_name(to_play)),
+        };
+
+        out
+    }
+}
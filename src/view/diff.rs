@@ -0,0 +1,100 @@
+//! A view of the difference between two positions.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use crate::board::{Board, Color, State, COLUMN_LETTERS};
+use crate::update::Position;
+use crate::view::{player_name, View};
+
+/// A [`View`] drawing the difference between an earlier position and the rendered [`Board`],
+/// marking each stone the board added with `+` and each stone it removed, ex. by a capture, with
+/// `-` before the stone, while unchanged stones are drawn as by [`AsciiView`](crate::AsciiView).
+///
+/// ```
+/// use go_engine::{update, Board, BoardSize, Color, DiffView, Message, Position, View};
+///
+/// let mut board = Board::new();
+/// update(&mut board, Message::SetSize(BoardSize::Nine)).unwrap();
+/// for (color, row, col) in [(Color::Black, 1, 0), (Color::White, 0, 0), (Color::Black, 5, 5)] {
+///     update(&mut board, Message::Play(color, Position::new(row, col))).unwrap();
+/// }
+///
+/// // Black captures the white stone in the corner
+/// let before = DiffView::new(&board);
+/// update(&mut board, Message::Play(Color::White, Position::new(7, 7))).unwrap();
+/// update(&mut board, Message::Play(Color::Black, Position::new(0, 1))).unwrap();
+///
+/// let rendered = before.render(&board);
+/// assert!(rendered.contains(" 2 X . . . . . . . .  2\n"));
+/// assert!(rendered.contains(" 1-O+X . . . . . . .  1\n"));
+/// assert!(rendered.ends_with("2 added, 1 removed, Black captured 1\n"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct DiffView {
+    before: HashMap<Position, Color>,
+}
+
+impl DiffView {
+    /// Constructs a new [`DiffView`] comparing the boards it renders against the current position
+    /// of the given [`Board`].
+    pub fn new(before: &Board) -> Self {
+        let before = before
+            .intersections()
+            .filter_map(|(pos, state)| match state {
+                State::Occupied(color) => Some((pos, color)),
+                _ => None,
+            })
+            .collect();
+        DiffView { before }
+    }
+}
+
+impl View for DiffView {
+    fn render(&self, board: &Board) -> String {
+        let size = board.size().to_u16();
+        let letters: String =
+            COLUMN_LETTERS.chars().take(size as usize).flat_map(|c| [' ', c]).collect();
+        let (mut added, mut black_removed, mut white_removed) = (0, 0, 0);
+        let mut out = String::new();
+
+        let _ = writeln!(out, "  {letters}");
+        for row in (0..size).rev() {
+            let _ = write!(out, "{:>2}", row + 1);
+            for col in 0..size {
+                let pos = Position::new(row, col);
+                let before = self.before.get(&pos).copied();
+                let after = match board.state(pos) {
+                    Some(State::Occupied(color)) => Some(color),
+                    _ => None,
+                };
+                if before != after {
+                    added += after.is_some() as u16;
+                    match before {
+                        Some(Color::Black) => black_removed += 1,
+                        Some(Color::White) => white_removed += 1,
+                        None => {}
+                    }
+                }
+                let _ = match (before, after) {
+                    (_, Some(after)) if before == Some(after) => write!(out, " {after}"),
+                    (_, Some(after)) => write!(out, "+{after}"),
+                    (Some(before), None) => write!(out, "-{before}"),
+                    (None, None) => write!(out, " ."),
+                };
+            }
+            let _ = writeln!(out, " {:>2}", row + 1);
+        }
+        let _ = writeln!(out, "  {letters}");
+
+        let _ = write!(out, "{added} added, {} removed", black_removed + white_removed);
+        // the stones of one color removed were captured by the other
+        for (color, captured) in [(Color::Black, white_removed), (Color::White, black_removed)] {
+            if captured > 0 {
+                let _ = write!(out, ", {} captured {captured}", player_name(color));
+            }
+        }
+        out.push('\n');
+
+        out
+    }
+}
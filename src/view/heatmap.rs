@@ -0,0 +1,116 @@
+//! An overlay of the statistics of the engine's search on the board.
+
+use std::fmt::Write;
+use crate::agent::{Candidate, EngineMove};
+use crate::board::{Board, State, COLUMN_LETTERS};
+use crate::update::Position;
+use crate::view::{player_name, View};
+
+/// The number of the most visited [`Candidate`]s listed below the board.
+const LISTED_CANDIDATES: usize = 5;
+
+/// The statistic of the search a [`HeatmapView`] shows.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Heat {
+    /// How often the search visited each move, relative to the most visited move.
+    Visits,
+    /// The estimated probability of winning after each move.
+    Winrate,
+}
+
+/// A [`View`] overlaying the statistics of a search of the engine on the board, so it shows where
+/// the engine is focusing.
+///
+/// Each move the search considered is drawn as a digit from `0` to `9`, its share of the visits
+/// of the most visited move or its winrate in tenths, over the board drawn with `X` for Black's
+/// stones and `O` for White's. The most visited moves are listed below the board with their
+/// visits and winrates.
+///
+/// ```
+/// use go_engine::{BoardSize, Color, Engine, HeatmapView, Position, View};
+///
+/// let mut engine = Engine::new(BoardSize::Nine, 6.5);
+/// engine.play(Color::Black, Position::new(4, 4)).unwrap();
+/// engine.play(Color::White, Position::new(2, 2)).unwrap();
+///
+/// let candidates = engine.candidates(200);
+/// let rendered = HeatmapView::visits(candidates.clone()).render(&engine.board());
+/// assert!(rendered.contains(" X . . . .  5\n") && rendered.contains(" O "));
+/// assert!(rendered.contains("Black to play, most visited moves:\n"));
+///
+/// // the most visited move is the hottest
+/// assert!(candidates.is_empty() || rendered.contains('9'));
+/// ```
+#[derive(Clone, Debug)]
+pub struct HeatmapView {
+    candidates: Vec<Candidate>,
+    heat: Heat,
+}
+
+impl HeatmapView {
+    /// Constructs a new [`HeatmapView`] showing the given [`Heat`] of the given [`Candidate`]s,
+    /// ex. those of [`Engine::candidates`](crate::Engine::candidates).
+    pub fn new(candidates: Vec<Candidate>, heat: Heat) -> Self {
+        HeatmapView { candidates, heat }
+    }
+
+    /// Constructs a new [`HeatmapView`] showing the visits of the given [`Candidate`]s.
+    pub fn visits(candidates: Vec<Candidate>) -> Self {
+        HeatmapView::new(candidates, Heat::Visits)
+    }
+
+    /// Constructs a new [`HeatmapView`] showing the winrates of the given [`Candidate`]s.
+    pub fn winrates(candidates: Vec<Candidate>) -> Self {
+        HeatmapView::new(candidates, Heat::Winrate)
+    }
+
+    /// The intensity, from 0 to 9, of the given [`Candidate`].
+    fn intensity(&self, candidate: &Candidate) -> u32 {
+        match self.heat {
+            Heat::Visits => {
+                let most = self.candidates.iter().map(|candidate| candidate.visits).max();
+                (u32::from(candidate.visits) * 9).div_ceil(u32::from(most.unwrap_or(1).max(1)))
+            }
+            Heat::Winrate => ((candidate.winrate * 10.0) as u32).min(9),
+        }
+    }
+}
+
+impl View for HeatmapView {
+    fn render(&self, board: &Board) -> String {
+        let size = board.size().to_u16();
+        let letters: String =
+            COLUMN_LETTERS.chars().take(size as usize).flat_map(|c| [' ', c]).collect();
+        let mut out = String::new();
+
+        let _ = writeln!(out, "  {letters}");
+        for row in (0..size).rev() {
+            let _ = write!(out, "{:>2}", row + 1);
+            for col in 0..size {
+                let pos = Position::new(row, col);
+                let candidate =
+                    self.candidates.iter().find(|candidate| candidate.mov == EngineMove::Play(pos));
+                let _ = match (board.state(pos), candidate) {
+                    (Some(State::Occupied(color)), _) => write!(out, " {color}"),
+                    (_, Some(candidate)) => write!(out, " {}", self.intensity(candidate)),
+                    _ => write!(out, " ."),
+                };
+            }
+            let _ = writeln!(out, " {:>2}", row + 1);
+        }
+        let _ = writeln!(out, "  {letters}");
+
+        let _ = writeln!(out, "{} to play, most visited moves:", player_name(board.player_turn()));
+        for candidate in self.candidates.iter().take(LISTED_CANDIDATES) {
+            let vertex = match candidate.mov {
+                EngineMove::Play(pos) => pos.to_string(),
+                EngineMove::Pass => "pass".to_string(),
+                EngineMove::Resign => "resign".to_string(),
+            };
+            let (visits, winrate) = (candidate.visits, candidate.winrate * 100.0);
+            let _ = writeln!(out, "{vertex:>6}: {visits} visits, {winrate:.1}% winrate");
+        }
+
+        out
+    }
+}
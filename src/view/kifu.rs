@@ -0,0 +1,100 @@
+//! A numbered-move diagram of the game played on the board.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use crate::board::{Board, Color, COLUMN_LETTERS};
+use crate::update::Position;
+use crate::view::{is_star_point, player_name, View};
+
+/// A [`View`] drawing the game played on the board as a kifu, the diagram of printed game
+/// reviews: each stone is labeled with the number of the move that played it, stones placed
+/// outside of play are drawn as `X` and `O`, and empty star points as `+`. The moves played where
+/// an earlier stone stood, along with passes, are listed below the diagram.
+///
+/// Stones keep their labels after being captured, so the diagram shows the whole game rather than
+/// its current position.
+///
+/// ```
+/// use go_engine::{update, Board, BoardSize, Color, KifuView, Message, Position, View};
+///
+/// let mut board = Board::new();
+/// update(&mut board, Message::SetSize(BoardSize::Nine)).unwrap();
+/// update(&mut board, Message::Play(Color::Black, Position::new(2, 2))).unwrap();
+/// update(&mut board, Message::Play(Color::White, Position::new(6, 6))).unwrap();
+/// update(&mut board, Message::Pass).unwrap();
+///
+/// let rendered = KifuView.render(&board);
+/// assert!(rendered.contains(" 7 . . + . . . 2 . .  7\n"));
+/// assert!(rendered.contains(" 3 . . 1 . . . + . .  3\n"));
+/// assert!(rendered.contains("Black plays the odd moves, White the even moves\n3: pass\n"));
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct KifuView;
+
+/// The label of an intersection of a kifu.
+#[derive(Copy, Clone)]
+enum Label {
+    /// A stone placed outside of play.
+    Setup(Color),
+    /// A stone played by the move of the given number.
+    Move(usize),
+}
+
+impl View for KifuView {
+    fn render(&self, board: &Board) -> String {
+        let size = board.size().to_u16();
+        let mut labels: HashMap<Position, Label> = HashMap::new();
+        let mut notes: Vec<String> = vec![];
+
+        for (color, pos) in board.setup() {
+            labels.insert(*pos, Label::Setup(*color));
+        }
+        for (index, (_, pos)) in board.moves().iter().enumerate() {
+            let number = index + 1;
+            match pos.map(|pos| (pos, labels.get(&pos).copied())) {
+                None => notes.push(format!("{number}: pass")),
+                Some((pos, None)) => {
+                    labels.insert(pos, Label::Move(number));
+                }
+                Some((_, Some(Label::Move(earlier)))) => {
+                    notes.push(format!("{number}: at {earlier}"));
+                }
+                Some((pos, Some(Label::Setup(_)))) => {
+                    notes.push(format!("{number}: at {pos}"));
+                }
+            }
+        }
+
+        // every label is as wide as the number of the last move
+        let width = board.moves().len().to_string().len();
+        let mut out = String::new();
+        let letters: String =
+            COLUMN_LETTERS.chars().take(size as usize).map(|c| format!(" {c:>width$}")).collect();
+
+        let _ = writeln!(out, "  {letters}");
+        for row in (0..size).rev() {
+            let _ = write!(out, "{:>2}", row + 1);
+            for col in 0..size {
+                let pos = Position::new(row, col);
+                let _ = match labels.get(&pos) {
+                    Some(Label::Move(number)) => write!(out, " {number:>width$}"),
+                    Some(Label::Setup(color)) => write!(out, " {:>width$}", color.to_string()),
+                    None if is_star_point(pos, size) => write!(out, " {:>width$}", "+"),
+                    None => write!(out, " {:>width$}", "."),
+                };
+            }
+            let _ = writeln!(out, " {:>2}", row + 1);
+        }
+        let _ = writeln!(out, "  {letters}");
+
+        if let Some((first, _)) = board.moves().first() {
+            let (odd, even) = (player_name(*first), player_name(first.opposite_color()));
+            let _ = writeln!(out, "{odd} plays the odd moves, {even} the even moves");
+        }
+        for note in notes {
+            let _ = writeln!(out, "{note}");
+        }
+
+        out
+    }
+}
@@ -3,7 +3,73 @@
 //! This module provides a few concrete implementations of views and the [`View`] trait for 
 //! implementing custom views.
 
+mod accessible;
+mod ascii;
+mod diff;
+mod heatmap;
+mod kifu;
+mod terminal;
+
+pub use accessible::AccessibleView;
+pub use ascii::AsciiView;
+pub use diff::DiffView;
+pub use heatmap::{Heat, HeatmapView};
+pub use kifu::KifuView;
+pub use terminal::TerminalView;
+
+use crate::board::{Board, Color, State};
+use crate::update::Position;
+
 /// The required functionality that any view of a game of Go must implement.
 pub trait View {
-    // todo
-}
\ No newline at end of file
+    /// Renders the current position of the given [`Board`] as text.
+    fn render(&self, board: &Board) -> String;
+}
+
+/// The [`View`] of the given name, `ascii` for [`AsciiView`], `terminal` for [`TerminalView`],
+/// `kifu` for [`KifuView`], or `accessible` for [`AccessibleView`], ignoring case, so views can be
+/// chosen at runtime.
+///
+/// ```
+/// use go_engine::view::view_named;
+///
+/// assert!(view_named("Terminal").is_some());
+/// assert!(view_named("braille").is_none());
+/// ```
+pub fn view_named(name: &str) -> Option<Box<dyn View>> {
+    match name.to_ascii_lowercase().as_str() {
+        "ascii" => Some(Box::new(AsciiView)),
+        "terminal" => Some(Box::new(TerminalView)),
+        "kifu" => Some(Box::new(KifuView)),
+        "accessible" => Some(Box::new(AccessibleView)),
+        _ => None,
+    }
+}
+
+/// Whether the given [`Position`] is a star point (hoshi) of a board of the given size: the
+/// corner points and center of a 9x9 board, along with the sides on larger boards.
+pub(crate) fn is_star_point(pos: Position, size: u16) -> bool {
+    let edge = if size == 9 { 2 } else { 3 };
+    let lines = [edge, size / 2, size - 1 - edge];
+    let (row, col) = (pos.row(), pos.col());
+    if !lines.contains(&row) || !lines.contains(&col) {
+        return false;
+    }
+    // only the center of the middle lines of a 9x9 board is a star point
+    size != 9 || (row == size / 2) == (col == size / 2)
+}
+
+/// The [`Position`] of the stone of the last move played on the given [`Board`], unless it was a
+/// pass, no move was played, or the stone was removed as suicide.
+pub(crate) fn last_stone(board: &Board) -> Option<Position> {
+    let last = board.moves().last().and_then(|(_, pos)| *pos)?;
+    matches!(board.state(last), Some(State::Occupied(_))).then_some(last)
+}
+
+/// The name of the player of the given [`Color`].
+pub(crate) fn player_name(color: Color) -> &'static str {
+    match color {
+        Color::Black => "Black",
+        Color::White => "White",
+    }
+}
@@ -0,0 +1,111 @@
+//! A colored view of the board for terminals.
+
+use std::fmt::Write;
+use crate::board::{Board, Color, State, COLUMN_LETTERS};
+use crate::update::Position;
+use crate::view::{is_star_point, last_stone, player_name, View};
+
+/// The ANSI escape codes coloring the board, drawn in 256 colors.
+const BOARD: &str = "\x1b[48;5;179m";
+const LINES: &str = "\x1b[38;5;94m";
+const BLACK_STONE: &str = "\x1b[38;5;16m";
+const WHITE_STONE: &str = "\x1b[38;5;231m";
+const LAST_MOVE: &str = "\x1b[48;5;220m";
+const KO: &str = "\x1b[38;5;160m";
+const RESET: &str = "\x1b[0m";
+
+/// A [`View`] drawing the board for terminals, with box-drawing characters for its lines and
+/// ANSI colors for its stones, highlighting the stone of the last move and marking the star points
+/// and the intersection forbidden by ko.
+///
+/// ```
+/// use go_engine::{update, Board, BoardSize, Color, Message, Position, TerminalView, View};
+///
+/// let mut board = Board::new();
+/// update(&mut board, Message::SetSize(BoardSize::Nine)).unwrap();
+/// update(&mut board, Message::Play(Color::Black, Position::new(2, 2))).unwrap();
+///
+/// let rendered = TerminalView.render(&board);
+/// assert!(rendered.starts_with("   A B C D E F G H J\n"));
+/// assert!(rendered.contains('●') && rendered.contains('┼') && rendered.contains('╋'));
+/// assert!(rendered.ends_with("White (○) to play\n"));
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TerminalView;
+
+impl View for TerminalView {
+    fn render(&self, board: &Board) -> String {
+        let size = board.size().to_u16();
+        let letters: String =
+            COLUMN_LETTERS.chars().take(size as usize).flat_map(|c| [' ', c]).collect();
+        let last_move = last_stone(board);
+        let mut out = String::new();
+
+        let _ = writeln!(out, "  {letters}");
+        for row in (0..size).rev() {
+            let _ = write!(out, "{:>2} {BOARD}", row + 1);
+            for col in 0..size {
+                let pos = Position::new(row, col);
+                let _ = match board.state(pos) {
+                    Some(State::Occupied(color)) if Some(pos) == last_move => {
+                        write!(out, "{LAST_MOVE}{}{BOARD}", stone(color))
+                    }
+                    Some(State::Occupied(color)) => write!(out, "{}", stone(color)),
+                    _ if board.ko() == Some(pos) => write!(out, "{KO}□"),
+                    _ if is_star_point(pos, size) => write!(out, "{LINES}╋"),
+                    _ => write!(out, "{LINES}{}", line(row, col, size)),
+                };
+                if col + 1 < size {
+                    let _ = write!(out, "{LINES}─");
+                }
+            }
+            let _ = writeln!(out, "{RESET} {}", row + 1);
+        }
+        let _ = writeln!(out, "  {letters}");
+
+        for color in [Color::Black, Color::White] {
+            let captures = board.captures(color);
+            let _ = writeln!(out, "{} ({}) captures: {captures}", player_name(color), glyph(color));
+        }
+        let to_play = board.player_turn();
+        let _ = match board.result() {
+            Some(result) => writeln!(out, "Game over: {result}"),
+            None => writeln!(out, "{} ({}) to play", player_name(to_play), glyph(to_play)),
+        };
+
+        out
+    }
+}
+
+/// The glyph of a stone of the given [`Color`].
+fn glyph(color: Color) -> char {
+    match color {
+        Color::Black => '●',
+        Color::White => '○',
+    }
+}
+
+/// A stone of the given [`Color`], colored for the board.
+fn stone(color: Color) -> String {
+    match color {
+        Color::Black => format!("{BLACK_STONE}●"),
+        Color::White => format!("{WHITE_STONE}●"),
+    }
+}
+
+/// The box-drawing character of the empty intersection at the given row and column of a board
+/// of the given size, where rows count up from the bottom.
+fn line(row: u16, col: u16, size: u16) -> char {
+    let (top, bottom, left, right) = (row + 1 == size, row == 0, col == 0, col + 1 == size);
+    match (top, bottom, left, right) {
+        (true, _, true, _) => '┌',
+        (true, _, _, true) => '┐',
+        (true, ..) => '┬',
+        (_, true, true, _) => '└',
+        (_, true, _, true) => '┘',
+        (_, true, ..) => '┴',
+        (.., true, _) => '├',
+        (.., true) => '┤',
+        _ => '┼',
+    }
+}
@@ -0,0 +1,298 @@
+//! WebAssembly bindings for embedding the [`Board`] and [`Engine`] in browser front ends.
+//!
+//! Enabled with the `wasm` feature. Colors are passed as `"black"` or `"white"` (or `"b"` and
+//! `"w"`), and intersections as vertices in Go notation, ex. `"D4"`, with `"pass"` for a pass, as
+//! in the Go Text Protocol. Failed moves throw a JavaScript `Error` with the reason.
+
+use crate::agent::{Engine, EngineMove};
+use crate::board::{Board, BoardSize, Color, State};
+use crate::update::{update, Message, Position, RuleViolation};
+use js_sys::{Array, Function, Object, Promise, Reflect};
+use std::cell::{Cell, RefCell};
+use std::fmt::Write;
+use std::rc::Rc;
+use std::time::Duration;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::{future_to_promise, JsFuture};
+
+/// The playouts [`WasmEngine::genmove_async`] runs between returning to the event loop.
+const PLAYOUT_BATCH: u16 = 10;
+
+#[wasm_bindgen]
+extern "C" {
+    /// The `setTimeout` of browsers, workers, and Node.js.
+    #[wasm_bindgen(js_name = setTimeout)]
+    fn set_timeout(handler: &Function, timeout: i32);
+}
+
+/// A [`Board`] played on from JavaScript.
+#[wasm_bindgen]
+pub struct WasmBoard(Board);
+
+/// An [`Engine`] playing a game from JavaScript.
+#[wasm_bindgen]
+pub struct WasmEngine {
+    /// The engine, shared with the searches of [`WasmEngine::genmove_async`] in progress.
+    engine: Rc<RefCell<Engine>>,
+    /// Set to end the searches of [`WasmEngine::genmove_async`] in progress early.
+    interrupt: Rc<Cell<Option<Interrupt>>>,
+}
+
+/// How to end the searches of [`WasmEngine::genmove_async`] in progress.
+#[derive(Copy, Clone)]
+enum Interrupt {
+    /// Play the best move found so far.
+    Stop,
+    /// Play nothing, rejecting the `Promise` of the search.
+    Cancel,
+}
+
+#[wasm_bindgen]
+impl WasmBoard {
+    /// Creates an empty board of the given size, which must be 9, 13, or 19.
+    #[wasm_bindgen(constructor)]
+    pub fn new(size: u16) -> Result<WasmBoard, JsError> {
+        let size = BoardSize::from_u16(size)
+            .ok_or_else(|| JsError::new(&format!("Unsupported board size: {size}")))?;
+        let board = Board::builder().size(size).build().map_err(JsError::from)?;
+        Ok(WasmBoard(board))
+    }
+
+    /// Plays a stone of the given color at the given vertex, or passes at `"pass"`.
+    pub fn play(&mut self, color: &str, vertex: &str) -> Result<(), JsError> {
+        let color = Color::from_name(color)
+            .ok_or_else(|| JsError::new(&format!("Invalid color: {color}")))?;
+        let message = match Position::parse_vertex(vertex, self.0.size())? {
+            Some(pos) => Message::Play(color, pos),
+            None if color == self.0.player_turn() => Message::Pass,
+            None => return Err(RuleViolation::OutOfTurn.into()),
+        };
+        update(&mut self.0, message).map(|_| ()).map_err(JsError::from)
+    }
+
+    /// The vertices where the player to move can legally play, not including a pass.
+    pub fn legal_moves(&self) -> Vec<String> {
+        let color = self.0.player_turn();
+        self.0
+            .intersections()
+            .filter_map(|(pos, state)| (state == State::Empty).then_some(pos))
+            .filter(|pos| self.0.try_play(color, *pos).is_ok())
+            .map(|pos| pos.to_string())
+            .collect()
+    }
+
+    /// The position as JSON, ex. `{"size":9,"toPlay":"white","moves":1,"ko":null,
+    /// "captures":{"black":0,"white":0},"stones":{"black":["E5"],"white":[]},"result":null}`.
+    pub fn to_json(&self) -> String {
+        let board = &self.0;
+        let size = board.size().to_u16();
+        let quoted = |vertex: String| format!("\"{vertex}\"");
+
+        let ko = board.ko().map_or("null".to_string(), |pos| quoted(pos.to_string()));
+        let result = board
+            .result()
+            .map_or("null".to_string(), |result| quoted(result.to_string()));
+
+        let mut json = String::new();
+        let _ = write!(
+            json,
+            "{{\"size\":{size},\"toPlay\":\"{}\",\"moves\":{},\"ko\":{ko},",
+            color_name(board.player_turn()),
+            board.moves().len(),
+        );
+        let _ = write!(
+            json,
+            "\"captures\":{{\"black\":{},\"white\":{}}},",
+            board.captures(Color::Black),
+            board.captures(Color::White),
+        );
+        let stones = [Color::Black, Color::White].map(|color| {
+            let stones: Vec<String> =
+                board.stones(color).map(|pos| quoted(pos.to_string())).collect();
+            format!("\"{}\":[{}]", color_name(color), stones.join(","))
+        });
+        let _ = write!(
+            json,
+            "\"stones\":{{{}}},\"result\":{result}}}",
+            stones.join(",")
+        );
+        json
+    }
+}
+
+#[wasm_bindgen]
+impl WasmEngine {
+    /// Creates an engine holding an empty game on a board of the given size, which must be 9,
+    /// 13, or 19, with the given komi.
+    #[wasm_bindgen(constructor)]
+    pub fn new(size: u16, komi: f64) -> Result<WasmEngine, JsError> {
+        let size = BoardSize::from_u16(size)
+            .ok_or_else(|| JsError::new(&format!("Unsupported board size: {size}")))?;
+        let engine = Engine::new(size, komi);
+        Ok(WasmEngine {
+            engine: Rc::new(RefCell::new(engine)),
+            interrupt: Rc::default(),
+        })
+    }
+
+    /// Sets the number of playouts the engine runs for each move it generates.
+    pub fn set_playouts(&mut self, playouts: u16) {
+        self.engine.borrow_mut().set_playouts(playouts);
+    }
+
+    /// Sets how long the engine searches for each move it generates, in milliseconds, running as
+    /// many playouts as fit in the time instead of those set by `set_playouts`, or removes the
+    /// limit given `undefined`.
+    pub fn set_time_limit(&mut self, milliseconds: Option<f64>) -> Result<(), JsError> {
+        let time = milliseconds
+            .map(|milliseconds| Duration::try_from_secs_f64(milliseconds / 1000.0))
+            .transpose()
+            .map_err(|_| JsError::new("Invalid time limit"))?;
+        self.engine.borrow_mut().set_time_limit(time);
+        Ok(())
+    }
+
+    /// Ends the searches of `genmove_async` in progress after their current batch of playouts,
+    /// playing the best moves they found so far.
+    pub fn stop(&self) {
+        self.interrupt.set(Some(Interrupt::Stop));
+    }
+
+    /// Ends the searches of `genmove_async` in progress after their current batch of playouts
+    /// without playing, rejecting their `Promise`s.
+    pub fn cancel(&self) {
+        self.interrupt.set(Some(Interrupt::Cancel));
+    }
+
+    /// Plays a stone of the given color at the given vertex, or passes at `"pass"`.
+    pub fn play(&mut self, color: &str, vertex: &str) -> Result<(), JsError> {
+        let color = Color::from_name(color)
+            .ok_or_else(|| JsError::new(&format!("Invalid color: {color}")))?;
+        let mut engine = self.engine.borrow_mut();
+        match Position::parse_vertex(vertex, engine.board().size())? {
+            Some(pos) => engine.play(color, pos).map_err(|e| JsError::new(&e)),
+            None => {
+                engine.pass(color);
+                Ok(())
+            }
+        }
+    }
+
+    /// Generates and plays the engine's move for the given color, returning its vertex, `"pass"`,
+    /// or `"resign"`.
+    pub fn genmove(&mut self, color: &str) -> Result<String, JsError> {
+        let color = Color::from_name(color)
+            .ok_or_else(|| JsError::new(&format!("Invalid color: {color}")))?;
+        Ok(move_name(self.engine.borrow_mut().genmove(color)))
+    }
+
+    /// Generates and plays the engine's move for the given color like `genmove`, returning a
+    /// `Promise` of its vertex, `"pass"`, or `"resign"`.
+    ///
+    /// The search returns to the event loop between batches of playouts so the page stays
+    /// responsive, calling `on_progress(playouts, bestMove)` after each batch, if given, and can
+    /// be ended early by `stop` or `cancel`. The `Promise` is rejected if the search is cancelled,
+    /// if a move is played before the search ends, or if `on_progress` throws.
+    pub fn genmove_async(
+        &self,
+        color: &str,
+        on_progress: Option<Function>,
+    ) -> Result<Promise, JsError> {
+        let color = Color::from_name(color)
+            .ok_or_else(|| JsError::new(&format!("Invalid color: {color}")))?;
+        let (engine, interrupt) = (Rc::clone(&self.engine), Rc::clone(&self.interrupt));
+        let mut search = engine.borrow().start_genmove(color);
+        interrupt.set(None);
+
+        Ok(future_to_promise(async move {
+            loop {
+                let done = search.step(PLAYOUT_BATCH);
+                if let Some(on_progress) = &on_progress {
+                    let best_move = JsValue::from(move_name(search.best_move()));
+                    on_progress.call2(&JsValue::NULL, &search.playouts().into(), &best_move)?;
+                }
+                if done {
+                    break;
+                }
+                next_tick().await?;
+                match interrupt.get() {
+                    Some(Interrupt::Stop) => break,
+                    Some(Interrupt::Cancel) => {
+                        return Err(JsError::new("The search was cancelled").into())
+                    }
+                    None => {}
+                }
+            }
+            let mov = engine
+                .borrow_mut()
+                .finish_genmove(search)
+                .map_err(|e| JsError::new(&e))?;
+            Ok(move_name(mov).into())
+        }))
+    }
+
+    /// Searches the current position for the given number of playouts without playing, giving up
+    /// to `top_k` of the moves the search considered, most visited first, as objects like
+    /// `{move: "D4", visits: 120, winrate: 0.56, pv: ["D4", "C3", "E5"]}`, where the winrate is of
+    /// the color to play and the principal variation `pv` starts with the move.
+    pub fn analyze(&self, playouts: u16, top_k: usize) -> Result<Array, JsValue> {
+        let candidates = self.engine.borrow().candidates(playouts);
+        let analysis = Array::new();
+        for candidate in candidates.into_iter().take(top_k) {
+            let pv: Array = candidate
+                .pv
+                .into_iter()
+                .map(|mov| JsValue::from(move_name(mov)))
+                .collect();
+            let object = Object::new();
+            Reflect::set(&object, &"move".into(), &move_name(candidate.mov).into())?;
+            Reflect::set(&object, &"visits".into(), &candidate.visits.into())?;
+            Reflect::set(&object, &"winrate".into(), &candidate.winrate.into())?;
+            Reflect::set(&object, &"pv".into(), &pv)?;
+            analysis.push(&object);
+        }
+        Ok(analysis)
+    }
+
+    /// The current position of the game.
+    pub fn board(&self) -> WasmBoard {
+        WasmBoard(self.engine.borrow().board())
+    }
+
+    /// Records the game so far in the Smart Game Format.
+    pub fn to_sgf(&self) -> String {
+        self.engine.borrow().to_sgf()
+    }
+}
+
+/// Waits for the next turn of the event loop, after the page has had a chance to run.
+async fn next_tick() -> Result<(), JsValue> {
+    let tick = Promise::new(&mut |resolve, _| set_timeout(&resolve, 0));
+    JsFuture::from(tick).await.map(|_| ())
+}
+
+/// The vertex of the given [`EngineMove`], `"pass"`, or `"resign"`.
+fn move_name(mov: EngineMove) -> String {
+    match mov {
+        EngineMove::Play(pos) => pos.to_string(),
+        EngineMove::Pass => "pass".to_string(),
+        EngineMove::Resign => "resign".to_string(),
+    }
+}
+
+/// Reseeds the random numbers of the engine's searches, so that the moves and analyses generated
+/// from now on are the same on every run, ex. for automated tests or repeatable puzzle hints.
+///
+/// Otherwise the random numbers are seeded by the browser's `crypto.getRandomValues`.
+#[wasm_bindgen]
+pub fn seed_rng(seed: u32) {
+    crate::agent::seed_rng(u64::from(seed));
+}
+
+/// The name of the given [`Color`] in JSON.
+fn color_name(color: Color) -> &'static str {
+    match color {
+        Color::Black => "black",
+        Color::White => "white",
+    }
+}